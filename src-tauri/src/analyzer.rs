@@ -9,7 +9,8 @@ use crate::livery_patterns;
 use crate::logger;
 use crate::logger::{tr, LogMsg};
 use crate::models::{
-    AddonType, AnalysisResult, DetectedItem, InstallTask, NavdataCycle, NavdataInfo,
+    AddonType, AnalysisPathError, AnalysisResult, DetectedItem, InstallTask, NavdataCycle,
+    NavdataInfo,
 };
 use crate::scanner::{NestedPasswordRequiredError, PasswordRequiredError, Scanner};
 
@@ -82,8 +83,8 @@ impl Analyzer {
 
         // Merge results
         let mut all_detected = Vec::new();
-        let mut errors = Vec::new();
         let mut password_required = Vec::new();
+        let mut path_errors = Vec::new();
         let mut nested_password_required = HashMap::new(); // NEW: Track nested password requirements
                                                            // Track which archives have passwords for setting on tasks later
         let mut archive_passwords: HashMap<String, String> = HashMap::new();
@@ -141,8 +142,10 @@ impl Analyzer {
                         logger::log_error(&error_msg, Some("analyzer"));
 
                         // For frontend display, use a cleaner format
-                        let display_msg = format!("{}: {}", tr(LogMsg::ScanFailed), e);
-                        errors.push(display_msg);
+                        path_errors.push(AnalysisPathError {
+                            path: path_str.clone(),
+                            message: format!("{}: {}", tr(LogMsg::ScanFailed), e),
+                        });
                     }
                 }
             }
@@ -163,10 +166,10 @@ impl Analyzer {
                         &format!("Ignoring addon at disk root: {}", item.path),
                         Some("analyzer"),
                     );
-                    errors.push(format!(
-                        "Ignored addon at disk root: {} ({})",
-                        item.display_name, item.path
-                    ));
+                    path_errors.push(AnalysisPathError {
+                        path: item.path.clone(),
+                        message: format!("{}: {}", tr(LogMsg::IgnoredDiskRoot), item.display_name),
+                    });
                 }
 
                 !is_root
@@ -199,9 +202,9 @@ impl Analyzer {
 
         crate::log_debug!(
             &format!(
-                "Analysis returned {} tasks, {} errors",
+                "Analysis returned {} tasks, {} path errors",
                 tasks.len(),
-                errors.len()
+                path_errors.len()
             ),
             "analysis"
         );
@@ -218,9 +221,10 @@ impl Analyzer {
 
         AnalysisResult {
             tasks,
-            errors,
+            errors: Vec::new(),
             password_required,
             nested_password_required,
+            path_errors,
         }
     }
 
@@ -731,6 +735,10 @@ impl Analyzer {
             enable_verification,       // Based on verification preferences
             livery_aircraft_type: item.livery_aircraft_type,
             livery_aircraft_found,
+            config_file_recursive: false,
+            scenery_preserve_patterns: Vec::new(),
+            desired_sort_order: None, // Chosen by the user in the frontend
+            install_as_symlink: false,
         }
     }
 
@@ -804,6 +812,7 @@ impl Analyzer {
             "zip" => self.estimate_zip_size(source_path),
             "7z" => self.estimate_7z_size(source_path),
             "rar" => self.estimate_rar_size(source_path),
+            "gz" | "tgz" => self.estimate_tar_gz_size(source_path),
             _ => (None, None),
         }
     }
@@ -976,6 +985,55 @@ impl Analyzer {
         self.check_size_warning(archive_size, estimated_uncompressed)
     }
 
+    /// Estimate .tar.gz uncompressed size by summing tar entry sizes
+    fn estimate_tar_gz_size(&self, archive_path: &Path) -> (Option<u64>, Option<String>) {
+        let archive_size = match fs::metadata(archive_path) {
+            Ok(m) => m.len(),
+            Err(e) => {
+                logger::log_debug(
+                    &format!("Failed to get tar.gz metadata: {}", e),
+                    Some("analyzer"),
+                    Some("analyzer.rs"),
+                );
+                return (None, None);
+            }
+        };
+
+        // Check cache first
+        if let Some(cached) = crate::cache::get_cached_metadata(archive_path) {
+            return self.check_size_warning(archive_size, cached.uncompressed_size);
+        }
+
+        let listing = crate::scanner::open_tar_gz(archive_path).and_then(|mut archive| {
+            let mut total: u64 = 0;
+            let mut file_count: usize = 0;
+            for entry in archive.entries()? {
+                let entry = entry?;
+                total = total.saturating_add(entry.header().size().unwrap_or(0));
+                file_count += 1;
+            }
+            Ok((total, file_count))
+        });
+
+        let estimated_uncompressed = match listing {
+            Ok((total, file_count)) if total > 0 => {
+                crate::cache::cache_metadata(archive_path, total, file_count);
+                total
+            }
+            Ok(_) => archive_size.saturating_mul(5),
+            Err(e) => {
+                logger::log_debug(
+                    &format!("Failed to open tar.gz for size estimation: {}", e),
+                    Some("analyzer"),
+                    Some("analyzer.rs"),
+                );
+                archive_size.saturating_mul(5)
+            }
+        };
+
+        self.check_size_warning(archive_size, estimated_uncompressed)
+    }
+
     /// Check if the archive size warrants a warning
     fn check_size_warning(
         &self,
@@ -1093,6 +1151,10 @@ mod tests {
             backup_liveries: true,
             backup_config_files: true,
             config_file_patterns: vec!["*_prefs.txt".to_string()],
+            config_file_recursive: false,
+            scenery_preserve_patterns: Vec::new(),
+            desired_sort_order: None,
+            install_as_symlink: false,
             extraction_chain: None,
             file_hashes: None,
             enable_verification: true,
@@ -1101,6 +1163,64 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_analyze_returns_partial_results() {
+        let temp = tempfile::tempdir().unwrap();
+        let xplane = temp.path().join("X-Plane 12");
+        let installed = xplane.join("Custom Scenery").join("KSEA");
+        fs::create_dir_all(&installed).unwrap();
+        let aircraft = temp.path().join("Downloads").join("C172");
+        fs::create_dir_all(&aircraft).unwrap();
+        fs::write(aircraft.join("c172.acf"), "I\n1100 Version\nACF\n").unwrap();
+
+        let installed_str = installed.to_string_lossy().to_string();
+        let result = Analyzer::new().analyze(
+            vec![
+                installed_str.clone(),
+                aircraft.to_string_lossy().to_string(),
+            ],
+            &xplane.to_string_lossy(),
+            None,
+            None,
+        );
+
+        // The input inside X-Plane fails, the aircraft is still analyzed
+        assert_eq!(result.path_errors.len(), 1);
+        assert_eq!(result.path_errors[0].path, installed_str);
+        assert!(result.errors.is_empty());
+        assert_eq!(result.tasks.len(), 1);
+        assert_eq!(result.tasks[0].addon_type, AddonType::Aircraft);
+    }
+
+    #[test]
+    fn test_analyze_misnamed_archive() {
+        use std::io::Write;
+
+        let temp = tempfile::tempdir().unwrap();
+        let xplane = temp.path().join("X-Plane 12");
+        fs::create_dir_all(xplane.join("Aircraft")).unwrap();
+
+        // A zip download saved with the wrong extension is routed by its magic bytes
+        let archive = temp.path().join("C172.7z");
+        let mut writer = zip::ZipWriter::new(fs::File::create(&archive).unwrap());
+        writer
+            .start_file("C172/c172.acf", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(b"I\n1100 Version\nACF\n").unwrap();
+        writer.finish().unwrap();
+
+        let result = Analyzer::new().analyze(
+            vec![archive.to_string_lossy().to_string()],
+            &xplane.to_string_lossy(),
+            None,
+            None,
+        );
+
+        assert!(result.errors.is_empty());
+        assert_eq!(result.tasks.len(), 1);
+        assert_eq!(result.tasks[0].addon_type, AddonType::Aircraft);
+    }
+
     #[test]
     fn test_deduplication_same_type() {
         let analyzer = Analyzer::new();
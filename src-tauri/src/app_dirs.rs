@@ -8,6 +8,19 @@ use std::path::PathBuf;
 /// App identifier matching tauri.conf.json
 const APP_IDENTIFIER: &str = "com.xfastmanager.tool";
 
+#[cfg(test)]
+thread_local! {
+    /// App data directory override for the current test thread
+    static TEST_APP_DATA_DIR: std::cell::RefCell<Option<PathBuf>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+/// Point this test thread's app data directory (database, audit log, ...) at `dir`
+#[cfg(test)]
+pub fn set_test_app_data_dir(dir: &std::path::Path) {
+    TEST_APP_DATA_DIR.with(|d| *d.borrow_mut() = Some(dir.to_path_buf()));
+}
+
 /// Get the app data directory for persistent storage
 ///
 /// Returns platform-specific paths:
@@ -15,6 +28,11 @@ const APP_IDENTIFIER: &str = "com.xfastmanager.tool";
 /// - macOS: ~/Library/Application Support/com.xfastmanager.tool
 /// - Linux: ~/.config/com.xfastmanager.tool
 pub fn get_app_data_dir() -> PathBuf {
+    #[cfg(test)]
+    if let Some(dir) = TEST_APP_DATA_DIR.with(|d| d.borrow().clone()) {
+        return dir;
+    }
+
     #[cfg(target_os = "windows")]
     {
         if let Some(app_data) = std::env::var_os("APPDATA") {
@@ -55,16 +73,31 @@ pub fn get_log_file_path() -> PathBuf {
     get_logs_dir().join("xfastmanager.log")
 }
 
+/// Get the directory holding per-installation log files
+pub fn get_install_logs_dir() -> PathBuf {
+    get_app_data_dir().join("install_logs")
+}
+
 /// Get the database file path
 pub fn get_database_path() -> PathBuf {
     get_app_data_dir().join("scenery.db")
 }
 
+/// Get the scenery manager audit log path (one JSON entry per line)
+pub fn get_scenery_audit_path() -> PathBuf {
+    get_app_data_dir().join("scenery_audit.jsonl")
+}
+
 /// Get the update check cache file path
 pub fn get_update_cache_path() -> PathBuf {
     get_app_data_dir().join("update_check_cache.txt")
 }
 
+/// Get the user-editable livery detection patterns file path
+pub fn get_livery_patterns_path() -> PathBuf {
+    get_app_data_dir().join("liveries.json")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,10 +1,15 @@
 use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
 use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
 use tauri::{AppHandle, Emitter};
 use uuid::Uuid;
 
+use crate::install_log::{InstallLog, InstallLogEvent};
 use crate::logger;
 use crate::models::{InstallPhase, InstallProgress, InstallTask};
 
@@ -14,6 +19,157 @@ const MIN_FREE_SPACE_BYTES: u64 = 1024 * 1024 * 1024;
 /// Maximum symlink resolution depth to prevent infinite loops
 const MAX_SYMLINK_DEPTH: usize = 40;
 
+/// Prefix of the staging directories created in the X-Plane root
+pub const TEMP_DIR_PREFIX: &str = ".xfastmanager_temp_";
+
+/// Leftover staging directories younger than this are kept by the startup sweep,
+/// so an install running in another app instance never loses its temp directory
+const STALE_TEMP_DIR_AGE: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Copy fallback emits progress after this many files...
+const COPY_PROGRESS_FILES: u64 = 64;
+
+/// ...or after this many bytes, whichever comes first
+const COPY_PROGRESS_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Staging directories owned by installers alive in this process
+static ACTIVE_TEMP_DIRS: Lazy<Mutex<HashSet<PathBuf>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// Error returned when an installation is cancelled through the cancel token
+#[derive(Debug)]
+pub struct InstallCancelled;
+
+impl std::fmt::Display for InstallCancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Installation cancelled by user")
+    }
+}
+
+impl std::error::Error for InstallCancelled {}
+
+/// Check whether an error (or any error in its chain) is an InstallCancelled
+pub fn is_install_cancelled(err: &anyhow::Error) -> bool {
+    err.chain().any(|e| e.is::<InstallCancelled>())
+}
+
+/// Fail with InstallCancelled once the cancel flag has been set
+fn check_cancel(cancel: Option<&AtomicBool>) -> Result<()> {
+    if cancel.is_some_and(|flag| flag.load(Ordering::SeqCst)) {
+        return Err(InstallCancelled.into());
+    }
+    Ok(())
+}
+
+/// Callback receiving (processed_bytes, total_bytes) while files are copied.
+/// A total of 0 means there was nothing to copy (e.g. the rename fast path).
+type CopyProgressFn<'a> = &'a dyn Fn(u64, u64);
+
+/// Byte counter for a recursive copy, reporting every COPY_PROGRESS_FILES
+/// files or COPY_PROGRESS_BYTES bytes
+struct CopyProgress<'a> {
+    total_bytes: u64,
+    processed_bytes: u64,
+    pending_files: u64,
+    pending_bytes: u64,
+    on_progress: Option<CopyProgressFn<'a>>,
+}
+
+impl<'a> CopyProgress<'a> {
+    fn new(src: &Path, on_progress: Option<CopyProgressFn<'a>>) -> Self {
+        // Only walk the source when someone is listening
+        let total_bytes = if on_progress.is_some() {
+            directory_size(src)
+        } else {
+            0
+        };
+        Self {
+            total_bytes,
+            processed_bytes: 0,
+            pending_files: 0,
+            pending_bytes: 0,
+            on_progress,
+        }
+    }
+
+    fn add_file(&mut self, bytes: u64) {
+        self.processed_bytes += bytes;
+        self.pending_files += 1;
+        self.pending_bytes += bytes;
+        if self.pending_files >= COPY_PROGRESS_FILES || self.pending_bytes >= COPY_PROGRESS_BYTES {
+            self.report();
+        }
+    }
+
+    fn report(&mut self) {
+        self.pending_files = 0;
+        self.pending_bytes = 0;
+        if let Some(on_progress) = self.on_progress {
+            on_progress(self.processed_bytes, self.total_bytes);
+        }
+    }
+}
+
+/// Total size of the regular files under `path` (symlinks are not followed)
+fn directory_size(path: &Path) -> u64 {
+    walkdir::WalkDir::new(path)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// Verification emits progress after this many files
+const VERIFY_PROGRESS_FILES: u64 = 256;
+
+/// File count and total size of a staged or installed directory tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TreeStats {
+    files: u64,
+    bytes: u64,
+}
+
+/// Count the regular files under `path` and their total size (symlinks are not followed)
+/// `on_file` receives the running file count every VERIFY_PROGRESS_FILES files
+fn tree_stats(path: &Path, on_file: Option<&dyn Fn(u64)>) -> Result<TreeStats> {
+    let mut stats = TreeStats { files: 0, bytes: 0 };
+    for entry in walkdir::WalkDir::new(path).follow_links(false) {
+        let entry = entry.context(format!("Failed to walk {:?}", path))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        stats.files += 1;
+        stats.bytes += entry
+            .metadata()
+            .context(format!("Failed to read metadata: {:?}", entry.path()))?
+            .len();
+        if stats.files % VERIFY_PROGRESS_FILES == 0 {
+            if let Some(on_file) = on_file {
+                on_file(stats.files);
+            }
+        }
+    }
+    Ok(stats)
+}
+
+/// Confirm that `path` holds exactly the files recorded before the move
+fn verify_tree(path: &Path, expected: TreeStats, on_file: Option<&dyn Fn(u64)>) -> Result<()> {
+    let actual = tree_stats(path, on_file)?;
+    if actual != expected {
+        anyhow::bail!(
+            "Verification failed for {:?}: expected {} files ({} bytes), found {} files ({} bytes)",
+            path,
+            expected.files,
+            expected.bytes,
+            actual.files,
+            actual.bytes
+        );
+    }
+    Ok(())
+}
+
 /// Atomic installer for safer installation operations
 pub struct AtomicInstaller {
     /// Temporary directory for staging files (same drive as target)
@@ -28,6 +184,12 @@ pub struct AtomicInstaller {
     total_tasks: usize,
     /// Current task index (for progress calculation)
     current_task: usize,
+    /// Install log receiving rollback actions
+    install_log: InstallLog,
+    /// Cancel flag checked between files while moving content into place
+    cancel: Option<Arc<AtomicBool>>,
+    /// Set once a fresh install has created the target, so rollback can remove it
+    created_target: bool,
 }
 
 impl AtomicInstaller {
@@ -40,21 +202,32 @@ impl AtomicInstaller {
     /// * `app_handle` - Tauri app handle for emitting progress events
     /// * `total_tasks` - Total number of tasks for progress calculation
     /// * `current_task` - Current task index for progress calculation
+    /// * `install_log` - Install log receiving rollback actions
     pub fn new(
         target_dir: &Path,
         xplane_root: &Path,
         app_handle: AppHandle,
         total_tasks: usize,
         current_task: usize,
+        install_log: InstallLog,
     ) -> Result<Self> {
         // Check available disk space
         check_disk_space(xplane_root)?;
 
-        // Create temp directory in X-Plane root directory
-        let temp_dir = xplane_root.join(format!(".xfastmanager_temp_{}", Uuid::new_v4()));
+        // Create temp directory in X-Plane root directory, never reusing an existing one
+        let mut temp_dir = xplane_root.join(format!("{}{}", TEMP_DIR_PREFIX, Uuid::new_v4()));
+        while temp_dir.exists() {
+            temp_dir = xplane_root.join(format!("{}{}", TEMP_DIR_PREFIX, Uuid::new_v4()));
+        }
 
-        fs::create_dir_all(&temp_dir)
-            .context(format!("Failed to create temp directory: {:?}", temp_dir))?;
+        if let Ok(mut active) = ACTIVE_TEMP_DIRS.lock() {
+            active.insert(temp_dir.clone());
+        }
+
+        if let Err(e) = fs::create_dir(&temp_dir) {
+            unregister_temp_dir(&temp_dir);
+            return Err(e).context(format!("Failed to create temp directory: {:?}", temp_dir));
+        }
 
         logger::log_info(
             &format!("Created atomic install temp directory: {:?}", temp_dir),
@@ -68,9 +241,22 @@ impl AtomicInstaller {
             app_handle,
             total_tasks,
             current_task,
+            install_log,
+            cancel: None,
+            created_target: false,
         })
     }
 
+    /// Stop file moves as soon as `token` is set (shared with TaskControl)
+    pub fn with_cancel_token(mut self, token: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    fn cancel_flag(&self) -> Option<&AtomicBool> {
+        self.cancel.as_deref()
+    }
+
     /// Emit progress event to frontend
     /// Note: We use percentage=90.0 for atomic operations since extraction (0-90%) is already done
     /// This prevents the progress bar from resetting to 0% during atomic move/backup/restore phases
@@ -90,6 +276,82 @@ impl AtomicInstaller {
         let _ = self.app_handle.emit("install-progress", &progress);
     }
 
+    /// Emit byte-level progress while files are moved into place
+    /// The copy fills the 90-100% band left over after extraction
+    fn emit_copy_progress(&self, processed_bytes: u64, total_bytes: u64) {
+        let fraction = if total_bytes == 0 {
+            1.0
+        } else {
+            (processed_bytes as f64 / total_bytes as f64).min(1.0)
+        };
+        let progress = InstallProgress {
+            percentage: 90.0 + fraction * 10.0,
+            total_bytes,
+            processed_bytes,
+            current_task_index: self.current_task,
+            total_tasks: self.total_tasks,
+            current_task_name: String::new(),
+            current_file: Some("Copying files to target directory...".to_string()),
+            phase: InstallPhase::Installing,
+            verification_progress: None,
+        };
+
+        let _ = self.app_handle.emit("install-progress", &progress);
+    }
+
+    /// Emit verification progress as installed files are checked against the staged tree
+    fn emit_verification_progress(&self, checked_files: u64, expected_files: u64) {
+        let fraction = if expected_files == 0 {
+            1.0
+        } else {
+            (checked_files as f64 / expected_files as f64).min(1.0)
+        };
+        let progress = InstallProgress {
+            percentage: 100.0,
+            total_bytes: 0,
+            processed_bytes: 0,
+            current_task_index: self.current_task,
+            total_tasks: self.total_tasks,
+            current_task_name: String::new(),
+            current_file: Some("Verifying installed files...".to_string()),
+            phase: InstallPhase::Verifying,
+            verification_progress: Some(fraction * 100.0),
+        };
+
+        let _ = self.app_handle.emit("install-progress", &progress);
+    }
+
+    /// Walk the target after the move and compare it with the staged tree
+    /// Catches truncated copies (e.g. on flaky network drives) before the backup is dropped
+    fn verify_installed(&self, expected: TreeStats) -> Result<()> {
+        self.emit_verification_progress(0, expected.files);
+        let on_file = |checked| self.emit_verification_progress(checked, expected.files);
+        verify_tree(&self.target_dir, expected, Some(&on_file))?;
+        self.emit_verification_progress(expected.files, expected.files);
+        Ok(())
+    }
+
+    /// Roll back after a failed move or verification and return the original error
+    fn fail_and_roll_back(&mut self, stage: &str, err: anyhow::Error) -> Result<()> {
+        logger::log_error(
+            &format!("{} failed, rolling back: {}", stage, err),
+            Some("atomic_installer"),
+        );
+        if let Err(rollback_err) = self.rollback() {
+            logger::log_error(
+                &format!("CRITICAL: Rollback failed: {}", rollback_err),
+                Some("atomic_installer"),
+            );
+            self.record_rollback(&format!(
+                "Failed to roll back {:?} after {} error: {}",
+                self.target_dir,
+                stage.to_lowercase(),
+                rollback_err
+            ));
+        }
+        Err(err)
+    }
+
     /// Get the temporary directory path
     pub fn temp_dir(&self) -> &Path {
         &self.temp_dir
@@ -115,13 +377,30 @@ impl AtomicInstaller {
         if !self.temp_dir.exists() || fs::read_dir(&self.temp_dir)?.next().is_none() {
             anyhow::bail!("Temp directory is empty, nothing to install");
         }
+        let staged = tree_stats(&self.temp_dir, None)?;
 
         // Atomic move: temp -> target
         self.emit_progress(
             "Moving files to target directory...",
             InstallPhase::Installing,
         );
-        atomic_move(&self.temp_dir, &self.target_dir)?;
+        // Claim the target before moving: a failed or cancelled copy leaves part of it
+        // behind, and rollback has to remove that as well
+        self.created_target = !self.target_dir.exists();
+        let on_progress = |processed, total| self.emit_copy_progress(processed, total);
+        let moved = move_path_cancellable(
+            &self.temp_dir,
+            &self.target_dir,
+            self.cancel_flag(),
+            Some(&on_progress),
+        );
+        if let Err(e) = moved {
+            return self.fail_and_roll_back("Fresh installation", e);
+        }
+
+        if let Err(e) = self.verify_installed(staged) {
+            return self.fail_and_roll_back("Post-install verification", e);
+        }
 
         logger::log_info(
             &format!("Fresh installation completed: {:?}", self.target_dir),
@@ -147,6 +426,10 @@ impl AtomicInstaller {
             Some("atomic_installer"),
         );
 
+        // Nothing has been touched yet, so a pending cancel needs no rollback
+        check_cancel(self.cancel_flag())?;
+        let staged = tree_stats(&self.temp_dir, None)?;
+
         // Create unique backup directory name to avoid conflicts
         let backup_dir = self
             .target_dir
@@ -200,8 +483,32 @@ impl AtomicInstaller {
             "Moving new files to target directory...",
             InstallPhase::Installing,
         );
-        match atomic_move(&self.temp_dir, &self.target_dir) {
+        let on_progress = |processed, total| self.emit_copy_progress(processed, total);
+        let moved = move_path_cancellable(
+            &self.temp_dir,
+            &self.target_dir,
+            self.cancel_flag(),
+            Some(&on_progress),
+        );
+        match moved {
             Ok(()) => {}
+            Err(e) if is_install_cancelled(&e) => {
+                logger::log_info(
+                    "Clean installation cancelled, restoring original directory",
+                    Some("atomic_installer"),
+                );
+                if let Err(rollback_err) = self.rollback() {
+                    logger::log_error(
+                        &format!("CRITICAL: Rollback failed: {}", rollback_err),
+                        Some("atomic_installer"),
+                    );
+                    self.record_rollback(&format!(
+                        "Failed to restore {:?} from {:?}: {}",
+                        self.target_dir, backup_dir, rollback_err
+                    ));
+                }
+                return Err(e);
+            }
             Err(e) => {
                 // Rollback: restore backup
                 logger::log_error(
@@ -214,14 +521,31 @@ impl AtomicInstaller {
                         &format!("CRITICAL: Rollback failed: {}", rollback_err),
                         Some("atomic_installer"),
                     );
+                    self.record_rollback(&format!(
+                        "Failed to restore {:?} from {:?}: {}",
+                        self.target_dir, backup_dir, rollback_err
+                    ));
+                } else {
+                    self.record_rollback(&format!(
+                        "Restored {:?} from backup after failed move: {}",
+                        self.target_dir, e
+                    ));
                 }
 
                 return Err(e);
             }
         }
 
+        // Verify before restoring backup files, which would change the counts
+        if let Err(e) = self.verify_installed(staged) {
+            return self.fail_and_roll_back("Post-install verification", e);
+        }
+
         // Step 3: Restore backup files (liveries, config files)
-        if task.backup_liveries || task.backup_config_files {
+        if task.backup_liveries
+            || task.backup_config_files
+            || !task.scenery_preserve_patterns.is_empty()
+        {
             self.emit_progress("Restoring backup files...", InstallPhase::Installing);
             if let Err(e) = self.restore_backup_files(task, &backup_dir) {
                 logger::log_error(
@@ -276,8 +600,35 @@ impl AtomicInstaller {
             InstallPhase::Installing,
         );
 
-        match merge_directories(&self.temp_dir, &self.target_dir) {
+        let on_progress = |processed, total| self.emit_copy_progress(processed, total);
+        match merge_directories(
+            &self.temp_dir,
+            &self.target_dir,
+            self.cancel_flag(),
+            Some(&on_progress),
+        ) {
             Ok(()) => {}
+            Err(e) if is_install_cancelled(&e) => {
+                // No backup is taken for a merge, so overwritten files can't be restored:
+                // report the partial merge instead of pretending to roll it back
+                logger::log_info(
+                    "Overwrite installation cancelled, files merged so far are kept",
+                    Some("atomic_installer"),
+                );
+                let detail = format!(
+                    "{:?} was left partially updated by the cancelled merge",
+                    self.target_dir
+                );
+                self.install_log.record(
+                    InstallLogEvent::Error,
+                    self.target_dir
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .unwrap_or("unknown"),
+                    &detail,
+                );
+                return Err(e.context(detail));
+            }
             Err(e) => {
                 // Check if error is because target doesn't exist
                 if !self.target_dir.exists() {
@@ -305,8 +656,6 @@ impl AtomicInstaller {
 
     /// Restore backup files (liveries and config files) from backup directory
     fn restore_backup_files(&self, task: &InstallTask, backup_dir: &Path) -> Result<()> {
-        use glob::Pattern;
-
         logger::log_info(
             "Restoring backup files from original installation",
             Some("atomic_installer"),
@@ -331,7 +680,7 @@ impl AtomicInstaller {
             }
         }
 
-        // Restore config files (only in root directory)
+        // Restore config files (only in root directory unless recursive)
         if task.backup_config_files && !task.config_file_patterns.is_empty() {
             logger::log_info(
                 &format!(
@@ -341,39 +690,44 @@ impl AtomicInstaller {
                 Some("atomic_installer"),
             );
 
-            for entry in fs::read_dir(backup_dir)? {
-                let entry = entry?;
-                let path = entry.path();
-
-                if path.is_file() {
-                    if let Some(filename) = path.file_name().and_then(|s| s.to_str()) {
-                        // Check if filename matches any pattern
-                        let matches = task.config_file_patterns.iter().any(|pattern| {
-                            Pattern::new(pattern)
-                                .map(|p| p.matches(filename))
-                                .unwrap_or(false)
-                        });
-
-                        if matches {
-                            let target_file = self.target_dir.join(filename);
-                            logger::log_info(
-                                &format!("Restoring config file: {}", filename),
-                                Some("atomic_installer"),
-                            );
-
-                            fs::copy(&path, &target_file)
-                                .context(format!("Failed to restore config file: {}", filename))?;
-                        }
-                    }
+            for relative in crate::installer::find_config_files(
+                backup_dir,
+                &task.config_file_patterns,
+                task.config_file_recursive,
+            ) {
+                let target_file = self.target_dir.join(&relative);
+                logger::log_info(
+                    &format!("Restoring config file: {}", relative.display()),
+                    Some("atomic_installer"),
+                );
+
+                if let Some(parent) = target_file.parent() {
+                    fs::create_dir_all(parent)?;
                 }
+                fs::copy(backup_dir.join(&relative), &target_file).context(format!(
+                    "Failed to restore config file: {}",
+                    relative.display()
+                ))?;
             }
         }
 
+        // Restore preserved scenery files (matched anywhere inside the package)
+        if !task.scenery_preserve_patterns.is_empty() {
+            let restored = copy_preserved_files(
+                backup_dir,
+                &self.target_dir,
+                &task.scenery_preserve_patterns,
+            )?;
+            logger::log_info(
+                &format!("Restored {} preserved scenery file(s)", restored),
+                Some("atomic_installer"),
+            );
+        }
+
         Ok(())
     }
 
     /// Rollback installation if something goes wrong
-    #[allow(dead_code)]
     pub fn rollback(&mut self) -> Result<()> {
         logger::log_error("Rolling back atomic installation", Some("atomic_installer"));
 
@@ -389,17 +743,35 @@ impl AtomicInstaller {
                 // Restore backup
                 fs::rename(backup_dir, &self.target_dir)
                     .context("Failed to restore backup during rollback")?;
+                self.record_rollback(&format!("Restored {:?} from backup", self.target_dir));
 
                 logger::log_info(
                     "Rollback completed: Original files restored",
                     Some("atomic_installer"),
                 );
             }
+        } else if self.created_target && self.target_dir.exists() {
+            // Fresh install: nothing to restore, just remove what was written
+            fs::remove_dir_all(&self.target_dir)
+                .context("Failed to remove partial installation during rollback")?;
+            self.created_target = false;
+            self.record_rollback(&format!("Removed incomplete {:?}", self.target_dir));
         }
 
         Ok(())
     }
 
+    /// Record a rollback action in the install log
+    fn record_rollback(&self, detail: &str) {
+        let subject = self
+            .target_dir
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown");
+        self.install_log
+            .record(InstallLogEvent::Rollback, subject, detail);
+    }
+
     /// Explicitly cleanup temp directory
     fn cleanup_temp_dir(&mut self) {
         if self.temp_dir.exists() {
@@ -442,71 +814,222 @@ impl Drop for AtomicInstaller {
                 );
             }
         }
+        unregister_temp_dir(&self.temp_dir);
+    }
+}
+
+fn unregister_temp_dir(temp_dir: &Path) {
+    if let Ok(mut active) = ACTIVE_TEMP_DIRS.lock() {
+        active.remove(temp_dir);
     }
 }
 
-/// Atomic move operation (rename on same filesystem)
-/// Falls back to copy+delete if rename fails (different filesystems)
-/// Note: If copy succeeds but delete fails, logs a warning but still returns Ok
+/// Remove staging directories left behind in the X-Plane root by a crashed install
+/// Directories still owned by an installer in this process, or modified recently
+/// (possibly by a concurrent install in another instance), are kept.
+/// Returns the number of directories removed
+pub fn cleanup_stale_temp_dirs(xplane_root: &Path) -> usize {
+    cleanup_temp_dirs_older_than(xplane_root, STALE_TEMP_DIR_AGE)
+}
+
+fn cleanup_temp_dirs_older_than(xplane_root: &Path, max_age: Duration) -> usize {
+    let Ok(read_dir) = fs::read_dir(xplane_root) else {
+        return 0;
+    };
+
+    let now = SystemTime::now();
+    let mut removed = 0;
+
+    for entry in read_dir.flatten() {
+        let path = entry.path();
+        let is_temp_dir = entry
+            .file_name()
+            .to_string_lossy()
+            .starts_with(TEMP_DIR_PREFIX)
+            && entry.file_type().map(|ft| ft.is_dir()).unwrap_or(false);
+        if !is_temp_dir {
+            continue;
+        }
+
+        let is_active = ACTIVE_TEMP_DIRS
+            .lock()
+            .map(|active| active.contains(&path))
+            .unwrap_or(true);
+        if is_active {
+            continue;
+        }
+
+        let age = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok());
+        if !age.is_some_and(|age| age >= max_age) {
+            continue;
+        }
+
+        match fs::remove_dir_all(&path) {
+            Ok(()) => {
+                removed += 1;
+                logger::log_info(
+                    &format!("Removed stale temp directory: {:?}", path),
+                    Some("atomic_installer"),
+                );
+            }
+            Err(e) => {
+                logger::log_error(
+                    &format!("Failed to remove stale temp directory {:?}: {}", path, e),
+                    Some("atomic_installer"),
+                );
+            }
+        }
+    }
+
+    removed
+}
+
+/// Move a file, directory or symlink from src to dst
+/// Uses rename when possible and falls back to copy+delete across filesystems.
+/// Symlinks are recreated rather than followed, and a partial copy is removed
+/// if the fallback fails so dst is never left half-populated.
+/// Note: If copy succeeds but deleting src fails, logs a warning but still returns Ok
 /// to prevent orphan files from blocking installation
-fn atomic_move(src: &Path, dst: &Path) -> Result<()> {
-    logger::log_info(
-        &format!("Atomic move: {:?} -> {:?}", src, dst),
-        Some("atomic_installer"),
+pub fn move_path(src: &Path, dst: &Path) -> Result<()> {
+    move_path_cancellable(src, dst, None, None)
+}
+
+/// move_path that checks `cancel` between files and reports copied bytes to
+/// `on_progress` when falling back to copy+delete
+fn move_path_cancellable(
+    src: &Path,
+    dst: &Path,
+    cancel: Option<&AtomicBool>,
+    on_progress: Option<CopyProgressFn>,
+) -> Result<()> {
+    crate::log_debug!(
+        &format!("Moving: {:?} -> {:?}", src, dst),
+        "atomic_installer"
     );
 
     // Try atomic rename first (only works on same filesystem)
     match fs::rename(src, dst) {
         Ok(()) => {
-            logger::log_info(
-                "Atomic move completed successfully (rename)",
-                Some("atomic_installer"),
-            );
+            // Nothing left to copy: report completion in one step
+            if let Some(on_progress) = on_progress {
+                on_progress(0, 0);
+            }
             Ok(())
         }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            Err(e).context(format!("Source does not exist: {:?}", src))
+        }
         Err(e) => {
             logger::log_info(
                 &format!("Rename failed ({}), falling back to copy+delete", e),
                 Some("atomic_installer"),
             );
+            move_by_copy(src, dst, cancel, on_progress)
+        }
+    }
+}
 
-            // Fallback: copy then delete
-            copy_directory_recursive(src, dst)?;
+/// Copy+delete fallback for move_path
+fn move_by_copy(
+    src: &Path,
+    dst: &Path,
+    cancel: Option<&AtomicBool>,
+    on_progress: Option<CopyProgressFn>,
+) -> Result<()> {
+    let metadata =
+        fs::symlink_metadata(src).context(format!("Failed to read metadata: {:?}", src))?;
+
+    let copy_result = if metadata.file_type().is_symlink() {
+        // The link itself is being moved, so its own target is the allowed base
+        copy_symlink(src, dst, src, 0)
+    } else if metadata.is_dir() {
+        copy_directory_recursive(src, dst, cancel, on_progress)
+    } else {
+        fs::copy(src, dst)
+            .map(|_| preserve_mtime(&metadata, dst))
+            .context(format!("Failed to copy file: {:?}", src))
+    };
 
-            // Attempt to remove source, but don't fail if it doesn't work
-            // (prevents orphan source files from blocking installation)
-            match fs::remove_dir_all(src) {
-                Ok(()) => {
-                    logger::log_info(
-                        "Atomic move completed (copy+delete fallback)",
-                        Some("atomic_installer"),
-                    );
-                }
-                Err(delete_err) => {
-                    // Log warning but don't fail - the copy succeeded
-                    // User may need to manually clean up the source
-                    logger::log_error(
-                        &format!(
-                            "Warning: Failed to remove source after copy: {}. \
-                             Manual cleanup of {:?} may be required.",
-                            delete_err, src
-                        ),
-                        Some("atomic_installer"),
-                    );
-                }
-            }
+    if let Err(e) = copy_result {
+        // Remove the partial copy so a failed move leaves dst untouched
+        let cleanup = match fs::symlink_metadata(dst) {
+            Ok(m) if m.is_dir() => fs::remove_dir_all(dst),
+            Ok(_) => fs::remove_file(dst),
+            Err(_) => Ok(()),
+        };
+        if let Err(cleanup_err) = cleanup {
+            logger::log_error(
+                &format!("Failed to remove partial copy {:?}: {}", dst, cleanup_err),
+                Some("atomic_installer"),
+            );
+        }
+        return Err(e);
+    }
 
-            Ok(())
+    // Attempt to remove source, but don't fail if it doesn't work
+    // (prevents orphan source files from blocking installation)
+    let remove_result = if metadata.is_dir() {
+        fs::remove_dir_all(src)
+    } else {
+        fs::remove_file(src)
+    };
+    match remove_result {
+        Ok(()) => {
+            logger::log_info(
+                "Move completed (copy+delete fallback)",
+                Some("atomic_installer"),
+            );
+        }
+        Err(delete_err) => {
+            // Log warning but don't fail - the copy succeeded
+            // User may need to manually clean up the source
+            logger::log_error(
+                &format!(
+                    "Warning: Failed to remove source after copy: {}. \
+                     Manual cleanup of {:?} may be required.",
+                    delete_err, src
+                ),
+                Some("atomic_installer"),
+            );
         }
     }
+
+    Ok(())
+}
+
+/// Give `dst` the modification time of the file or directory described by `src_metadata`
+/// fs::copy does not carry mtimes everywhere, and a copy stamped with the install time
+/// would make the scenery index re-classify the package on its next update
+pub fn preserve_mtime(src_metadata: &fs::Metadata, dst: &Path) {
+    let mtime = filetime::FileTime::from_last_modification_time(src_metadata);
+    if let Err(e) = filetime::set_file_mtime(dst, mtime) {
+        crate::log_debug!(
+            &format!("Failed to preserve modification time of {:?}: {}", dst, e),
+            "atomic_installer"
+        );
+    }
 }
 
 /// Recursively copy a directory
 /// Handles regular files, directories, and symbolic links
 /// Validates symlink targets to prevent path traversal attacks
-fn copy_directory_recursive(src: &Path, dst: &Path) -> Result<()> {
+/// Stops with InstallCancelled between files once `cancel` is set
+/// Reports copied bytes against the pre-computed source size to `on_progress`
+fn copy_directory_recursive(
+    src: &Path,
+    dst: &Path,
+    cancel: Option<&AtomicBool>,
+    on_progress: Option<CopyProgressFn>,
+) -> Result<()> {
     let visited = HashSet::new();
-    copy_directory_recursive_internal(src, dst, src, 0, &visited)
+    let mut progress = CopyProgress::new(src, on_progress);
+    copy_directory_recursive_internal(src, dst, src, 0, &visited, cancel, &mut progress)?;
+    progress.report();
+    Ok(())
 }
 
 /// Internal recursive copy with base directory tracking for symlink validation
@@ -517,6 +1040,8 @@ fn copy_directory_recursive_internal(
     base_dir: &Path,
     depth: usize,
     visited: &HashSet<PathBuf>,
+    cancel: Option<&AtomicBool>,
+    progress: &mut CopyProgress,
 ) -> Result<()> {
     // Security: Prevent infinite recursion from symlink cycles
     if depth > MAX_SYMLINK_DEPTH {
@@ -544,6 +1069,7 @@ fn copy_directory_recursive_internal(
     fs::create_dir_all(dst)?;
 
     for entry in fs::read_dir(src)? {
+        check_cancel(cancel)?;
         let entry = entry?;
         let src_path = entry.path();
         let dst_path = dst.join(entry.file_name());
@@ -556,10 +1082,28 @@ fn copy_directory_recursive_internal(
             copy_symlink(&src_path, &dst_path, base_dir, depth)?;
         } else if metadata.is_dir() {
             // Handle directory with incremented depth
-            copy_directory_recursive_internal(&src_path, &dst_path, base_dir, depth + 1, &new_visited)?;
+            copy_directory_recursive_internal(
+                &src_path,
+                &dst_path,
+                base_dir,
+                depth + 1,
+                &new_visited,
+                cancel,
+                progress,
+            )?;
         } else {
             // Handle regular file
-            fs::copy(&src_path, &dst_path)?;
+            let bytes = fs::copy(&src_path, &dst_path)?;
+            preserve_mtime(&metadata, &dst_path);
+            progress.add_file(bytes);
+        }
+    }
+
+    // Set last: creating the entries above bumps the directory's mtime
+    // The copy root keeps its fresh mtime so the scenery index sees the package as changed
+    if depth > 0 {
+        if let Ok(metadata) = fs::metadata(src) {
+            preserve_mtime(&metadata, dst);
         }
     }
 
@@ -641,13 +1185,63 @@ fn copy_symlink(src: &Path, dst: &Path, base_dir: &Path, depth: usize) -> Result
     Ok(())
 }
 
+/// Create a directory symlink at `link` pointing to `target`
+#[cfg(unix)]
+pub fn create_dir_symlink(target: &Path, link: &Path) -> Result<()> {
+    std::os::unix::fs::symlink(target, link).context(format!(
+        "Failed to create directory symlink: {:?} -> {:?}",
+        link, target
+    ))
+}
+
+/// Create a directory symlink at `link` pointing to `target`
+/// Windows only allows this with Developer Mode enabled or an elevated process
+#[cfg(windows)]
+pub fn create_dir_symlink(target: &Path, link: &Path) -> Result<()> {
+    /// ERROR_PRIVILEGE_NOT_HELD
+    const PRIVILEGE_NOT_HELD: i32 = 1314;
+
+    std::os::windows::fs::symlink_dir(target, link).map_err(|e| {
+        if e.raw_os_error() == Some(PRIVILEGE_NOT_HELD) {
+            anyhow::anyhow!(
+                "Creating directory symlinks requires Windows Developer Mode or running as administrator: {:?} -> {:?}",
+                link,
+                target
+            )
+        } else {
+            anyhow::Error::new(e).context(format!(
+                "Failed to create directory symlink: {:?} -> {:?}",
+                link, target
+            ))
+        }
+    })
+}
+
+/// Remove a directory symlink without touching the folder it points to
+pub fn remove_dir_symlink(link: &Path) -> Result<()> {
+    // Windows directory symlinks are removed like directories, unix links like files
+    #[cfg(windows)]
+    let result = fs::remove_dir(link);
+    #[cfg(not(windows))]
+    let result = fs::remove_file(link);
+
+    result.context(format!("Failed to remove directory symlink: {:?}", link))
+}
+
+/// Whether `path` itself is a symbolic link
+pub fn is_symlink(path: &Path) -> bool {
+    fs::symlink_metadata(path)
+        .map(|m| m.file_type().is_symlink())
+        .unwrap_or(false)
+}
+
 /// Copy a symbolic link from src to dst (Windows version)
 /// Windows requires different functions for file vs directory symlinks
 /// Validates that the symlink target is within the base directory (security check)
 /// Includes depth tracking to prevent infinite symlink loops
 #[cfg(windows)]
 fn copy_symlink(src: &Path, dst: &Path, base_dir: &Path, depth: usize) -> Result<()> {
-    use std::os::windows::fs::{symlink_dir, symlink_file};
+    use std::os::windows::fs::symlink_file;
 
     // Security: Check depth limit for symlink resolution
     if depth > MAX_SYMLINK_DEPTH {
@@ -720,10 +1314,7 @@ fn copy_symlink(src: &Path, dst: &Path, base_dir: &Path, depth: usize) -> Result
     };
 
     if target_is_dir {
-        symlink_dir(&target, dst).context(format!(
-            "Failed to create directory symlink: {:?} -> {:?}",
-            dst, target
-        ))?;
+        create_dir_symlink(&target, dst)?;
     } else {
         symlink_file(&target, dst).context(format!(
             "Failed to create file symlink: {:?} -> {:?}",
@@ -736,18 +1327,38 @@ fn copy_symlink(src: &Path, dst: &Path, base_dir: &Path, depth: usize) -> Result
 
 /// Merge directories: move all files from src to dst, overwriting existing files
 /// TOCTOU-safe: Uses atomic operations and handles race conditions gracefully
-fn merge_directories(src: &Path, dst: &Path) -> Result<()> {
+/// Stops with InstallCancelled between files once `cancel` is set
+/// Reports merged bytes against the pre-computed source size to `on_progress`
+fn merge_directories(
+    src: &Path,
+    dst: &Path,
+    cancel: Option<&AtomicBool>,
+    on_progress: Option<CopyProgressFn>,
+) -> Result<()> {
+    let mut progress = CopyProgress::new(src, on_progress);
+    merge_directories_internal(src, dst, cancel, &mut progress)?;
+    progress.report();
+    Ok(())
+}
+
+fn merge_directories_internal(
+    src: &Path,
+    dst: &Path,
+    cancel: Option<&AtomicBool>,
+    progress: &mut CopyProgress,
+) -> Result<()> {
     // Create destination if it doesn't exist (atomic - no TOCTOU issue)
     fs::create_dir_all(dst)?;
 
     for entry in fs::read_dir(src)? {
+        check_cancel(cancel)?;
         let entry = entry?;
         let src_path = entry.path();
         let dst_path = dst.join(entry.file_name());
 
         if src_path.is_dir() {
             // Recursively merge subdirectories
-            merge_directories(&src_path, &dst_path)?;
+            merge_directories_internal(&src_path, &dst_path, cancel, progress)?;
             // Remove the now-empty source directory
             if let Err(e) = fs::remove_dir(&src_path) {
                 logger::log_error(
@@ -760,20 +1371,20 @@ fn merge_directories(src: &Path, dst: &Path) -> Result<()> {
             // Instead of checking exists() first, just try to remove and ignore NotFound
             let _ = fs::remove_file(&dst_path); // Ignore error if file doesn't exist
 
-            match fs::rename(&src_path, &dst_path) {
-                Ok(()) => {}
-                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            match move_path_cancellable(&src_path, &dst_path, cancel, None) {
+                Ok(()) => progress.add_file(size),
+                Err(e)
+                    if e.downcast_ref::<std::io::Error>()
+                        .is_some_and(|io| io.kind() == std::io::ErrorKind::NotFound) =>
+                {
                     // Source was deleted (race condition), skip this file
                     logger::log_info(
                         &format!("Source file no longer exists, skipping: {:?}", src_path),
                         Some("atomic_installer"),
                     );
                 }
-                Err(_) => {
-                    // Fallback to copy (cross-device or other error)
-                    fs::copy(&src_path, &dst_path)?;
-                    let _ = fs::remove_file(&src_path); // Best effort cleanup
-                }
+                Err(e) => return Err(e),
             }
         }
     }
@@ -796,14 +1407,9 @@ fn merge_directories_skip_existing(src: &Path, dst: &Path) -> Result<()> {
         if src_path.is_dir() {
             merge_directories_skip_existing(&src_path, &dst_path)?;
         } else {
-            // Only copy if destination doesn't exist
+            // Only move if destination doesn't exist
             if !dst_path.exists() {
-                match fs::rename(&src_path, &dst_path) {
-                    Ok(()) => {}
-                    Err(_) => {
-                        fs::copy(&src_path, &dst_path)?;
-                    }
-                }
+                move_path(&src_path, &dst_path)?;
             }
         }
     }
@@ -811,10 +1417,123 @@ fn merge_directories_skip_existing(src: &Path, dst: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Copy files under `src_root` whose path relative to it matches any of `patterns`
+/// into `dst_root`, keeping their relative location. Matching is case-insensitive.
+/// Returns the number of files copied
+pub fn copy_preserved_files(
+    src_root: &Path,
+    dst_root: &Path,
+    patterns: &[String],
+) -> Result<usize> {
+    use glob::{MatchOptions, Pattern};
+    use walkdir::WalkDir;
+
+    let compiled: Vec<Pattern> = patterns
+        .iter()
+        .filter_map(|p| Pattern::new(p).ok())
+        .collect();
+    if compiled.is_empty() || !src_root.exists() {
+        return Ok(0);
+    }
+
+    let options = MatchOptions {
+        case_sensitive: false,
+        require_literal_separator: false,
+        require_literal_leading_dot: false,
+    };
+
+    let mut copied = 0;
+    for entry in WalkDir::new(src_root)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let Ok(relative) = entry.path().strip_prefix(src_root) else {
+            continue;
+        };
+        let relative_str = relative.to_string_lossy().replace('\\', "/");
+        if !compiled
+            .iter()
+            .any(|p| p.matches_with(&relative_str, options))
+        {
+            continue;
+        }
+
+        let dst_path = dst_root.join(relative);
+        if let Some(parent) = dst_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(entry.path(), &dst_path)
+            .context(format!("Failed to copy preserved file: {:?}", relative))?;
+        if let Ok(metadata) = entry.metadata() {
+            preserve_mtime(&metadata, &dst_path);
+        }
+        copied += 1;
+    }
+
+    Ok(copied)
+}
+
+/// Error returned when the target drive cannot hold a batch of installs
+#[derive(Debug)]
+pub struct InsufficientSpace {
+    /// Bytes the batch needs, including the safety margin
+    pub required: u64,
+    /// Bytes currently available on the target drive
+    pub available: u64,
+}
+
+impl std::fmt::Display for InsufficientSpace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Insufficient disk space: {} bytes required, {} bytes available",
+            self.required, self.available
+        )
+    }
+}
+
+impl std::error::Error for InsufficientSpace {}
+
+/// Compare the size of a batch against free space, reserving MIN_FREE_SPACE_BYTES
+pub fn check_space_requirement(
+    needed: u64,
+    available: u64,
+) -> std::result::Result<(), InsufficientSpace> {
+    let required = needed.saturating_add(MIN_FREE_SPACE_BYTES);
+    if available < required {
+        return Err(InsufficientSpace {
+            required,
+            available,
+        });
+    }
+    Ok(())
+}
+
 /// Check if there's sufficient disk space for atomic installation
 /// Requires at least MIN_FREE_SPACE_BYTES (1 GB) of free space
-#[cfg(target_os = "windows")]
 fn check_disk_space(path: &Path) -> Result<()> {
+    let free_bytes = available_disk_space(path)?;
+    let free_gb = free_bytes as f64 / (1024.0 * 1024.0 * 1024.0);
+    logger::log_info(
+        &format!("Available disk space: {:.2} GB", free_gb),
+        Some("atomic_installer"),
+    );
+
+    if free_bytes < MIN_FREE_SPACE_BYTES {
+        return Err(anyhow::anyhow!(
+            "Insufficient disk space: {:.2} GB available, at least 1 GB required",
+            free_gb
+        ));
+    }
+
+    Ok(())
+}
+
+/// Get free space available to the current user on the drive containing `path`
+#[cfg(target_os = "windows")]
+pub fn available_disk_space(path: &Path) -> Result<u64> {
     use std::os::windows::ffi::OsStrExt;
     use winapi::um::fileapi::GetDiskFreeSpaceExW;
 
@@ -845,25 +1564,12 @@ fn check_disk_space(path: &Path) -> Result<()> {
         }
     }
 
-    let free_gb = free_bytes as f64 / (1024.0 * 1024.0 * 1024.0);
-    logger::log_info(
-        &format!("Available disk space: {:.2} GB", free_gb),
-        Some("atomic_installer"),
-    );
-
-    if free_bytes < MIN_FREE_SPACE_BYTES {
-        return Err(anyhow::anyhow!(
-            "Insufficient disk space: {:.2} GB available, at least 1 GB required",
-            free_gb
-        ));
-    }
-
-    Ok(())
+    Ok(free_bytes)
 }
 
-/// Check disk space (Unix/Linux/macOS - using statvfs)
+/// Get available disk space (Unix/Linux/macOS - using statvfs)
 #[cfg(not(target_os = "windows"))]
-fn check_disk_space(path: &Path) -> Result<()> {
+pub fn available_disk_space(path: &Path) -> Result<u64> {
     use std::ffi::CString;
     use std::os::unix::ffi::OsStrExt;
 
@@ -882,20 +1588,254 @@ fn check_disk_space(path: &Path) -> Result<()> {
     // Calculate available space: f_bavail * f_frsize
     // f_bavail is the number of free blocks available to non-privileged process
     // f_frsize is the fragment size (preferred block size)
-    let available_bytes = stat.f_bavail as u64 * stat.f_frsize as u64;
-    let available_gb = available_bytes as f64 / (1024.0 * 1024.0 * 1024.0);
+    Ok(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
 
-    logger::log_info(
-        &format!("Available disk space: {:.2} GB", available_gb),
-        Some("atomic_installer"),
-    );
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_cleanup_stale_temp_dirs_skips_active() {
+        let temp = TempDir::new().unwrap();
+        let stale = temp.path().join(format!("{}stale", TEMP_DIR_PREFIX));
+        let active = temp.path().join(format!("{}active", TEMP_DIR_PREFIX));
+        let unrelated = temp.path().join("Custom Scenery");
+        for dir in [&stale, &active, &unrelated] {
+            fs::create_dir_all(dir).unwrap();
+        }
+        ACTIVE_TEMP_DIRS.lock().unwrap().insert(active.clone());
 
-    if available_bytes < MIN_FREE_SPACE_BYTES {
-        return Err(anyhow::anyhow!(
-            "Insufficient disk space: {:.2} GB available, at least 1 GB required",
-            available_gb
-        ));
+        // Recently modified directories survive the regular threshold
+        assert_eq!(cleanup_stale_temp_dirs(temp.path()), 0);
+
+        assert_eq!(cleanup_temp_dirs_older_than(temp.path(), Duration::ZERO), 1);
+        assert!(!stale.exists());
+        assert!(active.exists());
+        assert!(unrelated.exists());
+
+        unregister_temp_dir(&active);
     }
 
-    Ok(())
+    #[test]
+    fn test_move_path() {
+        let temp = TempDir::new().unwrap();
+        let src = temp.path().join("src");
+        fs::create_dir_all(src.join("sub")).unwrap();
+        fs::write(src.join("sub").join("file.txt"), "data").unwrap();
+
+        let dst = temp.path().join("dst");
+        move_path(&src, &dst).unwrap();
+        assert!(!src.exists());
+        let content = fs::read_to_string(dst.join("sub/file.txt")).unwrap();
+        assert_eq!(content, "data");
+
+        assert!(move_path(&src, &temp.path().join("other")).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_move_by_copy_preserves_symlinks() {
+        let temp = TempDir::new().unwrap();
+        let src = temp.path().join("src");
+        fs::create_dir_all(src.join("real")).unwrap();
+        fs::write(src.join("real").join("file.txt"), "data").unwrap();
+        std::os::unix::fs::symlink("real", src.join("link")).unwrap();
+
+        let dst = temp.path().join("dst");
+        move_by_copy(&src, &dst, None, None).unwrap();
+        assert!(!src.exists());
+        assert!(fs::symlink_metadata(dst.join("link"))
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        assert_eq!(fs::read_link(dst.join("link")).unwrap(), Path::new("real"));
+    }
+
+    #[test]
+    fn test_copy_directory_preserves_mtimes() {
+        let temp = TempDir::new().unwrap();
+        let src = temp.path().join("src");
+        fs::create_dir_all(src.join("Earth nav data")).unwrap();
+        fs::write(src.join("Earth nav data").join("tile.dsf"), "data").unwrap();
+        let old = filetime::FileTime::from_unix_time(1_600_000_000, 0);
+        filetime::set_file_mtime(src.join("Earth nav data").join("tile.dsf"), old).unwrap();
+        filetime::set_file_mtime(src.join("Earth nav data"), old).unwrap();
+
+        let dst = temp.path().join("dst");
+        copy_directory_recursive(&src, &dst, None, None).unwrap();
+
+        for relative in ["Earth nav data", "Earth nav data/tile.dsf"] {
+            let metadata = fs::metadata(dst.join(relative)).unwrap();
+            assert_eq!(
+                filetime::FileTime::from_last_modification_time(&metadata),
+                old
+            );
+        }
+    }
+
+    #[test]
+    fn test_reinstall_from_older_source_is_reclassified() {
+        let temp = TempDir::new().unwrap();
+        crate::app_dirs::set_test_app_data_dir(&temp.path().join("app_data"));
+        let xplane_path = temp.path().join("X-Plane");
+        let target = xplane_path.join("Custom Scenery").join("Library");
+        fs::create_dir_all(&target).unwrap();
+        fs::write(target.join("library.txt"), "EXPORT first/a.obj a.obj\n").unwrap();
+
+        let manager = crate::scenery_index::SceneryIndexManager::new(&xplane_path);
+        manager.update_index().unwrap();
+
+        // An extracted update whose files all predate the first install
+        let src = temp.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("library.txt"), "EXPORT second/b.obj b.obj\n").unwrap();
+        let old = filetime::FileTime::from_unix_time(1_600_000_000, 0);
+        filetime::set_file_mtime(src.join("library.txt"), old).unwrap();
+        filetime::set_file_mtime(&src, old).unwrap();
+
+        fs::remove_dir_all(&target).unwrap();
+        copy_directory_recursive(&src, &target, None, None).unwrap();
+
+        let index = manager.update_index().unwrap();
+        assert_eq!(
+            index.packages["Library"].exported_library_names,
+            vec!["second".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_dir_symlink_roundtrip() {
+        let temp = TempDir::new().unwrap();
+        let ortho = temp.path().join("ortho");
+        fs::create_dir_all(&ortho).unwrap();
+        fs::write(ortho.join("file.dsf"), "data").unwrap();
+
+        let link = temp.path().join("Custom Scenery").join("ortho");
+        fs::create_dir_all(link.parent().unwrap()).unwrap();
+        create_dir_symlink(&ortho, &link).unwrap();
+        assert!(is_symlink(&link));
+        assert!(link.join("file.dsf").is_file());
+
+        // Removing the link leaves the linked folder alone
+        remove_dir_symlink(&link).unwrap();
+        assert!(!is_symlink(&link));
+        assert!(ortho.join("file.dsf").is_file());
+        assert!(!is_symlink(&ortho));
+    }
+
+    #[test]
+    fn test_copy_directory_reports_progress() {
+        let temp = TempDir::new().unwrap();
+        let src = temp.path().join("src");
+        fs::create_dir_all(src.join("sub")).unwrap();
+        for i in 0..COPY_PROGRESS_FILES + 1 {
+            fs::write(src.join("sub").join(format!("{}.txt", i)), "abcd").unwrap();
+        }
+        let total = (COPY_PROGRESS_FILES + 1) * 4;
+
+        let updates = Mutex::new(Vec::new());
+        let on_progress = |processed: u64, total: u64| {
+            updates.lock().unwrap().push((processed, total));
+        };
+        let dst = temp.path().join("dst");
+        copy_directory_recursive(&src, &dst, None, Some(&on_progress)).unwrap();
+
+        let updates = updates.into_inner().unwrap();
+        assert_eq!(updates.len(), 2);
+        assert_eq!(updates[0], (COPY_PROGRESS_FILES * 4, total));
+        assert_eq!(updates.last(), Some(&(total, total)));
+    }
+
+    #[test]
+    fn test_cancelled_merge_and_copy() {
+        let temp = TempDir::new().unwrap();
+        let src = temp.path().join("src");
+        fs::create_dir_all(src.join("sub")).unwrap();
+        fs::write(src.join("sub").join("file.txt"), "new").unwrap();
+        let dst = temp.path().join("dst");
+        fs::create_dir_all(&dst).unwrap();
+        fs::write(dst.join("keep.txt"), "old").unwrap();
+
+        let cancel = AtomicBool::new(true);
+        let err = merge_directories(&src, &dst, Some(&cancel), None).unwrap_err();
+        assert!(is_install_cancelled(&err));
+        assert!(src.join("sub/file.txt").exists());
+        assert!(!dst.join("sub").exists());
+
+        // A cancelled copy fallback leaves no partial destination behind
+        let copy_dst = temp.path().join("copy");
+        let err = move_by_copy(&src, &copy_dst, Some(&cancel), None).unwrap_err();
+        assert!(is_install_cancelled(&err));
+        assert!(!copy_dst.exists());
+        assert!(src.exists());
+
+        cancel.store(false, Ordering::SeqCst);
+        let updates = Mutex::new(Vec::new());
+        let on_progress = |processed: u64, total: u64| {
+            updates.lock().unwrap().push((processed, total));
+        };
+        merge_directories(&src, &dst, Some(&cancel), Some(&on_progress)).unwrap();
+        assert_eq!(updates.into_inner().unwrap(), vec![(3, 3)]);
+        assert_eq!(fs::read_to_string(dst.join("sub/file.txt")).unwrap(), "new");
+        assert_eq!(fs::read_to_string(dst.join("keep.txt")).unwrap(), "old");
+    }
+
+    #[test]
+    fn test_copy_preserved_files() {
+        let temp = TempDir::new().unwrap();
+        let src = temp.path().join("src");
+        let dst = temp.path().join("dst");
+        fs::create_dir_all(src.join("plugins").join("settings")).unwrap();
+        fs::write(src.join("plugins").join("settings").join("User.INI"), "a").unwrap();
+        fs::write(src.join("settings.json"), "b").unwrap();
+        fs::write(src.join("Earth nav data.dsf"), "c").unwrap();
+
+        let patterns = vec!["*.ini".to_string(), "settings.json".to_string()];
+        let copied = copy_preserved_files(&src, &dst, &patterns).unwrap();
+
+        assert_eq!(copied, 2);
+        assert!(dst.join("plugins/settings/User.INI").exists());
+        assert!(dst.join("settings.json").exists());
+        assert!(!dst.join("Earth nav data.dsf").exists());
+        let missing = dst.join("missing");
+        assert_eq!(copy_preserved_files(&missing, &dst, &patterns).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_verify_tree_detects_truncation() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join("addon");
+        fs::create_dir_all(dir.join("objects")).unwrap();
+        fs::write(dir.join("a.acf"), b"aircraft").unwrap();
+        fs::write(dir.join("objects/b.obj"), b"object").unwrap();
+
+        let staged = tree_stats(&dir, None).unwrap();
+        assert_eq!(staged.files, 2);
+        assert_eq!(staged.bytes, 14);
+        assert!(verify_tree(&dir, staged, None).is_ok());
+
+        // Truncated file
+        fs::write(dir.join("objects/b.obj"), b"obj").unwrap();
+        assert!(verify_tree(&dir, staged, None).is_err());
+
+        // Missing file
+        fs::write(dir.join("objects/b.obj"), b"object").unwrap();
+        fs::remove_file(dir.join("a.acf")).unwrap();
+        assert!(verify_tree(&dir, staged, None).is_err());
+    }
+
+    #[test]
+    fn test_check_space_requirement() {
+        let needed = 500 * 1024 * 1024;
+        assert!(check_space_requirement(needed, needed + MIN_FREE_SPACE_BYTES).is_ok());
+
+        let err = check_space_requirement(needed, needed).unwrap_err();
+        assert_eq!(err.required, needed + MIN_FREE_SPACE_BYTES);
+        assert_eq!(err.available, needed);
+        assert!(err.to_string().contains("bytes required"));
+
+        assert!(check_space_requirement(u64::MAX, u64::MAX).is_ok());
+    }
 }
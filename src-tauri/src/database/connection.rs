@@ -42,7 +42,25 @@ pub fn get_database_path() -> PathBuf {
     app_dirs::get_database_path()
 }
 
+/// Size of the database on disk, including the WAL file if present
+pub fn get_database_file_size() -> u64 {
+    let db_path = get_database_path();
+    let mut wal_path = db_path.clone().into_os_string();
+    wal_path.push("-wal");
+    [db_path, PathBuf::from(wal_path)]
+        .iter()
+        .filter_map(|p| std::fs::metadata(p).ok())
+        .map(|m| m.len())
+        .sum()
+}
+
 /// Configure database pragmas for optimal performance
+///
+/// WAL lets readers keep working while batch_update_entries/save_all rewrite the index,
+/// at the cost of `-wal`/`-shm` sidecar files next to the database and no support for
+/// databases on network shares. With WAL, synchronous=NORMAL only risks losing the most
+/// recent commits on power loss (never corruption), which is fine for a rebuildable index.
+/// On in-memory databases journal_mode=WAL is a no-op and stays "memory".
 fn configure_pragmas(conn: &Connection) -> Result<(), ApiError> {
     // Performance optimizations:
     // - WAL mode: Better concurrent read/write performance
@@ -101,12 +119,8 @@ pub fn open_memory_connection() -> Result<DatabaseConnection, ApiError> {
     let conn = Connection::open_in_memory()
         .map_err(|e| ApiError::database(format!("Failed to open in-memory database: {}", e)))?;
 
-    conn.execute_batch(
-        "
-        PRAGMA foreign_keys=ON;
-        ",
-    )
-    .map_err(|e| ApiError::database(format!("Failed to configure database: {}", e)))?;
+    // Same pragmas as on disk so tests exercise the production configuration
+    configure_pragmas(&conn)?;
 
     Ok(DatabaseConnection::new(conn))
 }
@@ -129,5 +143,31 @@ mod tests {
             .query_row("PRAGMA foreign_keys", [], |row| row.get(0))
             .unwrap();
         assert_eq!(fk_enabled, 1);
+
+        // WAL is a no-op for in-memory databases
+        let journal_mode: String = conn
+            .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(journal_mode, "memory");
+        let busy_timeout: i64 = conn
+            .query_row("PRAGMA busy_timeout", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(busy_timeout, 5000);
+    }
+
+    #[test]
+    fn test_file_connection_uses_wal() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let conn = Connection::open(temp.path().join("test.db")).unwrap();
+        configure_pragmas(&conn).unwrap();
+
+        let journal_mode: String = conn
+            .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(journal_mode, "wal");
+        let synchronous: i64 = conn
+            .query_row("PRAGMA synchronous", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(synchronous, 1); // NORMAL
     }
 }
@@ -1,6 +1,10 @@
 //! Database schema migrations
 
-use super::schema::{CREATE_SCHEMA, CURRENT_SCHEMA_VERSION, GET_SCHEMA_VERSION, INSERT_SCHEMA_VERSION};
+use super::schema::{
+    ADD_AIRPORT_DETAILS_COLUMNS, ADD_CATEGORY_LOCKED_COLUMN, ADD_ICAO_CODE_COLUMN,
+    ADD_ICAO_MISMATCH_COLUMN, ADD_INSTALL_MARKER_COLUMN, CREATE_SCHEMA, CREATE_TILE_COVERAGE,
+    CURRENT_SCHEMA_VERSION, GET_SCHEMA_VERSION, INSERT_SCHEMA_VERSION,
+};
 use crate::error::ApiError;
 use crate::logger;
 use rusqlite::Connection;
@@ -66,9 +70,11 @@ pub fn apply_migrations(conn: &Connection) -> Result<(), ApiError> {
 }
 
 /// Create the initial database schema
+/// CREATE_SCHEMA is the version 1 schema; later versions are applied on top of it
 fn create_initial_schema(conn: &Connection) -> Result<(), ApiError> {
     conn.execute_batch(CREATE_SCHEMA)
         .map_err(|e| ApiError::migration_failed(format!("Failed to create database schema: {}", e)))?;
+    upgrade_schema(conn, 1)?;
 
     // Record the schema version
     let now = SystemTime::now()
@@ -92,18 +98,13 @@ fn create_initial_schema(conn: &Connection) -> Result<(), ApiError> {
 
 /// Apply incremental migrations from a given version
 fn apply_version_migrations(conn: &Connection, from_version: i32) -> Result<(), ApiError> {
-    // Currently no migrations needed since we're at version 1
-    // Future migrations would be applied here based on from_version
+    upgrade_schema(conn, from_version)?;
 
     let now = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap()
         .as_secs() as i64;
 
-    // For future migrations, add match arms like:
-    // if from_version < 2 { migrate_v1_to_v2(conn)?; }
-    // if from_version < 3 { migrate_v2_to_v3(conn)?; }
-
     // Record the final version
     conn.execute(
         INSERT_SCHEMA_VERSION,
@@ -124,6 +125,73 @@ fn apply_version_migrations(conn: &Connection, from_version: i32) -> Result<(),
     Ok(())
 }
 
+/// Apply the schema changes of every version after `from_version`
+fn upgrade_schema(conn: &Connection, from_version: i32) -> Result<(), ApiError> {
+    // For future migrations, add arms like:
+    // if from_version < 8 { migrate_v7_to_v8(conn)?; }
+    if from_version < 2 {
+        migrate_v1_to_v2(conn)?;
+    }
+    if from_version < 3 {
+        migrate_v2_to_v3(conn)?;
+    }
+    if from_version < 4 {
+        migrate_v3_to_v4(conn)?;
+    }
+    if from_version < 5 {
+        migrate_v4_to_v5(conn)?;
+    }
+    if from_version < 6 {
+        migrate_v5_to_v6(conn)?;
+    }
+    if from_version < 7 {
+        migrate_v6_to_v7(conn)?;
+    }
+    Ok(())
+}
+
+/// Version 2: tile coverage cache
+fn migrate_v1_to_v2(conn: &Connection) -> Result<(), ApiError> {
+    conn.execute_batch(CREATE_TILE_COVERAGE).map_err(|e| {
+        ApiError::migration_failed(format!("Failed to create tile_coverage table: {}", e))
+    })
+}
+
+/// Version 3: airport ICAO code (existing rows keep their data, icao_code starts as NULL)
+fn migrate_v2_to_v3(conn: &Connection) -> Result<(), ApiError> {
+    conn.execute_batch(ADD_ICAO_CODE_COLUMN)
+        .map_err(|e| ApiError::migration_failed(format!("Failed to add icao_code column: {}", e)))
+}
+
+/// Version 4: airport name and elevation (NULL until the package is re-indexed)
+fn migrate_v3_to_v4(conn: &Connection) -> Result<(), ApiError> {
+    conn.execute_batch(ADD_AIRPORT_DETAILS_COLUMNS)
+        .map_err(|e| {
+            ApiError::migration_failed(format!("Failed to add airport detail columns: {}", e))
+        })
+}
+
+/// Version 5: category lock on packages (unlocked until the user picks a category)
+fn migrate_v4_to_v5(conn: &Connection) -> Result<(), ApiError> {
+    conn.execute_batch(ADD_CATEGORY_LOCKED_COLUMN).map_err(|e| {
+        ApiError::migration_failed(format!("Failed to add category_locked column: {}", e))
+    })
+}
+
+/// Version 6: airport ICAO mismatch flag (false until the package is re-indexed)
+fn migrate_v5_to_v6(conn: &Connection) -> Result<(), ApiError> {
+    conn.execute_batch(ADD_ICAO_MISMATCH_COLUMN).map_err(|e| {
+        ApiError::migration_failed(format!("Failed to add icao_mismatch column: {}", e))
+    })
+}
+
+/// Version 7: cached install marker (NULL until the package is re-indexed)
+fn migrate_v6_to_v7(conn: &Connection) -> Result<(), ApiError> {
+    conn.execute_batch(ADD_INSTALL_MARKER_COLUMN).map_err(|e| {
+        ApiError::migration_failed(format!("Failed to add install_marker column: {}", e))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -151,4 +219,103 @@ mod tests {
         let version = get_current_version(&conn).unwrap();
         assert_eq!(version, Some(CURRENT_SCHEMA_VERSION));
     }
+
+    #[test]
+    fn test_migrate_from_v1() {
+        let conn = open_memory_connection().unwrap();
+
+        // Simulate a version 1 database with one package
+        conn.execute_batch(CREATE_SCHEMA).unwrap();
+        conn.execute(
+            INSERT_SCHEMA_VERSION,
+            rusqlite::params![1, 0, "Initial schema"],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO scenery_packages (folder_name, category, last_modified, indexed_at)
+             VALUES ('KSEA', 'Airport', 0, 0)",
+            [],
+        )
+        .unwrap();
+
+        apply_migrations(&conn).expect("Migration failed");
+
+        assert_eq!(
+            get_current_version(&conn).unwrap(),
+            Some(CURRENT_SCHEMA_VERSION)
+        );
+        let has_table: bool = conn
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type='table' AND name='tile_coverage'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(has_table);
+
+        // Existing rows are kept, with no ICAO code or airport details yet
+        let (icao_code, airport_name, airport_elevation_ft, icao_mismatch, install_marker): (
+            Option<String>,
+            Option<String>,
+            Option<i32>,
+            bool,
+            Option<String>,
+        ) = conn
+            .query_row(
+                "SELECT icao_code, airport_name, airport_elevation_ft, icao_mismatch, install_marker
+                 FROM scenery_packages WHERE folder_name = 'KSEA'",
+                [],
+                |row| {
+                    Ok((
+                        row.get(0)?,
+                        row.get(1)?,
+                        row.get(2)?,
+                        row.get(3)?,
+                        row.get(4)?,
+                    ))
+                },
+            )
+            .unwrap();
+        assert_eq!(icao_code, None);
+        assert_eq!(airport_name, None);
+        assert_eq!(airport_elevation_ft, None);
+        assert!(!icao_mismatch);
+        assert_eq!(install_marker, None);
+    }
+
+    #[test]
+    fn test_migrate_from_v4_adds_category_lock() {
+        let conn = open_memory_connection().unwrap();
+
+        // Simulate a version 4 database with one package
+        conn.execute_batch(CREATE_SCHEMA).unwrap();
+        migrate_v1_to_v2(&conn).unwrap();
+        migrate_v2_to_v3(&conn).unwrap();
+        migrate_v3_to_v4(&conn).unwrap();
+        conn.execute(
+            INSERT_SCHEMA_VERSION,
+            rusqlite::params![4, 0, "Migration completed"],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO scenery_packages (folder_name, category, last_modified, indexed_at)
+             VALUES ('Misdetected', 'Overlay', 0, 0)",
+            [],
+        )
+        .unwrap();
+
+        apply_migrations(&conn).expect("Migration failed");
+
+        // Existing rows keep their category and start unlocked
+        let (category, category_locked): (String, bool) = conn
+            .query_row(
+                "SELECT category, category_locked FROM scenery_packages
+                 WHERE folder_name = 'Misdetected'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(category, "Overlay");
+        assert!(!category_locked);
+    }
 }
@@ -2,8 +2,8 @@
 
 use crate::error::ApiError;
 use crate::logger;
-use crate::models::{SceneryCategory, SceneryIndex, SceneryPackageInfo};
-use rusqlite::{params, Connection, Transaction};
+use crate::models::{InstallMarker, SceneryCategory, SceneryIndex, SceneryPackageInfo};
+use rusqlite::{params, Connection, Row, Transaction};
 use std::collections::HashMap;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
@@ -53,6 +53,46 @@ fn string_to_category(s: &str) -> SceneryCategory {
     }
 }
 
+/// Serialize an install marker for the install_marker column
+fn marker_to_json(marker: &Option<InstallMarker>) -> Option<String> {
+    marker.as_ref().and_then(|m| serde_json::to_string(m).ok())
+}
+
+/// Map a scenery_packages row (columns in load_all order) to its id and package info
+/// Library lists are left empty for the caller to fill in
+fn package_from_row(row: &Row) -> rusqlite::Result<(i64, SceneryPackageInfo)> {
+    let category_str: String = row.get(2)?;
+    let info = SceneryPackageInfo {
+        folder_name: row.get(1)?,
+        category: string_to_category(&category_str),
+        sub_priority: row.get(3)?,
+        last_modified: unix_to_systemtime(row.get(4)?),
+        indexed_at: unix_to_systemtime(row.get(5)?),
+        has_apt_dat: row.get(6)?,
+        has_dsf: row.get(7)?,
+        has_library_txt: row.get(8)?,
+        has_textures: row.get(9)?,
+        has_objects: row.get(10)?,
+        texture_count: row.get(11)?,
+        earth_nav_tile_count: row.get(12)?,
+        enabled: row.get(13)?,
+        sort_order: row.get(14)?,
+        required_libraries: Vec::new(),
+        missing_libraries: Vec::new(),
+        exported_library_names: Vec::new(),
+        actual_path: row.get(15)?,
+        icao_code: row.get(16)?,
+        airport_name: row.get(17)?,
+        airport_elevation_ft: row.get(18)?,
+        category_locked: row.get(19)?,
+        icao_mismatch: row.get(20)?,
+        install_marker: row
+            .get::<_, Option<String>>(21)?
+            .and_then(|json| serde_json::from_str(&json).ok()),
+    };
+    Ok((row.get(0)?, info))
+}
+
 /// Scenery database query operations
 pub struct SceneryQueries;
 
@@ -66,32 +106,15 @@ impl SceneryQueries {
             .prepare(
                 "SELECT id, folder_name, category, sub_priority, last_modified, indexed_at,
                         has_apt_dat, has_dsf, has_library_txt, has_textures, has_objects,
-                        texture_count, earth_nav_tile_count, enabled, sort_order, actual_path
+                        texture_count, earth_nav_tile_count, enabled, sort_order, actual_path,
+                        icao_code, airport_name, airport_elevation_ft, category_locked,
+                        icao_mismatch, install_marker
                  FROM scenery_packages",
             )
             .map_err(|e| ApiError::database(format!("Failed to prepare query: {}", e)))?;
 
         let package_rows = stmt
-            .query_map([], |row| {
-                Ok((
-                    row.get::<_, i64>(0)?,  // id
-                    row.get::<_, String>(1)?,  // folder_name
-                    row.get::<_, String>(2)?,  // category
-                    row.get::<_, u8>(3)?,      // sub_priority
-                    row.get::<_, i64>(4)?,     // last_modified
-                    row.get::<_, i64>(5)?,     // indexed_at
-                    row.get::<_, bool>(6)?,    // has_apt_dat
-                    row.get::<_, bool>(7)?,    // has_dsf
-                    row.get::<_, bool>(8)?,    // has_library_txt
-                    row.get::<_, bool>(9)?,    // has_textures
-                    row.get::<_, bool>(10)?,   // has_objects
-                    row.get::<_, usize>(11)?,  // texture_count
-                    row.get::<_, u32>(12)?,    // earth_nav_tile_count
-                    row.get::<_, bool>(13)?,   // enabled
-                    row.get::<_, u32>(14)?,    // sort_order
-                    row.get::<_, Option<String>>(15)?, // actual_path
-                ))
-            })
+            .query_map([], package_from_row)
             .map_err(|e| ApiError::database(format!("Failed to query packages: {}", e)))?;
 
         // Collect package data with their IDs for library queries
@@ -100,48 +123,7 @@ impl SceneryQueries {
         for row_result in package_rows {
             let row = row_result
                 .map_err(|e| ApiError::database(format!("Failed to read package row: {}", e)))?;
-
-            let (
-                id,
-                folder_name,
-                category_str,
-                sub_priority,
-                last_modified,
-                indexed_at,
-                has_apt_dat,
-                has_dsf,
-                has_library_txt,
-                has_textures,
-                has_objects,
-                texture_count,
-                earth_nav_tile_count,
-                enabled,
-                sort_order,
-                actual_path,
-            ) = row;
-
-            let info = SceneryPackageInfo {
-                folder_name: folder_name.clone(),
-                category: string_to_category(&category_str),
-                sub_priority,
-                last_modified: unix_to_systemtime(last_modified),
-                indexed_at: unix_to_systemtime(indexed_at),
-                has_apt_dat,
-                has_dsf,
-                has_library_txt,
-                has_textures,
-                has_objects,
-                texture_count,
-                earth_nav_tile_count,
-                enabled,
-                sort_order,
-                required_libraries: Vec::new(),
-                missing_libraries: Vec::new(),
-                exported_library_names: Vec::new(),
-                actual_path,
-            };
-
-            package_data.push((id, info));
+            package_data.push(row);
         }
 
         // Load libraries for all packages in batch
@@ -181,6 +163,95 @@ impl SceneryQueries {
         })
     }
 
+    /// Load the packages of a single category, sorted by sort_order
+    /// Only the matching rows (and their libraries) are read from the database
+    pub fn load_by_category(
+        conn: &Connection,
+        category: &SceneryCategory,
+    ) -> Result<Vec<SceneryPackageInfo>, ApiError> {
+        let category_str = category_to_string(category);
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, folder_name, category, sub_priority, last_modified, indexed_at,
+                        has_apt_dat, has_dsf, has_library_txt, has_textures, has_objects,
+                        texture_count, earth_nav_tile_count, enabled, sort_order, actual_path,
+                        icao_code, airport_name, airport_elevation_ft, category_locked,
+                        icao_mismatch, install_marker
+                 FROM scenery_packages
+                 WHERE category = ?1
+                 ORDER BY sort_order, folder_name",
+            )
+            .map_err(|e| ApiError::database(format!("Failed to prepare query: {}", e)))?;
+
+        let rows = stmt
+            .query_map(params![category_str], package_from_row)
+            .map_err(|e| ApiError::database(format!("Failed to query packages: {}", e)))?;
+
+        let mut package_data: Vec<(i64, SceneryPackageInfo)> = Vec::new();
+        for row_result in rows {
+            let row = row_result
+                .map_err(|e| ApiError::database(format!("Failed to read package row: {}", e)))?;
+            package_data.push(row);
+        }
+
+        let required_libs =
+            Self::load_category_libraries(conn, "required_libraries", category_str)?;
+        let missing_libs = Self::load_category_libraries(conn, "missing_libraries", category_str)?;
+        let exported_libs =
+            Self::load_category_libraries(conn, "exported_libraries", category_str)?;
+
+        Ok(package_data
+            .into_iter()
+            .map(|(id, mut info)| {
+                if let Some(libs) = required_libs.get(&id) {
+                    info.required_libraries = libs.clone();
+                }
+                if let Some(libs) = missing_libs.get(&id) {
+                    info.missing_libraries = libs.clone();
+                }
+                if let Some(libs) = exported_libs.get(&id) {
+                    info.exported_library_names = libs.clone();
+                }
+                info
+            })
+            .collect())
+    }
+
+    /// Load the libraries of every package in one category from a library table
+    fn load_category_libraries(
+        conn: &Connection,
+        table_name: &str,
+        category_str: &str,
+    ) -> Result<HashMap<i64, Vec<String>>, ApiError> {
+        let mut result: HashMap<i64, Vec<String>> = HashMap::new();
+
+        let query = format!(
+            "SELECT l.package_id, l.library_name FROM {} l
+             JOIN scenery_packages p ON p.id = l.package_id
+             WHERE p.category = ?1
+             ORDER BY l.package_id, l.id",
+            table_name
+        );
+        let mut stmt = conn
+            .prepare(&query)
+            .map_err(|e| ApiError::database(format!("Failed to prepare library query: {}", e)))?;
+
+        let rows = stmt
+            .query_map(params![category_str], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(|e| ApiError::database(format!("Failed to query libraries: {}", e)))?;
+
+        for row_result in rows {
+            let (package_id, library_name) = row_result
+                .map_err(|e| ApiError::database(format!("Failed to read library row: {}", e)))?;
+            result.entry(package_id).or_default().push(library_name);
+        }
+
+        Ok(result)
+    }
+
     /// Load all libraries from a library table
     fn load_all_libraries(
         conn: &Connection,
@@ -212,7 +283,7 @@ impl SceneryQueries {
     }
 
     /// Get metadata value by key
-    fn get_metadata(conn: &Connection, key: &str) -> Result<Option<String>, ApiError> {
+    pub fn get_metadata(conn: &Connection, key: &str) -> Result<Option<String>, ApiError> {
         let result: Option<String> = conn
             .query_row(
                 "SELECT value FROM index_metadata WHERE key = ?1",
@@ -224,7 +295,7 @@ impl SceneryQueries {
     }
 
     /// Set metadata value
-    fn set_metadata(conn: &Connection, key: &str, value: &str) -> Result<(), ApiError> {
+    pub fn set_metadata(conn: &Connection, key: &str, value: &str) -> Result<(), ApiError> {
         conn.execute(
             "INSERT OR REPLACE INTO index_metadata (key, value) VALUES (?1, ?2)",
             params![key, value],
@@ -233,6 +304,14 @@ impl SceneryQueries {
         Ok(())
     }
 
+    /// Delete metadata value by key
+    pub fn delete_metadata(conn: &Connection, key: &str) -> Result<bool, ApiError> {
+        let rows_affected = conn
+            .execute("DELETE FROM index_metadata WHERE key = ?1", params![key])
+            .map_err(|e| ApiError::database(format!("Failed to delete metadata: {}", e)))?;
+        Ok(rows_affected > 0)
+    }
+
     /// Save a complete SceneryIndex to the database (replaces all data)
     /// Uses prepared statements and batch operations for optimal performance
     pub fn save_all(conn: &mut Connection, index: &SceneryIndex) -> Result<(), ApiError> {
@@ -254,8 +333,11 @@ impl SceneryQueries {
             "INSERT INTO scenery_packages (
                 folder_name, category, sub_priority, last_modified, indexed_at,
                 has_apt_dat, has_dsf, has_library_txt, has_textures, has_objects,
-                texture_count, earth_nav_tile_count, enabled, sort_order, actual_path
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)"
+                texture_count, earth_nav_tile_count, enabled, sort_order, actual_path,
+                icao_code, airport_name, airport_elevation_ft, category_locked,
+                icao_mismatch, install_marker
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16,
+                      ?17, ?18, ?19, ?20, ?21)"
         ).map_err(|e| ApiError::database(format!("Failed to prepare package statement: {}", e)))?;
 
         let mut req_lib_stmt = tx.prepare_cached(
@@ -288,6 +370,12 @@ impl SceneryQueries {
                 info.enabled,
                 info.sort_order,
                 &info.actual_path,
+                &info.icao_code,
+                &info.airport_name,
+                info.airport_elevation_ft,
+                info.category_locked,
+                info.icao_mismatch,
+                marker_to_json(&info.install_marker),
             ]).map_err(|e| ApiError::database(format!("Failed to insert package: {}", e)))?;
 
             let package_id = tx.last_insert_rowid();
@@ -335,8 +423,11 @@ impl SceneryQueries {
             "INSERT INTO scenery_packages (
                 folder_name, category, sub_priority, last_modified, indexed_at,
                 has_apt_dat, has_dsf, has_library_txt, has_textures, has_objects,
-                texture_count, earth_nav_tile_count, enabled, sort_order, actual_path
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+                texture_count, earth_nav_tile_count, enabled, sort_order, actual_path,
+                icao_code, airport_name, airport_elevation_ft, category_locked,
+                icao_mismatch, install_marker
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16,
+                      ?17, ?18, ?19, ?20, ?21)",
             params![
                 info.folder_name,
                 category_to_string(&info.category),
@@ -353,6 +444,12 @@ impl SceneryQueries {
                 info.enabled,
                 info.sort_order,
                 &info.actual_path,
+                &info.icao_code,
+                &info.airport_name,
+                info.airport_elevation_ft,
+                info.category_locked,
+                info.icao_mismatch,
+                marker_to_json(&info.install_marker),
             ],
         )
         .map_err(|e| ApiError::database(format!("Failed to insert package: {}", e)))?;
@@ -414,7 +511,9 @@ impl SceneryQueries {
                     category = ?2, sub_priority = ?3, last_modified = ?4, indexed_at = ?5,
                     has_apt_dat = ?6, has_dsf = ?7, has_library_txt = ?8, has_textures = ?9,
                     has_objects = ?10, texture_count = ?11, earth_nav_tile_count = ?12,
-                    enabled = ?13, sort_order = ?14, actual_path = ?15
+                    enabled = ?13, sort_order = ?14, actual_path = ?15, icao_code = ?16,
+                    airport_name = ?17, airport_elevation_ft = ?18, category_locked = ?19,
+                    icao_mismatch = ?20, install_marker = ?21
                  WHERE id = ?1",
                 params![
                     id,
@@ -432,6 +531,12 @@ impl SceneryQueries {
                     info.enabled,
                     info.sort_order,
                     &info.actual_path,
+                    &info.icao_code,
+                    &info.airport_name,
+                    info.airport_elevation_ft,
+                    info.category_locked,
+                    info.icao_mismatch,
+                    marker_to_json(&info.install_marker),
                 ],
             )
             .map_err(|e| ApiError::database(format!("Failed to update package: {}", e)))?;
@@ -510,76 +615,21 @@ impl SceneryQueries {
         conn: &Connection,
         folder_name: &str,
     ) -> Result<Option<SceneryPackageInfo>, ApiError> {
-        let row: Option<(i64, String, String, u8, i64, i64, bool, bool, bool, bool, bool, usize, u32, bool, u32, Option<String>)> = conn
+        let row = conn
             .query_row(
                 "SELECT id, folder_name, category, sub_priority, last_modified, indexed_at,
                         has_apt_dat, has_dsf, has_library_txt, has_textures, has_objects,
-                        texture_count, earth_nav_tile_count, enabled, sort_order, actual_path
+                        texture_count, earth_nav_tile_count, enabled, sort_order, actual_path,
+                        icao_code, airport_name, airport_elevation_ft, category_locked,
+                        icao_mismatch, install_marker
                  FROM scenery_packages WHERE folder_name = ?1",
                 params![folder_name],
-                |row| {
-                    Ok((
-                        row.get(0)?,
-                        row.get(1)?,
-                        row.get(2)?,
-                        row.get(3)?,
-                        row.get(4)?,
-                        row.get(5)?,
-                        row.get(6)?,
-                        row.get(7)?,
-                        row.get(8)?,
-                        row.get(9)?,
-                        row.get(10)?,
-                        row.get(11)?,
-                        row.get(12)?,
-                        row.get(13)?,
-                        row.get(14)?,
-                        row.get(15)?,
-                    ))
-                },
+                package_from_row,
             )
             .ok();
 
         match row {
-            Some((
-                id,
-                folder_name,
-                category_str,
-                sub_priority,
-                last_modified,
-                indexed_at,
-                has_apt_dat,
-                has_dsf,
-                has_library_txt,
-                has_textures,
-                has_objects,
-                texture_count,
-                earth_nav_tile_count,
-                enabled,
-                sort_order,
-                actual_path,
-            )) => {
-                let mut info = SceneryPackageInfo {
-                    folder_name,
-                    category: string_to_category(&category_str),
-                    sub_priority,
-                    last_modified: unix_to_systemtime(last_modified),
-                    indexed_at: unix_to_systemtime(indexed_at),
-                    has_apt_dat,
-                    has_dsf,
-                    has_library_txt,
-                    has_textures,
-                    has_objects,
-                    texture_count,
-                    earth_nav_tile_count,
-                    enabled,
-                    sort_order,
-                    required_libraries: Vec::new(),
-                    missing_libraries: Vec::new(),
-                    exported_library_names: Vec::new(),
-                    actual_path,
-                };
-
+            Some((id, mut info)) => {
                 // Load libraries
                 info.required_libraries = Self::load_package_libraries(conn, id, "required_libraries")?;
                 info.missing_libraries = Self::load_package_libraries(conn, id, "missing_libraries")?;
@@ -719,6 +769,183 @@ impl SceneryQueries {
         Ok(())
     }
 
+    /// Set the enabled state of many packages in a single transaction
+    /// sort_order is left untouched. Returns the number of packages whose state changed
+    pub fn set_enabled_bulk(
+        conn: &mut Connection,
+        folder_names: &[String],
+        enabled: bool,
+    ) -> Result<usize, ApiError> {
+        let tx = conn
+            .transaction()
+            .map_err(|e| ApiError::database(format!("Failed to start transaction: {}", e)))?;
+
+        let mut changed = 0;
+        {
+            let mut stmt = tx
+                .prepare_cached(
+                    "UPDATE scenery_packages SET enabled = ?1 WHERE folder_name = ?2 AND enabled != ?1",
+                )
+                .map_err(|e| ApiError::database(format!("Failed to prepare update statement: {}", e)))?;
+
+            for folder_name in folder_names {
+                changed += stmt.execute(params![enabled, folder_name]).map_err(|e| {
+                    ApiError::database(format!("Failed to update enabled state: {}", e))
+                })?;
+            }
+        }
+
+        Self::set_metadata(
+            &tx,
+            "last_updated",
+            &systemtime_to_unix(&SystemTime::now()).to_string(),
+        )?;
+
+        tx.commit()
+            .map_err(|e| ApiError::database(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(changed)
+    }
+
+    /// Assign sort_order from the position of each folder name in the list
+    /// All updates are applied in a single transaction
+    pub fn set_sort_orders(conn: &mut Connection, folder_names: &[String]) -> Result<(), ApiError> {
+        let tx = conn
+            .transaction()
+            .map_err(|e| ApiError::database(format!("Failed to start transaction: {}", e)))?;
+
+        {
+            let mut stmt = tx
+                .prepare_cached("UPDATE scenery_packages SET sort_order = ?1 WHERE folder_name = ?2")
+                .map_err(|e| ApiError::database(format!("Failed to prepare update statement: {}", e)))?;
+
+            for (i, folder_name) in folder_names.iter().enumerate() {
+                stmt.execute(params![i as u32, folder_name])
+                    .map_err(|e| ApiError::database(format!("Failed to update sort order: {}", e)))?;
+            }
+        }
+
+        Self::set_metadata(
+            &tx,
+            "last_updated",
+            &systemtime_to_unix(&SystemTime::now()).to_string(),
+        )?;
+
+        tx.commit()
+            .map_err(|e| ApiError::database(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Get folder names of packages exporting a library (case-insensitive), in sort order
+    pub fn packages_exporting(
+        conn: &Connection,
+        library_name: &str,
+    ) -> Result<Vec<String>, ApiError> {
+        let mut stmt = conn
+            .prepare(
+                "SELECT DISTINCT p.folder_name, p.sort_order
+                 FROM exported_libraries e
+                 JOIN scenery_packages p ON p.id = e.package_id
+                 WHERE e.library_name = ?1 COLLATE NOCASE
+                 ORDER BY p.sort_order",
+            )
+            .map_err(|e| ApiError::database(format!("Failed to prepare query: {}", e)))?;
+
+        let folders = stmt
+            .query_map(params![library_name], |row| row.get::<_, String>(0))
+            .map_err(|e| ApiError::database(format!("Failed to query exporting packages: {}", e)))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| ApiError::database(format!("Failed to read exporting packages: {}", e)))?;
+
+        Ok(folders)
+    }
+
+    /// Load the cached tile coverage
+    /// Returns folder name -> (package last_modified when computed, tiles)
+    pub fn load_tile_coverage(
+        conn: &Connection,
+    ) -> Result<HashMap<String, (SystemTime, Vec<(i32, i32)>)>, ApiError> {
+        let mut stmt = conn
+            .prepare("SELECT folder_name, last_modified, tiles FROM tile_coverage")
+            .map_err(|e| ApiError::database(format!("Failed to prepare query: {}", e)))?;
+
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })
+            .map_err(|e| ApiError::database(format!("Failed to query tile coverage: {}", e)))?;
+
+        let mut result = HashMap::new();
+        for row_result in rows {
+            let (folder_name, last_modified, tiles) = row_result.map_err(|e| {
+                ApiError::database(format!("Failed to read tile coverage row: {}", e))
+            })?;
+            // Unreadable rows are simply recomputed
+            if let Ok(tiles) = serde_json::from_str(&tiles) {
+                result.insert(folder_name, (unix_to_systemtime(last_modified), tiles));
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Store tile coverage for updated packages and drop removed ones in one transaction
+    pub fn save_tile_coverage(
+        conn: &mut Connection,
+        updated: &[(String, SystemTime, Vec<(i32, i32)>)],
+        removed: &[String],
+    ) -> Result<(), ApiError> {
+        let tx = conn
+            .transaction()
+            .map_err(|e| ApiError::database(format!("Failed to start transaction: {}", e)))?;
+
+        for (folder_name, last_modified, tiles) in updated {
+            let tiles = serde_json::to_string(tiles)
+                .map_err(|e| ApiError::database(format!("Failed to encode tiles: {}", e)))?;
+            tx.execute(
+                "INSERT OR REPLACE INTO tile_coverage (folder_name, last_modified, tiles)
+                 VALUES (?1, ?2, ?3)",
+                params![folder_name, systemtime_to_unix(last_modified), tiles],
+            )
+            .map_err(|e| ApiError::database(format!("Failed to save tile coverage: {}", e)))?;
+        }
+
+        for folder_name in removed {
+            tx.execute(
+                "DELETE FROM tile_coverage WHERE folder_name = ?1",
+                params![folder_name],
+            )
+            .map_err(|e| ApiError::database(format!("Failed to delete tile coverage: {}", e)))?;
+        }
+
+        tx.commit()
+            .map_err(|e| ApiError::database(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Lock or unlock a package's category
+    /// Locked categories were chosen by the user and are kept when the package is re-classified
+    pub fn set_category_locked(
+        conn: &Connection,
+        folder_name: &str,
+        locked: bool,
+    ) -> Result<bool, ApiError> {
+        let rows_affected = conn
+            .execute(
+                "UPDATE scenery_packages SET category_locked = ?1 WHERE folder_name = ?2",
+                params![locked, folder_name],
+            )
+            .map_err(|e| ApiError::database(format!("Failed to update category lock: {}", e)))?;
+
+        Ok(rows_affected > 0)
+    }
+
     /// Get package count
     pub fn get_package_count(conn: &Connection) -> Result<usize, ApiError> {
         let count: i64 = conn
@@ -727,6 +954,26 @@ impl SceneryQueries {
         Ok(count as usize)
     }
 
+    /// Rebuild the database file to reclaim space left behind by rebuild_index cycles
+    /// VACUUM cannot run inside a transaction, so this refuses to run if one is open
+    pub fn vacuum(conn: &Connection) -> Result<(), ApiError> {
+        if !conn.is_autocommit() {
+            return Err(ApiError::database(
+                "Cannot vacuum the database while a transaction is open",
+            ));
+        }
+
+        conn.execute_batch("VACUUM;")
+            .map_err(|e| ApiError::database(format!("Failed to vacuum database: {}", e)))?;
+
+        // In WAL mode the rewritten pages land in the -wal file; fold them back and truncate it
+        conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+            .map_err(|e| ApiError::database(format!("Failed to checkpoint database: {}", e)))?;
+
+        logger::log_info("Scenery database vacuumed", Some("database"));
+        Ok(())
+    }
+
     /// Check if database has any packages (uses EXISTS for optimal performance)
     pub fn has_packages(conn: &Connection) -> Result<bool, ApiError> {
         let exists: bool = conn
@@ -745,6 +992,7 @@ mod tests {
     use super::*;
     use crate::database::connection::open_memory_connection;
     use crate::database::migrations::apply_migrations;
+    use crate::models::test_package;
 
     fn setup_test_db() -> Connection {
         let conn = open_memory_connection().unwrap();
@@ -775,6 +1023,12 @@ mod tests {
             missing_libraries: vec![],
             exported_library_names: vec![],
             actual_path: None,
+            icao_code: Some("KSEA".to_string()),
+            airport_name: Some("Seattle Tacoma Intl".to_string()),
+            airport_elevation_ft: Some(433),
+            category_locked: false,
+            icao_mismatch: false,
+            install_marker: None,
         };
 
         SceneryQueries::update_package(&mut conn, &info).unwrap();
@@ -787,6 +1041,43 @@ mod tests {
         assert_eq!(loaded.category, SceneryCategory::Airport);
         assert!(loaded.has_apt_dat);
         assert_eq!(loaded.required_libraries, vec!["opensceneryx"]);
+        assert_eq!(loaded.icao_code.as_deref(), Some("KSEA"));
+        assert_eq!(loaded.airport_name.as_deref(), Some("Seattle Tacoma Intl"));
+        assert_eq!(loaded.airport_elevation_ft, Some(433));
+
+        // save_all / load_all round-trip the airport details as well
+        let index = SceneryQueries::load_all(&conn).unwrap();
+        SceneryQueries::save_all(&mut conn, &index).unwrap();
+        let reloaded = SceneryQueries::load_all(&conn).unwrap();
+        let airport = &reloaded.packages["TestAirport"];
+        assert_eq!(airport.icao_code.as_deref(), Some("KSEA"));
+        assert_eq!(airport.airport_name.as_deref(), Some("Seattle Tacoma Intl"));
+        assert_eq!(airport.airport_elevation_ft, Some(433));
+        assert!(!airport.category_locked);
+
+        // The category lock survives a full rewrite
+        assert!(SceneryQueries::set_category_locked(&conn, "TestAirport", true).unwrap());
+        assert!(!SceneryQueries::set_category_locked(&conn, "Missing", true).unwrap());
+        let index = SceneryQueries::load_all(&conn).unwrap();
+        SceneryQueries::save_all(&mut conn, &index).unwrap();
+        assert!(
+            SceneryQueries::get_package(&conn, "TestAirport")
+                .unwrap()
+                .unwrap()
+                .category_locked
+        );
+    }
+
+    #[test]
+    fn test_vacuum() {
+        let mut conn = setup_test_db();
+        SceneryQueries::vacuum(&conn).unwrap();
+
+        // Refused while a transaction is open
+        let tx = conn.transaction().unwrap();
+        assert!(SceneryQueries::vacuum(&tx).is_err());
+        tx.rollback().unwrap();
+        SceneryQueries::vacuum(&conn).unwrap();
     }
 
     #[test]
@@ -812,6 +1103,12 @@ mod tests {
             missing_libraries: vec![],
             exported_library_names: vec!["mylib".to_string()],
             actual_path: None,
+            icao_code: None,
+            airport_name: None,
+            airport_elevation_ft: None,
+            category_locked: false,
+            icao_mismatch: false,
+            install_marker: None,
         };
 
         SceneryQueries::update_package(&mut conn, &info).unwrap();
@@ -848,6 +1145,12 @@ mod tests {
                 missing_libraries: vec![],
                 exported_library_names: vec![],
                 actual_path: None,
+                icao_code: None,
+                airport_name: None,
+                airport_elevation_ft: None,
+                category_locked: false,
+                icao_mismatch: false,
+                install_marker: None,
             };
             SceneryQueries::update_package(&mut conn, &info).unwrap();
         }
@@ -855,4 +1158,172 @@ mod tests {
         let index = SceneryQueries::load_all(&conn).unwrap();
         assert_eq!(index.packages.len(), 3);
     }
+
+    #[test]
+    fn test_load_by_category() {
+        let mut conn = setup_test_db();
+
+        for (name, category, sort_order) in [
+            ("MeshB", SceneryCategory::Mesh, 5),
+            ("Airport", SceneryCategory::Airport, 0),
+            ("MeshA", SceneryCategory::Mesh, 2),
+        ] {
+            let info = SceneryPackageInfo {
+                has_dsf: true,
+                earth_nav_tile_count: 1,
+                required_libraries: vec![format!("{}_lib", name)],
+                ..test_package(name, category, sort_order, true)
+            };
+            SceneryQueries::update_package(&mut conn, &info).unwrap();
+        }
+
+        let mesh = SceneryQueries::load_by_category(&conn, &SceneryCategory::Mesh).unwrap();
+        let names: Vec<&str> = mesh.iter().map(|p| p.folder_name.as_str()).collect();
+        assert_eq!(names, vec!["MeshA", "MeshB"]);
+        assert_eq!(mesh[0].required_libraries, vec!["MeshA_lib"]);
+        assert_eq!(mesh[1].required_libraries, vec!["MeshB_lib"]);
+
+        let libraries = SceneryQueries::load_by_category(&conn, &SceneryCategory::Library).unwrap();
+        assert!(libraries.is_empty());
+    }
+
+    #[test]
+    fn test_set_sort_orders() {
+        let mut conn = setup_test_db();
+
+        for i in 0..3 {
+            let info = test_package(
+                &format!("Package{}", i),
+                SceneryCategory::Other,
+                i as u32,
+                true,
+            );
+            SceneryQueries::update_package(&mut conn, &info).unwrap();
+        }
+
+        let order = vec![
+            "Package2".to_string(),
+            "Package0".to_string(),
+            "Package1".to_string(),
+        ];
+        SceneryQueries::set_sort_orders(&mut conn, &order).unwrap();
+
+        let index = SceneryQueries::load_all(&conn).unwrap();
+        assert_eq!(index.packages["Package2"].sort_order, 0);
+        assert_eq!(index.packages["Package0"].sort_order, 1);
+        assert_eq!(index.packages["Package1"].sort_order, 2);
+    }
+
+    #[test]
+    fn test_set_enabled_bulk() {
+        let mut conn = setup_test_db();
+
+        for i in 0..3 {
+            let info = SceneryPackageInfo {
+                has_dsf: true,
+                earth_nav_tile_count: 1,
+                ..test_package(
+                    &format!("Ortho{}", i),
+                    SceneryCategory::Mesh,
+                    10 + i as u32,
+                    i != 2,
+                )
+            };
+            SceneryQueries::update_package(&mut conn, &info).unwrap();
+        }
+
+        let names: Vec<String> = (0..4).map(|i| format!("Ortho{}", i)).collect();
+        // Ortho2 is already disabled and Ortho3 doesn't exist
+        assert_eq!(
+            SceneryQueries::set_enabled_bulk(&mut conn, &names, false).unwrap(),
+            2
+        );
+
+        let index = SceneryQueries::load_all(&conn).unwrap();
+        for i in 0..3 {
+            let info = &index.packages[&format!("Ortho{}", i)];
+            assert!(!info.enabled);
+            assert_eq!(info.sort_order, 10 + i as u32);
+        }
+
+        assert_eq!(
+            SceneryQueries::set_enabled_bulk(&mut conn, &names, true).unwrap(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_packages_exporting() {
+        let mut conn = setup_test_db();
+
+        for (i, (name, exports)) in [
+            ("OpenSceneryX", vec!["opensceneryx"]),
+            ("OSX_Backup", vec!["OpenSceneryX", "opensceneryx/objects"]),
+            ("SAM_Library", vec!["sam"]),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let info = SceneryPackageInfo {
+                has_library_txt: true,
+                has_objects: true,
+                exported_library_names: exports.into_iter().map(String::from).collect(),
+                ..test_package(name, SceneryCategory::Library, i as u32, true)
+            };
+            SceneryQueries::update_package(&mut conn, &info).unwrap();
+        }
+
+        assert_eq!(
+            SceneryQueries::packages_exporting(&conn, "OPENSCENERYX").unwrap(),
+            vec!["OpenSceneryX".to_string(), "OSX_Backup".to_string()]
+        );
+        assert!(SceneryQueries::packages_exporting(&conn, "missing")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_metadata_roundtrip() {
+        let conn = setup_test_db();
+
+        assert!(SceneryQueries::get_metadata(&conn, "safe_mode_snapshot")
+            .unwrap()
+            .is_none());
+
+        SceneryQueries::set_metadata(&conn, "safe_mode_snapshot", "{}").unwrap();
+        assert_eq!(
+            SceneryQueries::get_metadata(&conn, "safe_mode_snapshot").unwrap(),
+            Some("{}".to_string())
+        );
+
+        assert!(SceneryQueries::delete_metadata(&conn, "safe_mode_snapshot").unwrap());
+        assert!(SceneryQueries::get_metadata(&conn, "safe_mode_snapshot")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_tile_coverage_roundtrip() {
+        let mut conn = setup_test_db();
+        let modified = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+
+        SceneryQueries::save_tile_coverage(
+            &mut conn,
+            &[
+                ("Ortho".to_string(), modified, vec![(47, 8), (47, 9)]),
+                ("Airport".to_string(), modified, vec![]),
+            ],
+            &[],
+        )
+        .unwrap();
+
+        let coverage = SceneryQueries::load_tile_coverage(&conn).unwrap();
+        assert_eq!(coverage.len(), 2);
+        assert_eq!(coverage["Ortho"], (modified, vec![(47, 8), (47, 9)]));
+        assert!(coverage["Airport"].1.is_empty());
+
+        SceneryQueries::save_tile_coverage(&mut conn, &[], &["Ortho".to_string()]).unwrap();
+        let coverage = SceneryQueries::load_tile_coverage(&conn).unwrap();
+        assert!(!coverage.contains_key("Ortho"));
+    }
 }
@@ -1,7 +1,7 @@
 //! Database schema definitions
 
 /// Current schema version for migration tracking
-pub const CURRENT_SCHEMA_VERSION: i32 = 1;
+pub const CURRENT_SCHEMA_VERSION: i32 = 7;
 
 /// SQL statements for creating the database schema
 pub const CREATE_SCHEMA: &str = r#"
@@ -74,6 +74,44 @@ CREATE INDEX IF NOT EXISTS idx_required_libraries_name ON required_libraries(lib
 CREATE INDEX IF NOT EXISTS idx_exported_libraries_name ON exported_libraries(library_name);
 "#;
 
+/// SQL for the tile coverage cache (added in version 2)
+/// Keyed by folder name rather than package id so it survives full index rewrites;
+/// last_modified is the package's last_modified when the coverage was computed
+pub const CREATE_TILE_COVERAGE: &str = r#"
+CREATE TABLE IF NOT EXISTS tile_coverage (
+    folder_name TEXT PRIMARY KEY,
+    last_modified INTEGER NOT NULL,
+    tiles TEXT NOT NULL
+);
+"#;
+
+/// SQL adding the airport ICAO code column (added in version 3)
+pub const ADD_ICAO_CODE_COLUMN: &str = r#"
+ALTER TABLE scenery_packages ADD COLUMN icao_code TEXT;
+"#;
+
+/// SQL adding the airport name and elevation columns (added in version 4)
+pub const ADD_AIRPORT_DETAILS_COLUMNS: &str = r#"
+ALTER TABLE scenery_packages ADD COLUMN airport_name TEXT;
+ALTER TABLE scenery_packages ADD COLUMN airport_elevation_ft INTEGER;
+"#;
+
+/// SQL adding the category lock column (added in version 5)
+/// Locked packages keep their user-chosen category across re-classification
+pub const ADD_CATEGORY_LOCKED_COLUMN: &str = r#"
+ALTER TABLE scenery_packages ADD COLUMN category_locked INTEGER NOT NULL DEFAULT 0;
+"#;
+
+/// SQL adding the airport ICAO mismatch flag (added in version 6)
+pub const ADD_ICAO_MISMATCH_COLUMN: &str = r#"
+ALTER TABLE scenery_packages ADD COLUMN icao_mismatch INTEGER NOT NULL DEFAULT 0;
+"#;
+
+/// SQL adding the install marker column, JSON or NULL (added in version 7)
+pub const ADD_INSTALL_MARKER_COLUMN: &str = r#"
+ALTER TABLE scenery_packages ADD COLUMN install_marker TEXT;
+"#;
+
 /// SQL statement to insert initial schema version
 pub const INSERT_SCHEMA_VERSION: &str = r#"
 INSERT OR REPLACE INTO schema_version (version, applied_at, description)
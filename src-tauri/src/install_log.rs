@@ -0,0 +1,312 @@
+//! Per-installation log files
+//!
+//! Every installation run writes its own timestamped file in the app data
+//! directory, recording installed files, skipped tasks, errors and rollback
+//! actions. Unlike the rolling application log, these files are kept intact
+//! so a failed install can be inspected afterwards.
+
+use anyhow::{anyhow, Result};
+use chrono::Local;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use walkdir::WalkDir;
+
+use crate::app_dirs;
+use crate::logger;
+
+/// Maximum number of install log files kept on disk (oldest are removed first)
+const MAX_INSTALL_LOGS: usize = 20;
+
+const INSTALL_LOG_PREFIX: &str = "install_";
+const INSTALL_LOG_EXTENSION: &str = "log";
+
+/// Kind of entry written to an install log
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstallLogEvent {
+    Task,
+    Installed,
+    Overwritten,
+    Skipped,
+    Error,
+    Rollback,
+    Summary,
+}
+
+impl InstallLogEvent {
+    fn as_str(&self) -> &'static str {
+        match self {
+            InstallLogEvent::Task => "TASK",
+            InstallLogEvent::Installed => "INSTALLED",
+            InstallLogEvent::Overwritten => "OVERWRITTEN",
+            InstallLogEvent::Skipped => "SKIPPED",
+            InstallLogEvent::Error => "ERROR",
+            InstallLogEvent::Rollback => "ROLLBACK",
+            InstallLogEvent::Summary => "SUMMARY",
+        }
+    }
+}
+
+/// Handle to the log file of a single installation run
+///
+/// Cloning is cheap and all clones write to the same file. Failing to create
+/// or write the file never fails the installation itself; the error is
+/// reported to the application log and further entries are dropped.
+#[derive(Clone)]
+pub struct InstallLog {
+    writer: Arc<Mutex<Option<BufWriter<File>>>>,
+}
+
+impl InstallLog {
+    /// Create a new timestamped install log in the app data directory
+    pub fn create() -> Self {
+        Self::create_in(&app_dirs::get_install_logs_dir())
+    }
+
+    fn create_in(dir: &Path) -> Self {
+        let file_name = format!(
+            "{}{}.{}",
+            INSTALL_LOG_PREFIX,
+            Local::now().format("%Y%m%d_%H%M%S_%3f"),
+            INSTALL_LOG_EXTENSION
+        );
+        let path = dir.join(file_name);
+
+        let writer = match fs::create_dir_all(dir).and_then(|_| File::create(&path)) {
+            Ok(file) => Some(BufWriter::new(file)),
+            Err(e) => {
+                logger::log_error(
+                    &format!("Failed to create install log {:?}: {}", path, e),
+                    Some("install_log"),
+                );
+                None
+            }
+        };
+
+        prune_install_logs(dir, MAX_INSTALL_LOGS);
+
+        Self {
+            writer: Arc::new(Mutex::new(writer)),
+        }
+    }
+
+    /// Append an entry to the log
+    pub fn record(&self, event: InstallLogEvent, subject: &str, detail: &str) {
+        let Ok(mut guard) = self.writer.lock() else {
+            return;
+        };
+        let Some(writer) = guard.as_mut() else {
+            return;
+        };
+
+        let timestamp = Local::now().format("%Y-%m-%d %H:%M:%S");
+        let line = if detail.is_empty() {
+            format!("[{}] [{}] {}\n", timestamp, event.as_str(), subject)
+        } else {
+            format!(
+                "[{}] [{}] {}: {}\n",
+                timestamp,
+                event.as_str(),
+                subject,
+                detail
+            )
+        };
+
+        if let Err(e) = writer.write_all(line.as_bytes()) {
+            logger::log_error(
+                &format!("Failed to write install log: {}", e),
+                Some("install_log"),
+            );
+            // Stop writing after the first failure to avoid flooding the app log
+            *guard = None;
+        }
+    }
+
+    /// Record the files under `target` written by the install of `subject`
+    /// Files missing from `before` are logged as installed, files whose size or
+    /// modification time changed as overwritten; untouched files are not logged
+    pub fn record_installed_files(&self, subject: &str, target: &Path, before: &TargetSnapshot) {
+        for (path, state) in TargetSnapshot::take(target).files {
+            let event = match before.files.get(&path) {
+                None => InstallLogEvent::Installed,
+                Some(previous) if *previous != state => InstallLogEvent::Overwritten,
+                Some(_) => continue,
+            };
+            self.record(event, subject, &path.display().to_string());
+        }
+    }
+
+    /// Flush buffered entries to disk
+    pub fn flush(&self) {
+        if let Ok(mut guard) = self.writer.lock() {
+            if let Some(writer) = guard.as_mut() {
+                let _ = writer.flush();
+            }
+        }
+    }
+}
+
+/// Size and modification time of every file under an install target
+/// Taken before merging into an existing target, so the install log can tell the
+/// files the install wrote from the ones that were already there
+#[derive(Default)]
+pub struct TargetSnapshot {
+    files: HashMap<PathBuf, (u64, Option<SystemTime>)>,
+}
+
+impl TargetSnapshot {
+    /// Snapshot the files under `target` (or `target` itself if it is a file)
+    pub fn take(target: &Path) -> Self {
+        let files = WalkDir::new(target)
+            .follow_links(false)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| !e.file_type().is_dir())
+            .filter_map(|e| {
+                let metadata = e.metadata().ok()?;
+                Some((e.into_path(), (metadata.len(), metadata.modified().ok())))
+            })
+            .collect();
+        Self { files }
+    }
+}
+
+/// List install log file names, newest first
+pub fn list_install_logs() -> Result<Vec<String>> {
+    list_install_logs_in(&app_dirs::get_install_logs_dir())
+}
+
+/// Read the contents of the most recent install log
+pub fn get_last_install_log() -> Result<String> {
+    let dir = app_dirs::get_install_logs_dir();
+    let latest = list_install_logs_in(&dir)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow!("No install logs found"))?;
+
+    fs::read_to_string(dir.join(&latest))
+        .map_err(|e| anyhow!("Failed to read install log {}: {}", latest, e))
+}
+
+fn list_install_logs_in(dir: &Path) -> Result<Vec<String>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut names: Vec<String> = fs::read_dir(dir)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| is_install_log_file(p))
+        .filter_map(|p| p.file_name().and_then(|n| n.to_str()).map(String::from))
+        .collect();
+
+    // File names embed a sortable timestamp
+    names.sort_unstable_by(|a, b| b.cmp(a));
+    Ok(names)
+}
+
+fn is_install_log_file(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e == INSTALL_LOG_EXTENSION)
+        && path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with(INSTALL_LOG_PREFIX))
+}
+
+/// Remove the oldest install logs so that at most `keep` remain
+fn prune_install_logs(dir: &Path, keep: usize) {
+    let Ok(names) = list_install_logs_in(dir) else {
+        return;
+    };
+
+    for name in names.into_iter().skip(keep) {
+        if let Err(e) = fs::remove_file(dir.join(&name)) {
+            logger::log_error(
+                &format!("Failed to remove old install log {}: {}", name, e),
+                Some("install_log"),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_install_log_records_and_prunes() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+
+        // Pre-existing logs with older timestamps
+        for i in 0..3 {
+            fs::write(
+                dir.join(format!("install_20000101_00000{}_000.log", i)),
+                "old",
+            )
+            .unwrap();
+        }
+        fs::write(dir.join("unrelated.txt"), "keep").unwrap();
+
+        let target = dir.join("Addon");
+        fs::create_dir_all(target.join("sub")).unwrap();
+        fs::write(target.join("a.txt"), "a").unwrap();
+        fs::write(target.join("sub").join("b.txt"), "b").unwrap();
+
+        let log = InstallLog::create_in(dir);
+        log.record(InstallLogEvent::Task, "Addon", "start");
+        log.record_installed_files("Addon", &target, &TargetSnapshot::default());
+        log.record(InstallLogEvent::Rollback, "Addon", "restored backup");
+        log.flush();
+
+        let names = list_install_logs_in(dir).unwrap();
+        assert_eq!(names.len(), 4);
+        assert!(!names[0].starts_with("install_2000"));
+
+        let content = fs::read_to_string(dir.join(&names[0])).unwrap();
+        assert!(content.contains("[TASK] Addon: start"));
+        assert_eq!(content.matches("[INSTALLED] Addon:").count(), 2);
+        assert!(content.contains("[ROLLBACK] Addon: restored backup"));
+
+        prune_install_logs(dir, 2);
+        let names = list_install_logs_in(dir).unwrap();
+        assert_eq!(names.len(), 2);
+        assert!(names.contains(&"install_20000101_000002_000.log".to_string()));
+        assert!(dir.join("unrelated.txt").exists());
+    }
+
+    #[test]
+    fn test_merge_install_logs_only_written_files() {
+        let temp = TempDir::new().unwrap();
+        let target = temp.path().join("Addon");
+        fs::create_dir_all(&target).unwrap();
+        fs::write(target.join("kept.txt"), "user file").unwrap();
+        fs::write(target.join("replaced.txt"), "v1").unwrap();
+
+        let before = TargetSnapshot::take(&target);
+        fs::write(target.join("replaced.txt"), "version 2").unwrap();
+        fs::write(target.join("new.txt"), "new").unwrap();
+
+        let log_dir = temp.path().join("logs");
+        let log = InstallLog::create_in(&log_dir);
+        log.record_installed_files("Addon", &target, &before);
+        log.flush();
+
+        let name = list_install_logs_in(&log_dir).unwrap().remove(0);
+        let content = fs::read_to_string(log_dir.join(name)).unwrap();
+        assert_eq!(content.matches("[INSTALLED] Addon:").count(), 1);
+        assert!(content.contains("new.txt"));
+        assert_eq!(content.matches("[OVERWRITTEN] Addon:").count(), 1);
+        assert!(content.contains("replaced.txt"));
+        assert!(!content.contains("kept.txt"));
+    }
+}
@@ -0,0 +1,91 @@
+//! Provenance markers for installed packages
+//!
+//! After a successful installation a small `.xfastinstall.json` file is written
+//! into the package root, recording where it came from and how it was installed.
+//! This lets the manager tell packages installed by this tool from manual ones.
+
+use anyhow::Result;
+use chrono::Local;
+use std::fs;
+use std::path::Path;
+
+use crate::models::{InstallMarker, InstallTask};
+
+/// File name of the marker inside an installed package
+pub const INSTALL_MARKER_FILE: &str = ".xfastinstall.json";
+
+impl InstallMarker {
+    /// Build the marker for a task that has just been installed
+    /// `strategy` describes how the target was written ("fresh", "clean" or "overwrite")
+    pub fn for_task(task: &InstallTask, strategy: &str) -> Self {
+        let version = task
+            .new_version_info
+            .as_ref()
+            .and_then(|v| v.version.clone())
+            .or_else(|| task.new_navdata_info.as_ref().and_then(|n| n.cycle.clone()));
+
+        Self {
+            source: task
+                .original_input_path
+                .clone()
+                .unwrap_or_else(|| task.source_path.clone()),
+            installed_at: Local::now().to_rfc3339(),
+            strategy: strategy.to_string(),
+            version,
+        }
+    }
+}
+
+/// Write the marker into an installed package folder
+/// Symlinked / linked installs and non-directory targets are skipped, since the
+/// package content doesn't belong to this installation.
+/// Returns true if the marker was written
+pub fn write_install_marker(folder: &Path, marker: &InstallMarker) -> Result<bool> {
+    let metadata = fs::symlink_metadata(folder)?;
+    if metadata.file_type().is_symlink() || !metadata.is_dir() {
+        return Ok(false);
+    }
+
+    let json = serde_json::to_string_pretty(marker)?;
+    fs::write(folder.join(INSTALL_MARKER_FILE), json)?;
+    Ok(true)
+}
+
+/// Read the marker of an installed package, if it was installed by this tool
+pub fn read_install_marker(folder: &Path) -> Option<InstallMarker> {
+    let content = fs::read_to_string(folder.join(INSTALL_MARKER_FILE)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_install_marker_round_trip() {
+        let temp = tempfile::tempdir().unwrap();
+        let package = temp.path().join("KSEA");
+        fs::create_dir_all(&package).unwrap();
+
+        let marker = InstallMarker {
+            source: "C:/Downloads/KSEA.zip".to_string(),
+            installed_at: "2024-05-01T10:00:00+00:00".to_string(),
+            strategy: "fresh".to_string(),
+            version: Some("1.2".to_string()),
+        };
+
+        assert!(read_install_marker(&package).is_none());
+        assert!(write_install_marker(&package, &marker).unwrap());
+        let read = read_install_marker(&package).unwrap();
+        assert_eq!(read.source, marker.source);
+        assert_eq!(read.strategy, "fresh");
+        assert_eq!(read.version.as_deref(), Some("1.2"));
+
+        #[cfg(unix)]
+        {
+            let link = temp.path().join("KSEA_link");
+            std::os::unix::fs::symlink(&package, &link).unwrap();
+            assert!(!write_install_marker(&link, &marker).unwrap());
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use anyhow::{Context, Result};
 use glob::Pattern;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Component, Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -8,10 +8,12 @@ use std::sync::{Arc, Mutex};
 use std::time::Instant;
 use tauri::{AppHandle, Emitter, Manager};
 
+use crate::install_log::{InstallLog, InstallLogEvent, TargetSnapshot};
+use crate::install_marker;
 use crate::logger;
 use crate::logger::{tr, LogMsg};
 use crate::models::{
-    AddonType, InstallPhase, InstallProgress, InstallResult, InstallTask, TaskResult,
+    AddonType, InstallMarker, InstallPhase, InstallProgress, InstallResult, InstallTask, TaskResult,
 };
 use crate::task_control::TaskControl;
 
@@ -50,6 +52,35 @@ impl CompiledPatterns {
     }
 }
 
+/// Maximum folder depth searched for config files when `config_file_recursive` is set
+const CONFIG_FILE_MAX_DEPTH: usize = 4;
+
+/// Find files whose name matches one of the config file patterns, relative to `dir`
+/// Only the root directory is searched unless `recursive` is set. The liveries folder
+/// is skipped because it is backed up on its own
+pub fn find_config_files(dir: &Path, patterns: &[String], recursive: bool) -> Vec<PathBuf> {
+    let compiled = CompiledPatterns::new(patterns);
+    let max_depth = if recursive { CONFIG_FILE_MAX_DEPTH } else { 1 };
+
+    walkdir::WalkDir::new(dir)
+        .min_depth(1)
+        .max_depth(max_depth)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|e| {
+            !(e.depth() == 1 && e.file_type().is_dir() && e.file_name() == "liveries")
+        })
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| {
+            e.file_name()
+                .to_str()
+                .is_some_and(|name| compiled.matches(name))
+        })
+        .filter_map(|e| e.path().strip_prefix(dir).ok().map(Path::to_path_buf))
+        .collect()
+}
+
 /// Sanitize a file path to prevent path traversal attacks
 /// Returns None if the path is unsafe (contains `..` or is absolute)
 pub fn sanitize_path(path: &Path) -> Option<PathBuf> {
@@ -69,6 +100,229 @@ pub fn sanitize_path(path: &Path) -> Option<PathBuf> {
     }
 }
 
+/// Whether a zip entry's Unix mode marks it as a symbolic link
+fn is_zip_symlink(mode: Option<u32>) -> bool {
+    cfg!(unix) && mode.map(|m| m & 0o170000 == 0o120000).unwrap_or(false)
+}
+
+/// Check that writing `out_path` stays inside `canonical_root` once links already on
+/// disk are followed: its deepest existing ancestor must resolve inside the root and
+/// `out_path` itself must not be an existing link.
+fn output_stays_inside(canonical_root: &Path, out_path: &Path) -> bool {
+    if fs::symlink_metadata(out_path)
+        .map(|m| m.file_type().is_symlink())
+        .unwrap_or(false)
+    {
+        return false;
+    }
+    let mut ancestor = out_path.parent();
+    while let Some(dir) = ancestor {
+        if fs::symlink_metadata(dir).is_ok() {
+            return dir
+                .canonicalize()
+                .map(|resolved| resolved.starts_with(canonical_root))
+                .unwrap_or(false);
+        }
+        ancestor = dir.parent();
+    }
+    false
+}
+
+/// Create the links stored in zip entries `links` (entry index, path relative to `target`).
+/// Runs after all regular files are written, so no file is ever written through a link;
+/// links that would resolve outside `target` are skipped or removed again
+#[cfg(unix)]
+fn create_zip_symlinks<R: std::io::Read + std::io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    target: &Path,
+    links: &[(usize, &Path)],
+) -> Result<()> {
+    use std::io::Read;
+
+    let canonical_target = target
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", target.display()))?;
+    for (index, relative_path) in links {
+        let mut link = String::new();
+        archive.by_index(*index)?.read_to_string(&mut link)?;
+        let link = PathBuf::from(link);
+        let outpath = target.join(relative_path);
+        if !link_stays_inside(relative_path, &link)
+            || !output_stays_inside(&canonical_target, &outpath)
+        {
+            logger::log_error(
+                &format!(
+                    "Skipping link escaping the addon folder: {}",
+                    relative_path.display()
+                ),
+                Some("installer"),
+            );
+            continue;
+        }
+        let _ = fs::remove_file(&outpath);
+        std::os::unix::fs::symlink(&link, &outpath)?;
+        if !link_resolves_inside(&canonical_target, &outpath) {
+            logger::log_error(
+                &format!(
+                    "Removing link escaping the addon folder: {}",
+                    relative_path.display()
+                ),
+                Some("installer"),
+            );
+            fs::remove_file(&outpath)?;
+        }
+    }
+    Ok(())
+}
+
+/// Check that a link created on disk resolves inside `canonical_root`.
+/// Dangling links are kept; writes through them are refused by output_stays_inside.
+fn link_resolves_inside(canonical_root: &Path, link_path: &Path) -> bool {
+    match link_path.canonicalize() {
+        Ok(resolved) => resolved.starts_with(canonical_root),
+        Err(_) => true,
+    }
+}
+
+/// Check that a link stored at `entry_path` (relative to the extraction root) with
+/// target `link` resolves inside the extraction root. Absolute targets are rejected.
+fn link_stays_inside(entry_path: &Path, link: &Path) -> bool {
+    let mut depth: usize = entry_path.components().count().saturating_sub(1);
+    for component in link.components() {
+        match component {
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if depth == 0 {
+                    return false;
+                }
+                depth -= 1;
+            }
+            Component::Prefix(_) | Component::RootDir => return false,
+        }
+    }
+    true
+}
+
+/// Unpack the entries of a tar archive under `target`, keeping only those under
+/// internal_root (with that prefix stripped). `on_file` is called with the relative
+/// path and size of every regular file written.
+fn unpack_tar_entries<R: std::io::Read>(
+    archive: &mut tar::Archive<R>,
+    target: &Path,
+    internal_root: Option<&str>,
+    mut on_file: impl FnMut(&Path, u64),
+) -> Result<()> {
+    let prefix = internal_root.map(|s| {
+        let normalized = s.replace('\\', "/");
+        if normalized.ends_with('/') {
+            normalized
+        } else {
+            format!("{}/", normalized)
+        }
+    });
+
+    fs::create_dir_all(target)?;
+    let canonical_target = target
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", target.display()))?;
+
+    for entry in archive.entries().context("Failed to read tar.gz archive")? {
+        let mut entry = entry.context("Failed to read tar.gz entry")?;
+        let entry_path = entry.path()?.to_string_lossy().replace('\\', "/");
+        let entry_path = entry_path.trim_start_matches("./").to_string();
+
+        // Strip internal_root, skipping entries outside of it
+        let relative = match prefix {
+            Some(ref p) => match entry_path.strip_prefix(p.as_str()) {
+                Some(rest) => rest.to_string(),
+                None => continue,
+            },
+            None => entry_path.clone(),
+        };
+        if relative.is_empty() {
+            continue;
+        }
+
+        let safe_relative = match sanitize_path(Path::new(&relative)) {
+            Some(p) => p,
+            None => {
+                logger::log_error(
+                    &format!("Skipping unsafe path in tar.gz archive: {}", entry_path),
+                    Some("installer"),
+                );
+                continue;
+            }
+        };
+
+        // Hard links reference archive paths that no longer exist once internal_root
+        // is stripped; symlinks must not point outside the extracted tree
+        let entry_type = entry.header().entry_type();
+        if entry_type.is_hard_link() {
+            logger::log_error(
+                &format!("Skipping hard link in tar.gz archive: {}", entry_path),
+                Some("installer"),
+            );
+            continue;
+        }
+        if entry_type.is_symlink() {
+            let link_ok = entry
+                .link_name()?
+                .map(|link| link_stays_inside(&safe_relative, &link))
+                .unwrap_or(false);
+            if !link_ok {
+                logger::log_error(
+                    &format!("Skipping link escaping the addon folder: {}", entry_path),
+                    Some("installer"),
+                );
+                continue;
+            }
+        }
+
+        // Links extracted earlier may redirect this path (e.g. `a -> .` chained with
+        // `b -> a/..`), so check where it actually lands on disk
+        let out_path = target.join(&safe_relative);
+        if !output_stays_inside(&canonical_target, &out_path) {
+            logger::log_error(
+                &format!("Skipping entry written through a link: {}", entry_path),
+                Some("installer"),
+            );
+            continue;
+        }
+        if let Some(parent) = out_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let size = entry.header().size().unwrap_or(0);
+        entry
+            .unpack(&out_path)
+            .with_context(|| format!("Failed to extract {}", entry_path))?;
+
+        if entry_type.is_symlink() && !link_resolves_inside(&canonical_target, &out_path) {
+            logger::log_error(
+                &format!("Removing link escaping the addon folder: {}", entry_path),
+                Some("installer"),
+            );
+            fs::remove_file(&out_path)?;
+            continue;
+        }
+
+        if entry_type.is_file() {
+            on_file(&safe_relative, size);
+        }
+    }
+
+    Ok(())
+}
+
+/// Determine an archive's format the same way the scanner does (magic bytes first,
+/// then the extension), failing for anything that isn't a supported archive
+fn archive_format(archive: &Path) -> Result<&'static str> {
+    crate::scanner::archive_format(archive).ok_or_else(|| {
+        anyhow::anyhow!("Unsupported archive format: {}", archive.display())
+    })
+}
+
 /// Optimized file copy with buffering for better performance
 /// Uses a larger buffer (4MB) for faster I/O operations
 fn copy_file_optimized<R: std::io::Read + ?Sized, W: std::io::Write>(
@@ -170,6 +424,82 @@ fn remove_dir_all_robust(path: &Path) -> Result<()> {
     Err(anyhow::anyhow!(err_msg))
 }
 
+/// Point `target` at `source` with a directory symlink created by `create_link`
+/// An existing link is replaced. An existing folder is staged in a temp directory in
+/// the X-Plane root (outside Custom Scenery, so it is never indexed meanwhile) and
+/// moved back if linking fails; it is only deleted once the link exists
+fn link_scenery_folder(
+    source: &Path,
+    target: &Path,
+    xplane_path: &Path,
+    create_link: impl Fn(&Path, &Path) -> Result<()>,
+) -> Result<()> {
+    use crate::atomic_installer::{is_symlink, remove_dir_symlink, TEMP_DIR_PREFIX};
+
+    let mut old_link = None;
+    let mut staged: Option<(PathBuf, PathBuf)> = None;
+    if is_symlink(target) {
+        old_link = Some(
+            fs::read_link(target).context(format!("Failed to read symlink: {:?}", target))?,
+        );
+        remove_dir_symlink(target)?;
+    } else if target.exists() {
+        // Never delete the folder we are about to link to
+        if let Ok(canonical_target) = target.canonicalize() {
+            if source.starts_with(&canonical_target) {
+                return Err(anyhow::anyhow!(
+                    "Symlink source is inside the target folder: {:?}",
+                    source
+                ));
+            }
+        }
+        let staging_dir =
+            xplane_path.join(format!("{}{}", TEMP_DIR_PREFIX, uuid::Uuid::new_v4()));
+        fs::create_dir(&staging_dir)
+            .context(format!("Failed to create temp directory: {:?}", staging_dir))?;
+        let staged_folder = staging_dir.join(target.file_name().unwrap_or_default());
+        if let Err(e) = fs::rename(target, &staged_folder) {
+            let _ = fs::remove_dir(&staging_dir);
+            return Err(e).context(format!("Failed to move existing folder aside: {:?}", target));
+        }
+        staged = Some((staging_dir, staged_folder));
+    }
+
+    if let Err(e) = create_link(source, target) {
+        let restored = match (&staged, &old_link) {
+            (Some((_, staged_folder)), _) => fs::rename(staged_folder, target)
+                .context(format!("Failed to move {:?} back", staged_folder)),
+            (None, Some(old_link)) => create_link(old_link, target),
+            (None, None) => Ok(()),
+        };
+        if let Err(restore_err) = restored {
+            // Keep the staging directory: it still holds the previous folder
+            return Err(e.context(format!(
+                "Failed to restore previous {:?}: {}",
+                target, restore_err
+            )));
+        }
+        if let Some((staging_dir, _)) = &staged {
+            let _ = fs::remove_dir(staging_dir);
+        }
+        return Err(e);
+    }
+
+    if let Some((staging_dir, _)) = staged {
+        if let Err(e) = remove_dir_all_robust(&staging_dir) {
+            logger::log_info(
+                &format!(
+                    "Warning: Failed to remove replaced folder {:?}: {}",
+                    staging_dir, e
+                ),
+                Some("installer"),
+            );
+        }
+    }
+
+    Ok(())
+}
+
 /// Directory statistics for backup verification
 struct DirectoryInfo {
     file_count: u64,
@@ -180,10 +510,10 @@ struct DirectoryInfo {
 struct AircraftBackup {
     temp_dir: PathBuf,
     liveries_path: Option<PathBuf>,
-    pref_files: Vec<(String, PathBuf)>, // (filename, temp_path)
+    pref_files: Vec<(String, PathBuf)>, // (relative path, temp_path)
     // For verification
     original_liveries_info: Option<DirectoryInfo>,
-    original_pref_sizes: Vec<(String, u64)>, // (filename, original_size)
+    original_pref_sizes: Vec<(String, u64)>, // (relative path, original_size)
 }
 
 /// Progress tracking context
@@ -421,6 +751,14 @@ impl Installer {
         // Reset task control at start of installation
         self.task_control.reset();
 
+        // Refuse the batch up front if the target drive cannot hold it
+        self.check_space_for_tasks(&tasks, Path::new(&xplane_path))?;
+
+        let install_log = InstallLog::create();
+
+        // Remove staging directories left behind by a crashed atomic install
+        crate::atomic_installer::cleanup_stale_temp_dirs(Path::new(&xplane_path));
+
         let mut ctx = ProgressContext::new(self.app_handle.clone(), tasks.len());
         let mut task_results = Vec::new();
         let mut successful = 0;
@@ -457,6 +795,11 @@ impl Installer {
                 // Mark remaining tasks as cancelled
                 for remaining_task in tasks.iter().skip(index) {
                     cancelled += 1;
+                    install_log.record(
+                        InstallLogEvent::Skipped,
+                        &remaining_task.display_name,
+                        "Cancelled by user",
+                    );
                     task_results.push(TaskResult {
                         task_id: remaining_task.id.clone(),
                         task_name: remaining_task.display_name.clone(),
@@ -488,12 +831,40 @@ impl Installer {
                 Some("installer"),
             );
 
+            install_log.record(
+                InstallLogEvent::Task,
+                &task.display_name,
+                &format!("{} -> {}", task.source_path, task.target_path),
+            );
+
             // Track target path for potential cleanup
             self.task_control
                 .add_processed_path(PathBuf::from(&task.target_path));
 
-            match self.install_task_with_progress(task, &ctx, atomic_install_enabled, &xplane_path)
-            {
+            // Merging keeps existing files, so remember them to log only what gets written
+            let merges = merges_into_existing_target(&task.addon_type, task.should_overwrite);
+            let target_before = if merges && Path::new(&task.target_path).exists() {
+                TargetSnapshot::take(Path::new(&task.target_path))
+            } else {
+                TargetSnapshot::default()
+            };
+
+            // Recorded in the package's install marker
+            let strategy = if !Path::new(&task.target_path).exists() {
+                "fresh"
+            } else if !task.should_overwrite {
+                "clean"
+            } else {
+                "overwrite"
+            };
+
+            match self.install_task_with_progress(
+                task,
+                &ctx,
+                atomic_install_enabled,
+                &xplane_path,
+                &install_log,
+            ) {
                 Ok(_) => {
                     // Check for skip request after installation but before verification
                     if self.task_control.is_skip_requested() {
@@ -511,6 +882,11 @@ impl Installer {
                         }
 
                         skipped += 1;
+                        install_log.record(
+                            InstallLogEvent::Skipped,
+                            &task.display_name,
+                            "Skipped by user",
+                        );
                         task_results.push(TaskResult {
                             task_id: task.id.clone(),
                             task_name: task.display_name.clone(),
@@ -577,6 +953,26 @@ impl Installer {
                             ctx.emit_progress(None, InstallPhase::Verifying);
 
                             successful += 1;
+                            install_log.record_installed_files(
+                                &task.display_name,
+                                Path::new(&task.target_path),
+                                &target_before,
+                            );
+                            // Navdata targets are shared folders, so they get no marker
+                            if !matches!(task.addon_type, AddonType::Navdata) {
+                                if let Err(e) = install_marker::write_install_marker(
+                                    Path::new(&task.target_path),
+                                    &InstallMarker::for_task(task, strategy),
+                                ) {
+                                    logger::log_error(
+                                        &format!(
+                                            "Failed to write install marker for {}: {}",
+                                            task.display_name, e
+                                        ),
+                                        Some("installer"),
+                                    );
+                                }
+                            }
                             logger::log_info(
                                 &format!(
                                     "{}: {}",
@@ -594,7 +990,7 @@ impl Installer {
                             });
 
                             // Delete source file after successful installation if enabled
-                            if delete_source_after_install {
+                            if delete_source_after_install && !task.install_as_symlink {
                                 if let Some(original_path) = &task.original_input_path {
                                     if let Err(e) =
                                         self.delete_source_file(original_path, &task.source_path)
@@ -657,6 +1053,32 @@ impl Installer {
                                     }
                                 }
                             }
+
+                            // Move to the position chosen by the user, if any
+                            // Applies with or without auto-sort, once, right after the install
+                            if let Some(sort_order) = task.desired_sort_order.filter(|_| {
+                                task.addon_type == AddonType::Scenery
+                                    || task.addon_type == AddonType::SceneryLibrary
+                            }) {
+                                let target_path = Path::new(&task.target_path);
+                                if let Some(folder_name) =
+                                    target_path.file_name().and_then(|n| n.to_str())
+                                {
+                                    let manager =
+                                        crate::scenery_packs_manager::SceneryPacksManager::new(
+                                            Path::new(&xplane_path),
+                                        );
+                                    if let Err(e) = manager.place_entry(folder_name, sort_order) {
+                                        logger::log_error(
+                                            &format!(
+                                                "Failed to move {} to position {}: {}",
+                                                folder_name, sort_order, e
+                                            ),
+                                            Some("installer"),
+                                        );
+                                    }
+                                }
+                            }
                         }
                         Err(verify_err) => {
                             crate::log_debug!(
@@ -672,6 +1094,11 @@ impl Installer {
 
                             failed += 1;
                             let error_msg = format!("Verification failed: {}", verify_err);
+                            install_log.record(
+                                InstallLogEvent::Error,
+                                &task.display_name,
+                                &error_msg,
+                            );
                             logger::log_error(
                                 &format!(
                                     "{} {}: {}",
@@ -691,6 +1118,28 @@ impl Installer {
                         }
                     }
                 }
+                Err(e) if crate::atomic_installer::is_install_cancelled(&e) => {
+                    logger::log_info(
+                        &format!("Task cancelled by user: {}", task.display_name),
+                        Some("installer"),
+                    );
+
+                    // Keep any explanation attached to the cancel (e.g. a partial merge)
+                    let message = if e.chain().count() > 1 {
+                        format!("Cancelled by user: {}", e)
+                    } else {
+                        "Cancelled by user".to_string()
+                    };
+                    cancelled += 1;
+                    install_log.record(InstallLogEvent::Skipped, &task.display_name, &message);
+                    task_results.push(TaskResult {
+                        task_id: task.id.clone(),
+                        task_name: task.display_name.clone(),
+                        success: false,
+                        error_message: Some(message),
+                        verification_stats: None,
+                    });
+                }
                 Err(e) => {
                     crate::log_debug!(
                         &format!(
@@ -705,6 +1154,7 @@ impl Installer {
 
                     failed += 1;
                     let error_msg = format!("{}", e);
+                    install_log.record(InstallLogEvent::Error, &task.display_name, &error_msg);
                     logger::log_error(
                         &format!(
                             "{} {}: {}",
@@ -760,6 +1210,16 @@ impl Installer {
             "installer_timing"
         );
 
+        install_log.record(
+            InstallLogEvent::Summary,
+            &format!("{} task(s)", tasks.len()),
+            &format!(
+                "{} successful, {} failed, {} skipped, {} cancelled",
+                successful, failed, skipped, cancelled
+            ),
+        );
+        install_log.flush();
+
         Ok(InstallResult {
             total_tasks: tasks.len(),
             successful_tasks: successful,
@@ -768,6 +1228,64 @@ impl Installer {
         })
     }
 
+    /// Sum the uncompressed size of all task sources
+    /// A source that can't be sized is skipped with a warning; its own install
+    /// reports the real error instead of failing the whole batch here
+    fn calculate_required_space(&self, tasks: &[InstallTask]) -> u64 {
+        let mut total = 0u64;
+        for task in tasks {
+            // Linked packages stay on the drive they already live on
+            if task.install_as_symlink {
+                continue;
+            }
+            let source = Path::new(&task.source_path);
+            let size = if source.is_dir() {
+                self.get_directory_size(source)
+            } else if source.is_file() {
+                self.get_archive_size(source, task.archive_internal_root.as_deref())
+            } else {
+                continue;
+            };
+            match size {
+                Ok(size) => total += size,
+                Err(e) => logger::log_info(
+                    &format!(
+                        "Warning: could not determine size of {} for the disk space check: {}",
+                        task.source_path, e
+                    ),
+                    Some("installer"),
+                ),
+            }
+        }
+        total
+    }
+
+    /// Ensure the drive holding `xplane_path` has room for every task plus a safety margin
+    fn check_space_for_tasks(&self, tasks: &[InstallTask], xplane_path: &Path) -> Result<()> {
+        let needed = self.calculate_required_space(tasks);
+        let available = match crate::atomic_installer::available_disk_space(xplane_path) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                logger::log_info(
+                    &format!("Warning: could not query free disk space: {}", e),
+                    Some("installer"),
+                );
+                return Ok(());
+            }
+        };
+
+        logger::log_info(
+            &format!(
+                "Disk space check: {} bytes needed, {} bytes available",
+                needed, available
+            ),
+            Some("installer"),
+        );
+
+        crate::atomic_installer::check_space_requirement(needed, available)?;
+        Ok(())
+    }
+
     /// Calculate total size of all tasks for progress tracking
     /// Returns (total_size, per_task_sizes) for proportional progress calculation
     /// Includes extra size for backup/restore operations during clean install
@@ -804,8 +1322,11 @@ impl Installer {
 
                         // Backup and restore config files (2x: backup + restore)
                         if task.backup_config_files {
-                            let config_size =
-                                self.get_config_files_size(target, &task.config_file_patterns);
+                            let config_size = self.get_config_files_size(
+                                target,
+                                &task.config_file_patterns,
+                                task.config_file_recursive,
+                            );
                             task_size += config_size * 2; // backup + restore
                         }
                     }
@@ -822,26 +1343,12 @@ impl Installer {
     }
 
     /// Get total size of config files matching patterns in a directory
-    fn get_config_files_size(&self, dir: &Path, patterns: &[String]) -> u64 {
-        // Pre-compile patterns once for efficiency
-        let compiled = CompiledPatterns::new(patterns);
-
-        let mut total = 0u64;
-        if let Ok(entries) = fs::read_dir(dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.is_file() {
-                    if let Some(name) = path.file_name().and_then(|s| s.to_str()) {
-                        if compiled.matches(name) {
-                            if let Ok(metadata) = fs::metadata(&path) {
-                                total += metadata.len();
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        total
+    fn get_config_files_size(&self, dir: &Path, patterns: &[String], recursive: bool) -> u64 {
+        find_config_files(dir, patterns, recursive)
+            .iter()
+            .filter_map(|relative| fs::metadata(dir.join(relative)).ok())
+            .map(|metadata| metadata.len())
+            .sum()
     }
 
     /// Verify that the installation was successful by checking for typical files
@@ -1288,10 +1795,7 @@ impl Installer {
         }
 
         // For archives, extract based on format
-        let ext = source
-            .extension()
-            .and_then(|s| s.to_str())
-            .ok_or_else(|| anyhow::anyhow!("No file extension"))?;
+        let ext = archive_format(source)?;
 
         match ext {
             "zip" => self.re_extract_from_zip(
@@ -1661,11 +2165,11 @@ impl Installer {
 
     /// Get uncompressed size of archive
     fn get_archive_size(&self, archive: &Path, internal_root: Option<&str>) -> Result<u64> {
-        let ext = archive.extension().and_then(|s| s.to_str());
-        match ext {
+        match archive_format(archive).ok() {
             Some("zip") => self.get_zip_size(archive, internal_root),
-            Some("7z") => self.get_7z_size(archive),
+            Some("7z") => self.get_7z_size(archive, internal_root),
             Some("rar") => self.get_rar_size(archive),
+            Some("gz") => self.get_tar_gz_size(archive, internal_root),
             _ => Ok(0),
         }
     }
@@ -1692,11 +2196,49 @@ impl Installer {
         Ok(total)
     }
 
-    /// Get uncompressed size of 7z archive (estimate from file size)
-    fn get_7z_size(&self, archive: &Path) -> Result<u64> {
-        // sevenz-rust2 doesn't have easy size query, use compressed size * 3 as estimate
-        let meta = fs::metadata(archive)?;
-        Ok(meta.len() * 3)
+    /// Get uncompressed size of 7z archive from its header
+    fn get_7z_size(&self, archive: &Path, internal_root: Option<&str>) -> Result<u64> {
+        let reader = match sevenz_rust2::Archive::open(archive) {
+            Ok(reader) => reader,
+            Err(_) => {
+                // Encrypted headers can't be read without a password, fall back to an estimate
+                let meta = fs::metadata(archive)?;
+                return Ok(meta.len() * 3);
+            }
+        };
+        let prefix = internal_root.map(|s| s.replace('\\', "/"));
+
+        let mut total = 0u64;
+        for entry in reader.files.iter().filter(|f| !f.is_directory()) {
+            let name = entry.name().replace('\\', "/");
+            if let Some(ref p) = prefix {
+                if !name.starts_with(p.as_str()) {
+                    continue;
+                }
+            }
+            total += entry.size();
+        }
+        Ok(total)
+    }
+
+    /// Get uncompressed size of a .tar.gz archive (entries under internal_root only)
+    fn get_tar_gz_size(&self, archive: &Path, internal_root: Option<&str>) -> Result<u64> {
+        let mut tar = crate::scanner::open_tar_gz(archive)?;
+        let prefix = internal_root.map(|s| s.replace('\\', "/"));
+
+        let mut total = 0u64;
+        for entry in tar.entries()? {
+            let entry = entry?;
+            let path = entry.path()?.to_string_lossy().replace('\\', "/");
+            let path = path.trim_start_matches("./");
+            if let Some(ref p) = prefix {
+                if !path.starts_with(p.as_str()) {
+                    continue;
+                }
+            }
+            total += entry.header().size().unwrap_or(0);
+        }
+        Ok(total)
     }
 
     /// Get uncompressed size of RAR archive
@@ -1721,6 +2263,7 @@ impl Installer {
         ctx: &ProgressContext,
         atomic_install_enabled: bool,
         xplane_path: &str,
+        install_log: &InstallLog,
     ) -> Result<()> {
         let source = Path::new(&task.source_path);
         let target = Path::new(&task.target_path);
@@ -1740,6 +2283,10 @@ impl Installer {
             "installer_timing"
         );
 
+        if task.install_as_symlink {
+            return self.install_task_as_symlink(task, source, target, Path::new(xplane_path));
+        }
+
         // Check if this is a nested archive installation
         if let Some(ref chain) = task.extraction_chain {
             crate::log_debug!(
@@ -1771,7 +2318,15 @@ impl Installer {
                 "[TIMING] Using atomic installation mode",
                 "installer_timing"
             );
-            self.install_task_atomic(task, source, target, ctx, password, xplane_path)?;
+            self.install_task_atomic(
+                task,
+                source,
+                target,
+                ctx,
+                password,
+                xplane_path,
+                install_log,
+            )?;
         } else {
             // Regular installation (non-nested, non-atomic)
             if !task.should_overwrite && target.exists() {
@@ -1800,6 +2355,54 @@ impl Installer {
         Ok(())
     }
 
+    /// Install a scenery task by linking Custom Scenery/<name> to the source folder
+    /// The package stays where it is; an existing target is replaced (clean install only)
+    fn install_task_as_symlink(
+        &self,
+        task: &InstallTask,
+        source: &Path,
+        target: &Path,
+        xplane_path: &Path,
+    ) -> Result<()> {
+        use crate::atomic_installer::{create_dir_symlink, is_symlink};
+
+        if !matches!(
+            task.addon_type,
+            AddonType::Scenery | AddonType::SceneryLibrary
+        ) {
+            return Err(anyhow::anyhow!(
+                "Symlink install is only supported for scenery: {}",
+                task.display_name
+            ));
+        }
+        if task.extraction_chain.is_some() || !source.is_dir() {
+            return Err(anyhow::anyhow!(
+                "Symlink install requires an extracted scenery folder: {:?}",
+                source
+            ));
+        }
+
+        let source = source
+            .canonicalize()
+            .context(format!("Failed to resolve source folder: {:?}", source))?;
+
+        if task.should_overwrite && target.exists() && !is_symlink(target) {
+            return Err(anyhow::anyhow!(
+                "Cannot merge into an existing folder when installing as a symlink: {:?}",
+                target
+            ));
+        }
+
+        link_scenery_folder(&source, target, xplane_path, create_dir_symlink)?;
+
+        logger::log_info(
+            &format!("Linked scenery: {:?} -> {:?}", target, source),
+            Some("installer"),
+        );
+
+        Ok(())
+    }
+
     /// Install content with progress tracking
     fn install_content_with_progress(
         &self,
@@ -2344,45 +2947,6 @@ impl Installer {
         Ok(())
     }
 
-    /// Move all contents from source directory to target directory
-    #[allow(dead_code)]
-    fn move_directory_contents(&self, source: &Path, target: &Path) -> Result<()> {
-        for entry in fs::read_dir(source)
-            .context(format!("Failed to read source directory: {:?}", source))?
-        {
-            let entry = entry?;
-            let source_path = entry.path();
-            let file_name = entry.file_name();
-            let target_path = target.join(&file_name);
-
-            if source_path.is_dir() {
-                // Try to rename (move) the directory
-                if let Err(_) = fs::rename(&source_path, &target_path) {
-                    // Fallback: copy and delete
-                    self.copy_directory_with_progress(
-                        &source_path,
-                        &target_path,
-                        &ProgressContext::new(self.app_handle.clone(), 1),
-                    )?;
-                    remove_dir_all_robust(&source_path).context(format!(
-                        "Failed to remove source directory: {:?}",
-                        source_path
-                    ))?;
-                }
-            } else {
-                // Try to rename (move) the file
-                if let Err(_) = fs::rename(&source_path, &target_path) {
-                    // Fallback: copy and delete
-                    fs::copy(&source_path, &target_path)
-                        .context(format!("Failed to copy file: {:?}", source_path))?;
-                    fs::remove_file(&source_path)
-                        .context(format!("Failed to remove source file: {:?}", source_path))?;
-                }
-            }
-        }
-        Ok(())
-    }
-
     /// Handle clean install with extraction chain (for nested archives)
     fn handle_clean_install_with_extraction_chain(
         &self,
@@ -2407,6 +2971,7 @@ impl Installer {
                     task.backup_liveries,
                     task.backup_config_files,
                     &task.config_file_patterns,
+                    task.config_file_recursive,
                 )?;
             }
             crate::models::AddonType::Navdata => {
@@ -2437,10 +3002,12 @@ impl Installer {
         backup_liveries: bool,
         backup_config_files: bool,
         config_file_patterns: &[String],
+        config_file_recursive: bool,
     ) -> Result<()> {
         use uuid::Uuid;
 
         // Step 1: Backup liveries and config files if requested
+        let mut config_files = Vec::new();
         let backup_dir = if (backup_liveries || backup_config_files) && target.exists() {
             let temp_dir = std::env::temp_dir();
             let backup_path = temp_dir.join(format!("xfastmanager_backup_{}", Uuid::new_v4()));
@@ -2455,24 +3022,18 @@ impl Installer {
                 }
             }
 
-            // Backup config files
+            // Backup config files (keeping their path relative to the aircraft folder)
             if backup_config_files {
-                for pattern in config_file_patterns {
-                    for entry in glob::glob(&target.join(pattern).to_string_lossy())
-                        .context("Failed to read glob pattern")?
-                    {
-                        if let Ok(config_file) = entry {
-                            if config_file.is_file() {
-                                if let Some(file_name) = config_file.file_name() {
-                                    let backup_file = backup_path.join(file_name);
-                                    fs::copy(&config_file, &backup_file).context(format!(
-                                        "Failed to backup config file: {:?}",
-                                        config_file
-                                    ))?;
-                                }
-                            }
-                        }
+                config_files =
+                    find_config_files(target, config_file_patterns, config_file_recursive);
+                for relative in &config_files {
+                    let config_file = target.join(relative);
+                    let backup_file = backup_path.join(relative);
+                    if let Some(parent) = backup_file.parent() {
+                        fs::create_dir_all(parent)?;
                     }
+                    fs::copy(&config_file, &backup_file)
+                        .context(format!("Failed to backup config file: {:?}", config_file))?;
                 }
             }
 
@@ -2502,16 +3063,14 @@ impl Installer {
             }
 
             // Restore config files
-            for entry in fs::read_dir(&backup_path).context("Failed to read backup directory")? {
-                let entry = entry?;
-                let path = entry.path();
-                if path.is_file() {
-                    if let Some(file_name) = path.file_name() {
-                        let target_file = target.join(file_name);
-                        fs::copy(&path, &target_file)
-                            .context(format!("Failed to restore config file: {:?}", path))?;
-                    }
+            for relative in &config_files {
+                let path = backup_path.join(relative);
+                let target_file = target.join(relative);
+                if let Some(parent) = target_file.parent() {
+                    fs::create_dir_all(parent)?;
                 }
+                fs::copy(&path, &target_file)
+                    .context(format!("Failed to restore config file: {:?}", path))?;
             }
 
             // Verify restoration and cleanup backup
@@ -2545,6 +3104,7 @@ impl Installer {
                     task.backup_liveries,
                     task.backup_config_files,
                     &task.config_file_patterns,
+                    task.config_file_recursive,
                 )?;
             }
             AddonType::Navdata => {
@@ -2558,6 +3118,14 @@ impl Installer {
                     password,
                 )?;
             }
+            AddonType::Scenery | AddonType::SceneryLibrary
+                if !task.scenery_preserve_patterns.is_empty() =>
+            {
+                // For Scenery with preserve patterns: backup matching files, delete, install, restore
+                self.handle_scenery_clean_install_with_progress(
+                    task, source, target, ctx, password,
+                )?;
+            }
             _ => {
                 // For other types: delete and reinstall using robust removal
                 if target.exists() {
@@ -2576,6 +3144,68 @@ impl Installer {
         Ok(())
     }
 
+    /// Scenery clean install that keeps files matching the task's preserve patterns
+    fn handle_scenery_clean_install_with_progress(
+        &self,
+        task: &InstallTask,
+        source: &Path,
+        target: &Path,
+        ctx: &ProgressContext,
+        password: Option<&str>,
+    ) -> Result<()> {
+        use crate::atomic_installer::copy_preserved_files;
+        use uuid::Uuid;
+
+        // Step 1: Backup preserved files
+        let backup_dir =
+            std::env::temp_dir().join(format!("xfastmanager_backup_{}", Uuid::new_v4()));
+        let preserved = copy_preserved_files(target, &backup_dir, &task.scenery_preserve_patterns)
+            .context("Failed to backup preserved scenery files")?;
+        logger::log_info(
+            &format!(
+                "Backed up {} preserved scenery file(s) to {:?}",
+                preserved, backup_dir
+            ),
+            Some("installer"),
+        );
+
+        // Step 2: Delete target folder
+        if target.exists() {
+            remove_dir_all_robust(target)
+                .context(format!("Failed to delete existing folder: {:?}", target))?;
+        }
+
+        // Step 3: Install new content with progress
+        let install_result = self.install_content_with_progress(
+            source,
+            target,
+            task.archive_internal_root.as_deref(),
+            ctx,
+            password,
+        );
+
+        // Step 4: Restore preserved files (keep the backup if this fails)
+        if preserved > 0 {
+            if let Err(e) =
+                copy_preserved_files(&backup_dir, target, &task.scenery_preserve_patterns)
+            {
+                let message = format!(
+                    "Failed to restore preserved files: {}. Your backup is preserved at: {:?}.",
+                    e, backup_dir
+                );
+                // Keep the install error that likely caused the restore to fail
+                return Err(match install_result {
+                    Err(install_err) => install_err.context(message),
+                    Ok(()) => anyhow::anyhow!(message),
+                });
+            }
+        }
+        let _ = fs::remove_dir_all(&backup_dir);
+
+        install_result?;
+        Ok(())
+    }
+
     /// Aircraft clean install with progress tracking
     fn handle_aircraft_clean_install_with_progress(
         &self,
@@ -2587,6 +3217,7 @@ impl Installer {
         backup_liveries: bool,
         backup_config_files: bool,
         config_patterns: &[String],
+        config_recursive: bool,
     ) -> Result<()> {
         // Step 1: Create backup of important files
         let backup = self.backup_aircraft_data(
@@ -2594,6 +3225,7 @@ impl Installer {
             backup_liveries,
             backup_config_files,
             config_patterns,
+            config_recursive,
             ctx,
         )?;
 
@@ -2658,6 +3290,7 @@ impl Installer {
         backup_liveries: bool,
         backup_config_files: bool,
         config_patterns: &[String],
+        config_recursive: bool,
         ctx: &ProgressContext,
     ) -> Result<Option<AircraftBackup>> {
         if !target.exists() {
@@ -2704,40 +3337,30 @@ impl Installer {
             }
         }
 
-        // Backup config files from root directory only if enabled
+        // Backup config files (root directory only unless recursive) if enabled
         if backup_config_files && !config_patterns.is_empty() {
             ctx.emit_progress(
                 Some("Backing up config files...".to_string()),
                 InstallPhase::Installing,
             );
 
-            // Pre-compile patterns once for efficiency
-            let compiled = CompiledPatterns::new(config_patterns);
-
-            for entry in fs::read_dir(target)? {
-                let entry = entry?;
-                let path = entry.path();
-
-                if path.is_file() {
-                    if let Some(name) = path.file_name().and_then(|s| s.to_str()) {
-                        if compiled.matches(name) {
-                            let original_size = fs::metadata(&path)?.len();
-                            let backup_path = temp_dir.join(name);
-                            fs::copy(&path, &backup_path)
-                                .context(format!("Failed to backup {}", name))?;
-                            backup
-                                .pref_files
-                                .push((name.to_string(), backup_path.clone()));
-                            backup
-                                .original_pref_sizes
-                                .push((name.to_string(), original_size));
-
-                            // Update progress for each config file with filename for real-time display
-                            ctx.add_bytes(original_size);
-                            ctx.emit_progress(Some(name.to_string()), InstallPhase::Installing);
-                        }
-                    }
+            for relative in find_config_files(target, config_patterns, config_recursive) {
+                let path = target.join(&relative);
+                let name = relative.to_string_lossy().to_string();
+                let original_size = fs::metadata(&path)?.len();
+                let backup_path = temp_dir.join(&relative);
+                if let Some(parent) = backup_path.parent() {
+                    fs::create_dir_all(parent)?;
                 }
+                fs::copy(&path, &backup_path).context(format!("Failed to backup {}", name))?;
+                backup.pref_files.push((name.clone(), backup_path.clone()));
+                backup
+                    .original_pref_sizes
+                    .push((name.clone(), original_size));
+
+                // Update progress for each config file with filename for real-time display
+                ctx.add_bytes(original_size);
+                ctx.emit_progress(Some(name), InstallPhase::Installing);
             }
         }
 
@@ -2891,6 +3514,9 @@ impl Installer {
 
             for (filename, backup_path) in &backup.pref_files {
                 let target_path = target.join(filename);
+                if let Some(parent) = target_path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
                 let size = fs::metadata(backup_path)?.len();
                 fs::copy(backup_path, &target_path)
                     .context(format!("Failed to restore pref file: {}", filename))?;
@@ -2987,7 +3613,8 @@ impl Installer {
                 let relative = source_path.strip_prefix(source)?;
                 let target_path = target.join(relative);
 
-                let file_size = entry.metadata()?.len();
+                let metadata = entry.metadata()?;
+                let file_size = metadata.len();
                 let file_name = source_path
                     .file_name()
                     .and_then(|s| s.to_str())
@@ -3000,6 +3627,8 @@ impl Installer {
                 let mut target_file = fs::File::create(&target_path)
                     .context(format!("Failed to create target file {:?}", target_path))?;
                 copy_file_optimized(&mut source_file, &mut target_file)?;
+                drop(target_file);
+                crate::atomic_installer::preserve_mtime(&metadata, &target_path);
 
                 // Remove read-only attribute from copied file to avoid future deletion issues
                 let _ = remove_readonly_attribute(&target_path);
@@ -3010,6 +3639,20 @@ impl Installer {
                 Ok(())
             })?;
 
+        // Directory mtimes last (deepest first), as filling them bumps their mtime
+        // The target root is skipped so the scenery index re-classifies the package
+        for entry in entries.iter().rev() {
+            if entry.depth() > 0 && entry.file_type().is_dir() {
+                if let Ok(metadata) = entry.metadata() {
+                    let relative = entry
+                        .path()
+                        .strip_prefix(source)
+                        .context("Failed to strip prefix")?;
+                    crate::atomic_installer::preserve_mtime(&metadata, &target.join(relative));
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -3023,10 +3666,7 @@ impl Installer {
         password: Option<&str>,
     ) -> Result<()> {
         let extract_start = Instant::now();
-        let extension = archive
-            .extension()
-            .and_then(|s| s.to_str())
-            .ok_or_else(|| anyhow::anyhow!("No file extension"))?;
+        let extension = archive_format(archive)?;
 
         crate::log_debug!(
             &format!("[TIMING] Archive extraction started: {} format", extension),
@@ -3041,6 +3681,7 @@ impl Installer {
             "rar" => {
                 self.extract_rar_with_progress(archive, target, internal_root, ctx, password)?
             }
+            "gz" => self.extract_tar_gz_with_progress(archive, target, internal_root, ctx)?,
             _ => return Err(anyhow::anyhow!("Unsupported archive format: {}", extension)),
         }
 
@@ -3072,7 +3713,8 @@ impl Installer {
 
         // Open archive and collect file metadata
         let file = fs::File::open(archive_path)?;
-        let mut archive = ZipArchive::new(file)?;
+        let mut archive = ZipArchive::new(file)
+            .with_context(|| format!("Not a readable ZIP archive: {}", archive_path.display()))?;
 
         let internal_root_normalized = internal_root.map(|s| s.replace('\\', "/"));
         let prefix = internal_root_normalized.as_deref();
@@ -3185,12 +3827,15 @@ impl Installer {
 
         // Create all directories first (sequential)
         let file = fs::File::open(archive_path)?;
-        let archive = ZipArchive::new(file)?;
+        let mut archive = ZipArchive::new(file)?;
 
-        for (_index, relative_path, is_dir, _, _) in &entries {
+        let mut link_indices: HashSet<usize> = HashSet::new();
+        for (index, relative_path, is_dir, is_encrypted, _) in &entries {
             if *is_dir {
                 let outpath = target.join(relative_path);
                 fs::create_dir_all(&outpath)?;
+            } else if !*is_encrypted && is_zip_symlink(archive.by_index_raw(*index)?.unix_mode()) {
+                link_indices.insert(*index);
             }
         }
 
@@ -3203,9 +3848,10 @@ impl Installer {
         let target = target.to_path_buf();
         let password_bytes = Arc::new(password_bytes);
 
+        // Links are created after all regular files so no file is written through one
         entries
             .par_iter()
-            .filter(|(_, _, is_dir, _, _)| !is_dir)
+            .filter(|(index, _, is_dir, _, _)| !is_dir && !link_indices.contains(index))
             .try_for_each(|(index, relative_path, _, is_encrypted, _)| -> Result<()> {
                 // Each thread opens its own ZipArchive instance
                 let file = fs::File::open(&archive_path)?;
@@ -3230,7 +3876,10 @@ impl Installer {
                                 size
                             }
                             Err(e) => {
-                                return Err(e.into());
+                                return Err(anyhow::Error::from(e).context(format!(
+                                    "Failed to decrypt ZIP entry (wrong password or unsupported encryption): {}",
+                                    relative_path.display()
+                                )));
                             }
                         }
                     } else {
@@ -3240,7 +3889,12 @@ impl Installer {
                         ));
                     }
                 } else {
-                    let mut file = archive.by_index(*index)?;
+                    let mut file = archive.by_index(*index).with_context(|| {
+                        format!(
+                            "Unsupported or corrupted ZIP entry: {}",
+                            relative_path.display()
+                        )
+                    })?;
                     let size = file.size();
                     let mut outfile = fs::File::create(&outpath)?;
                     copy_file_optimized(&mut file, &mut outfile)?;
@@ -3269,6 +3923,18 @@ impl Installer {
                 Ok(())
             })?;
 
+        // Recreate symlinks stored by Unix zip tools instead of writing them as files
+        #[cfg(unix)]
+        if !link_indices.is_empty() {
+            let links: Vec<(usize, &Path)> = entries
+                .iter()
+                .filter(|(index, _, _, _, _)| link_indices.contains(index))
+                .map(|(index, relative_path, _, _, _)| (*index, relative_path.as_path()))
+                .collect();
+            let mut archive = ZipArchive::new(fs::File::open(&archive_path)?)?;
+            create_zip_symlinks(&mut archive, &target, &links)?;
+        }
+
         Ok(())
     }
 
@@ -3629,11 +4295,41 @@ impl Installer {
         Ok(())
     }
 
+    /// Extract a .tar.gz/.tgz archive with progress tracking
+    /// Entries are unpacked directly into the target so Unix permissions and symlinks are
+    /// preserved. Only entries under internal_root are extracted, with that prefix stripped
+    /// (same single-root flattening as the ZIP path).
+    fn extract_tar_gz_with_progress(
+        &self,
+        archive_path: &Path,
+        target: &Path,
+        internal_root: Option<&str>,
+        ctx: &ProgressContext,
+    ) -> Result<()> {
+        let mut archive = crate::scanner::open_tar_gz(archive_path)?;
+        archive.set_preserve_permissions(true);
+        archive.set_preserve_mtime(true);
+
+        unpack_tar_entries(&mut archive, target, internal_root, |relative, size| {
+            ctx.add_bytes(size);
+            let display_name = relative
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            ctx.emit_progress(Some(display_name), InstallPhase::Installing);
+        })
+    }
+
     /// Cleanup a task by removing its target directory
     /// Used when a task is cancelled or skipped
     fn cleanup_task(&self, task: &InstallTask) -> Result<()> {
         let target = Path::new(&task.target_path);
 
+        // Only the link is ours; the folder it points to belongs to the user
+        if crate::atomic_installer::is_symlink(target) {
+            return crate::atomic_installer::remove_dir_symlink(target);
+        }
+
         if !target.exists() {
             return Ok(());
         }
@@ -3674,6 +4370,7 @@ impl Installer {
         ctx: &ProgressContext,
         password: Option<&str>,
         xplane_path: &str,
+        install_log: &InstallLog,
     ) -> Result<()> {
         use crate::atomic_installer::AtomicInstaller;
 
@@ -3687,7 +4384,9 @@ impl Installer {
             self.app_handle.clone(),
             ctx.total_tasks,
             ctx.current_task_index,
-        )?;
+            install_log.clone(),
+        )?
+        .with_cancel_token(self.task_control.cancel_token());
 
         // Step 1: Extract/copy to temp directory
         logger::log_info(
@@ -3710,7 +4409,7 @@ impl Installer {
         if !target.exists() {
             // Scenario 1: Fresh installation
             atomic.install_fresh()?;
-        } else if !task.should_overwrite {
+        } else if !merges_into_existing_target(&task.addon_type, task.should_overwrite) {
             // Scenario 2: Clean installation (should_overwrite=false means clean install)
             atomic.install_clean(task)?;
         } else {
@@ -3795,10 +4494,281 @@ impl Installer {
     }
 }
 
+/// List the target files an overwrite install of `task` would replace
+/// Reads the source listing (directory walk or archive index) without extracting
+/// anything and returns the target paths that already exist as files
+pub fn preview_overwrite(task: &InstallTask) -> Result<Vec<PathBuf>> {
+    if task.extraction_chain.is_some() {
+        return Err(anyhow::anyhow!(
+            "Overwrite preview is not supported for nested archives"
+        ));
+    }
+
+    overwritten_files(
+        Path::new(&task.source_path),
+        Path::new(&task.target_path),
+        task.archive_internal_root.as_deref(),
+    )
+}
+
+fn overwritten_files(
+    source: &Path,
+    target: &Path,
+    internal_root: Option<&str>,
+) -> Result<Vec<PathBuf>> {
+    if !target.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut overwritten: Vec<PathBuf> = list_source_files(source, internal_root)?
+        .into_iter()
+        .map(|relative| target.join(relative))
+        .filter(|path| path.is_file())
+        .collect();
+    overwritten.sort();
+    overwritten.dedup();
+
+    Ok(overwritten)
+}
+
+/// List the files of an install source relative to the install target
+fn list_source_files(source: &Path, internal_root: Option<&str>) -> Result<Vec<PathBuf>> {
+    if source.is_dir() {
+        return Ok(walkdir::WalkDir::new(source)
+            .follow_links(false)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .filter_map(|e| e.path().strip_prefix(source).ok().map(Path::to_path_buf))
+            .collect());
+    }
+
+    let extension = archive_format(source)?;
+
+    let names: Vec<String> = match extension {
+        "zip" => {
+            let mut archive = zip::ZipArchive::new(fs::File::open(source)?)?;
+            let mut names = Vec::new();
+            for i in 0..archive.len() {
+                let file = archive.by_index_raw(i)?;
+                if !file.is_dir() {
+                    names.push(file.name().to_string());
+                }
+            }
+            names
+        }
+        "7z" => sevenz_rust2::Archive::open(source)
+            .map_err(|e| anyhow::anyhow!("Failed to open 7z archive: {}", e))?
+            .files
+            .iter()
+            .filter(|f| !f.is_directory())
+            .map(|f| f.name().to_string())
+            .collect(),
+        "rar" => unrar::Archive::new(source)
+            .open_for_listing()
+            .map_err(|e| anyhow::anyhow!("Failed to open RAR archive: {:?}", e))?
+            .filter_map(|entry| entry.ok())
+            .filter(|e| !e.is_directory())
+            .map(|e| e.filename.to_string_lossy().to_string())
+            .collect(),
+        "gz" => {
+            let mut tar = crate::scanner::open_tar_gz(source)?;
+            let mut names = Vec::new();
+            for entry in tar.entries()? {
+                let entry = entry?;
+                if entry.header().entry_type().is_file() {
+                    names.push(entry.path()?.to_string_lossy().to_string());
+                }
+            }
+            names
+        }
+        _ => return Err(anyhow::anyhow!("Unsupported archive format: {}", extension)),
+    };
+
+    Ok(names
+        .iter()
+        .filter_map(|name| relative_archive_path(name, internal_root))
+        .collect())
+}
+
+/// Whether an atomic install into an existing target merges instead of replacing it
+/// Navdata always merges: its target is the shared Custom Data folder, which a clean
+/// install would replace as a whole
+fn merges_into_existing_target(addon_type: &AddonType, should_overwrite: bool) -> bool {
+    should_overwrite || matches!(addon_type, AddonType::Navdata)
+}
+
+/// Path of an archive entry relative to the install target
+/// Entries outside `internal_root` and unsafe paths are dropped
+fn relative_archive_path(name: &str, internal_root: Option<&str>) -> Option<PathBuf> {
+    let normalized = name.replace('\\', "/");
+    let normalized = normalized.trim_start_matches("./");
+
+    let relative = match internal_root {
+        Some(root) => {
+            let prefix = format!("{}/", root.replace('\\', "/").trim_end_matches('/'));
+            normalized.strip_prefix(prefix.as_str())?
+        }
+        None => normalized,
+    };
+
+    if relative.is_empty() {
+        return None;
+    }
+    sanitize_path(Path::new(relative))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_relative_archive_path() {
+        assert_eq!(
+            relative_archive_path("A330/objects/a.obj", Some("A330")),
+            Some(PathBuf::from("objects/a.obj"))
+        );
+        assert_eq!(
+            relative_archive_path("A330\\a330.acf", Some("A330/")),
+            Some(PathBuf::from("a330.acf"))
+        );
+        assert_eq!(
+            relative_archive_path("A330_variant/a.acf", Some("A330")),
+            None
+        );
+        assert_eq!(relative_archive_path("../evil.txt", None), None);
+        assert_eq!(
+            relative_archive_path("./readme.txt", None),
+            Some(PathBuf::from("readme.txt"))
+        );
+    }
+
+    #[test]
+    fn test_merges_into_existing_target() {
+        assert!(merges_into_existing_target(&AddonType::Scenery, true));
+        assert!(!merges_into_existing_target(&AddonType::Scenery, false));
+        assert!(!merges_into_existing_target(&AddonType::Aircraft, false));
+        // Clean navdata installs must not replace Custom Data
+        assert!(merges_into_existing_target(&AddonType::Navdata, false));
+        assert!(merges_into_existing_target(&AddonType::Navdata, true));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_link_scenery_folder() {
+        use crate::atomic_installer::{create_dir_symlink, TEMP_DIR_PREFIX};
+
+        let temp = tempfile::tempdir().unwrap();
+        let xplane = temp.path().join("X-Plane");
+        let custom_scenery = xplane.join("Custom Scenery");
+        let target = custom_scenery.join("Ortho_Europe");
+        let source = temp.path().join("Second Drive").join("Ortho_Europe");
+        fs::create_dir_all(&source).unwrap();
+        fs::create_dir_all(&target).unwrap();
+        fs::write(target.join("old.txt"), "old").unwrap();
+
+        let staging_dirs = || -> usize {
+            fs::read_dir(&xplane)
+                .unwrap()
+                .flatten()
+                .filter(|e| e.file_name().to_string_lossy().starts_with(TEMP_DIR_PREFIX))
+                .count()
+        };
+
+        // Linking fails: the existing folder is moved back untouched
+        let denied = |_: &Path, _: &Path| -> Result<()> { Err(anyhow::anyhow!("denied")) };
+        assert!(link_scenery_folder(&source, &target, &xplane, denied).is_err());
+        assert_eq!(fs::read_to_string(target.join("old.txt")).unwrap(), "old");
+        assert_eq!(staging_dirs(), 0);
+
+        // The old folder is never staged inside Custom Scenery
+        let staged_in_custom_scenery = |_: &Path, _: &Path| -> Result<()> {
+            let entries: Vec<_> = fs::read_dir(&custom_scenery).unwrap().flatten().collect();
+            assert!(entries.is_empty());
+            assert_eq!(staging_dirs(), 1);
+            Err(anyhow::anyhow!("denied"))
+        };
+        assert!(link_scenery_folder(&source, &target, &xplane, staged_in_custom_scenery).is_err());
+
+        // Linking succeeds: the target points at the source and the old folder is gone
+        link_scenery_folder(&source, &target, &xplane, create_dir_symlink).unwrap();
+        assert!(crate::atomic_installer::is_symlink(&target));
+        assert_eq!(fs::read_link(&target).unwrap(), source);
+        assert_eq!(staging_dirs(), 0);
+
+        // Replacing a link whose new link fails restores the old link
+        let other = temp.path().join("Other");
+        fs::create_dir_all(&other).unwrap();
+        assert!(link_scenery_folder(&other, &target, &xplane, |a: &Path, b: &Path| {
+            if a == other {
+                Err(anyhow::anyhow!("denied"))
+            } else {
+                create_dir_symlink(a, b)
+            }
+        })
+        .is_err());
+        assert_eq!(fs::read_link(&target).unwrap(), source);
+    }
+
+    #[test]
+    fn test_preview_overwrite_from_directory() {
+        let temp = tempfile::tempdir().unwrap();
+        let source = temp.path().join("source");
+        let target = temp.path().join("target");
+        fs::create_dir_all(source.join("objects")).unwrap();
+        fs::create_dir_all(target.join("objects")).unwrap();
+        fs::write(source.join("a.acf"), "new").unwrap();
+        fs::write(source.join("objects").join("b.obj"), "new").unwrap();
+        fs::write(source.join("c.txt"), "new").unwrap();
+        fs::write(target.join("a.acf"), "old").unwrap();
+        fs::write(target.join("objects").join("b.obj"), "old").unwrap();
+        fs::write(target.join("user.prf"), "keep").unwrap();
+
+        assert_eq!(list_source_files(&source, None).unwrap().len(), 3);
+        assert_eq!(
+            overwritten_files(&source, &target, None).unwrap(),
+            vec![target.join("a.acf"), target.join("objects").join("b.obj")]
+        );
+        assert!(
+            overwritten_files(&source, &temp.path().join("missing"), None)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_find_config_files() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        fs::create_dir_all(dir.join("Settings")).unwrap();
+        fs::create_dir_all(dir.join("liveries").join("Red")).unwrap();
+        fs::write(dir.join("A320_prefs.txt"), "root").unwrap();
+        fs::write(dir.join("Settings").join("fms_prefs.txt"), "sub").unwrap();
+        fs::write(dir.join("Settings").join("readme.txt"), "other").unwrap();
+        fs::write(
+            dir.join("liveries").join("Red").join("red_prefs.txt"),
+            "livery",
+        )
+        .unwrap();
+        let patterns = vec!["*_prefs.txt".to_string()];
+
+        // Default behavior only looks at the root directory
+        assert_eq!(
+            find_config_files(dir, &patterns, false),
+            vec![PathBuf::from("A320_prefs.txt")]
+        );
+
+        let mut found = find_config_files(dir, &patterns, true);
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                PathBuf::from("A320_prefs.txt"),
+                Path::new("Settings").join("fms_prefs.txt"),
+            ]
+        );
+    }
+
     #[test]
     fn test_sanitize_path_normal() {
         let path = Path::new("folder/subfolder/file.txt");
@@ -3852,6 +4822,142 @@ mod tests {
         assert!(result.is_none(), "Only parent dir should be rejected");
     }
 
+    #[test]
+    fn test_link_stays_inside() {
+        assert!(link_stays_inside(
+            Path::new("lin_x64/plugin.xpl"),
+            Path::new("../64/plugin.xpl")
+        ));
+        assert!(link_stays_inside(Path::new("a/b/link"), Path::new("./c")));
+        assert!(!link_stays_inside(Path::new("link"), Path::new("../outside")));
+        assert!(!link_stays_inside(
+            Path::new("a/link"),
+            Path::new("../../etc/passwd")
+        ));
+        assert!(!link_stays_inside(Path::new("a/link"), Path::new("/etc/passwd")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_tar_chained_symlink_cannot_escape() {
+        let temp = tempfile::tempdir().unwrap();
+        let target = temp.path().join("addon");
+
+        // `b -> a/..` passes the text check but resolves above the target through `a -> .`
+        let mut builder = tar::Builder::new(Vec::new());
+        for (name, link) in [("a", "."), ("b", "a/..")] {
+            let mut header = tar::Header::new_gnu();
+            header.set_entry_type(tar::EntryType::Symlink);
+            header.set_size(0);
+            builder.append_link(&mut header, name, link).unwrap();
+        }
+        let data = b"evil";
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, "b/evil.txt", &data[..])
+            .unwrap();
+        let bytes = builder.into_inner().unwrap();
+
+        let mut files = Vec::new();
+        let mut archive = tar::Archive::new(&bytes[..]);
+        unpack_tar_entries(&mut archive, &target, None, |relative, _| {
+            files.push(relative.to_path_buf())
+        })
+        .unwrap();
+
+        assert!(!temp.path().join("evil.txt").exists());
+        assert!(fs::symlink_metadata(target.join("a")).is_ok());
+        for file in &files {
+            let resolved = target.join(file).canonicalize().unwrap();
+            assert!(resolved.starts_with(target.canonicalize().unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_archive_format_prefers_magic_bytes() {
+        use std::io::Write;
+
+        let temp = tempfile::tempdir().unwrap();
+
+        // A zip download saved with the wrong extension
+        let misnamed = temp.path().join("scenery.7z");
+        let mut writer = zip::ZipWriter::new(fs::File::create(&misnamed).unwrap());
+        writer
+            .start_file(
+                "Earth nav data/a.dsf",
+                zip::write::SimpleFileOptions::default(),
+            )
+            .unwrap();
+        writer.write_all(b"data").unwrap();
+        writer.finish().unwrap();
+        assert_eq!(archive_format(&misnamed).unwrap(), "zip");
+
+        // Unknown content falls back to the extension
+        let empty = temp.path().join("EMPTY.ZIP");
+        fs::write(&empty, b"").unwrap();
+        assert_eq!(archive_format(&empty).unwrap(), "zip");
+
+        let text = temp.path().join("readme.txt");
+        fs::write(&text, b"hello").unwrap();
+        assert!(archive_format(&text).is_err());
+
+        // Gzip data is only a tarball when the name says so
+        let gzip = [0x1f, 0x8b, 0x08, 0x00];
+        let tarball = temp.path().join("scenery.tgz");
+        fs::write(&tarball, gzip).unwrap();
+        assert_eq!(archive_format(&tarball).unwrap(), "gz");
+        let plain = temp.path().join("notes.txt.gz");
+        fs::write(&plain, gzip).unwrap();
+        assert!(archive_format(&plain).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_create_zip_symlinks() {
+        use std::io::Write;
+
+        let temp = tempfile::tempdir().unwrap();
+        let archive_path = temp.path().join("links.zip");
+        let options = zip::write::SimpleFileOptions::default();
+        let mut writer = zip::ZipWriter::new(fs::File::create(&archive_path).unwrap());
+        writer.start_file("real/file.txt", options).unwrap();
+        writer.write_all(b"data").unwrap();
+        writer.add_symlink("link", "real", options).unwrap();
+        writer.add_symlink("real/parent", "..", options).unwrap();
+        writer.add_symlink("escape", "../outside", options).unwrap();
+        writer.finish().unwrap();
+
+        let target = temp.path().join("target");
+        fs::create_dir_all(target.join("real")).unwrap();
+        fs::write(target.join("real").join("file.txt"), "data").unwrap();
+
+        let mut archive = zip::ZipArchive::new(fs::File::open(&archive_path).unwrap()).unwrap();
+        let names: Vec<PathBuf> = (0..archive.len())
+            .map(|i| PathBuf::from(archive.by_index_raw(i).unwrap().name()))
+            .collect();
+        let links: Vec<(usize, &Path)> = (0..archive.len())
+            .filter(|i| is_zip_symlink(archive.by_index_raw(*i).unwrap().unix_mode()))
+            .map(|i| (i, names[i].as_path()))
+            .collect();
+        assert_eq!(links.len(), 3);
+
+        create_zip_symlinks(&mut archive, &target, &links).unwrap();
+
+        assert_eq!(fs::read_link(target.join("link")).unwrap(), Path::new("real"));
+        assert_eq!(
+            fs::read_to_string(target.join("link").join("file.txt")).unwrap(),
+            "data"
+        );
+        assert_eq!(
+            fs::read_link(target.join("real").join("parent")).unwrap(),
+            Path::new("..")
+        );
+        assert!(fs::symlink_metadata(target.join("escape")).is_err());
+    }
+
     #[test]
     fn test_zip_bomb_constants() {
         // Verify constants are reasonable
@@ -5,6 +5,8 @@ mod cache;
 mod database;
 mod error;
 mod hash_collector;
+mod install_log;
+mod install_marker;
 mod installer;
 mod livery_patterns;
 mod logger;
@@ -13,9 +15,11 @@ mod models;
 mod performance;
 mod registry;
 mod scanner;
+mod scenery_audit;
 mod scenery_classifier;
 mod scenery_index;
 mod scenery_packs_manager;
+mod scenery_watch;
 mod task_control;
 mod updater;
 mod verifier;
@@ -25,14 +29,16 @@ use std::collections::HashMap;
 use crate::error::ToTauriError;
 use analyzer::Analyzer;
 use installer::Installer;
+use management_index::RemoteVersionCache;
 use models::{
-    AircraftInfo, AnalysisResult, InstallResult, InstallTask, ManagementData,
-    NavdataManagerInfo, PluginInfo, SceneryIndexScanResult, SceneryIndexStats,
-    SceneryIndexStatus, SceneryManagerData, SceneryPackageInfo,
+    AircraftInfo, AnalysisResult, DatabaseVacuumResult, InstallResult, InstallTask, ManagementData,
+    NavdataManagerInfo, PluginInfo, SceneryIndexScanResult, SceneryIndexStats, SceneryIndexStatus,
+    SceneryManagerData, SceneryPackageInfo,
 };
 use scenery_index::SceneryIndexManager;
 use scenery_packs_manager::SceneryPacksManager;
-use task_control::TaskControl;
+use scenery_watch::SceneryWatcher;
+use task_control::{ScanControl, TaskControl, WalkMonitor};
 
 use tauri::{Emitter, Manager, State};
 
@@ -154,7 +160,30 @@ async fn install_addons(
                 delete_source_after_install.unwrap_or(false),
                 auto_sort_scenery.unwrap_or(false),
             )
-            .map_err(|e| format!("Installation failed: {}", e))
+            .map_err(|e| {
+                // Hand the frontend a structured error naming required and available bytes
+                if let Some(space) = e.downcast_ref::<atomic_installer::InsufficientSpace>() {
+                    let api_error = error::ApiError::with_details(
+                        error::ApiErrorCode::InsufficientSpace,
+                        space.to_string(),
+                        format!("required={} available={}", space.required, space.available),
+                    );
+                    if let Ok(json) = serde_json::to_string(&api_error) {
+                        return json;
+                    }
+                }
+                format!("Installation failed: {}", e)
+            })
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn preview_overwrite(task: InstallTask) -> Result<Vec<std::path::PathBuf>, String> {
+    tokio::task::spawn_blocking(move || {
+        installer::preview_overwrite(&task)
+            .map_err(|e| format!("Failed to preview overwrite: {}", e))
     })
     .await
     .map_err(|e| format!("Task join error: {}", e))?
@@ -178,6 +207,21 @@ async fn skip_current_task(task_control: State<'_, TaskControl>) -> Result<(), S
     Ok(())
 }
 
+#[tauri::command]
+async fn cancel_scan(scan_control: State<'_, ScanControl>) -> Result<(), String> {
+    scan_control.request_cancel();
+    logger::log_info("Scan cancellation requested", Some("task_control"));
+    Ok(())
+}
+
+/// Register a new scan and create a monitor that forwards progress to the
+/// frontend as walk-progress events
+fn start_walk_monitor(app_handle: tauri::AppHandle, scan_control: &ScanControl) -> WalkMonitor {
+    WalkMonitor::new(scan_control.begin_scan(), move |progress| {
+        let _ = app_handle.emit(task_control::WALK_PROGRESS_EVENT, progress);
+    })
+}
+
 // ============================================================================
 // Windows Registry Commands (Context Menu)
 // ============================================================================
@@ -230,6 +274,16 @@ fn get_all_logs() -> String {
     logger::get_all_logs()
 }
 
+#[tauri::command]
+fn get_last_install_log() -> Result<String, String> {
+    install_log::get_last_install_log().map_err(|e| format!("Failed to read install log: {}", e))
+}
+
+#[tauri::command]
+fn list_install_logs() -> Result<Vec<String>, String> {
+    install_log::list_install_logs().map_err(|e| format!("Failed to list install logs: {}", e))
+}
+
 #[tauri::command]
 fn open_log_folder() -> Result<(), String> {
     open_in_explorer(logger::get_log_folder())
@@ -359,6 +413,16 @@ fn set_log_level(level: String) {
     logger::set_log_level(log_level);
 }
 
+#[tauri::command]
+fn set_debug_enabled(enabled: bool) {
+    logger::set_debug_enabled(enabled);
+}
+
+#[tauri::command]
+fn is_debug_enabled() -> bool {
+    logger::is_debug_enabled()
+}
+
 // ========== Path Validation Commands ==========
 
 #[tauri::command]
@@ -367,30 +431,13 @@ fn check_path_exists(path: String) -> bool {
 }
 
 #[tauri::command]
-fn validate_xplane_path(path: String) -> Result<bool, String> {
-    let path_obj = std::path::Path::new(&path);
-
-    // Check if path exists
-    if !path_obj.exists() {
-        return Ok(false);
-    }
-
-    // Check if it's a directory
-    if !path_obj.is_dir() {
-        return Ok(false);
-    }
-
-    // Check for X-Plane executable
-    let exe_name = if cfg!(target_os = "windows") {
-        "X-Plane.exe"
-    } else if cfg!(target_os = "macos") {
-        "X-Plane.app"
-    } else {
-        "X-Plane"
-    };
+fn validate_xplane_path(path: String) -> Result<models::XPlaneInfo, String> {
+    Ok(management_index::inspect_xplane_path(std::path::Path::new(&path)))
+}
 
-    let exe_path = path_obj.join(exe_name);
-    Ok(exe_path.exists())
+#[tauri::command]
+fn get_xplane_version(xplane_path: String) -> Option<(u32, u32, u32)> {
+    management_index::get_xplane_version(std::path::Path::new(&xplane_path))
 }
 
 // ========== Update Commands ==========
@@ -459,11 +506,64 @@ async fn sort_scenery_packs(xplane_path: String) -> Result<bool, String> {
 }
 
 #[tauri::command]
-async fn rebuild_scenery_index(xplane_path: String) -> Result<SceneryIndexStats, String> {
+async fn auto_sort_except(
+    xplane_path: String,
+    categories: Vec<models::SceneryCategory>,
+) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let packs_manager = SceneryPacksManager::new(xplane_path);
+
+        packs_manager
+            .auto_sort_except(&categories)
+            .map_err(|e| format!("Failed to sort scenery: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn detect_moved_targets(
+    xplane_path: String,
+) -> Result<Vec<models::MovedShortcutTarget>, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let index_manager = SceneryIndexManager::new(xplane_path);
+
+        index_manager
+            .detect_moved_targets()
+            .map_err(|e| format!("Failed to detect moved shortcut targets: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn refresh_shortcut_targets(xplane_path: String) -> Result<usize, String> {
     tokio::task::spawn_blocking(move || {
         let xplane_path = std::path::Path::new(&xplane_path);
         let index_manager = SceneryIndexManager::new(xplane_path);
 
+        index_manager
+            .refresh_shortcut_targets()
+            .map_err(|e| format!("Failed to refresh shortcut targets: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn rebuild_scenery_index(
+    app_handle: tauri::AppHandle,
+    scan_control: State<'_, ScanControl>,
+    xplane_path: String,
+) -> Result<SceneryIndexStats, String> {
+    let monitor = std::sync::Arc::new(start_walk_monitor(app_handle, &scan_control));
+
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let index_manager = SceneryIndexManager::new(xplane_path).with_walk_monitor(monitor);
+
         logger::log_info("Rebuilding scenery index", Some("scenery"));
 
         index_manager
@@ -492,6 +592,26 @@ async fn get_scenery_index_stats(xplane_path: String) -> Result<SceneryIndexStat
     .map_err(|e| format!("Task join error: {}", e))?
 }
 
+#[tauri::command]
+async fn get_coverage(
+    app_handle: tauri::AppHandle,
+    scan_control: State<'_, ScanControl>,
+    xplane_path: String,
+) -> Result<Vec<models::TileCoverage>, String> {
+    let monitor = std::sync::Arc::new(start_walk_monitor(app_handle, &scan_control));
+
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let index_manager = SceneryIndexManager::new(xplane_path).with_walk_monitor(monitor);
+
+        index_manager
+            .get_coverage()
+            .map_err(|e| format!("Failed to get coverage: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
 #[tauri::command]
 async fn get_scenery_index_status(xplane_path: String) -> Result<SceneryIndexStatus, String> {
     tokio::task::spawn_blocking(move || {
@@ -507,10 +627,61 @@ async fn get_scenery_index_status(xplane_path: String) -> Result<SceneryIndexSta
 }
 
 #[tauri::command]
-async fn quick_scan_scenery_index(xplane_path: String) -> Result<SceneryIndexScanResult, String> {
+async fn get_scenery_by_category(
+    xplane_path: String,
+    category: models::SceneryCategory,
+) -> Result<Vec<SceneryPackageInfo>, String> {
     tokio::task::spawn_blocking(move || {
         let xplane_path = std::path::Path::new(&xplane_path);
         let index_manager = SceneryIndexManager::new(xplane_path);
+        index_manager
+            .get_packages_by_category(&category)
+            .map_err(|e| format!("Failed to load scenery packages: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn vacuum_scenery_database() -> Result<DatabaseVacuumResult, String> {
+    tokio::task::spawn_blocking(move || {
+        let size_before = database::get_database_file_size();
+
+        let conn =
+            database::open_connection().map_err(|e| format!("Failed to open database: {}", e))?;
+        database::SceneryQueries::vacuum(&conn)
+            .map_err(|e| format!("Failed to vacuum database: {}", e))?;
+        drop(conn);
+
+        let size_after = database::get_database_file_size();
+        logger::log_info(
+            &format!(
+                "Scenery database compacted: {} -> {} bytes",
+                size_before, size_after
+            ),
+            Some("scenery"),
+        );
+
+        Ok(DatabaseVacuumResult {
+            size_before,
+            size_after,
+        })
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn quick_scan_scenery_index(
+    app_handle: tauri::AppHandle,
+    scan_control: State<'_, ScanControl>,
+    xplane_path: String,
+) -> Result<SceneryIndexScanResult, String> {
+    let monitor = std::sync::Arc::new(start_walk_monitor(app_handle, &scan_control));
+
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let index_manager = SceneryIndexManager::new(xplane_path).with_walk_monitor(monitor);
 
         index_manager
             .quick_scan_and_update()
@@ -535,205 +706,958 @@ async fn sync_scenery_packs_with_folder(xplane_path: String) -> Result<usize, St
 }
 
 #[tauri::command]
-async fn get_scenery_manager_data(xplane_path: String) -> Result<SceneryManagerData, String> {
+async fn preview_scenery_auto_sort(
+    xplane_path: String,
+) -> Result<Vec<models::SceneryPackEntry>, String> {
     tokio::task::spawn_blocking(move || {
         let xplane_path = std::path::Path::new(&xplane_path);
-        let index_manager = SceneryIndexManager::new(xplane_path);
+        let packs_manager = SceneryPacksManager::new(xplane_path);
 
-        index_manager
-            .get_manager_data()
-            .map_err(|e| format!("Failed to get scenery manager data: {}", e))
+        packs_manager
+            .preview_auto_sort()
+            .map_err(|e| format!("Failed to preview scenery sort: {}", e))
     })
     .await
     .map_err(|e| format!("Task join error: {}", e))?
 }
 
 #[tauri::command]
-async fn update_scenery_entry(
+async fn preview_scenery_sort(
     xplane_path: String,
-    folder_name: String,
-    enabled: Option<bool>,
-    sort_order: Option<u32>,
-    category: Option<models::SceneryCategory>,
-) -> Result<(), String> {
+) -> Result<Vec<models::SortPreviewChange>, String> {
     tokio::task::spawn_blocking(move || {
         let xplane_path = std::path::Path::new(&xplane_path);
-        let index_manager = SceneryIndexManager::new(xplane_path);
+        let packs_manager = SceneryPacksManager::new(xplane_path);
 
-        index_manager
-            .update_entry(&folder_name, enabled, sort_order, category)
-            .map_err(|e| format!("Failed to update scenery entry: {}", e))
+        packs_manager
+            .preview_sort()
+            .map_err(|e| format!("Failed to preview scenery sort: {}", e))
     })
     .await
     .map_err(|e| format!("Task join error: {}", e))?
 }
 
 #[tauri::command]
-async fn move_scenery_entry(
+async fn check_install_conflicts(
     xplane_path: String,
-    folder_name: String,
-    new_sort_order: u32,
-) -> Result<(), String> {
+    task: InstallTask,
+) -> Result<Option<models::SceneryConflictInfo>, String> {
     tokio::task::spawn_blocking(move || {
         let xplane_path = std::path::Path::new(&xplane_path);
-        let index_manager = SceneryIndexManager::new(xplane_path);
-
-        index_manager
-            .move_entry(&folder_name, new_sort_order)
-            .map_err(|e| format!("Failed to move scenery entry: {}", e))
+        scenery_index::check_install_conflicts(xplane_path, &task)
+            .map_err(|e| format!("Failed to check install conflicts: {}", e))
     })
     .await
     .map_err(|e| format!("Task join error: {}", e))?
 }
 
 #[tauri::command]
-async fn apply_scenery_changes(
+async fn lint_scenery_packs_ini(
     xplane_path: String,
-    entries: Vec<models::SceneryEntryUpdate>,
-) -> Result<(), String> {
+) -> Result<Vec<models::IniLintFinding>, String> {
     tokio::task::spawn_blocking(move || {
         let xplane_path = std::path::Path::new(&xplane_path);
-        let index_manager = SceneryIndexManager::new(xplane_path);
-
-        logger::log_info("Applying scenery changes to index and ini", Some("scenery"));
+        let packs_manager = SceneryPacksManager::new(xplane_path);
 
-        // Update index with all entry changes
-        index_manager
-            .batch_update_entries(&entries)
-            .map_err(|e| format!("Failed to update index: {}", e))?;
+        packs_manager
+            .lint_ini()
+            .map_err(|e| format!("Failed to lint scenery_packs.ini: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
 
-        // Apply to ini file
+#[tauri::command]
+async fn normalize_scenery_packs_ini(xplane_path: String) -> Result<usize, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
         let packs_manager = SceneryPacksManager::new(xplane_path);
-        packs_manager
-            .apply_from_index()
-            .map_err(|e| format!("Failed to apply scenery changes: {}", e))?;
 
-        logger::log_info("Scenery changes applied successfully", Some("scenery"));
-        Ok(())
+        packs_manager
+            .normalize_ini()
+            .map_err(|e| format!("Failed to normalize scenery_packs.ini: {}", e))
     })
     .await
     .map_err(|e| format!("Task join error: {}", e))?
 }
 
-// ========== Management Commands ==========
-
 #[tauri::command]
-async fn scan_aircraft(xplane_path: String) -> Result<ManagementData<AircraftInfo>, String> {
+async fn detect_case_mismatches(
+    xplane_path: String,
+) -> Result<Vec<models::IniCaseMismatch>, String> {
     tokio::task::spawn_blocking(move || {
         let xplane_path = std::path::Path::new(&xplane_path);
-        management_index::scan_aircraft(xplane_path)
-            .map_err(|e| format!("Failed to scan aircraft: {}", e))
+        let packs_manager = SceneryPacksManager::new(xplane_path);
+
+        packs_manager
+            .detect_case_mismatches()
+            .map_err(|e| format!("Failed to check scenery_packs.ini casing: {}", e))
     })
     .await
     .map_err(|e| format!("Task join error: {}", e))?
 }
 
 #[tauri::command]
-async fn check_aircraft_updates(
-    mut aircraft: Vec<AircraftInfo>,
-) -> Result<Vec<AircraftInfo>, String> {
-    management_index::check_aircraft_updates(&mut aircraft).await;
-    Ok(aircraft)
+async fn fix_case_mismatches(xplane_path: String) -> Result<usize, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let packs_manager = SceneryPacksManager::new(xplane_path);
+
+        packs_manager
+            .fix_case_mismatches()
+            .map_err(|e| format!("Failed to fix scenery_packs.ini casing: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
 }
 
 #[tauri::command]
-async fn check_plugins_updates(
-    mut plugins: Vec<PluginInfo>,
-) -> Result<Vec<PluginInfo>, String> {
-    management_index::check_plugins_updates(&mut plugins).await;
-    Ok(plugins)
+async fn find_orphaned_ini_entries(
+    xplane_path: String,
+) -> Result<Vec<models::SceneryPackEntry>, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let packs_manager = SceneryPacksManager::new(xplane_path);
+
+        packs_manager
+            .find_orphaned_entries()
+            .map_err(|e| format!("Failed to find orphaned scenery_packs.ini entries: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
 }
 
 #[tauri::command]
-async fn scan_plugins(xplane_path: String) -> Result<ManagementData<PluginInfo>, String> {
+async fn remove_orphaned_ini_entries(xplane_path: String) -> Result<usize, String> {
     tokio::task::spawn_blocking(move || {
         let xplane_path = std::path::Path::new(&xplane_path);
-        management_index::scan_plugins(xplane_path)
-            .map_err(|e| format!("Failed to scan plugins: {}", e))
+        let packs_manager = SceneryPacksManager::new(xplane_path);
+
+        packs_manager
+            .remove_orphaned_entries()
+            .map_err(|e| format!("Failed to remove orphaned scenery_packs.ini entries: {}", e))
     })
     .await
     .map_err(|e| format!("Task join error: {}", e))?
 }
 
 #[tauri::command]
-async fn scan_navdata(xplane_path: String) -> Result<ManagementData<NavdataManagerInfo>, String> {
+async fn reconcile_scenery_packs_with_index(
+    xplane_path: String,
+) -> Result<models::IniReconcileResult, String> {
     tokio::task::spawn_blocking(move || {
         let xplane_path = std::path::Path::new(&xplane_path);
-        management_index::scan_navdata(xplane_path)
-            .map_err(|e| format!("Failed to scan navdata: {}", e))
+        let packs_manager = SceneryPacksManager::new(xplane_path);
+
+        packs_manager
+            .reconcile_with_index()
+            .map_err(|e| format!("Failed to reconcile scenery_packs.ini with index: {}", e))
     })
     .await
     .map_err(|e| format!("Task join error: {}", e))?
 }
 
 #[tauri::command]
-async fn toggle_management_item(
+async fn list_scenery_packs_backups(
     xplane_path: String,
-    item_type: String,
-    folder_name: String,
-) -> Result<bool, String> {
+) -> Result<Vec<models::SceneryPacksBackup>, String> {
     tokio::task::spawn_blocking(move || {
         let xplane_path = std::path::Path::new(&xplane_path);
-        management_index::toggle_management_item(xplane_path, &item_type, &folder_name)
-            .map_err(|e| error::ApiError::from(e))
+        let packs_manager = SceneryPacksManager::new(xplane_path);
+
+        packs_manager
+            .list_backups()
+            .map_err(|e| format!("Failed to list scenery_packs.ini backups: {}", e))
     })
     .await
     .map_err(|e| format!("Task join error: {}", e))?
-    .to_tauri_error()
 }
 
 #[tauri::command]
-async fn delete_management_item(
+async fn restore_scenery_packs_backup(
     xplane_path: String,
-    item_type: String,
-    folder_name: String,
+    backup_path: String,
 ) -> Result<(), String> {
     tokio::task::spawn_blocking(move || {
         let xplane_path = std::path::Path::new(&xplane_path);
-        management_index::delete_management_item(xplane_path, &item_type, &folder_name)
-            .map_err(|e| error::ApiError::from(e))
+        let packs_manager = SceneryPacksManager::new(xplane_path);
+
+        packs_manager
+            .restore_backup(std::path::Path::new(&backup_path))
+            .map_err(|e| format!("Failed to restore scenery_packs.ini backup: {}", e))
     })
     .await
     .map_err(|e| format!("Task join error: {}", e))?
-    .to_tauri_error()
 }
 
 #[tauri::command]
-async fn open_management_folder(
-    xplane_path: String,
-    item_type: String,
-    folder_name: String,
-) -> Result<(), String> {
+async fn get_scenery_manager_data(xplane_path: String) -> Result<SceneryManagerData, String> {
     tokio::task::spawn_blocking(move || {
         let xplane_path = std::path::Path::new(&xplane_path);
-        management_index::open_management_folder(xplane_path, &item_type, &folder_name)
-            .map_err(|e| error::ApiError::from(e))
+        let index_manager = SceneryIndexManager::new(xplane_path);
+
+        index_manager
+            .get_manager_data()
+            .map_err(|e| format!("Failed to get scenery manager data: {}", e))
     })
     .await
     .map_err(|e| format!("Task join error: {}", e))?
-    .to_tauri_error()
 }
 
 #[tauri::command]
-async fn set_cfg_disabled(
+async fn set_scenery_category(
     xplane_path: String,
-    item_type: String,
     folder_name: String,
-    disabled: bool,
+    category: Option<models::SceneryCategory>,
 ) -> Result<(), String> {
     tokio::task::spawn_blocking(move || {
         let xplane_path = std::path::Path::new(&xplane_path);
-        management_index::set_cfg_disabled(xplane_path, &item_type, &folder_name, disabled)
-            .map_err(|e| error::ApiError::from(e))
+        let index_manager = SceneryIndexManager::new(xplane_path);
+
+        index_manager
+            .set_category(&folder_name, category)
+            .map_err(|e| format!("Failed to set scenery category: {}", e))
     })
     .await
     .map_err(|e| format!("Task join error: {}", e))?
-    .to_tauri_error()
 }
 
-#[cfg_attr(mobile, tauri::mobile_entry_point)]
-pub fn run() {
-    tauri::Builder::default()
+#[tauri::command]
+async fn update_scenery_entry(
+    xplane_path: String,
+    folder_name: String,
+    enabled: Option<bool>,
+    sort_order: Option<u32>,
+    category: Option<models::SceneryCategory>,
+) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let index_manager = SceneryIndexManager::new(xplane_path);
+
+        let lock_category = category.is_some();
+        index_manager
+            .update_entry(&folder_name, enabled, sort_order, category)
+            .map_err(|e| format!("Failed to update scenery entry: {}", e))?;
+
+        // A category picked by the user is kept across re-indexing
+        if lock_category {
+            index_manager
+                .set_category_locked(&folder_name, true)
+                .map_err(|e| format!("Failed to lock scenery category: {}", e))?;
+        }
+        Ok(())
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn set_scenery_enabled_bulk(
+    xplane_path: String,
+    folder_names: Vec<String>,
+    enabled: bool,
+) -> Result<usize, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let index_manager = SceneryIndexManager::new(xplane_path);
+
+        index_manager
+            .set_enabled_bulk(&folder_names, enabled)
+            .map_err(|e| format!("Failed to update scenery enabled state: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+fn start_scenery_watch(
+    app_handle: tauri::AppHandle,
+    scenery_watcher: State<'_, SceneryWatcher>,
+    xplane_path: String,
+) -> Result<(), String> {
+    scenery_watcher
+        .start(app_handle, std::path::Path::new(&xplane_path))
+        .map_err(|e| format!("Failed to start scenery watch: {}", e))
+}
+
+#[tauri::command]
+fn stop_scenery_watch(scenery_watcher: State<'_, SceneryWatcher>) -> bool {
+    scenery_watcher.stop()
+}
+
+#[tauri::command]
+async fn enable_packages_with_satisfied_deps(xplane_path: String) -> Result<Vec<String>, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let index_manager = SceneryIndexManager::new(xplane_path);
+
+        index_manager
+            .enable_packages_with_satisfied_deps()
+            .map_err(|e| format!("Failed to enable packages: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn move_scenery_entry(
+    xplane_path: String,
+    folder_name: String,
+    new_sort_order: u32,
+) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let index_manager = SceneryIndexManager::new(xplane_path);
+
+        index_manager
+            .move_entry(&folder_name, new_sort_order)
+            .map_err(|e| format!("Failed to move scenery entry: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn set_scenery_full_order(
+    xplane_path: String,
+    folder_names: Vec<String>,
+) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let index_manager = SceneryIndexManager::new(xplane_path);
+
+        index_manager
+            .set_full_order(&folder_names)
+            .map_err(|e| format!("Failed to set scenery order: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn apply_scenery_changes(
+    xplane_path: String,
+    entries: Vec<models::SceneryEntryUpdate>,
+) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let index_manager = SceneryIndexManager::new(xplane_path);
+
+        logger::log_info("Applying scenery changes to index and ini", Some("scenery"));
+
+        // Update index with all entry changes
+        index_manager
+            .batch_update_entries(&entries)
+            .map_err(|e| format!("Failed to update index: {}", e))?;
+
+        // Apply to ini file
+        let packs_manager = SceneryPacksManager::new(xplane_path);
+        packs_manager
+            .apply_from_index()
+            .map_err(|e| format!("Failed to apply scenery changes: {}", e))?;
+
+        logger::log_info("Scenery changes applied successfully", Some("scenery"));
+        Ok(())
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn commit_manager_changes(
+    xplane_path: String,
+    entries: Vec<models::SceneryEntryUpdate>,
+) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let packs_manager = SceneryPacksManager::new(xplane_path);
+
+        packs_manager
+            .commit_manager_changes(&entries)
+            .map_err(|e| format!("Failed to commit scenery changes: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+fn get_audit_log(
+    xplane_path: String,
+    limit: Option<usize>,
+) -> Result<Vec<models::SceneryAuditEntry>, String> {
+    scenery_audit::read_entries(std::path::Path::new(&xplane_path), limit.unwrap_or(100))
+        .map_err(|e| format!("Failed to read scenery audit log: {}", e))
+}
+
+#[tauri::command]
+fn diff_index_exports(a_json: String, b_json: String) -> Result<models::IndexExportDiff, String> {
+    scenery_index::diff_index_exports(&a_json, &b_json)
+        .map_err(|e| format!("Failed to diff index exports: {}", e))
+}
+
+#[tauri::command]
+async fn export_scenery_index_json(xplane_path: String, path: String) -> Result<usize, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let index_manager = SceneryIndexManager::new(xplane_path);
+        index_manager
+            .export_json(std::path::Path::new(&path))
+            .map_err(|e| format!("Failed to export scenery index: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn import_scenery_index_json(
+    xplane_path: String,
+    path: String,
+) -> Result<models::SceneryIndexImportResult, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let index_manager = SceneryIndexManager::new(xplane_path);
+        index_manager
+            .import_json(std::path::Path::new(&path))
+            .map_err(|e| format!("Failed to import scenery index: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn undo_last(xplane_path: String) -> Result<Option<models::SceneryAuditEntry>, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let index_manager = SceneryIndexManager::new(xplane_path);
+
+        index_manager
+            .undo_last()
+            .map_err(|e| format!("Failed to undo last scenery change: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn get_scenery_ordering_config(
+    xplane_path: String,
+) -> Result<models::SceneryOrderingConfig, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let index_manager = SceneryIndexManager::new(xplane_path);
+
+        index_manager
+            .get_ordering_config()
+            .map_err(|e| format!("Failed to load ordering config: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn get_category_bands(xplane_path: String) -> Result<Vec<models::CategoryBand>, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let index_manager = SceneryIndexManager::new(xplane_path);
+
+        index_manager
+            .get_ordering_config()
+            .map(|config| config.category_bands())
+            .map_err(|e| format!("Failed to load ordering config: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn set_scenery_ordering_config(
+    xplane_path: String,
+    config: models::SceneryOrderingConfig,
+) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let index_manager = SceneryIndexManager::new(xplane_path);
+
+        index_manager
+            .set_ordering_config(&config)
+            .map_err(|e| format!("Failed to save ordering config: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn enter_scenery_safe_mode(xplane_path: String) -> Result<usize, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let index_manager = SceneryIndexManager::new(xplane_path);
+
+        index_manager
+            .enter_safe_mode()
+            .map_err(|e| format!("Failed to enter safe mode: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn exit_scenery_safe_mode(xplane_path: String) -> Result<usize, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let index_manager = SceneryIndexManager::new(xplane_path);
+
+        index_manager
+            .exit_safe_mode()
+            .map_err(|e| format!("Failed to exit safe mode: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn is_scenery_safe_mode_active(xplane_path: String) -> Result<bool, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let index_manager = SceneryIndexManager::new(xplane_path);
+
+        index_manager
+            .is_safe_mode_active()
+            .map_err(|e| format!("Failed to check safe mode: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn get_index_parallelism(xplane_path: String) -> Result<usize, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let index_manager = SceneryIndexManager::new(xplane_path);
+
+        index_manager
+            .get_index_parallelism()
+            .map_err(|e| format!("Failed to get index parallelism: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn set_index_parallelism(xplane_path: String, threads: usize) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let index_manager = SceneryIndexManager::new(xplane_path);
+
+        index_manager
+            .set_index_parallelism(threads)
+            .map_err(|e| format!("Failed to set index parallelism: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn get_scenery_package(
+    xplane_path: String,
+    folder_name: String,
+) -> Result<Option<SceneryPackageInfo>, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let index_manager = SceneryIndexManager::new(xplane_path);
+
+        index_manager
+            .get_package(&folder_name)
+            .map_err(|e| format!("Failed to get scenery package: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn get_dsf_info(
+    xplane_path: String,
+    folder_name: String,
+) -> Result<Option<models::DsfInfo>, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let index_manager = SceneryIndexManager::new(xplane_path);
+
+        index_manager
+            .get_dsf_info(&folder_name)
+            .map_err(|e| format!("Failed to read DSF header: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+fn is_dynamic_scenery_package(folder_name: String) -> bool {
+    scenery_index::is_dynamic_package(&folder_name)
+}
+
+#[tauri::command]
+async fn get_packages_exporting_library(
+    xplane_path: String,
+    library_name: String,
+) -> Result<Vec<String>, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let index_manager = SceneryIndexManager::new(xplane_path);
+
+        index_manager
+            .packages_exporting(&library_name)
+            .map_err(|e| format!("Failed to query library providers: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn resolve_missing_library(
+    xplane_path: String,
+    library_name: String,
+) -> Result<Vec<String>, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let index_manager = SceneryIndexManager::new(xplane_path);
+
+        index_manager
+            .resolve_missing_library(&library_name)
+            .map_err(|e| format!("Failed to resolve missing library: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn import_order_from_ini(
+    xplane_path: String,
+    ini_contents: String,
+) -> Result<Vec<String>, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let index_manager = SceneryIndexManager::new(xplane_path);
+
+        index_manager
+            .import_order_from_ini(&ini_contents)
+            .map_err(|e| format!("Failed to import scenery order: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn delete_scenery(
+    xplane_path: String,
+    folder_name: String,
+    remove_files: bool,
+    delete_shortcut_target: Option<bool>,
+) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let index_manager = SceneryIndexManager::new(xplane_path);
+
+        index_manager
+            .delete_scenery(
+                &folder_name,
+                remove_files,
+                delete_shortcut_target.unwrap_or(false),
+            )
+            .map_err(|e| format!("Failed to delete scenery: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn detect_disabled_scenery_dependencies(
+    xplane_path: String,
+) -> Result<Vec<(String, Vec<String>)>, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let index_manager = SceneryIndexManager::new(xplane_path);
+
+        index_manager
+            .detect_disabled_dependencies()
+            .map_err(|e| format!("Failed to detect disabled dependencies: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn detect_duplicate_library_exports(
+    xplane_path: String,
+) -> Result<HashMap<String, Vec<String>>, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let index_manager = SceneryIndexManager::new(xplane_path);
+
+        index_manager
+            .detect_duplicate_library_exports()
+            .map_err(|e| format!("Failed to detect duplicate library exports: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+// ========== Management Commands ==========
+
+#[tauri::command]
+async fn scan_aircraft(
+    app_handle: tauri::AppHandle,
+    scan_control: State<'_, ScanControl>,
+    xplane_path: String,
+) -> Result<ManagementData<AircraftInfo>, String> {
+    let monitor = start_walk_monitor(app_handle, &scan_control);
+
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        management_index::scan_aircraft(xplane_path, &monitor)
+            .map_err(|e| format!("Failed to scan aircraft: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn find_duplicate_aircraft(
+    app_handle: tauri::AppHandle,
+    scan_control: State<'_, ScanControl>,
+    xplane_path: String,
+) -> Result<Vec<Vec<String>>, String> {
+    let monitor = start_walk_monitor(app_handle, &scan_control);
+
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        management_index::find_duplicate_aircraft(xplane_path, &monitor)
+            .map_err(|e| format!("Failed to find duplicate aircraft: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn list_aircraft_without_version(
+    app_handle: tauri::AppHandle,
+    scan_control: State<'_, ScanControl>,
+    xplane_path: String,
+) -> Result<Vec<String>, String> {
+    let monitor = start_walk_monitor(app_handle, &scan_control);
+
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        management_index::list_aircraft_without_version(xplane_path, &monitor)
+            .map_err(|e| format!("Failed to list aircraft without version: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn get_overview(xplane_path: String) -> Result<models::Overview, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        management_index::get_overview(xplane_path)
+            .map_err(|e| format!("Failed to get overview: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn check_aircraft_updates(
+    version_cache: State<'_, RemoteVersionCache>,
+    xplane_path: String,
+    mut aircraft: Vec<AircraftInfo>,
+    force_refresh: Option<bool>,
+) -> Result<Vec<AircraftInfo>, String> {
+    management_index::check_aircraft_updates(
+        std::path::Path::new(&xplane_path),
+        &mut aircraft,
+        &version_cache,
+        force_refresh.unwrap_or(false),
+    )
+    .await;
+    Ok(aircraft)
+}
+
+#[tauri::command]
+async fn check_plugins_updates(
+    version_cache: State<'_, RemoteVersionCache>,
+    xplane_path: String,
+    mut plugins: Vec<PluginInfo>,
+    force_refresh: Option<bool>,
+) -> Result<Vec<PluginInfo>, String> {
+    management_index::check_plugins_updates(
+        std::path::Path::new(&xplane_path),
+        &mut plugins,
+        &version_cache,
+        force_refresh.unwrap_or(false),
+    )
+    .await;
+    Ok(plugins)
+}
+
+#[tauri::command]
+async fn scan_plugins(
+    app_handle: tauri::AppHandle,
+    scan_control: State<'_, ScanControl>,
+    xplane_path: String,
+) -> Result<ManagementData<PluginInfo>, String> {
+    let monitor = start_walk_monitor(app_handle, &scan_control);
+
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        management_index::scan_plugins(xplane_path, &monitor)
+            .map_err(|e| format!("Failed to scan plugins: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn scan_navdata(
+    app_handle: tauri::AppHandle,
+    scan_control: State<'_, ScanControl>,
+    xplane_path: String,
+) -> Result<ManagementData<NavdataManagerInfo>, String> {
+    let monitor = start_walk_monitor(app_handle, &scan_control);
+
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        management_index::scan_navdata(xplane_path, &monitor)
+            .map_err(|e| format!("Failed to scan navdata: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn disable_liveries(xplane_path: String, aircraft_folder: String) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        management_index::disable_liveries(xplane_path, &aircraft_folder)
+            .map_err(|e| format!("Failed to disable liveries: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn enable_liveries(xplane_path: String, aircraft_folder: String) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        management_index::enable_liveries(xplane_path, &aircraft_folder)
+            .map_err(|e| format!("Failed to enable liveries: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn toggle_management_item(
+    xplane_path: String,
+    item_type: String,
+    folder_name: String,
+    platform: Option<String>,
+) -> Result<bool, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        management_index::toggle_management_item(
+            xplane_path,
+            &item_type,
+            &folder_name,
+            platform.as_deref(),
+        )
+        .map_err(|e| error::ApiError::from(e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+    .to_tauri_error()
+}
+
+#[tauri::command]
+async fn delete_management_item(
+    xplane_path: String,
+    item_type: String,
+    folder_name: String,
+) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        management_index::delete_management_item(xplane_path, &item_type, &folder_name)
+            .map_err(|e| error::ApiError::from(e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+    .to_tauri_error()
+}
+
+#[tauri::command]
+async fn delete_management_items(
+    xplane_path: String,
+    items: Vec<(String, String)>,
+) -> Result<models::ManagementDeleteSummary, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        management_index::delete_management_items(xplane_path, &items)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))
+}
+
+#[tauri::command]
+async fn get_install_marker(
+    xplane_path: String,
+    item_type: String,
+    folder_name: String,
+) -> Result<Option<models::InstallMarker>, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        management_index::get_install_marker(xplane_path, &item_type, &folder_name)
+            .map_err(|e| error::ApiError::from(e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+    .to_tauri_error()
+}
+
+#[tauri::command]
+async fn open_management_folder(
+    xplane_path: String,
+    item_type: String,
+    folder_name: String,
+) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        management_index::open_management_folder(xplane_path, &item_type, &folder_name)
+            .map_err(|e| error::ApiError::from(e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+    .to_tauri_error()
+}
+
+#[tauri::command]
+async fn set_cfg_disabled(
+    xplane_path: String,
+    item_type: String,
+    folder_name: String,
+    disabled: bool,
+) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        management_index::set_cfg_disabled(xplane_path, &item_type, &folder_name, disabled)
+            .map_err(|e| error::ApiError::from(e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+    .to_tauri_error()
+}
+
+#[cfg_attr(mobile, tauri::mobile_entry_point)]
+pub fn run() {
+    tauri::Builder::default()
         .plugin(tauri_plugin_store::Builder::new().build())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
@@ -772,7 +1696,9 @@ pub fn run() {
             open_url,
             analyze_addons,
             install_addons,
+            preview_overwrite,
             cancel_installation,
+            cancel_scan,
             skip_current_task,
             register_context_menu,
             unregister_context_menu,
@@ -781,42 +1707,107 @@ pub fn run() {
             get_recent_logs,
             get_log_path,
             get_all_logs,
+            get_last_install_log,
+            list_install_logs,
             open_log_folder,
             open_scenery_folder,
             delete_scenery_folder,
             set_log_locale,
             set_log_level,
+            set_debug_enabled,
+            is_debug_enabled,
             check_path_exists,
             validate_xplane_path,
+            get_xplane_version,
             check_for_updates,
             get_last_check_time,
             // Scenery auto-sorting commands
             get_scenery_classification,
             sort_scenery_packs,
+            auto_sort_except,
             rebuild_scenery_index,
             get_scenery_index_stats,
+            detect_moved_targets,
+            get_coverage,
+            refresh_shortcut_targets,
             get_scenery_index_status,
+            get_scenery_by_category,
+            vacuum_scenery_database,
             quick_scan_scenery_index,
             sync_scenery_packs_with_folder,
+            preview_scenery_auto_sort,
+            preview_scenery_sort,
+            check_install_conflicts,
+            lint_scenery_packs_ini,
+            normalize_scenery_packs_ini,
+            detect_case_mismatches,
+            fix_case_mismatches,
+            find_orphaned_ini_entries,
+            remove_orphaned_ini_entries,
+            reconcile_scenery_packs_with_index,
+            list_scenery_packs_backups,
+            restore_scenery_packs_backup,
             // Scenery manager commands
             get_scenery_manager_data,
             update_scenery_entry,
+            set_scenery_category,
+            set_scenery_enabled_bulk,
+            start_scenery_watch,
+            stop_scenery_watch,
             move_scenery_entry,
+            enable_packages_with_satisfied_deps,
+            set_scenery_full_order,
+            import_order_from_ini,
             apply_scenery_changes,
+            commit_manager_changes,
+            get_audit_log,
+            undo_last,
+            diff_index_exports,
+            export_scenery_index_json,
+            import_scenery_index_json,
+            get_scenery_ordering_config,
+            set_scenery_ordering_config,
+            get_category_bands,
+            enter_scenery_safe_mode,
+            exit_scenery_safe_mode,
+            is_scenery_safe_mode_active,
+            delete_scenery,
+            detect_disabled_scenery_dependencies,
+            detect_duplicate_library_exports,
+            get_packages_exporting_library,
+            resolve_missing_library,
+            get_scenery_package,
+            get_dsf_info,
+            is_dynamic_scenery_package,
+            get_index_parallelism,
+            set_index_parallelism,
             // Management commands
             scan_aircraft,
+            find_duplicate_aircraft,
+            list_aircraft_without_version,
+            get_overview,
             check_aircraft_updates,
             scan_plugins,
             check_plugins_updates,
             scan_navdata,
             toggle_management_item,
+            disable_liveries,
+            enable_liveries,
             delete_management_item,
+            delete_management_items,
             open_management_folder,
+            get_install_marker,
             set_cfg_disabled
         ])
         .setup(|app| {
             // Initialize TaskControl state
             app.manage(TaskControl::new());
+            app.manage(ScanControl::new());
+            app.manage(RemoteVersionCache::new());
+            app.manage(SceneryWatcher::new());
+
+            // Merge user livery patterns (liveries.json) with the built-in set
+            livery_patterns::load_user_patterns();
 
             // Log application startup
             logger::log_info(&logger::tr(logger::LogMsg::AppStarted), Some("app"));
@@ -3,7 +3,13 @@
 //! This module defines patterns to detect aircraft liveries and map them
 //! to their corresponding aircraft types.
 
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use std::fs;
 use std::path::Path;
+use std::sync::RwLock;
+
+use crate::logger;
 
 /// Represents a single detection rule for livery identification
 #[derive(Debug, Clone)]
@@ -192,15 +198,122 @@ pub static LIVERY_PATTERNS: &[LiveryPattern] = &[
     },
 ];
 
+/// Owned detection rule, used for user-defined patterns loaded from liveries.json
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DetectionRuleConfig {
+    pub pattern_type: String,
+    pub pattern: String,
+    #[serde(default)]
+    pub parent_levels: usize,
+}
+
+/// Owned livery pattern, used for user-defined patterns loaded from liveries.json
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveryPatternConfig {
+    pub aircraft_type_id: String,
+    pub aircraft_name: String,
+    #[serde(default)]
+    pub detection_rules: Vec<DetectionRuleConfig>,
+    #[serde(default)]
+    pub acf_identifiers: Vec<String>,
+}
+
+impl From<&LiveryPattern> for LiveryPatternConfig {
+    fn from(pattern: &LiveryPattern) -> Self {
+        Self {
+            aircraft_type_id: pattern.aircraft_type_id.to_string(),
+            aircraft_name: pattern.aircraft_name.to_string(),
+            detection_rules: pattern
+                .detection_rules
+                .iter()
+                .map(|rule| DetectionRuleConfig {
+                    pattern_type: rule.pattern_type.to_string(),
+                    pattern: rule.pattern.to_string(),
+                    parent_levels: rule.parent_levels,
+                })
+                .collect(),
+            acf_identifiers: pattern
+                .acf_identifiers
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+        }
+    }
+}
+
+/// Patterns consulted by the detection functions: user entries first, then built-ins
+static ACTIVE_PATTERNS: Lazy<RwLock<Vec<LiveryPatternConfig>>> =
+    Lazy::new(|| RwLock::new(merge_patterns(Vec::new())));
+
+/// Merge user patterns with the compiled defaults
+/// A user entry replaces the built-in entry with the same aircraft_type_id
+fn merge_patterns(user: Vec<LiveryPatternConfig>) -> Vec<LiveryPatternConfig> {
+    let mut merged = user;
+    for builtin in LIVERY_PATTERNS {
+        let overridden = merged.iter().any(|p| {
+            p.aircraft_type_id
+                .eq_ignore_ascii_case(builtin.aircraft_type_id)
+        });
+        if !overridden {
+            merged.push(builtin.into());
+        }
+    }
+    merged
+}
+
+/// Parse the contents of a liveries.json file (a JSON array of patterns)
+fn parse_user_patterns(content: &str) -> Result<Vec<LiveryPatternConfig>, serde_json::Error> {
+    serde_json::from_str(content)
+}
+
+/// Load user livery patterns from liveries.json in the app config directory
+/// Keeps the compiled defaults when the file is absent or malformed
+pub fn load_user_patterns() {
+    let path = crate::app_dirs::get_livery_patterns_path();
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(_) => return,
+    };
+
+    match parse_user_patterns(&content) {
+        Ok(user) => {
+            let count = user.len();
+            if let Ok(mut active) = ACTIVE_PATTERNS.write() {
+                *active = merge_patterns(user);
+            }
+            logger::log_info(
+                &format!("Loaded {} user livery pattern(s) from {:?}", count, path),
+                Some("livery_patterns"),
+            );
+        }
+        Err(e) => {
+            logger::log_error(
+                &format!("Ignoring malformed livery patterns file {:?}: {}", path, e),
+                Some("livery_patterns"),
+            );
+        }
+    }
+}
+
 /// Check if a path matches a livery pattern
 /// Returns (aircraft_type_id, livery_root_path) if matched
-pub fn check_livery_pattern(file_path: &str) -> Option<(&'static str, String)> {
+pub fn check_livery_pattern(file_path: &str) -> Option<(String, String)> {
+    let active = ACTIVE_PATTERNS.read().ok()?;
+    check_livery_pattern_in(&active, file_path)
+}
+
+fn check_livery_pattern_in(
+    patterns: &[LiveryPatternConfig],
+    file_path: &str,
+) -> Option<(String, String)> {
     let normalized = file_path.replace('\\', "/");
     let normalized_lower = normalized.to_lowercase();
 
-    for pattern in LIVERY_PATTERNS {
-        for rule in pattern.detection_rules {
-            match rule.pattern_type {
+    for pattern in patterns {
+        for rule in &pattern.detection_rules {
+            match rule.pattern_type.as_str() {
                 "path" => {
                     // Path-based detection: look for folder path pattern
                     let pattern_lower = rule.pattern.to_lowercase();
@@ -211,13 +324,13 @@ pub fn check_livery_pattern(file_path: &str) -> Option<(&'static str, String)> {
                         } else {
                             prefix.trim_end_matches('/').to_string()
                         };
-                        return Some((pattern.aircraft_type_id, livery_root));
+                        return Some((pattern.aircraft_type_id.clone(), livery_root));
                     }
                 }
                 "file" => {
                     // File-based detection: match filename with glob pattern
                     if let Some(livery_root) = match_file_pattern(&normalized, &normalized_lower, rule) {
-                        return Some((pattern.aircraft_type_id, livery_root));
+                        return Some((pattern.aircraft_type_id.clone(), livery_root));
                     }
                 }
                 _ => {}
@@ -229,7 +342,7 @@ pub fn check_livery_pattern(file_path: &str) -> Option<(&'static str, String)> {
 }
 
 /// Match a file pattern and return the livery root if matched
-fn match_file_pattern(normalized: &str, normalized_lower: &str, rule: &DetectionRule) -> Option<String> {
+fn match_file_pattern(normalized: &str, normalized_lower: &str, rule: &DetectionRuleConfig) -> Option<String> {
     let pattern_lower = rule.pattern.to_lowercase();
 
     // Check if pattern contains a path separator (e.g., "objects/fuselage319*.png")
@@ -301,65 +414,60 @@ fn match_file_pattern(normalized: &str, normalized_lower: &str, rule: &Detection
     None
 }
 
-/// Simple glob matching supporting only '*' wildcard
+/// Simple glob matching supporting '*' (any run of characters) and '?' (exactly one character)
 fn matches_glob(pattern: &str, text: &str) -> bool {
-    if !pattern.contains('*') {
+    if !pattern.contains(['*', '?']) {
         return pattern == text;
     }
 
-    let parts: Vec<&str> = pattern.split('*').collect();
-
-    if parts.len() == 2 {
-        // Single wildcard: prefix*suffix
-        let prefix = parts[0];
-        let suffix = parts[1];
-        return text.starts_with(prefix) && text.ends_with(suffix) && text.len() >= prefix.len() + suffix.len();
-    }
-
-    // For more complex patterns, do a simple check
-    // This handles patterns like "a319_icon*.png"
-    let mut remaining = text;
-    for (i, part) in parts.iter().enumerate() {
-        if part.is_empty() {
-            continue;
-        }
-        if i == 0 {
-            // First part must be at the start
-            if !remaining.starts_with(part) {
-                return false;
-            }
-            remaining = &remaining[part.len()..];
-        } else if i == parts.len() - 1 {
-            // Last part must be at the end
-            if !remaining.ends_with(part) {
-                return false;
-            }
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    // Greedy match with backtracking to the most recent '*'
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            // Let the last '*' absorb one more character and retry
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, t));
         } else {
-            // Middle parts must exist somewhere
-            if let Some(pos) = remaining.find(part) {
-                remaining = &remaining[pos + part.len()..];
-            } else {
-                return false;
-            }
+            return false;
         }
     }
 
-    true
+    // Trailing '*'s match the empty string
+    pattern[p..].iter().all(|&c| c == '*')
 }
 
 /// Check if an ACF file name matches any known aircraft type
 /// Returns the aircraft_type_id if matched
-pub fn check_acf_identifier(acf_file_name: &str) -> Option<&'static str> {
+pub fn check_acf_identifier(acf_file_name: &str) -> Option<String> {
+    let active = ACTIVE_PATTERNS.read().ok()?;
+    check_acf_identifier_in(&active, acf_file_name)
+}
+
+fn check_acf_identifier_in(
+    patterns: &[LiveryPatternConfig],
+    acf_file_name: &str,
+) -> Option<String> {
     // Remove extension if present
     let name = Path::new(acf_file_name)
         .file_stem()
         .and_then(|s| s.to_str())
         .unwrap_or(acf_file_name);
 
-    for pattern in LIVERY_PATTERNS {
-        for identifier in pattern.acf_identifiers {
+    for pattern in patterns {
+        for identifier in &pattern.acf_identifiers {
             if name.eq_ignore_ascii_case(identifier) {
-                return Some(pattern.aircraft_type_id);
+                return Some(pattern.aircraft_type_id.clone());
             }
         }
     }
@@ -368,11 +476,13 @@ pub fn check_acf_identifier(acf_file_name: &str) -> Option<&'static str> {
 }
 
 /// Get the human-readable name for an aircraft type
-pub fn get_aircraft_name(aircraft_type_id: &str) -> Option<&'static str> {
-    LIVERY_PATTERNS
+pub fn get_aircraft_name(aircraft_type_id: &str) -> Option<String> {
+    ACTIVE_PATTERNS
+        .read()
+        .ok()?
         .iter()
         .find(|p| p.aircraft_type_id == aircraft_type_id)
-        .map(|p| p.aircraft_name)
+        .map(|p| p.aircraft_name.clone())
 }
 
 #[cfg(test)]
@@ -493,20 +603,86 @@ mod tests {
         assert!(!matches_glob("fuselage319.png", "fuselage320.png"));
     }
 
+    #[test]
+    fn test_matches_glob_single_char() {
+        assert!(matches_glob("fuselage320?.dds", "fuselage320a.dds"));
+        assert!(matches_glob("fuselage320?.dds", "fuselage320b.dds"));
+        assert!(!matches_glob("fuselage320?.dds", "fuselage320.dds"));
+        assert!(!matches_glob("fuselage320?.dds", "fuselage320ab.dds"));
+        // Mixed '?' and '*'
+        assert!(matches_glob("a319_?_icon*.png", "a319_n_icon11.png"));
+        assert!(matches_glob("a319_?_icon*.png", "a319_n_icon.png"));
+        assert!(!matches_glob("a319_?_icon*.png", "a319_neo_icon11.png"));
+        assert!(!matches_glob("a319_?_icon*.png", "a319__icon11.png"));
+        assert!(matches_glob("*_?.png", "livery_a.png"));
+        assert!(!matches_glob("*_?.png", "livery_ab.png"));
+    }
+
     #[test]
     fn test_check_acf_identifier() {
         // FF777
-        assert_eq!(check_acf_identifier("777-200ER.acf"), Some("FF777"));
-        assert_eq!(check_acf_identifier("777-200ER_xp12"), Some("FF777"));
-        assert_eq!(check_acf_identifier("777-F_xp12_lo.acf"), Some("FF777"));
+        assert_eq!(check_acf_identifier("777-200ER.acf").as_deref(), Some("FF777"));
+        assert_eq!(check_acf_identifier("777-200ER_xp12").as_deref(), Some("FF777"));
+        assert_eq!(check_acf_identifier("777-F_xp12_lo.acf").as_deref(), Some("FF777"));
         // Toliss
-        assert_eq!(check_acf_identifier("a319.acf"), Some("TOLISS_A319"));
-        assert_eq!(check_acf_identifier("a320_StdDef.acf"), Some("TOLISS_A320"));
-        assert_eq!(check_acf_identifier("a321_XP11.acf"), Some("TOLISS_A321"));
-        assert_eq!(check_acf_identifier("A330-900.acf"), Some("TOLISS_A339"));
+        assert_eq!(check_acf_identifier("a319.acf").as_deref(), Some("TOLISS_A319"));
+        assert_eq!(check_acf_identifier("a320_StdDef.acf").as_deref(), Some("TOLISS_A320"));
+        assert_eq!(check_acf_identifier("a321_XP11.acf").as_deref(), Some("TOLISS_A321"));
+        assert_eq!(check_acf_identifier("A330-900.acf").as_deref(), Some("TOLISS_A339"));
         // IXEG
-        assert_eq!(check_acf_identifier("B733.acf"), Some("IXEG_733"));
+        assert_eq!(check_acf_identifier("B733.acf").as_deref(), Some("IXEG_733"));
         // Unknown
-        assert_eq!(check_acf_identifier("unknown.acf"), None);
+        assert_eq!(check_acf_identifier("unknown.acf").as_deref(), None);
+    }
+
+    #[test]
+    fn test_user_patterns_override_builtins() {
+        let user = parse_user_patterns(
+            r#"[
+                {
+                    "aircraftTypeId": "ff777",
+                    "aircraftName": "Custom 777",
+                    "detectionRules": [{ "patternType": "file", "pattern": "custom777*.png" }],
+                    "acfIdentifiers": ["777-300ER"]
+                },
+                {
+                    "aircraftTypeId": "NEW_JET",
+                    "aircraftName": "New Jet",
+                    "detectionRules": [{ "patternType": "path", "pattern": "objects/newjet" }],
+                    "acfIdentifiers": ["newjet"]
+                }
+            ]"#,
+        )
+        .unwrap();
+        let merged = merge_patterns(user);
+
+        // The user entry replaces the built-in FF777 rules entirely
+        let ff777 = merged
+            .iter()
+            .filter(|p| p.aircraft_type_id.eq_ignore_ascii_case("FF777"))
+            .count();
+        assert_eq!(ff777, 1);
+        assert_eq!(
+            check_livery_pattern_in(&merged, "MyLivery/objects/777/texture.png"),
+            None
+        );
+        assert_eq!(
+            check_livery_pattern_in(&merged, "MyLivery/custom777_hd.png"),
+            Some(("ff777".to_string(), "MyLivery".to_string()))
+        );
+        assert_eq!(check_acf_identifier_in(&merged, "777-200ER.acf"), None);
+
+        // New aircraft types are added, built-ins without overrides are kept
+        assert_eq!(
+            check_acf_identifier_in(&merged, "newjet.acf").as_deref(),
+            Some("NEW_JET")
+        );
+        assert_eq!(
+            check_acf_identifier_in(&merged, "B733.acf").as_deref(),
+            Some("IXEG_733")
+        );
+
+        // Malformed files are rejected so the defaults stay in place
+        assert!(parse_user_patterns("{ not json").is_err());
     }
 }
@@ -2,13 +2,13 @@ use chrono::Local;
 use once_cell::sync::Lazy;
 use std::fs::{self, File, OpenOptions};
 use std::io::{BufRead, BufReader, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 
 use crate::app_dirs;
 
 const MAX_LOG_SIZE: u64 = 3 * 1024 * 1024; // 3MB
-const TRIM_TARGET_SIZE: u64 = 2 * 1024 * 1024; // Trim to 2MB when exceeds max
+const MAX_ROTATED_LOGS: usize = 3; // Keep xfastmanager.log.1 .. .3
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum LogLevel {
@@ -51,6 +51,7 @@ pub enum LogMsg {
     AnalysisCompleted,
     ScanFailed,
     CannotInstallFromXPlane,
+    IgnoredDiskRoot,
 }
 
 impl LogMsg {
@@ -67,6 +68,7 @@ impl LogMsg {
                 LogMsg::AnalysisCompleted => "Analysis completed",
                 LogMsg::ScanFailed => "Failed to scan",
                 LogMsg::CannotInstallFromXPlane => "Cannot install from X-Plane directory. Please drag files from outside X-Plane folder",
+                LogMsg::IgnoredDiskRoot => "Ignored addon at disk root",
             },
             Locale::Zh => match self {
                 LogMsg::AppStarted => "XFast Manager 已启动",
@@ -79,6 +81,7 @@ impl LogMsg {
                 LogMsg::AnalysisCompleted => "分析完成",
                 LogMsg::ScanFailed => "扫描失败",
                 LogMsg::CannotInstallFromXPlane => "无法从 X-Plane 目录内安装。请拖入 X-Plane 目录外的文件或压缩包",
+                LogMsg::IgnoredDiskRoot => "已忽略位于磁盘根目录的插件",
             },
         }
     }
@@ -180,51 +183,11 @@ impl LoggerInner {
     fn rotate_if_needed(&self) {
         if let Ok(metadata) = fs::metadata(&self.log_path) {
             if metadata.len() > MAX_LOG_SIZE {
-                self.trim_log_file();
-            }
-        }
-    }
-
-    fn trim_log_file(&self) {
-        // Read entire file
-        let content = match fs::read_to_string(&self.log_path) {
-            Ok(c) => c,
-            Err(_) => return,
-        };
-
-        let lines: Vec<&str> = content.lines().collect();
-        if lines.is_empty() {
-            return;
-        }
-
-        // Calculate how many bytes we need to remove
-        let current_size = content.len() as u64;
-        if current_size <= TRIM_TARGET_SIZE {
-            return;
-        }
-
-        let bytes_to_remove = current_size - TRIM_TARGET_SIZE;
-
-        // Find how many lines to skip from the beginning
-        let mut bytes_counted: u64 = 0;
-        let mut lines_to_skip = 0;
-
-        for line in &lines {
-            bytes_counted += line.len() as u64 + 1; // +1 for newline
-            lines_to_skip += 1;
-            if bytes_counted >= bytes_to_remove {
-                break;
+                if let Err(e) = rotate_log_files(&self.log_path, MAX_ROTATED_LOGS) {
+                    eprintln!("Failed to rotate log file: {}", e);
+                }
             }
         }
-
-        // Keep the remaining lines
-        let remaining_lines: Vec<&str> = lines.into_iter().skip(lines_to_skip).collect();
-        let new_content = remaining_lines.join("\n") + "\n";
-
-        // Write back
-        if let Err(e) = fs::write(&self.log_path, new_content) {
-            eprintln!("Failed to trim log file: {}", e);
-        }
     }
 
     fn read_recent_lines(&self, count: usize) -> Vec<String> {
@@ -262,6 +225,33 @@ impl LoggerInner {
     }
 }
 
+/// Path of the `index`-th rotated log file (e.g. xfastmanager.log.1)
+fn rotated_log_path(log_path: &Path, index: usize) -> PathBuf {
+    let mut name = log_path.as_os_str().to_os_string();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+/// Shift the active log to .1, .1 to .2 and so on, dropping the oldest beyond `keep`
+/// The next write starts a fresh active log
+fn rotate_log_files(log_path: &Path, keep: usize) -> std::io::Result<()> {
+    if keep == 0 {
+        return fs::remove_file(log_path);
+    }
+
+    let oldest = rotated_log_path(log_path, keep);
+    if oldest.exists() {
+        fs::remove_file(&oldest)?;
+    }
+    for index in (1..keep).rev() {
+        let from = rotated_log_path(log_path, index);
+        if from.exists() {
+            fs::rename(&from, rotated_log_path(log_path, index + 1))?;
+        }
+    }
+    fs::rename(log_path, rotated_log_path(log_path, 1))
+}
+
 static LOGGER: Lazy<Mutex<LoggerInner>> = Lazy::new(|| Mutex::new(LoggerInner::new()));
 
 // Public API
@@ -314,6 +304,16 @@ pub fn set_log_level(level: LogLevel) {
     }
 }
 
+/// Switch debug logging on or off (off falls back to the default Info level)
+/// Debug mode also makes scenery indexing classify packages sequentially for ordered logs
+pub fn set_debug_enabled(enabled: bool) {
+    set_log_level(if enabled {
+        LogLevel::Debug
+    } else {
+        LogLevel::Info
+    });
+}
+
 pub fn is_debug_enabled() -> bool {
     if let Ok(logger) = LOGGER.lock() {
         logger.get_min_level() <= LogLevel::Debug
@@ -364,3 +364,30 @@ pub fn get_log_folder() -> PathBuf {
         PathBuf::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotate_log_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let log_path = temp_dir.path().join("xfastmanager.log");
+
+        for round in 0..4 {
+            fs::write(&log_path, format!("round {}", round)).unwrap();
+            rotate_log_files(&log_path, 2).unwrap();
+            assert!(!log_path.exists());
+        }
+
+        assert_eq!(
+            fs::read_to_string(rotated_log_path(&log_path, 1)).unwrap(),
+            "round 3"
+        );
+        assert_eq!(
+            fs::read_to_string(rotated_log_path(&log_path, 2)).unwrap(),
+            "round 2"
+        );
+        assert!(!rotated_log_path(&log_path, 3).exists());
+    }
+}
@@ -7,16 +7,218 @@
 //! - Aircraft: Rename .acf <-> .xfma files (not scanning subdirectories)
 //! - Plugins: Rename .xpl <-> .xfmp files (including subdirectories)
 
+use crate::livery_patterns::check_acf_identifier;
 use crate::logger;
-use crate::models::{AircraftInfo, ManagementData, NavdataManagerInfo, PluginInfo};
+use crate::models::{
+    AircraftInfo, InstallMarker, ManagementData, ManagementDeleteOutcome, ManagementDeleteSummary,
+    NavdataManagerInfo, Overview, PluginInfo, XPlaneInfo,
+};
+use crate::scenery_index::SceneryIndexManager;
+use crate::task_control::WalkMonitor;
 use anyhow::{anyhow, Result};
+use once_cell::sync::Lazy;
 use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use walkdir::WalkDir;
 
+/// Maximum directory depth searched for .xpl/.xfmp files inside a plugin folder.
+/// Shared by scanning and toggling so any plugin that is detected can also be toggled.
+/// Defaults to 5, which covers shims that place their .xpl at depth 4 (e.g. XPLM).
+const PLUGIN_SCAN_DEPTH: usize = 5;
+
+/// Navdata cycle file and the name it is renamed to while the navdata is disabled
+const CYCLE_JSON: &str = "cycle.json";
+const DISABLED_CYCLE_JSON: &str = "cycle.json.disabled";
+
+/// Aircraft liveries folder and the name it is renamed to while liveries are disabled
+const LIVERIES_FOLDER: &str = "liveries";
+const DISABLED_LIVERIES_FOLDER: &str = "liveries.disabled";
+
+/// How long a fetched remote version is reused before it is fetched again
+const REMOTE_VERSION_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Maximum number of skunkcrafts_updater.cfg requests in flight during an update check
+const MAX_CONCURRENT_UPDATE_FETCHES: usize = 8;
+
+/// Attempts per skunkcrafts_updater.cfg request; the delay doubles after each failure
+const REMOTE_VERSION_FETCH_ATTEMPTS: u32 = 3;
+const REMOTE_VERSION_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// Aircraft and plugins (folder names) found to have an update by the update checks
+#[derive(Default)]
+struct FoundUpdates {
+    aircraft: HashSet<String>,
+    plugins: HashSet<String>,
+}
+
+/// Update check results per X-Plane installation. A check only covers the items sent
+/// to it, so its results are merged per folder instead of replacing a total
+static FOUND_UPDATES: Lazy<Mutex<HashMap<PathBuf, FoundUpdates>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Merge the (folder name, has update) results of an update check
+fn record_update_results<'a>(
+    xplane_path: &Path,
+    kind: fn(&mut FoundUpdates) -> &mut HashSet<String>,
+    results: impl Iterator<Item = (&'a str, bool)>,
+) {
+    let Ok(mut found) = FOUND_UPDATES.lock() else {
+        return;
+    };
+    let found = kind(found.entry(xplane_path.to_path_buf()).or_default());
+    for (folder_name, has_update) in results {
+        if has_update {
+            found.insert(folder_name.to_string());
+        } else {
+            found.remove(folder_name);
+        }
+    }
+}
+
+/// Number of aircraft and plugins of this installation found to have an update
+/// that are still installed
+fn count_found_updates(xplane_path: &Path) -> usize {
+    let Ok(found) = FOUND_UPDATES.lock() else {
+        return 0;
+    };
+    let Some(found) = found.get(xplane_path) else {
+        return 0;
+    };
+    let aircraft_dir = xplane_path.join("Aircraft");
+    let plugins_dir = xplane_path.join("Resources").join("plugins");
+    found
+        .aircraft
+        .iter()
+        .filter(|folder| aircraft_dir.join(folder).is_dir())
+        .count()
+        + found
+            .plugins
+            .iter()
+            .filter(|folder| plugins_dir.join(folder).is_dir())
+            .count()
+}
+
+/// Get add-on totals across all managers for the home screen
+/// Uses the scenery index and directory listings only (no file parsing),
+/// so it stays fast compared to the full management scans
+pub fn get_overview(xplane_path: &Path) -> Result<Overview> {
+    let scenery = SceneryIndexManager::new(xplane_path).get_stats()?;
+
+    Ok(Overview {
+        scenery,
+        aircraft_count: count_aircraft(xplane_path),
+        plugin_count: count_plugins(xplane_path),
+        navdata_count: count_navdata(xplane_path),
+        updates_available: count_found_updates(xplane_path),
+    })
+}
+
+/// Count aircraft folders (folders containing .acf/.xfma files) with the same
+/// depth limit as `scan_aircraft`, without reading any aircraft files
+fn count_aircraft(xplane_path: &Path) -> usize {
+    fn count_recursive(current_path: &Path, depth: usize) -> usize {
+        if depth > 3 {
+            return 0;
+        }
+        let Ok(read_dir) = fs::read_dir(current_path) else {
+            return 0;
+        };
+
+        let mut count = 0;
+        for entry in read_dir.flatten() {
+            if !entry.file_type().is_ok_and(|ft| ft.is_dir())
+                || entry.file_name().to_string_lossy().starts_with('.')
+            {
+                continue;
+            }
+            let path = entry.path();
+            if is_aircraft_folder(&path) {
+                count += 1;
+            } else {
+                count += count_recursive(&path, depth + 1);
+            }
+        }
+        count
+    }
+
+    count_recursive(&xplane_path.join("Aircraft"), 0)
+}
+
+fn is_aircraft_folder(folder: &Path) -> bool {
+    let Ok(read_dir) = fs::read_dir(folder) else {
+        return false;
+    };
+    read_dir.flatten().any(|entry| {
+        entry.file_type().is_ok_and(|ft| ft.is_file())
+            && entry
+                .path()
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| e.eq_ignore_ascii_case("acf") || e.eq_ignore_ascii_case("xfma"))
+    })
+}
+
+/// Count plugin folders in Resources/plugins
+fn count_plugins(xplane_path: &Path) -> usize {
+    let Ok(read_dir) = fs::read_dir(xplane_path.join("Resources").join("plugins")) else {
+        return 0;
+    };
+    read_dir
+        .flatten()
+        .filter(|entry| {
+            entry.file_type().is_ok_and(|ft| ft.is_dir())
+                && !entry.file_name().to_string_lossy().starts_with('.')
+        })
+        .count()
+}
+
+/// Count navdata entries (cycle.json files, enabled or not) in Custom Data
+fn count_navdata(xplane_path: &Path) -> usize {
+    WalkDir::new(xplane_path.join("Custom Data"))
+        .max_depth(10)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && is_cycle_json_name(&e.file_name().to_string_lossy()))
+        .filter(|e| !shadowed_by_enabled_cycle(e.path()))
+        .count()
+}
+
+/// Whether `name` is cycle.json or its disabled form
+fn is_cycle_json_name(name: &str) -> bool {
+    name.eq_ignore_ascii_case(CYCLE_JSON) || name.eq_ignore_ascii_case(DISABLED_CYCLE_JSON)
+}
+
+/// A cycle.json.disabled next to a cycle.json is a leftover: the enabled file wins
+fn shadowed_by_enabled_cycle(cycle_path: &Path) -> bool {
+    let is_disabled = cycle_path
+        .file_name()
+        .is_some_and(|n| n.eq_ignore_ascii_case(DISABLED_CYCLE_JSON));
+    is_disabled && find_cycle_file(cycle_path.parent().unwrap_or(cycle_path), CYCLE_JSON).is_some()
+}
+
+/// Find `name` in `folder`, ignoring case
+fn find_cycle_file(folder: &Path, name: &str) -> Option<std::path::PathBuf> {
+    fs::read_dir(folder)
+        .ok()?
+        .flatten()
+        .find(|entry| {
+            entry.file_type().is_ok_and(|ft| ft.is_file())
+                && entry.file_name().eq_ignore_ascii_case(name)
+        })
+        .map(|entry| entry.path())
+}
+
 /// Scan aircraft in the X-Plane Aircraft folder
-pub fn scan_aircraft(xplane_path: &Path) -> Result<ManagementData<AircraftInfo>> {
+/// Every visited folder is reported to `monitor`, which can also cancel the scan
+pub fn scan_aircraft(
+    xplane_path: &Path,
+    monitor: &WalkMonitor,
+) -> Result<ManagementData<AircraftInfo>> {
     let aircraft_path = xplane_path.join("Aircraft");
     if !aircraft_path.exists() {
         return Err(anyhow!("Aircraft folder not found"));
@@ -24,10 +226,8 @@ pub fn scan_aircraft(xplane_path: &Path) -> Result<ManagementData<AircraftInfo>>
 
     logger::log_info("Scanning aircraft folder...", Some("management"));
 
-    let mut entries: Vec<AircraftInfo> = Vec::new();
-
     // Scan up to 3 levels deep for .acf or .xfma files
-    scan_aircraft_recursive(&aircraft_path, &aircraft_path, 0, 3, &mut entries)?;
+    let mut entries = scan_aircraft_folders(&aircraft_path, 3, monitor)?;
 
     // Sort by display name
     entries.sort_by(|a, b| a.display_name.to_lowercase().cmp(&b.display_name.to_lowercase()));
@@ -47,83 +247,150 @@ pub fn scan_aircraft(xplane_path: &Path) -> Result<ManagementData<AircraftInfo>>
     })
 }
 
-fn scan_aircraft_recursive(
+/// Find aircraft installed more than once (e.g. after a botched update)
+/// Every .acf (or disabled .xfma) file is grouped by base name and its known aircraft
+/// type from `check_acf_identifier`, so a folder with several .acf files is matched on
+/// each of them. Returns groups of folder names (relative to Aircraft) with at least
+/// two entries
+pub fn find_duplicate_aircraft(
+    xplane_path: &Path,
+    monitor: &WalkMonitor,
+) -> Result<Vec<Vec<String>>> {
+    let data = scan_aircraft(xplane_path, monitor)?;
+
+    let mut groups: HashMap<(String, Option<String>), Vec<String>> = HashMap::new();
+    for info in &data.entries {
+        for acf_file in info.acf_files.iter().chain(&info.xfma_files) {
+            let base_name = Path::new(acf_file)
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+            let folders = groups
+                .entry((base_name, check_acf_identifier(acf_file)))
+                .or_default();
+            if !folders.contains(&info.folder_name) {
+                folders.push(info.folder_name.clone());
+            }
+        }
+    }
+
+    let mut duplicates: Vec<Vec<String>> = groups
+        .into_values()
+        .filter(|folders| folders.len() > 1)
+        .map(|mut folders| {
+            folders.sort();
+            folders
+        })
+        .collect();
+    duplicates.sort();
+    duplicates.dedup();
+
+    logger::log_info(
+        &format!("Found {} duplicate aircraft groups", duplicates.len()),
+        Some("management"),
+    );
+
+    Ok(duplicates)
+}
+
+/// List aircraft whose version can't be detected (no skunkcrafts_updater.cfg version
+/// and no version file), i.e. those that can't be tracked for updates
+/// Returns folder names relative to Aircraft, sorted by display name
+pub fn list_aircraft_without_version(
+    xplane_path: &Path,
+    monitor: &WalkMonitor,
+) -> Result<Vec<String>> {
+    let data = scan_aircraft(xplane_path, monitor)?;
+
+    let folders: Vec<String> = data
+        .entries
+        .into_iter()
+        .filter(|info| info.version.is_none())
+        .map(|info| info.folder_name)
+        .collect();
+
+    logger::log_info(
+        &format!(
+            "Found {} of {} aircraft without version info",
+            folders.len(),
+            data.total_count
+        ),
+        Some("management"),
+    );
+
+    Ok(folders)
+}
+
+/// Find aircraft folders below `base_path`, at most `max_depth + 1` levels deep.
+/// All aircraft folders are gathered in one walk first and then scanned in a single
+/// parallel pass. A folder with .acf/.xfma files is an aircraft and is not descended
+/// into; any other folder is searched for nested aircraft
+fn scan_aircraft_folders(
     base_path: &Path,
-    current_path: &Path,
-    depth: usize,
     max_depth: usize,
-    entries: &mut Vec<AircraftInfo>,
-) -> Result<()> {
-    if depth > max_depth {
-        return Ok(());
-    }
+    monitor: &WalkMonitor,
+) -> Result<Vec<AircraftInfo>> {
+    let folders = find_aircraft_folders(base_path, max_depth, monitor)?;
 
-    let read_dir = match fs::read_dir(current_path) {
-        Ok(rd) => rd,
-        Err(_) => return Ok(()),
-    };
+    Ok(folders
+        .par_iter()
+        .filter_map(|folder| scan_single_aircraft_folder(folder, base_path))
+        .collect())
+}
 
-    // Collect subdirectories first
-    let mut subdirs: Vec<(std::path::PathBuf, String)> = Vec::new();
-    for entry in read_dir.flatten() {
-        let ft = match entry.file_type() {
-            Ok(ft) => ft,
-            Err(_) => continue,
-        };
-        if !ft.is_dir() {
+/// Walk `base_path` and collect every folder holding .acf/.xfma files
+/// Files are visited before subfolders, so once an aircraft file is seen the rest of
+/// its folder (including nested folders) is skipped
+fn find_aircraft_folders(
+    base_path: &Path,
+    max_depth: usize,
+    monitor: &WalkMonitor,
+) -> Result<Vec<PathBuf>> {
+    let mut folders = Vec::new();
+    let mut walker = WalkDir::new(base_path)
+        .min_depth(1)
+        .max_depth(max_depth + 2)
+        .sort_by(|a, b| b.file_type().is_file().cmp(&a.file_type().is_file()))
+        .into_iter()
+        .filter_entry(|entry| !entry.file_name().to_string_lossy().starts_with('.'));
+
+    while let Some(entry) = walker.next() {
+        let Ok(entry) = entry else {
             continue;
-        }
-        let path = entry.path();
-        let folder_name = match path.file_name().and_then(|s| s.to_str()) {
-            Some(name) => name.to_string(),
-            None => continue,
         };
-        // Skip hidden folders
-        if folder_name.starts_with('.') {
+        if entry.file_type().is_dir() {
+            if entry.depth() <= max_depth + 1 {
+                monitor.step(entry.path())?;
+            }
             continue;
         }
-        subdirs.push((path, folder_name));
-    }
-
-    // Process subdirectories in parallel, each doing a single read_dir pass
-    let results: Vec<Option<AircraftInfo>> = subdirs
-        .par_iter()
-        .map(|(path, folder_name)| {
-            let info = scan_single_aircraft_folder(path, base_path, folder_name);
-            info
-        })
-        .collect();
 
-    // Collect results and recurse for non-aircraft folders
-    let mut recurse_dirs: Vec<&std::path::PathBuf> = Vec::new();
-    for (i, result) in results.into_iter().enumerate() {
-        if let Some(info) = result {
-            entries.push(info);
-        } else {
-            recurse_dirs.push(&subdirs[i].0);
+        let name = entry.file_name().to_string_lossy().to_lowercase();
+        if entry.depth() > 1 && (name.ends_with(".acf") || name.ends_with(".xfma")) {
+            if let Some(folder) = entry.path().parent() {
+                folders.push(folder.to_path_buf());
+            }
+            walker.skip_current_dir();
         }
     }
 
-    for dir in recurse_dirs {
-        scan_aircraft_recursive(base_path, dir, depth + 1, max_depth, entries)?;
-    }
-
-    Ok(())
+    Ok(folders)
 }
 
 /// Scan a single aircraft folder in one directory read pass.
 /// Returns Some(AircraftInfo) if it contains .acf/.xfma files, None otherwise.
-fn scan_single_aircraft_folder(
-    folder: &Path,
-    base_path: &Path,
-    folder_name: &str,
-) -> Option<AircraftInfo> {
+fn scan_single_aircraft_folder(folder: &Path, base_path: &Path) -> Option<AircraftInfo> {
     let read_dir = fs::read_dir(folder).ok()?;
+    let folder_name = folder
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
 
-    let mut acf_file: Option<String> = None;
-    let mut xfma_file: Option<String> = None;
+    let mut acf_files: Vec<String> = Vec::new();
+    let mut xfma_files: Vec<String> = Vec::new();
     let mut has_liveries = false;
     let mut livery_count = 0;
+    let mut liveries_disabled = false;
     let mut updater_cfg_path: Option<std::path::PathBuf> = None;
     let mut version_file_paths: Vec<std::path::PathBuf> = Vec::new();
 
@@ -140,10 +407,10 @@ fn scan_single_aircraft_folder(
 
         if ft.is_file() {
             // Check for .acf / .xfma
-            if acf_file.is_none() && name_lower.ends_with(".acf") {
-                acf_file = Some(name.clone());
-            } else if xfma_file.is_none() && name_lower.ends_with(".xfma") {
-                xfma_file = Some(name.clone());
+            if name_lower.ends_with(".acf") {
+                acf_files.push(name.clone());
+            } else if name_lower.ends_with(".xfma") {
+                xfma_files.push(name.clone());
             }
             // Check for version sources
             if name_lower == "skunkcrafts_updater.cfg" {
@@ -155,7 +422,9 @@ fn scan_single_aircraft_folder(
                 version_file_paths.push(entry.path());
             }
         } else if ft.is_dir() {
-            if name_lower == "liveries" {
+            if name_lower == DISABLED_LIVERIES_FOLDER {
+                liveries_disabled = true;
+            } else if name_lower == LIVERIES_FOLDER {
                 // Count liveries
                 if let Ok(liveries_rd) = fs::read_dir(entry.path()) {
                     for lv_entry in liveries_rd.flatten() {
@@ -170,14 +439,27 @@ fn scan_single_aircraft_folder(
     }
 
     // Must have .acf or .xfma to be recognized as aircraft
-    let (acf_name, enabled) = if let Some(name) = acf_file {
-        (name, true)
-    } else if let Some(name) = xfma_file {
-        (name, false)
+    // Any .acf present means enabled, even if .xfma files sit next to it
+    acf_files.sort();
+    xfma_files.sort();
+    let (acf_name, enabled) = if let Some(name) = acf_files.first() {
+        (name.clone(), true)
+    } else if let Some(name) = xfma_files.first() {
+        (name.clone(), false)
     } else {
         return None;
     };
 
+    if !acf_files.is_empty() && !xfma_files.is_empty() {
+        logger::log_info(
+            &format!(
+                "Warning: aircraft '{}' has both .acf {:?} and .xfma {:?} files, treating it as enabled",
+                folder_name, acf_files, xfma_files
+            ),
+            Some("management"),
+        );
+    }
+
     // Read version info (priority: skunkcrafts_updater.cfg > version files)
     let (version, update_url, cfg_disabled) =
         read_version_from_paths(updater_cfg_path.as_deref(), &version_file_paths);
@@ -190,16 +472,19 @@ fn scan_single_aircraft_folder(
 
     Some(AircraftInfo {
         folder_name: relative_path,
-        display_name: folder_name.to_string(),
+        display_name: folder_name,
         acf_file: acf_name,
         enabled,
         has_liveries,
         livery_count,
+        liveries_disabled,
         version,
         update_url,
         latest_version: None, // Will be populated by check_aircraft_updates
         has_update: false,    // Will be set by check_aircraft_updates
         cfg_disabled,
+        acf_files,
+        xfma_files,
     })
 }
 
@@ -387,8 +672,70 @@ pub fn try_parse_digit_version(s: &str) -> Option<String> {
     Some(parts.join("."))
 }
 
+/// Parse a dotted version ("2.3.10", "v2.10", "1.10b") into its components, each a
+/// number plus any non-numeric suffix ("10b" -> (10, "b")).
+/// Pure digit strings are split like try_parse_digit_version ("020310" -> [2, 3, 10]).
+/// Returns None if any component doesn't start with a number
+fn parse_version_components(version: &str) -> Option<Vec<(u32, String)>> {
+    let version = version.trim();
+    let version = version.strip_prefix(['v', 'V']).unwrap_or(version);
+    if version.is_empty() {
+        return None;
+    }
+
+    let dotted = if !version.contains('.') && version.chars().all(|c| c.is_ascii_digit()) {
+        try_parse_digit_version(version).unwrap_or_else(|| version.to_string())
+    } else {
+        version.to_string()
+    };
+
+    dotted
+        .split('.')
+        .map(|part| {
+            let part = part.trim();
+            let digits_end = part
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(part.len());
+            let number = part[..digits_end].parse::<u32>().ok()?;
+            Some((number, part[digits_end..].to_string()))
+        })
+        .collect()
+}
+
+/// Compare two versions component by component, numerically ("1.10" > "1.9",
+/// "2.3" == "2.3.0", "1.10b" > "1.9b").
+/// Returns None if either version can't be parsed, or if the first differing
+/// component only differs in its suffix ("1.2b" vs "1.2")
+pub fn compare_versions(a: &str, b: &str) -> Option<std::cmp::Ordering> {
+    let a = parse_version_components(a)?;
+    let b = parse_version_components(b)?;
+    let missing = (0, String::new());
+    for i in 0..a.len().max(b.len()) {
+        let (a_number, a_suffix) = a.get(i).unwrap_or(&missing);
+        let (b_number, b_suffix) = b.get(i).unwrap_or(&missing);
+        match a_number.cmp(b_number) {
+            std::cmp::Ordering::Equal if a_suffix != b_suffix => return None,
+            std::cmp::Ordering::Equal => {}
+            ordering => return Some(ordering),
+        }
+    }
+    Some(std::cmp::Ordering::Equal)
+}
+
+/// Whether `remote` is newer than `local`; falls back to string inequality
+/// when either version isn't numeric
+fn is_newer_version(local: &str, remote: &str) -> bool {
+    match compare_versions(remote, local) {
+        Some(ordering) => ordering.is_gt(),
+        None => remote.trim() != local.trim(),
+    }
+}
+
 /// Scan plugins in the X-Plane Resources/plugins folder
-pub fn scan_plugins(xplane_path: &Path) -> Result<ManagementData<PluginInfo>> {
+pub fn scan_plugins(
+    xplane_path: &Path,
+    monitor: &WalkMonitor,
+) -> Result<ManagementData<PluginInfo>> {
     let plugins_path = xplane_path.join("Resources").join("plugins");
     if !plugins_path.exists() {
         return Err(anyhow!("Plugins folder not found"));
@@ -420,9 +767,13 @@ pub fn scan_plugins(xplane_path: &Path) -> Result<ManagementData<PluginInfo>> {
     // Process plugin folders in parallel
     let mut entries: Vec<PluginInfo> = subdirs
         .par_iter()
-        .filter_map(|(path, folder_name)| {
-            scan_single_plugin_folder(path, folder_name)
+        .map(|(path, folder_name)| {
+            monitor.step(path)?;
+            Ok(scan_single_plugin_folder(path, folder_name))
         })
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .flatten()
         .collect();
 
     // Sort by display name
@@ -489,7 +840,11 @@ fn find_xpl_and_xfmp_files(folder: &Path) -> (Vec<String>, Vec<String>) {
     let mut xpl_files = Vec::new();
     let mut xfmp_files = Vec::new();
 
-    for entry in WalkDir::new(folder).max_depth(3).into_iter().filter_map(|e| e.ok()) {
+    for entry in WalkDir::new(folder)
+        .max_depth(PLUGIN_SCAN_DEPTH)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
         if !entry.file_type().is_file() {
             continue;
         }
@@ -513,21 +868,50 @@ fn find_xpl_and_xfmp_files(folder: &Path) -> (Vec<String>, Vec<String>) {
     (xpl_files, xfmp_files)
 }
 
+/// Detect the platform of a plugin binary from its header magic bytes
+/// PE (MZ) -> win, ELF -> lin, Mach-O / universal (fat) binary -> mac
+fn detect_binary_platform(path: &Path) -> Option<&'static str> {
+    let mut header = [0u8; 4];
+    let mut file = fs::File::open(path).ok()?;
+    file.read_exact(&mut header).ok()?;
+
+    match header {
+        [b'M', b'Z', _, _] => Some("win"),
+        [0x7f, b'E', b'L', b'F'] => Some("lin"),
+        // Mach-O 32/64-bit (both byte orders) and fat/universal binaries
+        [0xfe, 0xed, 0xfa, 0xce]
+        | [0xfe, 0xed, 0xfa, 0xcf]
+        | [0xce, 0xfa, 0xed, 0xfe]
+        | [0xcf, 0xfa, 0xed, 0xfe]
+        | [0xca, 0xfe, 0xba, 0xbe]
+        | [0xbe, 0xba, 0xfe, 0xca] => Some("mac"),
+        _ => None,
+    }
+}
+
 fn detect_plugin_platform(folder: &Path, xpl_files: &[String]) -> String {
     let mut has_win = false;
     let mut has_mac = false;
     let mut has_lin = false;
 
     for xpl_file in xpl_files {
-        let lower = xpl_file.to_lowercase();
-        if lower.contains("win") {
-            has_win = true;
-        }
-        if lower.contains("mac") {
-            has_mac = true;
-        }
-        if lower.contains("lin") {
-            has_lin = true;
+        // Prefer the binary header; fall back to the filename heuristic
+        match detect_binary_platform(&folder.join(xpl_file)) {
+            Some("win") => has_win = true,
+            Some("mac") => has_mac = true,
+            Some("lin") => has_lin = true,
+            _ => {
+                let lower = xpl_file.to_lowercase();
+                if lower.contains("win") {
+                    has_win = true;
+                }
+                if lower.contains("mac") {
+                    has_mac = true;
+                }
+                if lower.contains("lin") {
+                    has_lin = true;
+                }
+            }
         }
     }
 
@@ -571,7 +955,10 @@ fn detect_plugin_platform(folder: &Path, xpl_files: &[String]) -> String {
 }
 
 /// Scan navdata in the X-Plane Custom Data folder
-pub fn scan_navdata(xplane_path: &Path) -> Result<ManagementData<NavdataManagerInfo>> {
+pub fn scan_navdata(
+    xplane_path: &Path,
+    monitor: &WalkMonitor,
+) -> Result<ManagementData<NavdataManagerInfo>> {
     let custom_data_path = xplane_path.join("Custom Data");
     if !custom_data_path.exists() {
         return Err(anyhow!("Custom Data folder not found"));
@@ -587,11 +974,15 @@ pub fn scan_navdata(xplane_path: &Path) -> Result<ManagementData<NavdataManagerI
         .into_iter()
         .filter_map(|e| e.ok())
     {
+        if entry.file_type().is_dir() {
+            monitor.step(entry.path())?;
+            continue;
+        }
         if !entry.file_type().is_file() {
             continue;
         }
         if let Some(name) = entry.file_name().to_str() {
-            if name.eq_ignore_ascii_case("cycle.json") {
+            if is_cycle_json_name(name) && !shadowed_by_enabled_cycle(entry.path()) {
                 let path = entry.path();
                 if let Some(parent) = path.parent() {
                     if let Some(info) = parse_cycle_json(path, &custom_data_path, parent) {
@@ -620,6 +1011,8 @@ pub fn scan_navdata(xplane_path: &Path) -> Result<ManagementData<NavdataManagerI
     })
 }
 
+/// Read a navdata entry from its cycle.json, which is disabled when the file
+/// has been renamed to cycle.json.disabled
 fn parse_cycle_json(
     cycle_json_path: &Path,
     base_path: &Path,
@@ -648,21 +1041,27 @@ fn parse_cycle_json(
         provider_name,
         cycle,
         airac,
-        enabled: true, // Always enabled (toggle not supported)
+        enabled: !cycle_json_path
+            .file_name()
+            .is_some_and(|n| n.eq_ignore_ascii_case(DISABLED_CYCLE_JSON)),
     })
 }
 
 /// Toggle enabled state for a management item
 /// - Aircraft: Rename .acf <-> .xfma files (not scanning subdirectories)
-/// - Plugins: Rename .xpl <-> .xfmp files (including subdirectories)
+/// - Plugins: Rename .xpl <-> .xfmp files (including subdirectories); `platform`
+///   ("win", "mac" or "lin") restricts the rename to that platform's binaries
+/// - Navdata: Rename cycle.json <-> cycle.json.disabled
 pub fn toggle_management_item(
     xplane_path: &Path,
     item_type: &str,
     folder_name: &str,
+    platform: Option<&str>,
 ) -> Result<bool> {
     let base_path = match item_type {
         "aircraft" => xplane_path.join("Aircraft"),
         "plugin" => xplane_path.join("Resources").join("plugins"),
+        "navdata" => xplane_path.join("Custom Data"),
         _ => return Err(anyhow!("Unknown item type: {}", item_type)),
     };
 
@@ -673,12 +1072,15 @@ pub fn toggle_management_item(
 
     match item_type {
         "aircraft" => toggle_aircraft_files(&current_path, folder_name),
-        "plugin" => toggle_plugin_files(&current_path, folder_name),
+        "plugin" => toggle_plugin_files(&current_path, folder_name, platform),
+        "navdata" => toggle_navdata_cycle(&current_path, folder_name),
         _ => Err(anyhow!("Unknown item type: {}", item_type)),
     }
 }
 
 /// Toggle aircraft files: .acf <-> .xfma (only in the folder, not subdirectories)
+/// A folder with any .acf is enabled (enable wins), so a mixed folder gets disabled.
+/// Every rename target is checked first, no rename overwrites a file
 fn toggle_aircraft_files(folder_path: &Path, folder_name: &str) -> Result<bool> {
     let read_dir = fs::read_dir(folder_path)?;
 
@@ -700,61 +1102,145 @@ fn toggle_aircraft_files(folder_path: &Path, folder_name: &str) -> Result<bool>
         }
     }
 
-    let new_enabled = if !acf_files.is_empty() {
+    let (sources, new_ext, new_enabled) = if !acf_files.is_empty() {
         // Currently enabled (has .acf files), disable by renaming to .xfma
-        for acf_path in &acf_files {
-            let new_path = acf_path.with_extension("xfma");
-            fs::rename(acf_path, &new_path)?;
-        }
-        logger::log_info(
-            &format!(
-                "Disabled aircraft '{}': renamed {} .acf file(s) to .xfma",
-                folder_name,
-                acf_files.len()
-            ),
-            Some("management"),
-        );
-        false
+        (acf_files, "xfma", false)
     } else if !xfma_files.is_empty() {
-        // Currently disabled (has .xfma files), enable by renaming to .acf
-        for xfma_path in &xfma_files {
-            let new_path = xfma_path.with_extension("acf");
-            fs::rename(xfma_path, &new_path)?;
-        }
-        logger::log_info(
-            &format!(
-                "Enabled aircraft '{}': renamed {} .xfma file(s) to .acf",
-                folder_name,
-                xfma_files.len()
-            ),
-            Some("management"),
-        );
-        true
+        // Currently disabled, enable by renaming .xfma files to .acf
+        (xfma_files, "acf", true)
     } else {
         return Err(anyhow!("No .acf or .xfma files found in aircraft folder"));
     };
 
+    let renames: Vec<_> = sources
+        .iter()
+        .map(|path| (path, path.with_extension(new_ext)))
+        .collect();
+    if let Some((_, existing)) = renames.iter().find(|(_, target)| target.exists()) {
+        return Err(anyhow!(
+            "Cannot {} aircraft '{}': {:?} already exists",
+            if new_enabled { "enable" } else { "disable" },
+            folder_name,
+            existing.file_name().unwrap_or_default()
+        ));
+    }
+
+    for (path, target) in &renames {
+        fs::rename(path, target)?;
+    }
+
+    logger::log_info(
+        &format!(
+            "{} aircraft '{}': renamed {} .{} file(s) to .{}",
+            if new_enabled { "Enabled" } else { "Disabled" },
+            folder_name,
+            renames.len(),
+            if new_enabled { "xfma" } else { "acf" },
+            new_ext
+        ),
+        Some("management"),
+    );
+
+    Ok(new_enabled)
+}
+
+/// Toggle navdata: cycle.json <-> cycle.json.disabled in the provider folder
+fn toggle_navdata_cycle(folder_path: &Path, folder_name: &str) -> Result<bool> {
+    let new_enabled = if let Some(cycle_path) = find_cycle_file(folder_path, CYCLE_JSON) {
+        fs::rename(&cycle_path, folder_path.join(DISABLED_CYCLE_JSON))?;
+        false
+    } else if let Some(disabled_path) = find_cycle_file(folder_path, DISABLED_CYCLE_JSON) {
+        fs::rename(&disabled_path, folder_path.join(CYCLE_JSON))?;
+        true
+    } else {
+        return Err(anyhow!("No cycle.json found in navdata folder"));
+    };
+
+    logger::log_info(
+        &format!(
+            "{} navdata '{}'",
+            if new_enabled { "Enabled" } else { "Disabled" },
+            folder_name
+        ),
+        Some("management"),
+    );
+
     Ok(new_enabled)
 }
 
+/// Platform of a single plugin binary for platform-restricted toggles
+/// Uses the header magic first, then a win/mac/lin file stem or a platform subfolder
+/// (win_x64/, mac_x64/, lin_x64/)
+fn plugin_file_platform(folder: &Path, path: &Path) -> Option<&'static str> {
+    if let Some(platform) = detect_binary_platform(path) {
+        return Some(platform);
+    }
+
+    let stem = path.file_stem()?.to_string_lossy().to_lowercase();
+    let relative = path.strip_prefix(folder).unwrap_or(path);
+    let parent_dirs: Vec<String> = relative
+        .parent()
+        .map(|parent| {
+            parent
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().to_lowercase())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    ["win", "mac", "lin"].into_iter().find(|platform| {
+        stem == *platform
+            || parent_dirs
+                .iter()
+                .any(|dir| dir == platform || *dir == format!("{}_x64", platform))
+    })
+}
+
 /// Toggle plugin files: .xpl <-> .xfmp (including subdirectories)
-fn toggle_plugin_files(folder_path: &Path, folder_name: &str) -> Result<bool> {
+/// With a platform only that platform's binaries are renamed, so a plugin on a drive
+/// shared between operating systems can be disabled for one of them
+fn toggle_plugin_files(
+    folder_path: &Path,
+    folder_name: &str,
+    platform: Option<&str>,
+) -> Result<bool> {
+    if let Some(platform) = platform {
+        if !matches!(platform, "win" | "mac" | "lin") {
+            return Err(anyhow!("Unknown plugin platform: {}", platform));
+        }
+    }
+
     let mut xpl_files: Vec<std::path::PathBuf> = Vec::new();
     let mut xfmp_files: Vec<std::path::PathBuf> = Vec::new();
 
     // Use walkdir to find all .xpl and .xfmp files recursively
-    for entry in WalkDir::new(folder_path).max_depth(10).into_iter().filter_map(|e| e.ok()) {
+    for entry in WalkDir::new(folder_path)
+        .max_depth(PLUGIN_SCAN_DEPTH)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
         let path = entry.path();
         if !path.is_file() {
             continue;
         }
 
-        if let Some(ext) = path.extension() {
-            if ext.eq_ignore_ascii_case("xpl") {
-                xpl_files.push(path.to_path_buf());
-            } else if ext.eq_ignore_ascii_case("xfmp") {
-                xfmp_files.push(path.to_path_buf());
-            }
+        let Some(ext) = path.extension() else {
+            continue;
+        };
+        let is_xpl = ext.eq_ignore_ascii_case("xpl");
+        if !is_xpl && !ext.eq_ignore_ascii_case("xfmp") {
+            continue;
+        }
+
+        // Only plugin binaries get their header read
+        if platform.is_some() && plugin_file_platform(folder_path, path) != platform {
+            continue;
+        }
+
+        if is_xpl {
+            xpl_files.push(path.to_path_buf());
+        } else {
+            xfmp_files.push(path.to_path_buf());
         }
     }
 
@@ -788,6 +1274,11 @@ fn toggle_plugin_files(folder_path: &Path, folder_name: &str) -> Result<bool> {
             Some("management"),
         );
         true
+    } else if let Some(platform) = platform {
+        return Err(anyhow!(
+            "No {} .xpl or .xfmp files found in plugin folder",
+            platform
+        ));
     } else {
         return Err(anyhow!("No .xpl or .xfmp files found in plugin folder"));
     };
@@ -795,6 +1286,79 @@ fn toggle_plugin_files(folder_path: &Path, folder_name: &str) -> Result<bool> {
     Ok(new_enabled)
 }
 
+/// Hide an aircraft's liveries by renaming its liveries folder to liveries.disabled
+/// The aircraft's .acf/.xfma enabled state is not touched
+pub fn disable_liveries(xplane_path: &Path, aircraft_folder: &str) -> Result<()> {
+    rename_liveries_folder(
+        xplane_path,
+        aircraft_folder,
+        LIVERIES_FOLDER,
+        DISABLED_LIVERIES_FOLDER,
+    )
+}
+
+/// Restore liveries previously hidden with disable_liveries
+pub fn enable_liveries(xplane_path: &Path, aircraft_folder: &str) -> Result<()> {
+    rename_liveries_folder(
+        xplane_path,
+        aircraft_folder,
+        DISABLED_LIVERIES_FOLDER,
+        LIVERIES_FOLDER,
+    )
+}
+
+fn rename_liveries_folder(
+    xplane_path: &Path,
+    aircraft_folder: &str,
+    from: &str,
+    to: &str,
+) -> Result<()> {
+    // Only plain relative names: "..", absolute or prefixed paths would escape Aircraft
+    let plain_name = Path::new(aircraft_folder)
+        .components()
+        .all(|c| matches!(c, std::path::Component::Normal(_)));
+    if !plain_name {
+        return Err(anyhow!("Invalid aircraft folder: {}", aircraft_folder));
+    }
+
+    let aircraft_path = xplane_path.join("Aircraft");
+    let folder_path = aircraft_path.join(aircraft_folder);
+    if !folder_path.is_dir() {
+        return Err(anyhow!("Folder not found: {}", aircraft_folder));
+    }
+
+    // Security: the resolved folder must still be inside Aircraft (e.g. not via a symlink)
+    let folder_path = folder_path.canonicalize()?;
+    let canonical_base = aircraft_path.canonicalize()?;
+    if !folder_path.starts_with(&canonical_base) || folder_path == canonical_base {
+        return Err(anyhow!("Invalid aircraft folder: {}", aircraft_folder));
+    }
+
+    // Folder names are matched case-insensitively, like the aircraft scan
+    let source = fs::read_dir(&folder_path)?
+        .flatten()
+        .find(|e| {
+            e.file_type().map(|ft| ft.is_dir()).unwrap_or(false)
+                && e.file_name().to_string_lossy().to_lowercase() == from
+        })
+        .map(|e| e.path())
+        .ok_or_else(|| anyhow!("No {} folder in {}", from, aircraft_folder))?;
+
+    let target = folder_path.join(to);
+    if target.exists() {
+        return Err(anyhow!("{} already exists in {}", to, aircraft_folder));
+    }
+
+    fs::rename(&source, &target)?;
+
+    logger::log_info(
+        &format!("Renamed {} to {} in '{}'", from, to, aircraft_folder),
+        Some("management"),
+    );
+
+    Ok(())
+}
+
 /// Delete a management item folder
 pub fn delete_management_item(xplane_path: &Path, item_type: &str, folder_name: &str) -> Result<()> {
     let base_path = match item_type {
@@ -805,15 +1369,20 @@ pub fn delete_management_item(xplane_path: &Path, item_type: &str, folder_name:
     };
 
     let target_path = base_path.join(folder_name);
-    if !target_path.exists() {
-        return Err(anyhow!("Folder not found: {}", folder_name));
-    }
 
     // Safety check: ensure path is within the expected base directory
-    if !target_path.starts_with(&base_path) {
+    // ("..", absolute or prefixed names would escape it despite the join)
+    let plain_name = Path::new(folder_name)
+        .components()
+        .all(|c| matches!(c, std::path::Component::Normal(_)));
+    if !plain_name || !target_path.starts_with(&base_path) {
         return Err(anyhow!("Invalid path"));
     }
 
+    if !target_path.exists() {
+        return Err(anyhow!("Folder not found: {}", folder_name));
+    }
+
     fs::remove_dir_all(&target_path)?;
 
     logger::log_info(
@@ -824,6 +1393,74 @@ pub fn delete_management_item(xplane_path: &Path, item_type: &str, folder_name:
     Ok(())
 }
 
+/// Delete several management items in one call
+/// Each (item_type, folder_name) pair is deleted independently; failures are
+/// collected in the summary instead of aborting the batch
+pub fn delete_management_items(
+    xplane_path: &Path,
+    items: &[(String, String)],
+) -> ManagementDeleteSummary {
+    let results: Vec<ManagementDeleteOutcome> = items
+        .iter()
+        .map(|(item_type, folder_name)| {
+            let result = delete_management_item(xplane_path, item_type, folder_name);
+            if let Err(e) = &result {
+                logger::log_error(
+                    &format!(
+                        "Failed to delete {} folder {}: {}",
+                        item_type, folder_name, e
+                    ),
+                    Some("management"),
+                );
+            }
+            ManagementDeleteOutcome {
+                item_type: item_type.clone(),
+                folder_name: folder_name.clone(),
+                success: result.is_ok(),
+                error_message: result.err().map(|e| e.to_string()),
+            }
+        })
+        .collect();
+
+    let deleted = results.iter().filter(|r| r.success).count();
+    logger::log_info(
+        &format!(
+            "Batch delete: {} of {} items deleted",
+            deleted,
+            results.len()
+        ),
+        Some("management"),
+    );
+
+    ManagementDeleteSummary {
+        deleted,
+        failed: results.len() - deleted,
+        results,
+    }
+}
+
+/// Read the install marker (.xfastinstall.json) of a management item
+/// Returns None for items installed manually or before markers were written
+pub fn get_install_marker(
+    xplane_path: &Path,
+    item_type: &str,
+    folder_name: &str,
+) -> Result<Option<InstallMarker>> {
+    let base_path = match item_type {
+        "aircraft" => xplane_path.join("Aircraft"),
+        "plugin" => xplane_path.join("Resources").join("plugins"),
+        "scenery" => xplane_path.join("Custom Scenery"),
+        _ => return Err(anyhow!("Unknown item type: {}", item_type)),
+    };
+
+    let folder_path = base_path.join(folder_name);
+    if !folder_path.exists() {
+        return Err(anyhow!("Folder not found: {}", folder_name));
+    }
+
+    Ok(crate::install_marker::read_install_marker(&folder_path))
+}
+
 /// Open a management item folder in the system file explorer
 pub fn open_management_folder(
     xplane_path: &Path,
@@ -866,11 +1503,114 @@ pub fn open_management_folder(
     Ok(())
 }
 
+/// A remote version together with the validators the server sent for it
+/// Used to make conditional requests once the TTL has expired
+#[derive(Debug, Clone, PartialEq)]
+struct RemoteVersionEntry {
+    version: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+impl RemoteVersionEntry {
+    fn has_validators(&self) -> bool {
+        self.etag.is_some() || self.last_modified.is_some()
+    }
+}
+
+/// In-memory cache of remote versions keyed by update base URL (kept in Tauri state)
+/// Only successful lookups are cached, so a failed fetch is retried on the next check.
+/// ETag/Last-Modified validators are kept past the TTL so later checks can be answered
+/// with a 304 instead of downloading the config again
+pub struct RemoteVersionCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (Instant, String)>>,
+    validators: Mutex<HashMap<String, RemoteVersionEntry>>,
+}
+
+impl RemoteVersionCache {
+    pub fn new() -> Self {
+        Self::with_ttl(REMOTE_VERSION_TTL)
+    }
+
+    fn with_ttl(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+            validators: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn cache_key(base_url: &str) -> String {
+        base_url.trim_end_matches('/').to_string()
+    }
+
+    /// Get a cached version that is still within the TTL
+    fn get(&self, base_url: &str) -> Option<String> {
+        let entries = self.entries.lock().ok()?;
+        entries
+            .get(&Self::cache_key(base_url))
+            .filter(|(fetched_at, _)| fetched_at.elapsed() < self.ttl)
+            .map(|(_, version)| version.clone())
+    }
+
+    fn insert(&self, base_url: &str, version: &str) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.insert(
+                Self::cache_key(base_url),
+                (Instant::now(), version.to_string()),
+            );
+        }
+    }
+
+    /// Get the last response with validators for a conditional request, regardless of TTL
+    fn get_validated(&self, base_url: &str) -> Option<RemoteVersionEntry> {
+        let validators = self.validators.lock().ok()?;
+        validators.get(&Self::cache_key(base_url)).cloned()
+    }
+
+    fn insert_entry(&self, base_url: &str, entry: &RemoteVersionEntry) {
+        self.insert(base_url, &entry.version);
+        if let Ok(mut validators) = self.validators.lock() {
+            if entry.has_validators() {
+                validators.insert(Self::cache_key(base_url), entry.clone());
+            } else {
+                validators.remove(&Self::cache_key(base_url));
+            }
+        }
+    }
+
+    /// Return the cached version, or fetch it when missing, expired or `force_refresh` is set
+    /// The fetch is conditional when validators from an earlier response are known
+    pub async fn get_or_fetch(&self, base_url: String, force_refresh: bool) -> Option<String> {
+        if !force_refresh {
+            if let Some(version) = self.get(&base_url) {
+                return Some(version);
+            }
+        }
+
+        let previous = self.get_validated(&base_url);
+        let entry = fetch_remote_version(base_url.clone(), previous.as_ref()).await?;
+        self.insert_entry(&base_url, &entry);
+        Some(entry.version)
+    }
+}
+
+impl Default for RemoteVersionCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Check for aircraft updates by fetching remote skunkcrafts_updater.cfg files
 /// This function modifies the aircraft list in place, setting latest_version and has_update
-pub async fn check_aircraft_updates(aircraft: &mut [AircraftInfo]) {
-    use futures::future::join_all;
-
+/// Remote versions fetched within the cache TTL are reused unless `force_refresh` is set
+pub async fn check_aircraft_updates(
+    xplane_path: &Path,
+    aircraft: &mut [AircraftInfo],
+    cache: &RemoteVersionCache,
+    force_refresh: bool,
+) {
     // Collect aircraft with update URLs
     let update_tasks: Vec<_> = aircraft
         .iter()
@@ -878,33 +1618,35 @@ pub async fn check_aircraft_updates(aircraft: &mut [AircraftInfo]) {
         .filter_map(|(idx, a)| a.update_url.as_ref().map(|url| (idx, url.clone())))
         .collect();
 
-    if update_tasks.is_empty() {
-        return;
-    }
+    if !update_tasks.is_empty() {
+        let results = fetch_remote_versions(update_tasks, cache, force_refresh).await;
 
-    // Fetch all remote configs in parallel
-    let fetch_futures: Vec<_> = update_tasks
-        .iter()
-        .map(|(_, url)| fetch_remote_version(url.clone()))
-        .collect();
-
-    let results = join_all(fetch_futures).await;
-
-    // Update aircraft with results
-    for ((idx, _), result) in update_tasks.into_iter().zip(results) {
-        if let Some(remote_version) = result {
-            let local_version = aircraft[idx].version.as_deref().unwrap_or("");
-            aircraft[idx].latest_version = Some(remote_version.clone());
-            aircraft[idx].has_update = remote_version != local_version;
+        // Update aircraft with results
+        for (idx, result) in results {
+            if let Some(remote_version) = result {
+                let local_version = aircraft[idx].version.as_deref().unwrap_or("");
+                aircraft[idx].latest_version = Some(remote_version.clone());
+                aircraft[idx].has_update = is_newer_version(local_version, &remote_version);
+            }
         }
     }
+
+    record_update_results(
+        xplane_path,
+        |found| &mut found.aircraft,
+        aircraft.iter().map(|a| (a.folder_name.as_str(), a.has_update)),
+    );
 }
 
 /// Check for plugin updates by fetching remote skunkcrafts_updater.cfg files
 /// This function modifies the plugins list in place, setting latest_version and has_update
-pub async fn check_plugins_updates(plugins: &mut [PluginInfo]) {
-    use futures::future::join_all;
-
+/// Remote versions fetched within the cache TTL are reused unless `force_refresh` is set
+pub async fn check_plugins_updates(
+    xplane_path: &Path,
+    plugins: &mut [PluginInfo],
+    cache: &RemoteVersionCache,
+    force_refresh: bool,
+) {
     // Collect plugins with update URLs
     let update_tasks: Vec<_> = plugins
         .iter()
@@ -912,30 +1654,53 @@ pub async fn check_plugins_updates(plugins: &mut [PluginInfo]) {
         .filter_map(|(idx, p)| p.update_url.as_ref().map(|url| (idx, url.clone())))
         .collect();
 
-    if update_tasks.is_empty() {
-        return;
-    }
-
-    // Fetch all remote configs in parallel
-    let fetch_futures: Vec<_> = update_tasks
-        .iter()
-        .map(|(_, url)| fetch_remote_version(url.clone()))
-        .collect();
-
-    let results = join_all(fetch_futures).await;
+    if !update_tasks.is_empty() {
+        let results = fetch_remote_versions(update_tasks, cache, force_refresh).await;
 
-    // Update plugins with results
-    for ((idx, _), result) in update_tasks.into_iter().zip(results) {
-        if let Some(remote_version) = result {
-            let local_version = plugins[idx].version.as_deref().unwrap_or("");
-            plugins[idx].latest_version = Some(remote_version.clone());
-            plugins[idx].has_update = remote_version != local_version;
+        // Update plugins with results
+        for (idx, result) in results {
+            if let Some(remote_version) = result {
+                let local_version = plugins[idx].version.as_deref().unwrap_or("");
+                plugins[idx].latest_version = Some(remote_version.clone());
+                plugins[idx].has_update = is_newer_version(local_version, &remote_version);
+            }
         }
     }
+
+    record_update_results(
+        xplane_path,
+        |found| &mut found.plugins,
+        plugins.iter().map(|p| (p.folder_name.as_str(), p.has_update)),
+    );
+}
+
+/// Fetch the remote versions of (index, base_url) pairs with at most
+/// MAX_CONCURRENT_UPDATE_FETCHES requests in flight.
+/// Results come back in completion order, each tagged with its original index
+async fn fetch_remote_versions(
+    update_tasks: Vec<(usize, String)>,
+    cache: &RemoteVersionCache,
+    force_refresh: bool,
+) -> Vec<(usize, Option<String>)> {
+    use futures::stream::{self, StreamExt};
+
+    stream::iter(update_tasks)
+        .map(|(idx, url)| async move { (idx, cache.get_or_fetch(url, force_refresh).await) })
+        .buffer_unordered(MAX_CONCURRENT_UPDATE_FETCHES)
+        .collect()
+        .await
 }
 
 /// Fetch remote version from skunkcrafts_updater.cfg
-async fn fetch_remote_version(base_url: String) -> Option<String> {
+/// Timeouts, connection errors and server errors are retried with exponential backoff;
+/// other HTTP errors (e.g. 404) fail immediately.
+/// With a `previous` entry the request is conditional and a 304 reuses its version
+async fn fetch_remote_version(
+    base_url: String,
+    previous: Option<&RemoteVersionEntry>,
+) -> Option<RemoteVersionEntry> {
+    use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+
     let url = format!("{}/skunkcrafts_updater.cfg", base_url.trim_end_matches('/'));
 
     // Build client with system proxy support (reads from Windows system settings)
@@ -944,18 +1709,64 @@ async fn fetch_remote_version(base_url: String) -> Option<String> {
         .build()
         .ok()?;
 
-    let response = client.get(&url).send().await.ok()?;
+    let mut delay = REMOTE_VERSION_RETRY_DELAY;
+    let mut attempt = 1;
+    let (content, etag, last_modified) = loop {
+        let mut request = client.get(&url);
+        if let Some(previous) = previous {
+            if let Some(etag) = &previous.etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &previous.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
 
-    if !response.status().is_success() {
-        logger::log_debug(
-            &format!("Failed to fetch remote config: {} - {}", url, response.status()),
-            Some("management"),
-            None,
-        );
-        return None;
-    }
+        let (error, retryable) = match request.send().await {
+            Ok(response) if response.status() == reqwest::StatusCode::NOT_MODIFIED => {
+                if let Some(previous) = previous {
+                    return Some(previous.clone());
+                }
+                (response.status().to_string(), false)
+            }
+            Ok(response) if response.status().is_success() => {
+                let header = |name: reqwest::header::HeaderName| {
+                    response
+                        .headers()
+                        .get(name)
+                        .and_then(|value| value.to_str().ok())
+                        .map(|value| value.to_string())
+                };
+                let etag = header(ETAG);
+                let last_modified = header(LAST_MODIFIED);
+                match response.text().await {
+                    Ok(content) => break (content, etag, last_modified),
+                    Err(e) => (e.to_string(), is_retryable_request_error(&e)),
+                }
+            }
+            Ok(response) => (
+                response.status().to_string(),
+                is_retryable_status(response.status()),
+            ),
+            Err(e) => (e.to_string(), is_retryable_request_error(&e)),
+        };
 
-    let content = response.text().await.ok()?;
+        if !retryable || attempt >= REMOTE_VERSION_FETCH_ATTEMPTS {
+            logger::log_debug(
+                &format!(
+                    "Failed to fetch remote config after {} attempt(s): {} - {}",
+                    attempt, url, error
+                ),
+                Some("management"),
+                None,
+            );
+            return None;
+        }
+
+        tokio::time::sleep(delay).await;
+        delay *= 2;
+        attempt += 1;
+    };
 
     // Parse version from config
     for line in content.lines() {
@@ -965,7 +1776,11 @@ async fn fetch_remote_version(base_url: String) -> Option<String> {
             if parts.len() == 2 {
                 let version = parts[1].trim();
                 if !version.is_empty() {
-                    return Some(version.to_string());
+                    return Some(RemoteVersionEntry {
+                        version: version.to_string(),
+                        etag,
+                        last_modified,
+                    });
                 }
             }
         }
@@ -974,6 +1789,91 @@ async fn fetch_remote_version(base_url: String) -> Option<String> {
     None
 }
 
+/// Whether a failed request is worth retrying (timeouts, connection failures and
+/// interrupted bodies); request-building errors such as a bad URL are not
+fn is_retryable_request_error(error: &reqwest::Error) -> bool {
+    error.is_timeout() || error.is_connect() || error.is_body()
+}
+
+/// Whether an HTTP error status is transient (server errors and rate limiting)
+fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+    status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+}
+
+/// Inspect a folder and report which parts of an X-Plane installation it contains
+/// Returns an all-false XPlaneInfo when the path is not a directory
+pub fn inspect_xplane_path(path: &Path) -> XPlaneInfo {
+    if !path.is_dir() {
+        return XPlaneInfo::default();
+    }
+
+    let exe_name = if cfg!(target_os = "windows") {
+        "X-Plane.exe"
+    } else if cfg!(target_os = "macos") {
+        "X-Plane.app"
+    } else {
+        "X-Plane"
+    };
+
+    XPlaneInfo {
+        is_valid: path.join(exe_name).exists(),
+        has_custom_scenery: path.join("Custom Scenery").is_dir(),
+        has_aircraft: path.join("Aircraft").is_dir(),
+        has_plugins: path.join("Resources").join("plugins").is_dir(),
+        has_custom_data: path.join("Custom Data").is_dir(),
+        version: read_xplane_version(path),
+    }
+}
+
+/// Read the X-Plane version from Log.txt (written on every launch),
+/// falling back to Resources/version.txt
+fn read_xplane_version(xplane_path: &Path) -> Option<String> {
+    if let Ok(file) = fs::File::open(xplane_path.join("Log.txt")) {
+        let mut first_line = String::new();
+        if BufReader::new(file).read_line(&mut first_line).is_ok() {
+            if let Some(version) = parse_xplane_log_version(&first_line) {
+                return Some(version);
+            }
+        }
+    }
+
+    let content = fs::read_to_string(xplane_path.join("Resources").join("version.txt")).ok()?;
+    let version = content.lines().next()?.trim();
+    (!version.is_empty()).then(|| version.to_string())
+}
+
+/// Detect the installed X-Plane version as (major, minor, patch)
+/// Returns None when the version can't be read, so callers keep their default behavior
+pub fn get_xplane_version(xplane_path: &Path) -> Option<(u32, u32, u32)> {
+    parse_version_triple(&read_xplane_version(xplane_path)?)
+}
+
+/// Parse a version string such as "12.1.1-r1" or "11.55r2" into (major, minor, patch)
+/// Missing minor/patch components default to 0
+fn parse_version_triple(version: &str) -> Option<(u32, u32, u32)> {
+    let numeric: String = version
+        .trim()
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.')
+        .collect();
+    let mut parts = numeric.split('.').map(|p| p.parse::<u32>().ok());
+
+    let major = parts.next()??;
+    let minor = parts.next().flatten().unwrap_or(0);
+    let patch = parts.next().flatten().unwrap_or(0);
+    Some((major, minor, patch))
+}
+
+/// Parse the version from the first Log.txt line,
+/// e.g. "Log.txt for X-Plane 12.1.1-r1 (build 121101 64-bit, Vulkan)"
+fn parse_xplane_log_version(line: &str) -> Option<String> {
+    let (_, rest) = line.split_once("X-Plane ")?;
+    let version = rest.split_whitespace().next()?;
+    version
+        .starts_with(|c: char| c.is_ascii_digit())
+        .then(|| version.to_string())
+}
+
 /// Set the disabled| field in skunkcrafts_updater.cfg for an aircraft or plugin
 /// If the cfg file doesn't exist, returns Ok without creating it
 pub fn set_cfg_disabled(
@@ -1044,3 +1944,468 @@ pub fn set_cfg_disabled(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan_aircraft_info(folder: &Path, base_path: &Path) -> AircraftInfo {
+        scan_single_aircraft_folder(folder, base_path).expect("not an aircraft")
+    }
+
+    #[test]
+    fn test_detect_plugin_platform_from_binary_header() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let folder = temp_dir.path();
+
+        // Misleading names: header magic should win over the filename
+        fs::write(folder.join("plugin_a.xpl"), [0xca, 0xfe, 0xba, 0xbe, 0, 0]).unwrap();
+        assert_eq!(
+            detect_plugin_platform(folder, &["plugin_a.xpl".to_string()]),
+            "mac"
+        );
+
+        fs::write(folder.join("win.xpl"), [0x7f, b'E', b'L', b'F', 2, 1]).unwrap();
+        assert_eq!(detect_plugin_platform(folder, &["win.xpl".to_string()]), "lin");
+
+        fs::write(folder.join("plugin_b.xpl"), b"MZ\x90\x00").unwrap();
+        assert_eq!(
+            detect_plugin_platform(
+                folder,
+                &["plugin_a.xpl".to_string(), "plugin_b.xpl".to_string()]
+            ),
+            "multi"
+        );
+    }
+
+    #[test]
+    fn test_overview_counts() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+
+        let aircraft = root.join("Aircraft");
+        let c172 = aircraft.join("Laminar Research").join("C172");
+        fs::create_dir_all(&c172).unwrap();
+        fs::write(c172.join("c172.acf"), "").unwrap();
+        fs::create_dir_all(aircraft.join("A320")).unwrap();
+        fs::write(aircraft.join("A320").join("a320.xfma"), "").unwrap();
+        fs::create_dir_all(aircraft.join("Empty")).unwrap();
+
+        let plugins = root.join("Resources").join("plugins");
+        fs::create_dir_all(plugins.join("XPUIPC")).unwrap();
+        fs::create_dir_all(plugins.join(".hidden")).unwrap();
+        fs::write(plugins.join("readme.txt"), "").unwrap();
+
+        let custom_data = root.join("Custom Data");
+        fs::create_dir_all(custom_data.join("GNS430")).unwrap();
+        fs::write(custom_data.join("cycle.json"), "{}").unwrap();
+        fs::write(custom_data.join("GNS430").join("cycle.json"), "{}").unwrap();
+
+        assert_eq!(count_aircraft(root), 2);
+        assert_eq!(count_plugins(root), 1);
+        assert_eq!(count_navdata(root), 2);
+
+        // Update results are kept per installation and merged across partial checks
+        record_update_results(root, |found| &mut found.aircraft, [("A320", true)].into_iter());
+        record_update_results(
+            root,
+            |found| &mut found.aircraft,
+            [("Laminar Research/C172", true)].into_iter(),
+        );
+        record_update_results(root, |found| &mut found.plugins, [("XPUIPC", true)].into_iter());
+        assert_eq!(count_found_updates(root), 3);
+        record_update_results(root, |found| &mut found.aircraft, [("A320", false)].into_iter());
+        assert_eq!(count_found_updates(root), 2);
+        // Uninstalled items no longer count
+        fs::remove_dir_all(plugins.join("XPUIPC")).unwrap();
+        assert_eq!(count_found_updates(root), 1);
+
+        let missing = tempfile::tempdir().unwrap();
+        assert_eq!(count_found_updates(missing.path()), 0);
+        assert_eq!(count_aircraft(missing.path()), 0);
+        assert_eq!(count_plugins(missing.path()), 0);
+        assert_eq!(count_navdata(missing.path()), 0);
+    }
+
+    #[test]
+    fn test_find_duplicate_aircraft() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let aircraft = temp_dir.path().join("Aircraft");
+
+        for (folder, files) in [
+            ("C172", &["c172.acf"][..]),
+            ("C172_old", &["c172.xfma"][..]),
+            ("C182", &["c182.acf"][..]),
+            ("FF777", &["777-200ER.acf", "777-200F.acf"][..]),
+            ("FF777 freighter", &["777-200F.acf"][..]),
+        ] {
+            fs::create_dir_all(aircraft.join(folder)).unwrap();
+            for file in files {
+                fs::write(aircraft.join(folder).join(file), "").unwrap();
+            }
+        }
+
+        let duplicates = find_duplicate_aircraft(temp_dir.path(), &WalkMonitor::silent()).unwrap();
+        assert_eq!(
+            duplicates,
+            vec![
+                vec!["C172".to_string(), "C172_old".to_string()],
+                vec!["FF777".to_string(), "FF777 freighter".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_toggle_navdata() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let provider = temp_dir.path().join("Custom Data").join("Navigraph");
+        fs::create_dir_all(&provider).unwrap();
+        fs::write(
+            provider.join("Cycle.json"),
+            r#"{"name": "Navigraph", "cycle": "2401"}"#,
+        )
+        .unwrap();
+
+        assert!(!toggle_management_item(temp_dir.path(), "navdata", "Navigraph", None).unwrap());
+        assert!(provider.join(DISABLED_CYCLE_JSON).exists());
+
+        // Disabled entries are still found by the scan
+        let data = scan_navdata(temp_dir.path(), &WalkMonitor::silent()).unwrap();
+        assert_eq!(data.total_count, 1);
+        assert_eq!(data.enabled_count, 0);
+        assert_eq!(data.entries[0].cycle.as_deref(), Some("2401"));
+        assert_eq!(count_navdata(temp_dir.path()), 1);
+
+        assert!(toggle_management_item(temp_dir.path(), "navdata", "Navigraph", None).unwrap());
+        assert!(provider.join(CYCLE_JSON).exists());
+        let data = scan_navdata(temp_dir.path(), &WalkMonitor::silent()).unwrap();
+        assert_eq!(data.enabled_count, 1);
+    }
+
+    #[test]
+    fn test_get_install_marker() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let aircraft = temp_dir.path().join("Aircraft");
+        fs::create_dir_all(aircraft.join("Manual")).unwrap();
+        fs::create_dir_all(aircraft.join("Installed")).unwrap();
+        let written = InstallMarker {
+            source: "A320.zip".to_string(),
+            installed_at: "2024-05-01T10:00:00+00:00".to_string(),
+            strategy: "clean".to_string(),
+            version: None,
+        };
+        crate::install_marker::write_install_marker(&aircraft.join("Installed"), &written).unwrap();
+
+        let marker = get_install_marker(temp_dir.path(), "aircraft", "Installed")
+            .unwrap()
+            .unwrap();
+        assert_eq!(marker.source, "A320.zip");
+        assert_eq!(marker.strategy, "clean");
+        assert!(get_install_marker(temp_dir.path(), "aircraft", "Manual")
+            .unwrap()
+            .is_none());
+        assert!(get_install_marker(temp_dir.path(), "aircraft", "Missing").is_err());
+    }
+
+    #[test]
+    fn test_delete_management_items() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let aircraft = temp_dir.path().join("Aircraft");
+        let plugins = temp_dir.path().join("Resources").join("plugins");
+        fs::create_dir_all(aircraft.join("Old A320")).unwrap();
+        fs::create_dir_all(plugins.join("OldPlugin")).unwrap();
+        fs::create_dir_all(temp_dir.path().join("Outside")).unwrap();
+
+        let items = vec![
+            ("aircraft".to_string(), "Old A320".to_string()),
+            ("plugin".to_string(), "OldPlugin".to_string()),
+            ("plugin".to_string(), "../../Outside".to_string()),
+            ("aircraft".to_string(), "Missing".to_string()),
+            ("scenery".to_string(), "Anything".to_string()),
+        ];
+        let summary = delete_management_items(temp_dir.path(), &items);
+
+        assert_eq!(summary.deleted, 2);
+        assert_eq!(summary.failed, 3);
+        assert!(!aircraft.join("Old A320").exists());
+        assert!(!plugins.join("OldPlugin").exists());
+        // A crafted folder name must not escape the plugins folder
+        assert!(temp_dir.path().join("Outside").exists());
+        assert_eq!(
+            summary.results[2].error_message.as_deref(),
+            Some("Invalid path")
+        );
+        assert!(summary.results.iter().skip(2).all(|r| !r.success));
+    }
+
+    #[test]
+    fn test_toggle_plugin_single_platform() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let plugin = temp_dir
+            .path()
+            .join("Resources")
+            .join("plugins")
+            .join("XPUIPC");
+        let toggle =
+            |platform| toggle_management_item(temp_dir.path(), "plugin", "XPUIPC", platform);
+        fs::create_dir_all(plugin.join("lin_x64")).unwrap();
+        fs::write(plugin.join("win.xpl"), b"MZ\x90\x00").unwrap();
+        fs::write(plugin.join("mac.xpl"), [0xcf, 0xfa, 0xed, 0xfe]).unwrap();
+        // No recognizable header: the platform comes from the subfolder
+        fs::write(plugin.join("lin_x64").join("XPUIPC.xpl"), b"").unwrap();
+
+        assert!(!toggle(Some("win")).unwrap());
+        assert!(plugin.join("win.xfmp").exists());
+        assert!(plugin.join("mac.xpl").exists());
+        assert!(plugin.join("lin_x64").join("XPUIPC.xpl").exists());
+
+        assert!(!toggle(Some("lin")).unwrap());
+        assert!(plugin.join("lin_x64").join("XPUIPC.xfmp").exists());
+
+        assert!(toggle(Some("win")).unwrap());
+        assert!(plugin.join("win.xpl").exists());
+        assert!(toggle(Some("bsd")).is_err());
+    }
+
+    #[test]
+    fn test_scan_aircraft_nested_folders() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let aircraft = temp_dir.path().join("Aircraft");
+        for (folder, acf) in [
+            ("Boeing/737", "737.acf"),
+            // Inside an aircraft folder: not a separate aircraft
+            ("Boeing/737/variants/800", "800.acf"),
+            ("Laminar/Extra/Old/Cessna", "c172.xfma"),
+            ("A/B/C/D/TooDeep", "deep.acf"),
+            (".hidden/Secret", "secret.acf"),
+        ] {
+            fs::create_dir_all(aircraft.join(folder)).unwrap();
+            fs::write(aircraft.join(folder).join(acf), "").unwrap();
+        }
+
+        let data = scan_aircraft(temp_dir.path(), &WalkMonitor::silent()).unwrap();
+        let mut folders: Vec<String> = data
+            .entries
+            .iter()
+            .map(|a| a.folder_name.replace('\\', "/"))
+            .collect();
+        folders.sort();
+        assert_eq!(folders, vec!["Boeing/737", "Laminar/Extra/Old/Cessna"]);
+        assert_eq!(data.enabled_count, 1);
+    }
+
+    #[test]
+    fn test_list_aircraft_without_version() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let aircraft = temp_dir.path().join("Aircraft");
+
+        for folder in ["Versioned", "Tracked", "Unknown"] {
+            fs::create_dir_all(aircraft.join(folder)).unwrap();
+            fs::write(
+                aircraft.join(folder).join("plane.acf"),
+                "I\n1100 Version\nACF\n",
+            )
+            .unwrap();
+        }
+        fs::write(aircraft.join("Versioned").join("version.txt"), "v1.2.3\n").unwrap();
+        fs::write(
+            aircraft.join("Tracked").join("skunkcrafts_updater.cfg"),
+            "module|https://example.com/tracked\nversion|2.0\n",
+        )
+        .unwrap();
+
+        let folders =
+            list_aircraft_without_version(temp_dir.path(), &WalkMonitor::silent()).unwrap();
+        assert_eq!(folders, vec!["Unknown".to_string()]);
+    }
+
+    #[test]
+    fn test_detect_plugin_platform_falls_back_to_filename() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let folder = temp_dir.path();
+
+        // Unreadable/unknown header falls back to the filename heuristic
+        fs::write(folder.join("win.xpl"), b"").unwrap();
+        assert_eq!(detect_plugin_platform(folder, &["win.xpl".to_string()]), "win");
+    }
+
+    #[test]
+    fn test_disable_and_enable_liveries() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let base = temp_dir.path().join("Aircraft");
+        let aircraft = base.join("C172");
+        fs::create_dir_all(aircraft.join("Liveries").join("Red")).unwrap();
+        fs::create_dir_all(aircraft.join("Liveries").join("Blue")).unwrap();
+        fs::write(aircraft.join("c172.acf"), "").unwrap();
+
+        disable_liveries(temp_dir.path(), "C172").unwrap();
+        assert!(aircraft.join(DISABLED_LIVERIES_FOLDER).is_dir());
+        let info = scan_aircraft_info(&aircraft, &base);
+        assert!(info.enabled);
+        assert!(info.liveries_disabled);
+        assert!(!info.has_liveries);
+        assert_eq!(info.livery_count, 0);
+        assert!(disable_liveries(temp_dir.path(), "C172").is_err());
+
+        enable_liveries(temp_dir.path(), "C172").unwrap();
+        let info = scan_aircraft_info(&aircraft, &base);
+        assert!(!info.liveries_disabled);
+        assert_eq!(info.livery_count, 2);
+
+        // Paths outside Aircraft are rejected
+        let outside = temp_dir.path().join("Outside");
+        fs::create_dir_all(outside.join(LIVERIES_FOLDER)).unwrap();
+        assert!(disable_liveries(temp_dir.path(), "../Outside").is_err());
+        assert!(disable_liveries(temp_dir.path(), &outside.to_string_lossy()).is_err());
+        assert!(outside.join(LIVERIES_FOLDER).is_dir());
+    }
+
+    #[test]
+    fn test_toggle_aircraft_mixed_state() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let base = temp_dir.path().join("Aircraft");
+        let aircraft = base.join("B738");
+        fs::create_dir_all(&aircraft).unwrap();
+        fs::write(aircraft.join("b738.acf"), "").unwrap();
+        fs::write(aircraft.join("b738_cargo.xfma"), "").unwrap();
+
+        let info = scan_aircraft_info(&aircraft, &base);
+        assert!(info.enabled);
+        assert_eq!(info.acf_file, "b738.acf");
+        assert_eq!(info.acf_files, vec!["b738.acf"]);
+        assert_eq!(info.xfma_files, vec!["b738_cargo.xfma"]);
+
+        // Mixed state is enabled, so toggling disables it
+        assert!(!toggle_aircraft_files(&aircraft, "B738").unwrap());
+        assert!(aircraft.join("b738.xfma").exists());
+        assert!(aircraft.join("b738_cargo.xfma").exists());
+        assert!(!aircraft.join("b738.acf").exists());
+
+        // Enabling renames every .xfma back to .acf
+        assert!(toggle_aircraft_files(&aircraft, "B738").unwrap());
+        assert!(aircraft.join("b738.acf").exists());
+        assert!(aircraft.join("b738_cargo.acf").exists());
+
+        // No file is renamed when any target already exists
+        fs::write(aircraft.join("b738_cargo.xfma"), "old").unwrap();
+        assert!(toggle_aircraft_files(&aircraft, "B738").is_err());
+        assert!(aircraft.join("b738.acf").exists());
+        assert_eq!(
+            fs::read_to_string(aircraft.join("b738_cargo.xfma")).unwrap(),
+            "old"
+        );
+    }
+
+    #[test]
+    fn test_inspect_xplane_path() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+
+        // Not an X-Plane folder
+        let info = inspect_xplane_path(root);
+        assert!(!info.is_valid);
+        assert!(!info.has_custom_scenery);
+        assert!(inspect_xplane_path(&root.join("missing")).version.is_none());
+
+        fs::create_dir_all(root.join("Custom Scenery")).unwrap();
+        fs::create_dir_all(root.join("Resources").join("plugins")).unwrap();
+        fs::write(
+            root.join("Log.txt"),
+            "Log.txt for X-Plane 12.1.1-r1 (build 121101 64-bit, Vulkan)\nmore\n",
+        )
+        .unwrap();
+
+        let info = inspect_xplane_path(root);
+        assert!(info.has_custom_scenery);
+        assert!(info.has_plugins);
+        assert!(!info.has_aircraft);
+        assert!(!info.has_custom_data);
+        assert_eq!(info.version.as_deref(), Some("12.1.1-r1"));
+        assert_eq!(parse_xplane_log_version("Log.txt for X-Plane"), None);
+        assert_eq!(get_xplane_version(root), Some((12, 1, 1)));
+    }
+
+    #[test]
+    fn test_parse_version_triple() {
+        assert_eq!(parse_version_triple("12.1.1-r1"), Some((12, 1, 1)));
+        assert_eq!(parse_version_triple("11.55r2"), Some((11, 55, 0)));
+        assert_eq!(parse_version_triple("12"), Some((12, 0, 0)));
+        assert_eq!(parse_version_triple("beta"), None);
+        assert_eq!(parse_version_triple(""), None);
+    }
+
+    #[test]
+    fn test_remote_version_cache() {
+        let cache = RemoteVersionCache::new();
+        assert_eq!(cache.get("https://example.com/a330"), None);
+
+        cache.insert("https://example.com/a330/", "1.2.0");
+        assert_eq!(
+            cache.get("https://example.com/a330").as_deref(),
+            Some("1.2.0")
+        );
+
+        // Expired entries are ignored
+        let expired = RemoteVersionCache::with_ttl(Duration::ZERO);
+        expired.insert("https://example.com/a330", "1.2.0");
+        assert_eq!(expired.get("https://example.com/a330"), None);
+
+        // Validators outlive the TTL so the next fetch can be conditional
+        let entry = RemoteVersionEntry {
+            version: "1.3.0".to_string(),
+            etag: Some("\"abc123\"".to_string()),
+            last_modified: None,
+        };
+        expired.insert_entry("https://example.com/a330/", &entry);
+        assert_eq!(expired.get("https://example.com/a330"), None);
+        assert_eq!(
+            expired.get_validated("https://example.com/a330"),
+            Some(entry)
+        );
+
+        // A response without validators drops the stale ones
+        expired.insert_entry(
+            "https://example.com/a330",
+            &RemoteVersionEntry {
+                version: "1.4.0".to_string(),
+                etag: None,
+                last_modified: None,
+            },
+        );
+        assert_eq!(expired.get_validated("https://example.com/a330"), None);
+    }
+
+    #[test]
+    fn test_is_retryable_status() {
+        use reqwest::StatusCode;
+
+        assert!(is_retryable_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(is_retryable_status(StatusCode::BAD_GATEWAY));
+        assert!(is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(!is_retryable_status(StatusCode::NOT_FOUND));
+        assert!(!is_retryable_status(StatusCode::FORBIDDEN));
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        use std::cmp::Ordering;
+
+        assert_eq!(compare_versions("2.10", "2.9"), Some(Ordering::Greater));
+        assert_eq!(compare_versions("1.9", "1.10"), Some(Ordering::Less));
+        assert_eq!(compare_versions("1.10b", "1.9b"), Some(Ordering::Greater));
+        assert_eq!(compare_versions("1.2.1", "1.2b"), None);
+        assert_eq!(compare_versions("2.3.10 ", "2.3.10"), Some(Ordering::Equal));
+        assert_eq!(compare_versions("2.3", "2.3.0"), Some(Ordering::Equal));
+        assert_eq!(compare_versions("v1.2", "1.3"), Some(Ordering::Less));
+        assert_eq!(compare_versions("020310", "2.3.9"), Some(Ordering::Greater));
+        assert_eq!(compare_versions("1.2b", "1.2"), None);
+
+        assert!(is_newer_version("2.9", "2.10"));
+        assert!(!is_newer_version("2.3.10", "2.3.10 "));
+        assert!(!is_newer_version("2.4", "2.3"));
+        assert!(is_newer_version("1.2b", "1.2c"));
+        assert!(is_newer_version("1.9", "1.10"));
+        assert!(!is_newer_version("1.10b", "1.9c"));
+        assert!(!is_newer_version("1.2b", " 1.2b"));
+    }
+}
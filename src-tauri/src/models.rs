@@ -115,6 +115,23 @@ pub struct InstallTask {
     pub backup_config_files: bool,
     /// Glob patterns for config files to backup (Aircraft only)
     pub config_file_patterns: Vec<String>,
+    /// Whether config file patterns are also matched in subfolders (bounded depth),
+    /// e.g. "Settings" or "avionics" (Aircraft only). Files keep their relative path
+    #[serde(default)]
+    pub config_file_recursive: bool,
+    /// Glob patterns (relative to the package root) for files kept across
+    /// clean installs (Scenery only), e.g. per-user settings shipped inside the package
+    #[serde(default)]
+    pub scenery_preserve_patterns: Vec<String>,
+    /// Sort position to move the package to once it has been added to the scenery
+    /// index (Scenery only, applied when scenery auto-sort is enabled)
+    /// None keeps the default position of its category
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub desired_sort_order: Option<u32>,
+    /// Create a directory symlink from Custom Scenery/<name> to the source folder
+    /// instead of copying it (Scenery only, source must be an extracted folder)
+    #[serde(default)]
+    pub install_as_symlink: bool,
     /// File hashes collected during scanning (for verification)
     /// Key: relative path within addon, Value: FileHash
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -134,6 +151,7 @@ pub struct InstallTask {
 #[serde(rename_all = "camelCase")]
 pub struct AnalysisResult {
     pub tasks: Vec<InstallTask>,
+    /// Errors not tied to a single input path; per-path failures are in path_errors
     pub errors: Vec<String>,
     /// List of archive paths that require a password
     #[serde(default)]
@@ -142,6 +160,18 @@ pub struct AnalysisResult {
     /// Key format: "parent.zip/nested.zip", Value: "parent.zip"
     #[serde(default)]
     pub nested_password_required: HashMap<String, String>,
+    /// Input paths that could not be analyzed and why
+    /// Other inputs are still analyzed; their tasks are in `tasks`
+    #[serde(default)]
+    pub path_errors: Vec<AnalysisPathError>,
+}
+
+/// An input path that failed during analysis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalysisPathError {
+    pub path: String,
+    pub message: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -213,6 +243,36 @@ pub enum InstallPhase {
     Finalizing,
 }
 
+/// Provenance marker written into packages installed by this tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallMarker {
+    /// Archive or folder the package was installed from
+    pub source: String,
+    /// Installation time (RFC 3339)
+    pub installed_at: String,
+    /// How the target was written: "fresh", "clean" or "overwrite"
+    pub strategy: String,
+    /// Package version (or navdata cycle) if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+/// Progress event emitted while walking a whole library (scenery index rebuilds,
+/// aircraft / plugin / navdata scans)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalkProgress {
+    /// Number of folders visited so far
+    pub scanned: u64,
+    /// Folder currently being processed
+    pub current_path: String,
+    /// Number of folders the walk will visit, when known up front
+    /// (scenery index rebuilds / updates)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<u64>,
+}
+
 /// Result of a single task installation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -324,6 +384,18 @@ impl SceneryCategory {
     }
 }
 
+/// A scenery category with its sorting priority, for rendering category headers
+/// The frontend localizes headers by category key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryBand {
+    pub category: SceneryCategory,
+    /// Sorting priority (lower = higher in scenery_packs.ini)
+    pub priority: u8,
+    /// *GLOBAL_AIRPORTS* is written right before this band
+    pub global_airports_before: bool,
+}
+
 /// Information about a classified scenery package
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -362,6 +434,75 @@ pub struct SceneryPackageInfo {
     /// instead of "Custom Scenery/{folder_name}/". Contains the resolved target path.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub actual_path: Option<String>,
+    /// Airport ICAO code read from apt.dat (packages with apt.dat only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icao_code: Option<String>,
+    /// Airport name from the apt.dat header row
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub airport_name: Option<String>,
+    /// Airport elevation in feet from the apt.dat header row
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub airport_elevation_ft: Option<i32>,
+    /// Category was chosen by the user; re-classification keeps category and sub_priority
+    #[serde(default)]
+    pub category_locked: bool,
+    /// The apt.dat header ICAO and its `1302 icao_code` row disagree (author error)
+    #[serde(default)]
+    pub icao_mismatch: bool,
+    /// Provenance marker read when the package was indexed, if installed by this tool
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub install_marker: Option<InstallMarker>,
+}
+
+impl SceneryPackageInfo {
+    /// Whether the package looks like a broken or partial install: it was classified as
+    /// Mesh only because an Earth nav data folder exists, but contains no DSF or apt.dat
+    pub fn is_incomplete(&self) -> bool {
+        self.category == SceneryCategory::Mesh && !self.has_dsf && !self.has_apt_dat
+    }
+
+    /// Whether the package is an orthophoto pack (a Mesh package matched by folder
+    /// prefix and texture count, see `scenery_classifier::is_orthophoto_package`)
+    pub fn is_orthophoto(&self) -> bool {
+        self.category == SceneryCategory::Mesh
+            && crate::scenery_classifier::is_orthophoto_package(&self.folder_name, self.texture_count)
+    }
+}
+
+/// Minimal enabled-or-disabled package for tests; set other fields with struct-update syntax
+#[cfg(test)]
+pub(crate) fn test_package(
+    name: &str,
+    category: SceneryCategory,
+    sort_order: u32,
+    enabled: bool,
+) -> SceneryPackageInfo {
+    SceneryPackageInfo {
+        folder_name: name.to_string(),
+        category,
+        sub_priority: 0,
+        last_modified: SystemTime::now(),
+        indexed_at: SystemTime::now(),
+        has_apt_dat: false,
+        has_dsf: false,
+        has_library_txt: false,
+        has_textures: false,
+        has_objects: false,
+        texture_count: 0,
+        earth_nav_tile_count: 0,
+        enabled,
+        sort_order,
+        required_libraries: vec![],
+        missing_libraries: vec![],
+        exported_library_names: vec![],
+        actual_path: None,
+        icao_code: None,
+        airport_name: None,
+        airport_elevation_ft: None,
+        category_locked: false,
+        icao_mismatch: false,
+        install_marker: None,
+    }
 }
 
 /// DSF file header information
@@ -373,8 +514,25 @@ pub struct DsfHeader {
     pub terrain_references: Vec<String>,
 }
 
+/// DSF header summary for diagnosing classification issues
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DsfInfo {
+    /// DSF file that was read, relative to the scenery package
+    pub dsf_path: String,
+    pub is_overlay: bool,
+    pub creation_agent: Option<String>,
+    pub object_reference_count: usize,
+    pub terrain_reference_count: usize,
+    /// First few object references, in DSF order
+    pub sample_object_references: Vec<String>,
+    /// First few terrain references, in DSF order
+    pub sample_terrain_references: Vec<String>,
+}
+
 /// Entry in scenery_packs.ini
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SceneryPackEntry {
     /// true = SCENERY_PACK, false = SCENERY_PACK_DISABLED
     pub enabled: bool,
@@ -384,6 +542,83 @@ pub struct SceneryPackEntry {
     pub is_global_airports: bool,
 }
 
+/// A scenery_packs.ini backup in Custom Scenery
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneryPacksBackup {
+    pub path: String,
+    pub file_name: String,
+    /// Backup time parsed from the file name (RFC 3339)
+    pub created_at: String,
+}
+
+/// How one scenery_packs.ini entry would change if the ini were re-sorted from the index
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SortPreviewChange {
+    pub path: String,
+    pub is_global_airports: bool,
+    /// 0-based position in the current ini (None if the entry isn't in it)
+    pub old_position: Option<usize>,
+    /// 0-based position after sorting (None if sorting would drop the entry)
+    pub new_position: Option<usize>,
+    pub old_enabled: Option<bool>,
+    pub new_enabled: Option<bool>,
+}
+
+/// Result of reconciling scenery_packs.ini with the scenery index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IniReconcileResult {
+    /// Index packages that were missing from the ini and have been added
+    pub added: usize,
+    /// Ini entries without an index package that have been removed
+    pub removed: usize,
+}
+
+/// Details about an existing scenery folder that an install would replace
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneryConflictInfo {
+    pub folder_name: String,
+    /// Category of the existing package from the scenery index (None if not indexed)
+    pub existing_category: Option<SceneryCategory>,
+    /// Version read from the existing folder's version files, if any
+    pub existing_version: Option<String>,
+}
+
+/// Kind of formatting problem found in scenery_packs.ini
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum IniLintKind {
+    /// The file doesn't end with a newline (the last entry may be mis-read)
+    MissingTrailingNewline,
+    /// The file mixes CRLF and LF line endings
+    MixedLineEndings,
+    /// A line uses tab characters instead of a single space separator
+    TabCharacter,
+}
+
+/// A formatting problem found in scenery_packs.ini
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IniLintFinding {
+    pub kind: IniLintKind,
+    /// 1-based line number, if the finding refers to a specific line
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+/// scenery_packs.ini entry whose folder name differs in case from the folder on disk
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct IniCaseMismatch {
+    /// Path as written in scenery_packs.ini
+    pub ini_path: String,
+    /// Actual folder name in Custom Scenery
+    pub actual_folder: String,
+}
+
 /// Persistent index of scenery classifications
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SceneryIndex {
@@ -403,6 +638,16 @@ pub struct SceneryIndexStats {
     pub last_updated: SystemTime,
 }
 
+/// Result of compacting the scenery database
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseVacuumResult {
+    /// Database size in bytes before VACUUM (including the WAL file)
+    pub size_before: u64,
+    /// Database size in bytes after VACUUM
+    pub size_after: u64,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SceneryIndexStatus {
@@ -430,6 +675,33 @@ pub struct SceneryManagerEntry {
     pub sort_order: u32,
     pub missing_libraries: Vec<String>,
     pub required_libraries: Vec<String>,
+    /// Content is generated on the fly (e.g., AutoOrtho), so it is skipped by scans
+    #[serde(default)]
+    pub is_dynamic: bool,
+    /// Has an Earth nav data folder but no DSF or apt.dat (likely a broken download)
+    #[serde(default)]
+    pub is_incomplete: bool,
+    /// Mesh package detected as an orthophoto pack by folder name and texture count
+    #[serde(default)]
+    pub is_orthophoto: bool,
+    /// Provenance marker if the package was installed by this tool
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub install_marker: Option<InstallMarker>,
+    /// Airport ICAO code (packages with apt.dat only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icao_code: Option<String>,
+    /// Airport name (packages with apt.dat only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub airport_name: Option<String>,
+    /// Airport elevation in feet (packages with apt.dat only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub airport_elevation_ft: Option<i32>,
+    /// Category was chosen by the user and is kept across re-indexing
+    #[serde(default)]
+    pub category_locked: bool,
+    /// The apt.dat header ICAO and its `1302 icao_code` row disagree
+    #[serde(default)]
+    pub icao_mismatch: bool,
 }
 
 /// Simplified entry for batch updates (only fields that can be changed)
@@ -441,6 +713,102 @@ pub struct SceneryEntryUpdate {
     pub sort_order: u32,
 }
 
+/// Editable state of a scenery package, as recorded in the audit log
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneryAuditState {
+    pub enabled: bool,
+    pub sort_order: u32,
+    pub category: SceneryCategory,
+    #[serde(default)]
+    pub category_locked: bool,
+}
+
+/// State of one package before and after an audited operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneryAuditChange {
+    pub folder_name: String,
+    pub before: SceneryAuditState,
+    pub after: SceneryAuditState,
+}
+
+/// One audited scenery manager operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneryAuditEntry {
+    /// Time of the operation (RFC 3339)
+    pub timestamp: String,
+    /// X-Plane installation the operation was made on
+    #[serde(default)]
+    pub xplane_path: String,
+    /// Operation name, e.g. "move_entry", "update_entry", "batch_update_entries"
+    pub op: String,
+    pub changes: Vec<SceneryAuditChange>,
+}
+
+/// Shortcut entry whose target no longer matches the path stored in the index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MovedShortcutTarget {
+    pub folder_name: String,
+    /// actual_path stored in the index (and written to scenery_packs.ini)
+    pub stored_path: String,
+    /// Where the shortcut points now; None if it can't be resolved anymore
+    pub resolved_path: Option<String>,
+}
+
+/// Package whose category differs between two scenery index exports
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexCategoryDiff {
+    pub folder_name: String,
+    pub category_a: SceneryCategory,
+    pub category_b: SceneryCategory,
+}
+
+/// Package whose position differs between two scenery index exports
+/// Positions are 0-based and only count packages present in both exports
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexOrderDiff {
+    pub folder_name: String,
+    pub position_a: usize,
+    pub position_b: usize,
+}
+
+/// Differences between two scenery index JSON exports (A and B)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexExportDiff {
+    pub only_in_a: Vec<String>,
+    pub only_in_b: Vec<String>,
+    pub category_changes: Vec<IndexCategoryDiff>,
+    pub order_changes: Vec<IndexOrderDiff>,
+}
+
+/// Result of importing a scenery index JSON export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneryIndexImportResult {
+    /// Number of packages written to the index
+    pub imported: usize,
+    /// Packages in the export whose folders no longer exist
+    pub skipped: Vec<String>,
+    /// Local packages missing from the export, kept as they were
+    pub kept: usize,
+}
+
+/// DSF tiles covered by a scenery package, for the coverage map
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TileCoverage {
+    pub folder_name: String,
+    pub category: SceneryCategory,
+    /// (latitude, longitude) of each covered tile's south-west corner
+    pub tiles: Vec<(i32, i32)>,
+}
+
 /// Data for scenery manager UI
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -451,6 +819,90 @@ pub struct SceneryManagerData {
     pub missing_deps_count: usize,
     /// Whether the index differs from the ini file and needs to be synced
     pub needs_sync: bool,
+    /// What differs, when needs_sync is set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sync_divergence: Option<IniSyncDivergence>,
+}
+
+/// How scenery_packs.ini differs from the scenery index
+/// Ini entries without an index package (added by hand) are not reported
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IniSyncDivergence {
+    /// scenery_packs.ini doesn't exist
+    pub ini_missing: bool,
+    /// Index packages not listed in the ini
+    pub missing_from_ini: Vec<String>,
+    /// Index packages listed more than once in the ini
+    pub duplicated_in_ini: Vec<String>,
+    /// Packages whose ini enabled state differs from the index
+    pub enabled_mismatches: Vec<String>,
+    /// Packages that have to move for the ini to follow the index sort order
+    pub out_of_order: Vec<String>,
+}
+
+impl IniSyncDivergence {
+    pub fn is_synced(&self) -> bool {
+        !self.ini_missing
+            && self.missing_from_ini.is_empty()
+            && self.duplicated_in_ini.is_empty()
+            && self.enabled_mismatches.is_empty()
+            && self.out_of_order.is_empty()
+    }
+}
+
+/// User-configurable options for scenery category ordering
+/// Stored in the index metadata; every option defaults to the built-in behavior
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SceneryOrderingConfig {
+    /// Order Mesh (orthophoto) packages by covered tile region instead of tile count and name
+    pub sort_mesh_by_region: bool,
+    /// Extra folder-name glob patterns (case-insensitive) for library-only packages
+    /// that are pinned to FixedHighPriority alongside SAM
+    pub high_priority_patterns: Vec<String>,
+    /// *GLOBAL_AIRPORTS* is inserted before the first package of this category band
+    /// (or any lower-priority band). Defaults to DefaultAirport, i.e. after custom airports
+    pub global_airports_before: SceneryCategory,
+    /// Write all disabled entries after the enabled ones (each group keeps its sort order)
+    /// instead of leaving them interleaved at their sort positions
+    pub disabled_at_bottom: bool,
+}
+
+impl SceneryOrderingConfig {
+    /// All categories as priority bands under this config, highest priority first
+    pub fn category_bands(&self) -> Vec<CategoryBand> {
+        let mut bands: Vec<CategoryBand> = [
+            SceneryCategory::FixedHighPriority,
+            SceneryCategory::Airport,
+            SceneryCategory::DefaultAirport,
+            SceneryCategory::Library,
+            SceneryCategory::Overlay,
+            SceneryCategory::AirportMesh,
+            SceneryCategory::Mesh,
+            SceneryCategory::Other,
+        ]
+        .into_iter()
+        .map(|category| CategoryBand {
+            priority: category.priority(),
+            global_airports_before: category == self.global_airports_before,
+            category,
+        })
+        .collect();
+        bands.sort_by_key(|band| band.priority);
+        bands
+    }
+}
+
+impl Default for SceneryOrderingConfig {
+    fn default() -> Self {
+        Self {
+            sort_mesh_by_region: false,
+            high_priority_patterns: Vec::new(),
+            global_airports_before: SceneryCategory::DefaultAirport,
+            disabled_at_bottom: false,
+        }
+    }
 }
 
 // ========== Management Data Structures ==========
@@ -465,6 +917,8 @@ pub struct AircraftInfo {
     pub enabled: bool,
     pub has_liveries: bool,
     pub livery_count: usize,
+    /// Installed version; None if the aircraft has no version source
+    /// (no skunkcrafts_updater.cfg version and no readable version file)
     pub version: Option<String>,
     /// URL for checking updates (from skunkcrafts_updater.cfg module| field)
     pub update_url: Option<String>,
@@ -474,6 +928,16 @@ pub struct AircraftInfo {
     pub has_update: bool,
     /// Whether disabled in skunkcrafts_updater.cfg (disabled|true)
     pub cfg_disabled: Option<bool>,
+    /// Whether the liveries folder has been moved aside (liveries.disabled)
+    #[serde(default)]
+    pub liveries_disabled: bool,
+    /// Names of the .acf files in the aircraft folder
+    #[serde(default)]
+    pub acf_files: Vec<String>,
+    /// Names of the .xfma (disabled .acf) files in the aircraft folder
+    /// When both lists are non-empty the enabled state is ambiguous; it is reported as enabled
+    #[serde(default)]
+    pub xfma_files: Vec<String>,
 }
 
 /// Plugin information for management UI
@@ -507,6 +971,21 @@ pub struct NavdataManagerInfo {
     pub enabled: bool,
 }
 
+/// What was found at a candidate X-Plane installation path
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct XPlaneInfo {
+    /// Whether the path contains the X-Plane executable
+    pub is_valid: bool,
+    pub has_custom_scenery: bool,
+    pub has_aircraft: bool,
+    /// Resources/plugins exists
+    pub has_plugins: bool,
+    pub has_custom_data: bool,
+    /// X-Plane version (e.g., "12.1.1-r1"), read from Log.txt or Resources/version.txt
+    pub version: Option<String>,
+}
+
 /// Management data for UI
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -516,6 +995,39 @@ pub struct ManagementData<T> {
     pub enabled_count: usize,
 }
 
+/// Outcome of deleting one management item in a batch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManagementDeleteOutcome {
+    pub item_type: String,
+    pub folder_name: String,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_message: Option<String>,
+}
+
+/// Summary of a batch delete of management items
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManagementDeleteSummary {
+    pub deleted: usize,
+    pub failed: usize,
+    /// Per-item outcomes, in request order
+    pub results: Vec<ManagementDeleteOutcome>,
+}
+
+/// Add-on totals across all managers for the home screen
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Overview {
+    pub scenery: SceneryIndexStats,
+    pub aircraft_count: usize,
+    pub plugin_count: usize,
+    pub navdata_count: usize,
+    /// Aircraft and plugins with an update, as found by the last update check
+    pub updates_available: usize,
+}
+
 // SystemTime serialization helper
 mod systemtime_serde {
     use serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -580,6 +1092,34 @@ mod tests {
         assert!(SceneryCategory::AirportMesh.priority() < SceneryCategory::Mesh.priority());
     }
 
+    #[test]
+    fn test_category_bands_follow_priority() {
+        let bands = SceneryOrderingConfig::default().category_bands();
+        assert_eq!(bands.len(), 8);
+        assert_eq!(bands[0].category, SceneryCategory::FixedHighPriority);
+        assert_eq!(bands[7].category, SceneryCategory::Mesh);
+        assert!(bands.windows(2).all(|w| w[0].priority < w[1].priority));
+
+        // The *GLOBAL_AIRPORTS* position follows the config
+        let marked = |config: &SceneryOrderingConfig| -> Vec<SceneryCategory> {
+            config
+                .category_bands()
+                .into_iter()
+                .filter(|band| band.global_airports_before)
+                .map(|band| band.category)
+                .collect()
+        };
+        assert_eq!(
+            marked(&SceneryOrderingConfig::default()),
+            vec![SceneryCategory::DefaultAirport]
+        );
+        let config = SceneryOrderingConfig {
+            global_airports_before: SceneryCategory::Library,
+            ..Default::default()
+        };
+        assert_eq!(marked(&config), vec![SceneryCategory::Library]);
+    }
+
     #[test]
     fn test_scenery_category_serialization() {
         let airport = SceneryCategory::Airport;
@@ -728,12 +1268,22 @@ mod tests {
             sort_order: 10,
             missing_libraries: vec![],
             required_libraries: vec!["opensceneryx".to_string()],
+            is_dynamic: false,
+            is_incomplete: false,
+            is_orthophoto: false,
+            install_marker: None,
+            icao_code: Some("KSEA".to_string()),
+            airport_name: None,
+            airport_elevation_ft: None,
+            category_locked: false,
+            icao_mismatch: false,
         };
 
         let json = serde_json::to_string(&entry).unwrap();
         assert!(json.contains("MyAirport"));
         assert!(json.contains("Airport"));
         assert!(json.contains("opensceneryx"));
+        assert!(json.contains("\"icaoCode\":\"KSEA\""));
     }
 
     #[test]
@@ -751,6 +1301,10 @@ mod tests {
                     update_url: None,
                     latest_version: None,
                     has_update: false,
+                    cfg_disabled: None,
+                    liveries_disabled: false,
+                    acf_files: vec!["A320.acf".to_string()],
+                    xfma_files: vec![],
                 },
             ],
             total_count: 1,
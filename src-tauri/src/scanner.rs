@@ -112,19 +112,95 @@ fn is_archive_file(filename: &str) -> bool {
 }
 
 /// Get archive format from filename
-fn get_archive_format(filename: &str) -> Option<String> {
+fn get_archive_format(filename: &str) -> Option<&'static str> {
     let lower = filename.to_lowercase();
     if lower.ends_with(".zip") {
-        Some("zip".to_string())
+        Some("zip")
     } else if lower.ends_with(".7z") {
-        Some("7z".to_string())
+        Some("7z")
     } else if lower.ends_with(".rar") {
-        Some("rar".to_string())
+        Some("rar")
+    } else if lower.ends_with(".tar.gz") || lower.ends_with(".tgz") {
+        Some("gz")
     } else {
         None
     }
 }
 
+/// Determine an archive's format from its magic bytes, falling back to the extension
+/// Misnamed downloads (e.g. a zip saved as .7z) are therefore scanned and extracted correctly.
+/// Gzip data is only treated as a tarball when the name is .tar.gz/.tgz
+pub fn archive_format(path: &Path) -> Option<&'static str> {
+    let name_format = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(get_archive_format);
+
+    match detect_archive_format(path) {
+        Some("gz") => name_format.filter(|format| *format == "gz"),
+        Some(format) => Some(format),
+        None => name_format,
+    }
+}
+
+/// Check if a file starts with the gzip magic bytes (0x1f 0x8b)
+pub fn is_gzip_file(path: &Path) -> bool {
+    use std::io::Read;
+
+    let mut magic = [0u8; 2];
+    fs::File::open(path)
+        .and_then(|mut f| f.read_exact(&mut magic))
+        .map(|_| magic == [0x1f, 0x8b])
+        .unwrap_or(false)
+}
+
+/// Detect an archive format from its leading magic bytes
+/// Returns the same format names used for extensions ("zip", "7z", "rar", "gz")
+pub fn detect_archive_format(path: &Path) -> Option<&'static str> {
+    use std::io::Read;
+
+    let mut header = [0u8; 8];
+    let read = fs::File::open(path)
+        .and_then(|mut f| f.read(&mut header))
+        .ok()?;
+    archive_format_from_magic(&header[..read])
+}
+
+/// Match archive magic bytes (zip local/empty/spanned headers, 7z, RAR 4/5, gzip)
+pub fn archive_format_from_magic(header: &[u8]) -> Option<&'static str> {
+    if header.starts_with(b"PK\x03\x04")
+        || header.starts_with(b"PK\x05\x06")
+        || header.starts_with(b"PK\x07\x08")
+    {
+        Some("zip")
+    } else if header.starts_with(&[0x37, 0x7A, 0xBC, 0xAF, 0x27, 0x1C]) {
+        Some("7z")
+    } else if header.starts_with(b"Rar!\x1a\x07") {
+        Some("rar")
+    } else if header.starts_with(&[0x1f, 0x8b]) {
+        Some("gz")
+    } else {
+        None
+    }
+}
+
+/// Open a .tar.gz/.tgz archive for reading, rejecting files that aren't gzip data
+pub fn open_tar_gz(
+    archive_path: &Path,
+) -> Result<tar::Archive<flate2::read::GzDecoder<std::io::BufReader<fs::File>>>> {
+    if !is_gzip_file(archive_path) {
+        return Err(anyhow::anyhow!(
+            "Not a gzip-compressed tar archive (bad magic bytes): {}",
+            archive_path.display()
+        ));
+    }
+
+    let file = fs::File::open(archive_path)
+        .with_context(|| format!("Failed to open tar.gz archive: {}", archive_path.display()))?;
+    let decoder = flate2::read::GzDecoder::new(std::io::BufReader::new(file));
+    Ok(tar::Archive::new(decoder))
+}
+
 /// Scans a directory or archive and detects addon types based on markers
 ///
 /// Scanner is thread-safe as it contains no mutable state.
@@ -222,6 +298,63 @@ impl Scanner {
         false
     }
 
+    /// Identify plugin directories and addon marker files from archive entry paths
+    /// Paths must already use '/' separators. Returns (plugin_dirs, markers) where each
+    /// marker is (path, marker_type), in archive order
+    fn collect_archive_markers<I>(paths: I) -> (HashSet<String>, Vec<(String, &'static str)>)
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut plugin_dirs: HashSet<String> = HashSet::new();
+        let mut marker_files: Vec<(String, &'static str)> = Vec::new();
+        let mut detected_livery_roots: HashSet<String> = HashSet::new();
+
+        for path in paths {
+            if path.is_empty() || Self::should_ignore_archive_path(&path) {
+                continue;
+            }
+
+            // Check for livery patterns first (before any potential moves)
+            if let Some((_, livery_root)) = livery_patterns::check_livery_pattern(&path) {
+                if detected_livery_roots.insert(livery_root) {
+                    marker_files.push((path.clone(), "livery"));
+                }
+            }
+
+            // Identify plugin directories and marker files
+            if path.ends_with(".xpl") {
+                if let Some(parent) = Path::new(&path).parent() {
+                    let parent_str = parent.to_string_lossy();
+                    let parent_name = parent.file_name().and_then(|s| s.to_str()).unwrap_or("");
+
+                    let plugin_root = if matches!(
+                        parent_name,
+                        "32" | "64" | "win" | "lin" | "mac" | "win_x64" | "mac_x64" | "lin_x64"
+                    ) {
+                        parent
+                            .parent()
+                            .map(|p| p.to_string_lossy().to_string())
+                            .unwrap_or(parent_str.to_string())
+                    } else {
+                        parent_str.to_string()
+                    };
+                    plugin_dirs.insert(plugin_root);
+                }
+                marker_files.push((path, "xpl"));
+            } else if path.ends_with(".acf") {
+                marker_files.push((path, "acf"));
+            } else if path.ends_with("library.txt") {
+                marker_files.push((path, "library"));
+            } else if path.ends_with(".dsf") {
+                marker_files.push((path, "dsf"));
+            } else if path.ends_with("cycle.json") {
+                marker_files.push((path, "navdata"));
+            }
+        }
+
+        (plugin_dirs, marker_files)
+    }
+
     /// Scan a path (file or directory) and detect all addon types
     pub fn scan_path(&self, path: &Path, password: Option<&str>) -> Result<Vec<DetectedItem>> {
         let original_input_path = path.to_string_lossy().to_string();
@@ -263,21 +396,16 @@ impl Scanner {
         archive_path: &Path,
         ctx: &mut ScanContext,
     ) -> Result<Vec<DetectedItem>> {
-        let extension = archive_path
-            .extension()
-            .and_then(|s| s.to_str())
-            .unwrap_or("")
-            .to_lowercase();
-
         let password = ctx
             .passwords
             .get(&archive_path.to_string_lossy().to_string())
             .cloned(); // Clone the password to avoid borrow issues
 
-        match extension.as_str() {
-            "zip" => self.scan_zip_with_context(archive_path, ctx, password.as_deref()),
-            "7z" => self.scan_7z_with_context(archive_path, ctx, password.as_deref()),
-            "rar" => self.scan_rar_with_context(archive_path, ctx, password.as_deref()),
+        match archive_format(archive_path) {
+            Some("zip") => self.scan_zip_with_context(archive_path, ctx, password.as_deref()),
+            Some("7z") => self.scan_7z_with_context(archive_path, ctx, password.as_deref()),
+            Some("rar") => self.scan_rar_with_context(archive_path, ctx, password.as_deref()),
+            Some("gz") => self.scan_tar_gz(archive_path),
             _ => Ok(Vec::new()),
         }
     }
@@ -430,16 +558,11 @@ impl Scanner {
         archive_path: &Path,
         password: Option<&str>,
     ) -> Result<Vec<DetectedItem>> {
-        let extension = archive_path
-            .extension()
-            .and_then(|s| s.to_str())
-            .unwrap_or("")
-            .to_lowercase();
-
-        match extension.as_str() {
-            "zip" => self.scan_zip(archive_path, password),
-            "7z" => self.scan_7z(archive_path, password),
-            "rar" => self.scan_rar(archive_path, password),
+        match archive_format(archive_path) {
+            Some("zip") => self.scan_zip(archive_path, password),
+            Some("7z") => self.scan_7z(archive_path, password),
+            Some("rar") => self.scan_rar(archive_path, password),
+            Some("gz") => self.scan_tar_gz(archive_path),
             _ => {
                 // Silently skip non-archive files (no extension or unsupported format)
                 // Return empty result instead of error
@@ -838,8 +961,9 @@ impl Scanner {
         }
 
         // Get archive format
-        let format = get_archive_format(nested_path)
-            .ok_or_else(|| anyhow::anyhow!("Unknown archive format: {}", nested_path))?;
+        let format = archive_format(&temp_archive_path)
+            .ok_or_else(|| anyhow::anyhow!("Unknown archive format: {}", nested_path))?
+            .to_string();
 
         // Check if this nested archive has its own password
         let nested_password =
@@ -1173,8 +1297,9 @@ impl Scanner {
         }
 
         // Get archive format
-        let format = get_archive_format(nested_path)
-            .ok_or_else(|| anyhow::anyhow!("Unknown archive format: {}", nested_path))?;
+        let format = archive_format(&temp_archive_path)
+            .ok_or_else(|| anyhow::anyhow!("Unknown archive format: {}", nested_path))?
+            .to_string();
 
         // Check if this nested archive has its own password
         let nested_password =
@@ -1335,57 +1460,12 @@ impl Scanner {
         let mut detected = Vec::new();
 
         // Collect file paths and identify markers in a single pass
-        let mut plugin_dirs: HashSet<String> = HashSet::new();
-        let mut marker_files: Vec<(String, &str)> = Vec::new(); // (path, marker_type)
-        let mut detected_livery_roots: HashSet<String> = HashSet::new();
-
-        for entry in &archive.files {
-            let file_path = entry.name().to_string();
-            let normalized = file_path.replace('\\', "/");
-
-            // Skip ignored paths
-            if Self::should_ignore_archive_path(&normalized) {
-                continue;
-            }
-
-            // Check for livery patterns first (before any potential moves)
-            if let Some((_, livery_root)) = livery_patterns::check_livery_pattern(&normalized) {
-                if !detected_livery_roots.contains(&livery_root) {
-                    detected_livery_roots.insert(livery_root.clone());
-                    marker_files.push((normalized.clone(), "livery"));
-                }
-            }
-
-            // Identify plugin directories
-            if normalized.ends_with(".xpl") {
-                if let Some(parent) = Path::new(&normalized).parent() {
-                    let parent_str = parent.to_string_lossy();
-                    let parent_name = parent.file_name().and_then(|s| s.to_str()).unwrap_or("");
-
-                    let plugin_root = if matches!(
-                        parent_name,
-                        "32" | "64" | "win" | "lin" | "mac" | "win_x64" | "mac_x64" | "lin_x64"
-                    ) {
-                        parent
-                            .parent()
-                            .map(|p| p.to_string_lossy().to_string())
-                            .unwrap_or(parent_str.to_string())
-                    } else {
-                        parent_str.to_string()
-                    };
-                    plugin_dirs.insert(plugin_root);
-                }
-                marker_files.push((normalized, "xpl"));
-            } else if normalized.ends_with(".acf") {
-                marker_files.push((normalized, "acf"));
-            } else if normalized.ends_with("library.txt") {
-                marker_files.push((normalized, "library"));
-            } else if normalized.ends_with(".dsf") {
-                marker_files.push((normalized, "dsf"));
-            } else if normalized.ends_with("cycle.json") {
-                marker_files.push((normalized, "navdata"));
-            }
-        }
+        let (plugin_dirs, mut marker_files) = Self::collect_archive_markers(
+            archive
+                .files
+                .iter()
+                .map(|entry| entry.name().replace('\\', "/")),
+        );
 
         // Sort marker files by depth (process shallower paths first)
         marker_files.sort_by(|a, b| {
@@ -1765,6 +1845,99 @@ impl Scanner {
         Ok(content)
     }
 
+    /// Scan a .tar.gz/.tgz archive
+    /// Tarballs are not seekable, so entry paths (and cycle.json contents) are collected
+    /// in a single streaming pass. Nested archives inside tarballs are not scanned.
+    fn scan_tar_gz(&self, archive_path: &Path) -> Result<Vec<DetectedItem>> {
+        use std::io::Read;
+
+        let mut archive = open_tar_gz(archive_path)?;
+
+        let mut entry_paths: Vec<String> = Vec::new();
+        let mut navdata_contents: HashMap<String, String> = HashMap::new();
+
+        let entries = archive
+            .entries()
+            .map_err(|e| anyhow::anyhow!("Failed to read tar.gz archive: {}", e))?;
+
+        for entry in entries {
+            let mut entry =
+                entry.map_err(|e| anyhow::anyhow!("Failed to read tar.gz entry: {}", e))?;
+            let file_path = entry.path()?.to_string_lossy().to_string();
+            let normalized = file_path
+                .replace('\\', "/")
+                .trim_start_matches("./")
+                .to_string();
+
+            if normalized.ends_with("cycle.json") {
+                // Read now: the stream can't be revisited after this entry
+                let mut content = String::new();
+                if entry.read_to_string(&mut content).is_ok() {
+                    navdata_contents.insert(normalized.clone(), content);
+                }
+            }
+            entry_paths.push(normalized);
+        }
+
+        let (plugin_dirs, mut marker_files) = Self::collect_archive_markers(entry_paths);
+
+        // Sort marker files by depth (process shallower paths first)
+        marker_files.sort_by(|a, b| {
+            let depth_a = a.0.matches('/').count();
+            let depth_b = b.0.matches('/').count();
+            depth_a.cmp(&depth_b)
+        });
+
+        let mut detected = Vec::new();
+        let mut skip_prefixes: Vec<String> = Vec::new();
+
+        for (file_path, marker_type) in marker_files {
+            // Check if inside a skip prefix (already detected addon)
+            if skip_prefixes
+                .iter()
+                .any(|prefix| file_path.starts_with(prefix))
+            {
+                continue;
+            }
+
+            // Check if .acf/.dsf is inside a plugin directory
+            if (marker_type == "acf" || marker_type == "dsf")
+                && Self::is_archive_path_inside_plugin_dirs(&file_path, &plugin_dirs)
+            {
+                continue;
+            }
+
+            let item = match marker_type {
+                "acf" => self.detect_aircraft_in_archive(&file_path, archive_path)?,
+                "library" => self.detect_scenery_library(&file_path, archive_path)?,
+                "dsf" => self.detect_scenery_dsf(&file_path, archive_path)?,
+                "xpl" => self.detect_plugin_in_archive(&file_path, archive_path)?,
+                "navdata" => match navdata_contents.get(&file_path) {
+                    Some(content) => {
+                        self.detect_navdata_in_archive(&file_path, content, archive_path)?
+                    }
+                    None => None,
+                },
+                "livery" => self.detect_livery_in_archive(&file_path, archive_path)?,
+                _ => None,
+            };
+
+            if let Some(item) = item {
+                if let Some(ref internal_root) = item.archive_internal_root {
+                    let prefix = if internal_root.ends_with('/') {
+                        internal_root.clone()
+                    } else {
+                        format!("{}/", internal_root)
+                    };
+                    skip_prefixes.push(prefix);
+                }
+                detected.push(item);
+            }
+        }
+
+        Ok(detected)
+    }
+
     /// Scan a ZIP archive with context (supports nested archives)
     /// OPTIMIZED: Single pass to collect both addon markers and nested archives
     fn scan_zip_with_context(
@@ -2180,8 +2353,10 @@ impl Scanner {
         }
 
         // Get archive format
-        let format = get_archive_format(nested_path)
-            .ok_or_else(|| anyhow::anyhow!("Unknown archive format: {}", nested_path))?;
+        let format = archive_format_from_magic(&nested_data)
+            .or_else(|| get_archive_format(nested_path))
+            .ok_or_else(|| anyhow::anyhow!("Unknown archive format: {}", nested_path))?
+            .to_string();
 
         // Check if this nested archive has its own password
         let nested_password =
@@ -2191,14 +2366,14 @@ impl Scanner {
         let nested_info = NestedArchiveInfo {
             internal_path: nested_path.to_string(),
             password: nested_password.clone(),
-            format,
+            format: format.clone(),
         };
 
         // Push to context chain
         ctx.push_archive(nested_info.clone());
 
         // For ZIP nested archives, scan in-memory
-        let nested_result = if nested_path.to_lowercase().ends_with(".zip") {
+        let nested_result = if format == "zip" {
             // Create in-memory ZIP archive
             let cursor = std::io::Cursor::new(nested_data);
             match zip::ZipArchive::new(cursor) {
@@ -3199,8 +3374,8 @@ impl Scanner {
                 .to_string();
 
             // Get the aircraft name for display
-            let aircraft_name = livery_patterns::get_aircraft_name(aircraft_type_id)
-                .unwrap_or(aircraft_type_id);
+            let aircraft_name = livery_patterns::get_aircraft_name(&aircraft_type_id)
+                .unwrap_or_else(|| aircraft_type_id.clone());
 
             Ok(Some(DetectedItem {
                 original_input_path: String::new(),
@@ -3210,7 +3385,7 @@ impl Scanner {
                 archive_internal_root: None,
                 extraction_chain: None,
                 navdata_info: None,
-                livery_aircraft_type: Some(aircraft_type_id.to_string()),
+                livery_aircraft_type: Some(aircraft_type_id),
                 version_info: None,
             }))
         } else {
@@ -3248,8 +3423,8 @@ impl Scanner {
             };
 
             // Get the aircraft name for display
-            let aircraft_name = livery_patterns::get_aircraft_name(aircraft_type_id)
-                .unwrap_or(aircraft_type_id);
+            let aircraft_name = livery_patterns::get_aircraft_name(&aircraft_type_id)
+                .unwrap_or_else(|| aircraft_type_id.clone());
 
             Ok(Some(DetectedItem {
                 original_input_path: String::new(),
@@ -3259,7 +3434,7 @@ impl Scanner {
                 archive_internal_root: internal_root,
                 extraction_chain: None,
                 navdata_info: None,
-                livery_aircraft_type: Some(aircraft_type_id.to_string()),
+                livery_aircraft_type: Some(aircraft_type_id),
                 version_info: None,
             }))
         } else {
@@ -0,0 +1,265 @@
+//! Audit trail for scenery manager edits
+//!
+//! Every mutating scenery index operation (moves, entry updates, batch updates)
+//! appends one JSON line to an audit file in the app data directory, recording
+//! the X-Plane installation and the state of each affected package before and
+//! after the change. Entries are read, undone and cleared per installation; the
+//! most recent one is removed only once its undo has been applied. The file is
+//! capped at `MAX_AUDIT_ENTRIES` lines, dropping the oldest entries first.
+//! Operations that rewrite sort order or enabled state without recording an entry
+//! (re-sorts, full reorders, imports, rebuilds) clear the log, so undo never
+//! restores positions that no longer fit the index.
+
+use anyhow::Result;
+use chrono::Local;
+use once_cell::sync::Lazy;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+
+use crate::app_dirs;
+use crate::logger;
+use crate::models::{SceneryAuditChange, SceneryAuditEntry};
+
+/// Operation names recorded in the audit log
+pub const OP_MOVE_ENTRY: &str = "move_entry";
+pub const OP_UPDATE_ENTRY: &str = "update_entry";
+pub const OP_BATCH_UPDATE_ENTRIES: &str = "batch_update_entries";
+pub const OP_SET_ENABLED_BULK: &str = "set_enabled_bulk";
+pub const OP_SET_CATEGORY: &str = "set_category";
+
+/// Maximum number of audit entries kept on disk
+const MAX_AUDIT_ENTRIES: usize = 500;
+
+/// Serializes access to the audit file (append / pop / trim)
+static AUDIT_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// Key identifying an X-Plane installation in the audit log
+fn installation_key(xplane_path: &Path) -> String {
+    xplane_path
+        .to_string_lossy()
+        .replace('\\', "/")
+        .trim_end_matches('/')
+        .to_string()
+}
+
+/// Append an audit entry for `op` on the installation at `xplane_path`
+/// Changes where nothing differs are dropped; failing to write never fails the
+/// operation itself, the error is only reported to the application log
+pub fn record(xplane_path: &Path, op: &str, changes: Vec<SceneryAuditChange>) {
+    let changes: Vec<SceneryAuditChange> = changes
+        .into_iter()
+        .filter(|c| c.before != c.after)
+        .collect();
+    if changes.is_empty() {
+        return;
+    }
+
+    let entry = SceneryAuditEntry {
+        timestamp: Local::now().to_rfc3339(),
+        xplane_path: installation_key(xplane_path),
+        op: op.to_string(),
+        changes,
+    };
+
+    if let Err(e) = append_entry_in(
+        &app_dirs::get_scenery_audit_path(),
+        &entry,
+        MAX_AUDIT_ENTRIES,
+    ) {
+        logger::log_error(
+            &format!("Failed to write scenery audit entry: {}", e),
+            Some("scenery_audit"),
+        );
+    }
+}
+
+/// Read up to `limit` audit entries of the installation at `xplane_path`, newest first
+pub fn read_entries(xplane_path: &Path, limit: usize) -> Result<Vec<SceneryAuditEntry>> {
+    let _guard = AUDIT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let key = installation_key(xplane_path);
+    Ok(read_entries_in(&app_dirs::get_scenery_audit_path())?
+        .into_iter()
+        .rev()
+        .filter(|entry| entry.xplane_path == key)
+        .take(limit)
+        .collect())
+}
+
+/// Most recent audit entry of the installation at `xplane_path`, left in the log
+pub fn last_entry(xplane_path: &Path) -> Result<Option<SceneryAuditEntry>> {
+    Ok(read_entries(xplane_path, 1)?.pop())
+}
+
+/// Remove `entry` once it has been undone
+/// Only the installation's most recent entry is removed, and only if it is still `entry`
+pub fn remove_last(entry: &SceneryAuditEntry) -> Result<bool> {
+    remove_last_in(&app_dirs::get_scenery_audit_path(), entry)
+}
+
+/// Drop the audit entries of the installation at `xplane_path` after an unrecorded
+/// change to its sort order or enabled state
+pub fn clear(xplane_path: &Path, reason: &str) {
+    if let Err(e) = clear_in(&app_dirs::get_scenery_audit_path(), &installation_key(xplane_path)) {
+        logger::log_error(
+            &format!("Failed to clear scenery audit log: {}", e),
+            Some("scenery_audit"),
+        );
+        return;
+    }
+    crate::log_debug!(
+        &format!("Cleared scenery audit log after {}", reason),
+        "scenery_audit"
+    );
+}
+
+fn clear_in(path: &Path, key: &str) -> Result<()> {
+    let _guard = AUDIT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let entries = read_entries_in(path)?;
+    if entries.iter().any(|entry| entry.xplane_path == key) {
+        let kept: Vec<SceneryAuditEntry> = entries
+            .into_iter()
+            .filter(|entry| entry.xplane_path != key)
+            .collect();
+        write_entries_in(path, &kept)?;
+    }
+    Ok(())
+}
+
+fn append_entry_in(path: &Path, entry: &SceneryAuditEntry, max_entries: usize) -> Result<()> {
+    let _guard = AUDIT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)?;
+    drop(file);
+
+    let entries = read_entries_in(path)?;
+    if entries.len() > max_entries {
+        write_entries_in(path, &entries[entries.len() - max_entries..])?;
+    }
+
+    Ok(())
+}
+
+fn remove_last_in(path: &Path, entry: &SceneryAuditEntry) -> Result<bool> {
+    let _guard = AUDIT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    let mut entries = read_entries_in(path)?;
+    let last = entries
+        .iter()
+        .rposition(|e| e.xplane_path == entry.xplane_path);
+    match last {
+        Some(pos) if entries[pos].timestamp == entry.timestamp && entries[pos].op == entry.op => {
+            entries.remove(pos);
+            write_entries_in(path, &entries)?;
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
+/// Read all entries, oldest first; unreadable lines are skipped
+fn read_entries_in(path: &Path) -> Result<Vec<SceneryAuditEntry>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    Ok(fs::read_to_string(path)?
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+fn write_entries_in(path: &Path, entries: &[SceneryAuditEntry]) -> Result<()> {
+    let mut content = String::new();
+    for entry in entries {
+        content.push_str(&serde_json::to_string(entry)?);
+        content.push('\n');
+    }
+    fs::write(path, content)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{SceneryAuditState, SceneryCategory};
+    use tempfile::TempDir;
+
+    fn change(folder_name: &str, before: u32, after: u32) -> SceneryAuditChange {
+        let state = |sort_order| SceneryAuditState {
+            enabled: true,
+            sort_order,
+            category: SceneryCategory::Airport,
+            category_locked: false,
+        };
+        SceneryAuditChange {
+            folder_name: folder_name.to_string(),
+            before: state(before),
+            after: state(after),
+        }
+    }
+
+    fn entry(xplane_path: &str, op: &str, changes: Vec<SceneryAuditChange>) -> SceneryAuditEntry {
+        SceneryAuditEntry {
+            timestamp: Local::now().to_rfc3339(),
+            xplane_path: xplane_path.to_string(),
+            op: op.to_string(),
+            changes,
+        }
+    }
+
+    #[test]
+    fn test_audit_append_trim_and_remove() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("audit").join("scenery_audit.jsonl");
+
+        for i in 0..4 {
+            let e = entry("X12", "move_entry", vec![change("A", i, i + 1)]);
+            append_entry_in(&path, &e, 3).unwrap();
+        }
+
+        // Bounded: only the newest 3 entries remain
+        let entries = read_entries_in(&path).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].changes[0].before.sort_order, 1);
+
+        // Only the most recent entry can be removed
+        assert!(!remove_last_in(&path, &entries[1]).unwrap());
+        assert!(remove_last_in(&path, &entries[2]).unwrap());
+        assert!(!remove_last_in(&path, &entries[2]).unwrap());
+        assert_eq!(read_entries_in(&path).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_audit_entries_are_per_installation() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("scenery_audit.jsonl");
+
+        let x11 = entry("D:/X-Plane 11", "move_entry", vec![change("A", 0, 1)]);
+        let x12 = entry("D:/X-Plane 12", "update_entry", vec![change("B", 2, 3)]);
+        append_entry_in(&path, &x11, 10).unwrap();
+        append_entry_in(&path, &x12, 10).unwrap();
+
+        // The other installation's newer entry doesn't hide this one
+        assert!(remove_last_in(&path, &x11).unwrap());
+        assert_eq!(read_entries_in(&path).unwrap().len(), 1);
+
+        append_entry_in(&path, &x11, 10).unwrap();
+        clear_in(&path, "D:/X-Plane 12").unwrap();
+        let remaining = read_entries_in(&path).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].xplane_path, "D:/X-Plane 11");
+
+        assert_eq!(
+            installation_key(Path::new("D:\\X-Plane 11\\")),
+            "D:/X-Plane 11"
+        );
+    }
+}
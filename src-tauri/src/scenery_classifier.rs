@@ -3,18 +3,26 @@
 //! This module analyzes scenery packages and determines their category
 //! by parsing DSF file headers and checking file system structure.
 
-use crate::models::{DsfHeader, SceneryCategory, SceneryPackageInfo};
+use crate::models::{DsfHeader, DsfInfo, SceneryCategory, SceneryPackageInfo};
 use anyhow::{anyhow, Result};
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::fs::File;
 use std::io::Read;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 use walkdir::WalkDir;
 
 const MAX_PLUGIN_SCAN_DEPTH: usize = 5;
 
+/// Maximum number of definition strings collected per DSF definition list.
+/// Past the cap, object definitions are only kept when they could still change
+/// the result of `extract_required_libraries` (a new leading path component).
+const MAX_DSF_DEFINITIONS: usize = 10_000;
+
+/// Number of object/terrain references included in a DsfInfo sample
+const DSF_INFO_SAMPLE_SIZE: usize = 20;
+
 /// Check if folder contains plugins (.xpl files)
 fn has_plugins(scenery_path: &Path) -> bool {
     let plugins_path = scenery_path.join("plugins");
@@ -43,8 +51,44 @@ fn has_plugins(scenery_path: &Path) -> bool {
     false
 }
 
+/// Default airport package that X-Plane 11 ships inside Custom Scenery
+/// (X-Plane 12 keeps it in Global Scenery and references it as *GLOBAL_AIRPORTS*)
+const XP11_GLOBAL_AIRPORTS_FOLDER: &str = "Global Airports";
+
 /// Main entry point for scenery classification
-pub fn classify_scenery(scenery_path: &Path, _xplane_path: &Path) -> Result<SceneryPackageInfo> {
+/// Classifies by package contents, then applies defaults for the detected X-Plane version
+/// (unchanged when the version is unknown)
+pub fn classify_scenery(scenery_path: &Path, xplane_path: &Path) -> Result<SceneryPackageInfo> {
+    let mut info = classify_scenery_contents(scenery_path)?;
+    apply_version_defaults(&mut info, xplane_path);
+    Ok(info)
+}
+
+/// Adjust a classification for the installed X-Plane major version
+fn apply_version_defaults(info: &mut SceneryPackageInfo, xplane_path: &Path) {
+    // Only look up the version for packages that need it (reading it costs a file read)
+    if !info
+        .folder_name
+        .eq_ignore_ascii_case(XP11_GLOBAL_AIRPORTS_FOLDER)
+        || info.category != SceneryCategory::Airport
+    {
+        return;
+    }
+
+    // XP11's default airports must stay below custom airports
+    if crate::management_index::get_xplane_version(xplane_path)
+        .is_some_and(|(major, _, _)| major == 11)
+    {
+        crate::log_debug!(
+            "  X-Plane 11 default airports package, using DefaultAirport",
+            "scenery_classifier"
+        );
+        info.category = SceneryCategory::DefaultAirport;
+    }
+}
+
+/// Classify a scenery package from its contents only
+fn classify_scenery_contents(scenery_path: &Path) -> Result<SceneryPackageInfo> {
     let folder_name = scenery_path
         .file_name()
         .and_then(|s| s.to_str())
@@ -177,54 +221,21 @@ pub fn classify_scenery(scenery_path: &Path, _xplane_path: &Path) -> Result<Scen
         "scenery_classifier"
     );
 
-    // Decision Tree Classification
-    // Parse DSF header if available
-    let dsf_header_opt: Option<DsfHeader> = if !dsf_files.is_empty() {
-        match parse_dsf_header(&dsf_files[0]) {
-            Ok(header) => {
-                if let Some(ref agent) = header.creation_agent {
-                    crate::log_debug!(
-                        &format!("  creation_agent: {}", agent),
-                        "scenery_classifier"
-                    );
-                }
-                crate::log_debug!(
-                    &format!("  sim/overlay: {}", header.is_overlay),
-                    "scenery_classifier"
-                );
-                crate::log_debug!(
-                    &format!(
-                        "  has terrain refs: {} (count: {})",
-                        !header.terrain_references.is_empty(),
-                        header.terrain_references.len()
-                    ),
-                    "scenery_classifier"
-                );
-                if !header.terrain_references.is_empty() {
-                    crate::log_debug!(
-                        &format!(
-                            "  terrain refs sample: {:?}",
-                            header.terrain_references.iter().take(3).collect::<Vec<_>>()
-                        ),
-                        "scenery_classifier"
-                    );
-                }
-                Some(header)
-            }
-            Err(e) => {
-                crate::log_debug!(
-                    &format!("  Failed to parse DSF: {}", e),
-                    "scenery_classifier"
-                );
-                None
-            }
-        }
-    } else {
-        None
-    };
+    // Dual-purpose package: library.txt alongside real scenery content (apt.dat or DSF tiles).
+    // It is categorized by its scenery role (Airport/Overlay/Mesh) so it sorts in the
+    // scenery band, while its library.txt exports still register it as a library provider.
+    // A library with an empty Earth nav data folder has no scenery role and stays a Library
+    let is_dual_purpose =
+        has_library_txt && has_earth_nav_data && (has_apt_dat || !dsf_files.is_empty());
+    if is_dual_purpose {
+        crate::log_debug!(
+            "  Dual-purpose package (library.txt + scenery), classifying by scenery role",
+            "scenery_classifier"
+        );
+    }
 
     // Decision Tree:
-    // 1. Has apt.dat OR (DSF with WorldEditor creation_agent) → Airport
+    // 1. Has apt.dat → Airport (decided before the full DSF header parse)
     if has_apt_dat {
         crate::log_debug!(
             &format!("  ✓ Classified as Airport (has apt.dat)"),
@@ -233,8 +244,22 @@ pub fn classify_scenery(scenery_path: &Path, _xplane_path: &Path) -> Result<Scen
 
         // Extract required libraries but don't check for missing ones yet
         // (will be done after index is built)
-        let required_libraries = if let Some(ref header) = dsf_header_opt {
-            extract_required_libraries(&header.object_references)
+        // Fast path: the category is already known, so only the object definitions
+        // are read from the DSF - the PROP section and terrain references are skipped
+        let required_libraries = if let Some(first_dsf) = dsf_files.first() {
+            let parse_start = std::time::Instant::now();
+            let object_references = parse_dsf_object_references(first_dsf).unwrap_or_else(|e| {
+                log_unreadable_dsf(&folder_name, first_dsf, &e);
+                Vec::new()
+            });
+            crate::log_debug!(
+                &format!(
+                    "  [TIMING] DSF object references parsed in {:.2}ms",
+                    parse_start.elapsed().as_secs_f64() * 1000.0
+                ),
+                "scenery_classifier"
+            );
+            extract_required_libraries(&object_references)
         } else {
             Vec::new()
         };
@@ -274,6 +299,50 @@ pub fn classify_scenery(scenery_path: &Path, _xplane_path: &Path) -> Result<Scen
         )?);
     }
 
+    // Decision Tree Classification
+    // Parse DSF header if available (only needed when there is no apt.dat)
+    let dsf_header_opt: Option<DsfHeader> = if !dsf_files.is_empty() {
+        match parse_dsf_header(&dsf_files[0]) {
+            Ok(header) => {
+                if let Some(ref agent) = header.creation_agent {
+                    crate::log_debug!(
+                        &format!("  creation_agent: {}", agent),
+                        "scenery_classifier"
+                    );
+                }
+                crate::log_debug!(
+                    &format!("  sim/overlay: {}", header.is_overlay),
+                    "scenery_classifier"
+                );
+                crate::log_debug!(
+                    &format!(
+                        "  has terrain refs: {} (count: {})",
+                        !header.terrain_references.is_empty(),
+                        header.terrain_references.len()
+                    ),
+                    "scenery_classifier"
+                );
+                if !header.terrain_references.is_empty() {
+                    crate::log_debug!(
+                        &format!(
+                            "  terrain refs sample: {:?}",
+                            header.terrain_references.iter().take(3).collect::<Vec<_>>()
+                        ),
+                        "scenery_classifier"
+                    );
+                }
+                Some(header)
+            }
+            Err(e) => {
+                log_unreadable_dsf(&folder_name, &dsf_files[0], &e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    // 1b. DSF with WorldEditor creation_agent (no apt.dat) → Airport
     if let Some(ref header) = dsf_header_opt {
         if let Some(ref agent) = header.creation_agent {
             if agent.to_lowercase().contains("worldeditor") {
@@ -367,8 +436,9 @@ pub fn classify_scenery(scenery_path: &Path, _xplane_path: &Path) -> Result<Scen
         }
     }
 
-    // 3. Has library.txt but no Earth nav data → Library or FixedHighPriority (SAM)
-    if has_library_txt && !has_earth_nav_data {
+    // 3. Has library.txt but no scenery role (no Earth nav data, or no tiles in it)
+    //    → Library or FixedHighPriority (SAM)
+    if has_library_txt && !is_dual_purpose {
         // Check if it's a SAM library
         // Match patterns:
         // 1. "sam" as a separate word: "SAM_Library", "open_SAM_library"
@@ -452,9 +522,19 @@ pub fn classify_scenery(scenery_path: &Path, _xplane_path: &Path) -> Result<Scen
             "scenery_classifier"
         );
 
-        // All mesh/orthophoto scenery is now classified as Mesh
+        // All mesh/orthophoto scenery is classified as Mesh; orthophoto packs are told
+        // apart by is_orthophoto_package (sorted above regular mesh, no airport-mesh detection)
         let category = SceneryCategory::Mesh;
 
+        // No DSF and no apt.dat: most likely an interrupted download or partial extract.
+        // It is still indexed as Mesh but reported as incomplete (see is_incomplete)
+        if dsf_files.is_empty() {
+            crate::log_debug!(
+                "  ⚠ Earth nav data without DSF or apt.dat, likely an incomplete install",
+                "scenery_classifier"
+            );
+        }
+
         let (required_libraries, missing_libraries) = if let Some(ref header) = dsf_header_opt {
             let required = extract_required_libraries(&header.object_references);
             // Missing libraries will be calculated later in update_missing_libraries()
@@ -591,7 +671,7 @@ pub fn classify_scenery(scenery_path: &Path, _xplane_path: &Path) -> Result<Scen
     )?)
 }
 
-/// Check if apt.dat exists recursively in Earth nav data directories
+/// Check if a valid apt.dat exists recursively in Earth nav data directories
 fn check_apt_dat_recursive(scenery_path: &Path) -> Result<bool> {
     // apt.dat is always in Earth nav data folder, so only search there
     let earth_nav_path = scenery_path.join("Earth nav data");
@@ -620,7 +700,10 @@ fn check_apt_dat_recursive(scenery_path: &Path) -> Result<bool> {
     Ok(false)
 }
 
-/// Validate apt.dat file format (first line "I", second line starts with "1")
+/// apt.dat format versions written by X-Plane and WorldEditor
+const KNOWN_APT_DAT_VERSIONS: &[u32] = &[850, 1000, 1050, 1100, 1130, 1200, 1300];
+
+/// Validate apt.dat file format (byte order line followed by a known version)
 fn validate_apt_dat(path: &Path) -> Result<bool> {
     let mut file = File::open(path)?;
     let mut buffer = [0u8; 256];
@@ -631,34 +714,48 @@ fn validate_apt_dat(path: &Path) -> Result<bool> {
     }
 
     let content = String::from_utf8_lossy(&buffer[..bytes_read]);
-    let lines: Vec<&str> = content.lines().collect();
-
-    if lines.len() < 2 {
-        return Ok(false);
-    }
+    Ok(is_apt_dat_header(&content))
+}
 
-    // First line should be "I" or "A"
-    let first_line = lines[0].trim();
+/// Check the start of an apt.dat: the first line is "I" or "A" (byte order), the
+/// second starts with the version as its row code (e.g. "1100 Version - ...")
+/// Known versions and any other 4-digit value are accepted
+fn is_apt_dat_header(content: &str) -> bool {
+    let mut lines = content.lines();
+
+    let first_line = lines
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches('\u{feff}')
+        .trim();
     if first_line != "I" && first_line != "A" {
-        return Ok(false);
+        return false;
     }
 
-    // Second line should start with version number (usually 1000, 1050, 1100, 1200, etc.)
-    let second_line = lines[1].trim();
-    if second_line.starts_with("1") || second_line.starts_with("850") {
-        return Ok(true);
+    // The row code must be a whole token, so "1foo" or "1.0" are rejected
+    let Some(row_code) = lines.next().and_then(|line| line.split_whitespace().next()) else {
+        return false;
+    };
+    if !row_code.bytes().all(|b| b.is_ascii_digit()) {
+        return false;
     }
 
-    Ok(false)
+    row_code.parse::<u32>().is_ok_and(|version| {
+        KNOWN_APT_DAT_VERSIONS.contains(&version) || (1000..=9999).contains(&version)
+    })
 }
 
 /// Find first DSF file in scenery package (for classification)
+/// Empty (zero-byte) DSFs are only returned if no other DSF exists, so a single
+/// truncated tile doesn't prevent reading a valid header from another one
 fn find_dsf_files(scenery_path: &Path) -> Result<Vec<std::path::PathBuf>> {
     // Only need one DSF file for classification, so return as soon as we find one
     // Use a more efficient approach: check common locations first
 
     crate::log_debug!("  Starting DSF search...", "scenery_classifier");
 
+    let mut empty_dsf: Option<PathBuf> = None;
+
     // First, try to find DSF in Earth nav data subdirectories (most common location)
     let earth_nav_path = scenery_path.join("Earth nav data");
     if earth_nav_path.exists() {
@@ -684,6 +781,10 @@ fn find_dsf_files(scenery_path: &Path) -> Result<Vec<std::path::PathBuf>> {
             if entry.file_type().is_file() {
                 if let Some(ext) = entry.path().extension() {
                     if ext.eq_ignore_ascii_case("dsf") {
+                        if is_empty_file(&entry) {
+                            empty_dsf.get_or_insert_with(|| entry.path().to_path_buf());
+                            continue;
+                        }
                         crate::log_debug!(
                             &format!("  Found DSF after {} entries: {:?}", count, entry.path()),
                             "scenery_classifier"
@@ -715,6 +816,10 @@ fn find_dsf_files(scenery_path: &Path) -> Result<Vec<std::path::PathBuf>> {
         if entry.file_type().is_file() {
             if let Some(ext) = entry.path().extension() {
                 if ext.eq_ignore_ascii_case("dsf") {
+                    if is_empty_file(&entry) {
+                        empty_dsf.get_or_insert_with(|| entry.path().to_path_buf());
+                        continue;
+                    }
                     crate::log_debug!(
                         &format!("  Found DSF: {:?}", entry.path()),
                         "scenery_classifier"
@@ -725,11 +830,36 @@ fn find_dsf_files(scenery_path: &Path) -> Result<Vec<std::path::PathBuf>> {
         }
     }
 
+    // Only empty DSFs: the package still has tiles, but no header can be read
+    if let Some(path) = empty_dsf {
+        crate::log_debug!(
+            &format!("  Only empty DSF files found: {:?}", path),
+            "scenery_classifier"
+        );
+        return Ok(vec![path]);
+    }
+
     // No DSF files found
     crate::log_debug!("  No DSF files found", "scenery_classifier");
     Ok(Vec::new())
 }
 
+/// Whether a walked file is zero bytes long (or its size can't be read)
+fn is_empty_file(entry: &walkdir::DirEntry) -> bool {
+    entry.metadata().map(|m| m.len() == 0).unwrap_or(true)
+}
+
+/// Report a DSF that can't be read; classification falls back to the folder structure
+fn log_unreadable_dsf(folder_name: &str, dsf_path: &Path, error: &anyhow::Error) {
+    crate::logger::log_info(
+        &format!(
+            "Warning: unreadable DSF {:?} in {}, classifying by folder structure: {}",
+            dsf_path, folder_name, error
+        ),
+        Some("scenery_classifier"),
+    );
+}
+
 /// Check if DSF file is 7z compressed
 fn is_dsf_compressed(dsf_path: &Path) -> Result<bool> {
     let mut file = File::open(dsf_path)?;
@@ -743,6 +873,10 @@ fn is_dsf_compressed(dsf_path: &Path) -> Result<bool> {
     Ok(magic == [0x37, 0x7A, 0xBC, 0xAF])
 }
 
+/// Maximum depth searched for the DSF inside an extracted 7z archive
+/// (covers layouts like `<root>/Earth nav data/+50+010/+55+013.dsf`)
+const MAX_EXTRACTED_DSF_DEPTH: usize = 6;
+
 /// Decompress 7z DSF file
 fn decompress_dsf(dsf_path: &Path) -> Result<Vec<u8>> {
     use sevenz_rust2::decompress_file;
@@ -751,38 +885,42 @@ fn decompress_dsf(dsf_path: &Path) -> Result<Vec<u8>> {
     let temp_dir = tempfile::tempdir()?;
     decompress_file(dsf_path, temp_dir.path())?;
 
-    // Use WalkDir to recursively search for the DSF file
-    // Max depth 3 handles cases where 7z extracts to nested directories
-    for entry in WalkDir::new(temp_dir.path())
-        .max_depth(3)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        if entry.file_type().is_file() {
-            // Prefer files with .dsf extension
-            if entry.path().extension().map_or(false, |e| e.eq_ignore_ascii_case("dsf")) {
-                return std::fs::read(entry.path()).map_err(|e| anyhow!("{}", e));
-            }
-        }
-    }
+    let extracted = find_extracted_dsf(temp_dir.path())
+        .ok_or_else(|| anyhow!("No file found in decompressed 7z archive"))?;
+    std::fs::read(&extracted).map_err(|e| anyhow!("{}", e))
+}
 
-    // If no .dsf file found, try reading the first file encountered
-    // (the 7z might contain raw data without extension)
-    for entry in WalkDir::new(temp_dir.path())
-        .max_depth(3)
+/// Locate the DSF inside an extracted 7z archive
+/// Some archives extract into a nested `Earth nav data/...` tree, so the search is recursive.
+/// Files with a .dsf extension are preferred; otherwise the first file found is used
+/// (the 7z might contain raw data without extension)
+fn find_extracted_dsf(dir: &Path) -> Option<PathBuf> {
+    let mut first_file = None;
+
+    for entry in WalkDir::new(dir)
+        .max_depth(MAX_EXTRACTED_DSF_DEPTH)
+        .sort_by_file_name()
         .into_iter()
         .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
     {
-        if entry.file_type().is_file() {
-            return std::fs::read(entry.path()).map_err(|e| anyhow!("{}", e));
+        if entry
+            .path()
+            .extension()
+            .is_some_and(|e| e.eq_ignore_ascii_case("dsf"))
+        {
+            return Some(entry.into_path());
+        }
+        if first_file.is_none() {
+            first_file = Some(entry.into_path());
         }
     }
 
-    Err(anyhow!("No file found in decompressed 7z archive"))
+    first_file
 }
 
-/// Parse DSF file header
-pub fn parse_dsf_header(dsf_path: &Path) -> Result<DsfHeader> {
+/// Read DSF data (decompressing 7z DSFs) and verify the magic bytes
+fn read_dsf_data(dsf_path: &Path) -> Result<Vec<u8>> {
     // Check if compressed
     let is_compressed = is_dsf_compressed(dsf_path)?;
 
@@ -798,6 +936,21 @@ pub fn parse_dsf_header(dsf_path: &Path) -> Result<DsfHeader> {
         return Err(anyhow!("Invalid DSF file: missing magic bytes"));
     }
 
+    Ok(data)
+}
+
+/// Parse only the object/polygon/network definitions of a DSF file
+/// Used when the category is already known and only required libraries are needed
+fn parse_dsf_object_references(dsf_path: &Path) -> Result<Vec<String>> {
+    let data = read_dsf_data(dsf_path)?;
+    let (object_references, _) = extract_dsf_definitions(&data)?;
+    Ok(object_references)
+}
+
+/// Parse DSF file header
+pub fn parse_dsf_header(dsf_path: &Path) -> Result<DsfHeader> {
+    let data = read_dsf_data(dsf_path)?;
+
     // Extract properties from PROP section
     let properties = extract_dsf_properties(&data)?;
 
@@ -815,6 +968,31 @@ pub fn parse_dsf_header(dsf_path: &Path) -> Result<DsfHeader> {
     })
 }
 
+/// Read the header of the DSF that classification would use for this package
+/// Returns None if the package has no DSF
+pub fn read_dsf_info(scenery_path: &Path) -> Result<Option<DsfInfo>> {
+    let Some(dsf_path) = find_dsf_files(scenery_path)?.into_iter().next() else {
+        return Ok(None);
+    };
+    let header = parse_dsf_header(&dsf_path)?;
+    let relative = dsf_path.strip_prefix(scenery_path).unwrap_or(&dsf_path);
+    Ok(Some(dsf_info_from_header(relative, header)))
+}
+
+/// Summarize a parsed DSF header: reference counts plus a sample of each list
+fn dsf_info_from_header(dsf_path: &Path, header: DsfHeader) -> DsfInfo {
+    let sample = |refs: &[String]| refs.iter().take(DSF_INFO_SAMPLE_SIZE).cloned().collect();
+    DsfInfo {
+        dsf_path: dsf_path.to_string_lossy().replace('\\', "/"),
+        is_overlay: header.is_overlay,
+        creation_agent: header.creation_agent,
+        object_reference_count: header.object_references.len(),
+        terrain_reference_count: header.terrain_references.len(),
+        sample_object_references: sample(&header.object_references),
+        sample_terrain_references: sample(&header.terrain_references),
+    }
+}
+
 /// Extract properties from DSF PROP section
 fn extract_dsf_properties(data: &[u8]) -> Result<HashMap<String, String>> {
     let mut properties = HashMap::new();
@@ -961,10 +1139,70 @@ fn extract_dsf_definitions(data: &[u8]) -> Result<(Vec<String>, Vec<String>)> {
     Ok((object_refs, terrain_refs))
 }
 
+/// Bounded collector for DSF definition strings
+struct DefinitionCollector {
+    definitions: Vec<String>,
+    /// Library lookup keys already collected (only tracked for object definitions)
+    seen_keys: Option<HashSet<String>>,
+    truncated: usize,
+}
+
+impl DefinitionCollector {
+    fn new(track_library_keys: bool) -> Self {
+        Self {
+            definitions: Vec::new(),
+            seen_keys: track_library_keys.then(HashSet::new),
+            truncated: 0,
+        }
+    }
+
+    /// Key that fully determines `extract_library_name` for a definition:
+    /// the first path component, and whether the path has more than one component
+    fn library_key(def: &str) -> String {
+        match def.split_once('/') {
+            Some((first, _)) => format!("{}/", first),
+            None => def.to_string(),
+        }
+    }
+
+    fn push(&mut self, def: String) {
+        let Some(seen_keys) = self.seen_keys.as_mut() else {
+            if self.definitions.len() < MAX_DSF_DEFINITIONS {
+                self.definitions.push(def);
+            } else {
+                self.truncated += 1;
+            }
+            return;
+        };
+
+        let is_new_key = seen_keys.insert(Self::library_key(&def));
+        if self.definitions.len() < MAX_DSF_DEFINITIONS || is_new_key {
+            self.definitions.push(def);
+        } else {
+            self.truncated += 1;
+        }
+    }
+
+    fn finish(self, kind: &str) -> Vec<String> {
+        if self.truncated > 0 {
+            crate::log_debug!(
+                &format!(
+                    "  DSF {} definitions truncated: kept {}, skipped {}",
+                    kind,
+                    self.definitions.len(),
+                    self.truncated
+                ),
+                "scenery_classifier"
+            );
+        }
+        self.definitions
+    }
+}
+
 /// Extract definitions from DEFN atom
 fn extract_definitions_from_defn(defn_data: &[u8]) -> Result<(Vec<String>, Vec<String>)> {
-    let mut object_refs = Vec::new();
-    let mut terrain_refs = Vec::new();
+    let mut object_refs = DefinitionCollector::new(true);
+    let mut terrain_refs = DefinitionCollector::new(false);
     let mut offset = 0;
 
     while offset + 8 <= defn_data.len() {
@@ -984,26 +1222,21 @@ fn extract_definitions_from_defn(defn_data: &[u8]) -> Result<(Vec<String>, Vec<S
         if atom_id == b"TRET" {
             // Terrain definitions
             let def_data = &defn_data[offset + 8..offset + atom_len];
-            if let Ok(defs) = parse_definition_strings(def_data) {
-                terrain_refs.extend(defs);
-            }
+            parse_definition_strings(def_data, &mut terrain_refs);
         } else if atom_id == b"TJBO" || atom_id == b"YLOP" || atom_id == b"WTEN" {
             // Object, polygon, and network definitions
             let def_data = &defn_data[offset + 8..offset + atom_len];
-            if let Ok(defs) = parse_definition_strings(def_data) {
-                object_refs.extend(defs);
-            }
+            parse_definition_strings(def_data, &mut object_refs);
         }
 
         offset += atom_len;
     }
 
-    Ok((object_refs, terrain_refs))
+    Ok((object_refs.finish("object"), terrain_refs.finish("terrain")))
 }
 
-/// Parse null-terminated definition strings
-fn parse_definition_strings(def_data: &[u8]) -> Result<Vec<String>> {
-    let mut definitions = Vec::new();
+/// Parse null-terminated definition strings into a collector
+fn parse_definition_strings(def_data: &[u8], collector: &mut DefinitionCollector) {
     let mut i = 0;
 
     while i < def_data.len() {
@@ -1019,36 +1252,84 @@ fn parse_definition_strings(def_data: &[u8]) -> Result<Vec<String>> {
 
         let def = String::from_utf8_lossy(&def_data[i..i + end]).to_string();
         if !def.is_empty() && (def.contains('/') || def.contains('.')) {
-            definitions.push(def);
+            collector.push(def);
         }
 
         i += end + 1;
     }
+}
 
-    Ok(definitions)
+/// count_texture_files stops counting once this many textures are found
+const MAX_COUNTED_TEXTURES: usize = 5;
+
+/// Folder name prefixes of photo scenery packs that can't be told apart from mesh by
+/// their DSF header (AutoOrtho exports, PhotoXP and hand-made ortho packs)
+const ORTHO_FOLDER_PREFIXES: &[&str] = &[
+    "zortho4xp_",
+    "zphotoxp_",
+    "zortho_",
+    "z_ortho_",
+    "ortho_",
+    "zzortho",
+];
+
+/// Whether a Mesh package is an orthophoto pack
+/// Requires both an ortho folder prefix and a full texture count, so terrain-only
+/// meshes with a similar name stay regular mesh. Orthophotos keep the Mesh category;
+/// they are flagged in the manager, sorted above regular mesh and excluded from
+/// airport-mesh detection
+pub fn is_orthophoto_package(folder_name: &str, texture_count: usize) -> bool {
+    let folder_name_lower = folder_name.to_lowercase();
+    texture_count >= MAX_COUNTED_TEXTURES
+        && ORTHO_FOLDER_PREFIXES
+            .iter()
+            .any(|prefix| folder_name_lower.starts_with(prefix))
 }
 
-/// Count texture files in scenery folder (up to 5 for classification)
+/// Count texture files in scenery folder (up to MAX_COUNTED_TEXTURES for classification)
 fn count_texture_files(scenery_path: &Path) -> Result<usize> {
     let textures_path = scenery_path.join("textures");
     if !textures_path.exists() {
         return Ok(0);
     }
 
-    // Only count up to 5 textures - enough to determine if this is an orthophoto scenery
+    // Only count up to MAX_COUNTED_TEXTURES textures - enough to determine if this is an orthophoto scenery
+    // Symlinks are followed, so track canonical paths to avoid re-entering directories
+    // reached through a symlink loop and counting the same file twice
+    let mut visited_dirs: HashSet<PathBuf> = HashSet::new();
+    let mut counted_files: HashSet<PathBuf> = HashSet::new();
     let mut count = 0;
-    for entry in WalkDir::new(&textures_path)
+    let mut walker = WalkDir::new(&textures_path)
         .follow_links(true) // Explicitly follow symbolic links
         .max_depth(3)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
+        .into_iter();
+
+    while let Some(entry) = walker.next() {
+        let entry = match entry {
+            Ok(e) => e,
+            Err(_) => continue,
+        };
+
+        let canonical = match entry.path().canonicalize() {
+            Ok(p) => p,
+            Err(_) => continue,
+        };
+
+        if entry.file_type().is_dir() {
+            if !visited_dirs.insert(canonical) {
+                walker.skip_current_dir();
+            }
+            continue;
+        }
+
         if entry.file_type().is_file() {
             if let Some(ext) = entry.path().extension() {
                 let ext_lower = ext.to_string_lossy().to_lowercase();
-                if ext_lower == "dds" || ext_lower == "png" || ext_lower == "jpg" {
+                if (ext_lower == "dds" || ext_lower == "png" || ext_lower == "jpg")
+                    && counted_files.insert(canonical)
+                {
                     count += 1;
-                    if count >= 5 {
+                    if count >= MAX_COUNTED_TEXTURES {
                         // Found enough textures for classification
                         return Ok(count);
                     }
@@ -1241,6 +1522,11 @@ fn build_package_info(
 ) -> Result<SceneryPackageInfo> {
     // Calculate sub-priority based on category and folder name
     let sub_priority = calculate_sub_priority(&category, &folder_name);
+    let airport = if has_apt_dat {
+        crate::scenery_index::read_airport_details(scenery_path)
+    } else {
+        None
+    };
 
     Ok(SceneryPackageInfo {
         folder_name,
@@ -1261,6 +1547,12 @@ fn build_package_info(
         enabled: true, // Default to enabled
         sort_order: 0, // Will be assigned during index rebuild
         actual_path: None, // Will be set by index manager for shortcut entries
+        icao_code: airport.as_ref().and_then(|a| a.icao().map(String::from)),
+        airport_name: airport.as_ref().and_then(|a| a.name.clone()),
+        airport_elevation_ft: airport.as_ref().and_then(|a| a.elevation_ft),
+        category_locked: false,
+        icao_mismatch: airport.is_some_and(|a| a.has_icao_mismatch()),
+        install_marker: crate::install_marker::read_install_marker(scenery_path),
     })
 }
 
@@ -1297,6 +1589,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_is_orthophoto_package() {
+        assert!(is_orthophoto_package("zPhotoXP_+47+008", 5));
+        assert!(is_orthophoto_package("zOrtho4XP_+47+008", 5));
+        assert!(is_orthophoto_package("Ortho_Switzerland", 5));
+        // A matching name alone is not enough
+        assert!(!is_orthophoto_package("zPhotoXP_+47+008", 2));
+        // Neither is a texture-heavy mesh with an unrelated name
+        assert!(!is_orthophoto_package("HD_Mesh_v4", 5));
+        assert!(!is_orthophoto_package("Orthodox_Church_Mesh", 5));
+    }
+
     #[test]
     fn test_extract_library_name() {
         // Should extract library names
@@ -1320,9 +1624,264 @@ mod tests {
         );
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_count_texture_files_symlink_loop() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let textures = temp_dir.path().join("textures");
+        let sub = textures.join("sub");
+        fs::create_dir_all(&sub).unwrap();
+        fs::write(textures.join("a.dds"), b"").unwrap();
+        fs::write(sub.join("b.dds"), b"").unwrap();
+
+        // Self-referential symlink back into textures, plus an alias of an existing subfolder
+        std::os::unix::fs::symlink(&textures, textures.join("loop")).unwrap();
+        std::os::unix::fs::symlink(&sub, textures.join("alias")).unwrap();
+
+        assert_eq!(count_texture_files(temp_dir.path()).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_definition_cap_keeps_required_libraries() {
+        let mut def_data = Vec::new();
+        for i in 0..(MAX_DSF_DEFINITIONS + 500) {
+            def_data.extend_from_slice(format!("objects/local_{}.obj\0", i).as_bytes());
+        }
+        def_data.extend_from_slice(b"opensceneryx/objects/tree.obj\0");
+        def_data.extend_from_slice(b"objects/after_cap.obj\0");
+
+        let mut collector = DefinitionCollector::new(true);
+        parse_definition_strings(&def_data, &mut collector);
+        let refs = collector.finish("object");
+
+        assert_eq!(refs.len(), MAX_DSF_DEFINITIONS + 1);
+        assert_eq!(
+            extract_required_libraries(&refs),
+            vec!["opensceneryx".to_string()]
+        );
+
+        let mut terrain = DefinitionCollector::new(false);
+        parse_definition_strings(&def_data, &mut terrain);
+        assert_eq!(terrain.finish("terrain").len(), MAX_DSF_DEFINITIONS);
+    }
+
+    #[test]
+    fn test_dual_purpose_classification() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let library_txt = "A\n800\nLIBRARY\n\nEXPORT mylib/tree.obj objects/tree.obj\n";
+
+        // library.txt with an empty Earth nav data folder has no scenery role
+        let library = temp_dir.path().join("Lib_Pack");
+        fs::create_dir_all(library.join("Earth nav data")).unwrap();
+        fs::write(library.join("library.txt"), library_txt).unwrap();
+        let info = classify_scenery(&library, temp_dir.path()).unwrap();
+        assert_eq!(info.category, SceneryCategory::Library);
+
+        // library.txt alongside an airport is classified by its scenery role
+        let airport = temp_dir.path().join("Airport_With_Lib");
+        fs::create_dir_all(airport.join("Earth nav data")).unwrap();
+        fs::write(airport.join("library.txt"), library_txt).unwrap();
+        fs::write(
+            airport.join("Earth nav data").join("apt.dat"),
+            "I\n1100 Version\n\n1 100 0 0 TEST Test Airport\n",
+        )
+        .unwrap();
+        let info = classify_scenery(&airport, temp_dir.path()).unwrap();
+        assert_eq!(info.category, SceneryCategory::Airport);
+        assert!(info.has_library_txt);
+        assert_eq!(info.exported_library_names, vec!["mylib".to_string()]);
+    }
+
+    #[test]
+    fn test_airport_icao_code_populated() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        let airport = root.join("Custom Scenery").join("KSEA_Seattle");
+        fs::create_dir_all(airport.join("Earth nav data")).unwrap();
+        fs::write(
+            airport.join("Earth nav data").join("apt.dat"),
+            "I\n1100 Version\n\n1 433 0 0 KSEA Seattle Tacoma\n\
+             1302 datum_lat 47.45\n1302 datum_lon -122.31\n",
+        )
+        .unwrap();
+
+        let info = classify_scenery(&airport, root).unwrap();
+        assert_eq!(info.category, SceneryCategory::Airport);
+        assert_eq!(info.icao_code.as_deref(), Some("KSEA"));
+        assert_eq!(info.airport_name.as_deref(), Some("Seattle Tacoma"));
+        assert_eq!(info.airport_elevation_ft, Some(433));
+        assert!(!info.icao_mismatch);
+
+        // The header ICAO wins, and the disagreeing icao_code row is flagged
+        fs::write(
+            airport.join("Earth nav data").join("apt.dat"),
+            "I\n1100 Version\n\n1 433 0 0 KSEA Seattle Tacoma\n\
+             1302 icao_code KBFI\n1302 datum_lat 47.45\n1302 datum_lon -122.31\n",
+        )
+        .unwrap();
+        let info = classify_scenery(&airport, root).unwrap();
+        assert_eq!(info.icao_code.as_deref(), Some("KSEA"));
+        assert!(info.icao_mismatch);
+    }
+
+    #[test]
+    fn test_xp11_global_airports_classification() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        let global = root.join("Custom Scenery").join("Global Airports");
+        fs::create_dir_all(global.join("Earth nav data")).unwrap();
+        fs::write(
+            global.join("Earth nav data").join("apt.dat"),
+            "I\n1100 Version\n\n1 100 0 0 TEST Test Airport\n",
+        )
+        .unwrap();
+
+        // Unknown version: unchanged
+        let info = classify_scenery(&global, root).unwrap();
+        assert_eq!(info.category, SceneryCategory::Airport);
+
+        fs::write(
+            root.join("Log.txt"),
+            "Log.txt for X-Plane 12.1.1-r1 (build 121101)\n",
+        )
+        .unwrap();
+        let info = classify_scenery(&global, root).unwrap();
+        assert_eq!(info.category, SceneryCategory::Airport);
+
+        fs::write(
+            root.join("Log.txt"),
+            "Log.txt for X-Plane 11.55r2 (build 115502)\n",
+        )
+        .unwrap();
+        let info = classify_scenery(&global, root).unwrap();
+        assert_eq!(info.category, SceneryCategory::DefaultAirport);
+    }
+
+    #[test]
+    fn test_unreadable_dsf_falls_back_to_structure() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        let package = root.join("Custom Scenery").join("Broken_Mesh");
+        let tile_dir = package.join("Earth nav data").join("+50+010");
+        fs::create_dir_all(&tile_dir).unwrap();
+
+        // Zero-byte DSF only: still indexed as Mesh instead of failing
+        fs::write(tile_dir.join("+50+010.dsf"), b"").unwrap();
+        assert_eq!(
+            find_dsf_files(&package).unwrap(),
+            vec![tile_dir.join("+50+010.dsf")]
+        );
+        let info = classify_scenery(&package, root).unwrap();
+        assert_eq!(info.category, SceneryCategory::Mesh);
+        assert!(info.has_dsf);
+
+        // A non-empty DSF is preferred over the empty one, even if it is corrupt
+        fs::write(tile_dir.join("+50+011.dsf"), b"not a dsf").unwrap();
+        assert_eq!(
+            find_dsf_files(&package).unwrap(),
+            vec![tile_dir.join("+50+011.dsf")]
+        );
+        let info = classify_scenery(&package, root).unwrap();
+        assert_eq!(info.category, SceneryCategory::Mesh);
+    }
+
+    #[test]
+    fn test_dsf_info_from_header() {
+        let header = DsfHeader {
+            is_overlay: true,
+            creation_agent: Some("WorldEditor".to_string()),
+            object_references: (0..30).map(|i| format!("objects/{}.obj", i)).collect(),
+            terrain_references: vec!["lib/g10/terrain10/forest.ter".to_string()],
+        };
+
+        let info = dsf_info_from_header(Path::new("Earth nav data/+50+010/+50+010.dsf"), header);
+        assert_eq!(info.dsf_path, "Earth nav data/+50+010/+50+010.dsf");
+        assert!(info.is_overlay);
+        assert_eq!(info.creation_agent.as_deref(), Some("WorldEditor"));
+        assert_eq!(info.object_reference_count, 30);
+        assert_eq!(info.sample_object_references.len(), DSF_INFO_SAMPLE_SIZE);
+        assert_eq!(info.sample_object_references[0], "objects/0.obj");
+        assert_eq!(info.terrain_reference_count, 1);
+        assert_eq!(info.sample_terrain_references.len(), 1);
+
+        // Packages without a DSF have nothing to report
+        let temp_dir = tempfile::tempdir().unwrap();
+        assert!(read_dsf_info(temp_dir.path()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_incomplete_package_detection() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let broken = temp_dir.path().join("Broken_Download");
+        fs::create_dir_all(broken.join("Earth nav data").join("+40-080")).unwrap();
+
+        let info = classify_scenery(&broken, temp_dir.path()).unwrap();
+        assert_eq!(info.category, SceneryCategory::Mesh);
+        assert!(info.is_incomplete());
+    }
+
+    #[test]
+    fn test_find_extracted_dsf_nested() {
+        let temp = tempfile::tempdir().unwrap();
+        let tile_dir = temp
+            .path()
+            .join("Scenery")
+            .join("Earth nav data")
+            .join("+50+010");
+        std::fs::create_dir_all(&tile_dir).unwrap();
+        std::fs::write(temp.path().join("Scenery").join("readme"), b"notes").unwrap();
+        std::fs::write(tile_dir.join("+55+013.dsf"), b"XPLNEDSF").unwrap();
+
+        let found = find_extracted_dsf(temp.path()).unwrap();
+        assert_eq!(found, tile_dir.join("+55+013.dsf"));
+
+        // Without a .dsf file, the first file is used as raw data
+        std::fs::remove_file(tile_dir.join("+55+013.dsf")).unwrap();
+        let found = find_extracted_dsf(temp.path()).unwrap();
+        assert_eq!(found, temp.path().join("Scenery").join("readme"));
+
+        let empty = tempfile::tempdir().unwrap();
+        assert!(find_extracted_dsf(empty.path()).is_none());
+    }
+
+    #[test]
+    fn test_decompress_dsf_nested_7z() {
+        let temp = tempfile::tempdir().unwrap();
+        let source = temp.path().join("source");
+        let tile_dir = source
+            .join("Scenery")
+            .join("Earth nav data")
+            .join("+50+010");
+        std::fs::create_dir_all(&tile_dir).unwrap();
+        std::fs::write(source.join("Scenery").join("readme"), b"notes").unwrap();
+        std::fs::write(tile_dir.join("+55+013.dsf"), b"XPLNEDSF").unwrap();
+
+        let archive = temp.path().join("+55+013.dsf");
+        sevenz_rust2::compress_to_path(&source, &archive).unwrap();
+        assert!(is_dsf_compressed(&archive).unwrap());
+
+        assert_eq!(decompress_dsf(&archive).unwrap(), b"XPLNEDSF");
+    }
+
     #[test]
     fn test_validate_apt_dat_format() {
-        // This test would need actual test files
-        // For now, just verify the function exists and compiles
+        assert!(is_apt_dat_header("I\n1100 Version - data cycle 2013.10\n"));
+        assert!(is_apt_dat_header("A\r\n850 Generated\r\n"));
+        assert!(is_apt_dat_header("\u{feff}I\n1300\n"));
+        // Unlisted 4-digit versions are accepted for future formats
+        assert!(is_apt_dat_header("I\n1400 Version\n"));
+
+        assert!(!is_apt_dat_header("I\n1foo bar\n"));
+        assert!(!is_apt_dat_header("I\n1 100 0 0 KSEA\n"));
+        assert!(!is_apt_dat_header("I\n900 Version\n"));
+        assert!(!is_apt_dat_header("Readme\n1100 Version\n"));
+        assert!(!is_apt_dat_header("I\n"));
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let apt_dat = temp_dir.path().join("apt.dat");
+        fs::write(&apt_dat, "I\n1200 Version - WorldEditor\n").unwrap();
+        assert!(validate_apt_dat(&apt_dat).unwrap());
+        fs::write(&apt_dat, "I\n1foo stray text file\n").unwrap();
+        assert!(!validate_apt_dat(&apt_dat).unwrap());
     }
 }
@@ -6,17 +6,23 @@
 use crate::database::{apply_migrations, open_connection, SceneryQueries, CURRENT_SCHEMA_VERSION};
 use crate::logger;
 use crate::models::{
-    SceneryCategory, SceneryIndex, SceneryIndexScanResult, SceneryIndexStats, SceneryIndexStatus,
-    SceneryManagerData, SceneryManagerEntry, SceneryPackageInfo,
+    IndexCategoryDiff, IndexExportDiff, IndexOrderDiff, MovedShortcutTarget, SceneryAuditChange,
+    SceneryAuditEntry, SceneryAuditState, SceneryCategory, SceneryIndex, SceneryIndexImportResult,
+    SceneryIndexScanResult, SceneryIndexStats, SceneryIndexStatus, SceneryManagerData,
+    SceneryManagerEntry, SceneryOrderingConfig, SceneryPackageInfo, TileCoverage,
 };
-use crate::scenery_classifier::classify_scenery;
+use crate::scenery_audit;
+use crate::scenery_classifier::{classify_scenery, is_orthophoto_package};
+use crate::scenery_packs_manager::{extract_folder_name, parse_ini, SceneryPacksManager};
+use crate::task_control::WalkMonitor;
 use anyhow::{anyhow, Result};
+use once_cell::sync::Lazy;
 use rayon::prelude::*;
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex, MutexGuard, TryLockError};
 use std::time::SystemTime;
 
 // ============================================================================
@@ -222,12 +228,12 @@ mod shortcut_resolver {
 
 /// Resolve Windows shortcut (.lnk) to actual path using Windows COM API
 #[cfg(windows)]
-fn resolve_shortcut(lnk_path: &Path) -> Option<PathBuf> {
+pub fn resolve_shortcut(lnk_path: &Path) -> Option<PathBuf> {
     shortcut_resolver::resolve(lnk_path)
 }
 
 #[cfg(not(windows))]
-fn resolve_shortcut(_lnk_path: &Path) -> Option<PathBuf> {
+pub fn resolve_shortcut(_lnk_path: &Path) -> Option<PathBuf> {
     None
 }
 
@@ -250,45 +256,203 @@ fn is_sam_folder_name(folder_name: &str) -> bool {
     has_sam_word || has_sam_suffix
 }
 
+/// Folder name prefixes of packages whose content is generated on the fly
+/// (AutoOrtho XPME_* packages and its z_autoortho / z_ao_* mount points)
+const DYNAMIC_PACKAGE_PREFIXES: &[&str] = &["xpme_", "z_autoortho", "z_ao_"];
+
+/// Check if a package is dynamically generated (e.g., by AutoOrtho).
+/// These trees change constantly and may be backed by a virtual filesystem,
+/// so staleness, conflict and mesh-overlap scans skip them.
+pub fn is_dynamic_package(folder_name: &str) -> bool {
+    let folder_lower = folder_name.to_lowercase();
+    DYNAMIC_PACKAGE_PREFIXES
+        .iter()
+        .any(|prefix| folder_lower.starts_with(prefix))
+}
+
+/// Check if a Custom Scenery entry must never be deleted through the scenery manager:
+/// the *GLOBAL_AIRPORTS* marker and the default X-Plane landmark / airport packages
+fn is_protected_scenery_folder(folder_name: &str) -> bool {
+    let folder_lower = folder_name.to_lowercase();
+    folder_name == "*GLOBAL_AIRPORTS*"
+        || folder_lower == "global airports"
+        || folder_lower.starts_with("x-plane landmarks")
+}
+
+/// Check if a folder should be pinned to FixedHighPriority: SAM libraries plus any
+/// user-defined glob patterns from the ordering config (matched case-insensitively)
+fn is_high_priority_folder_name(folder_name: &str, patterns: &[glob::Pattern]) -> bool {
+    let options = glob::MatchOptions {
+        case_sensitive: false,
+        ..Default::default()
+    };
+    is_sam_folder_name(folder_name)
+        || patterns
+            .iter()
+            .any(|p| p.matches_with(folder_name, options))
+}
+
+/// Keep a locked category (and its sub-priority) from the previously indexed
+/// version of a package on its freshly classified info
+fn carry_category_lock(info: &mut SceneryPackageInfo, previous: Option<&SceneryPackageInfo>) {
+    if let Some(previous) = previous.filter(|p| p.category_locked) {
+        info.category = previous.category.clone();
+        info.sub_priority = previous.sub_priority;
+        info.category_locked = true;
+    }
+}
+
+/// Compile the user-defined high-priority patterns, skipping invalid ones
+fn compile_high_priority_patterns(config: &SceneryOrderingConfig) -> Vec<glob::Pattern> {
+    config
+        .high_priority_patterns
+        .iter()
+        .filter_map(|p| glob::Pattern::new(p).ok())
+        .collect()
+}
+
 /// Common sorting comparison for non-FixedHighPriority scenery packages
 /// This ensures consistent ordering between rebuild_index, recalculate_sort_order, and reset_sort_order
+/// `mesh_regions` maps Mesh folder names to their south-west tile; it is only populated
+/// when region sorting is enabled in the ordering config
 fn compare_packages_for_sorting(
     name_a: &str,
     info_a: &SceneryPackageInfo,
     name_b: &str,
     info_b: &SceneryPackageInfo,
+    mesh_regions: &HashMap<String, (i32, i32)>,
 ) -> std::cmp::Ordering {
-    let priority_a = (info_a.category.priority(), info_a.sub_priority);
-    let priority_b = (info_b.category.priority(), info_b.sub_priority);
-
-    match priority_a.cmp(&priority_b) {
-        std::cmp::Ordering::Equal => {
-            if info_a.category == info_b.category
-                && matches!(
-                    info_a.category,
-                    SceneryCategory::Overlay
-                        | SceneryCategory::AirportMesh
-                        | SceneryCategory::Mesh
-                )
-            {
-                // For Mesh category with sub_priority > 0 (XPME), sort only by folder name
-                // XPME mesh should be at the bottom of Mesh category, sorted alphabetically
-                if info_a.category == SceneryCategory::Mesh && info_a.sub_priority > 0 {
-                    name_a.to_lowercase().cmp(&name_b.to_lowercase())
-                } else {
-                    // Non-XPME: sort by tile count first, then folder name
-                    match info_a.earth_nav_tile_count.cmp(&info_b.earth_nav_tile_count) {
-                        std::cmp::Ordering::Equal => {
-                            name_a.to_lowercase().cmp(&name_b.to_lowercase())
-                        }
-                        other => other,
-                    }
-                }
-            } else {
-                name_a.to_lowercase().cmp(&name_b.to_lowercase())
+    let key_a = package_sort_key(name_a, info_a, mesh_regions);
+    key_a.cmp(&package_sort_key(name_b, info_b, mesh_regions))
+}
+
+/// Total sort key of a package, so the comparison stays transitive when packages
+/// with and without a mesh region are mixed:
+/// (category priority, sub_priority, orthophotos first, region (packages without one last),
+/// tile count, name)
+/// Region and tile count only apply to Overlay/AirportMesh/Mesh; XPME mesh
+/// (Mesh with sub_priority > 0) sorts by folder name only
+fn package_sort_key(
+    name: &str,
+    info: &SceneryPackageInfo,
+    mesh_regions: &HashMap<String, (i32, i32)>,
+) -> (u8, u8, bool, (bool, Option<(i32, i32)>), u32, String) {
+    let by_coverage = matches!(
+        info.category,
+        SceneryCategory::Overlay | SceneryCategory::AirportMesh | SceneryCategory::Mesh
+    ) && !(info.category == SceneryCategory::Mesh && info.sub_priority > 0);
+
+    let region = if by_coverage {
+        mesh_regions.get(name).copied()
+    } else {
+        None
+    };
+    // Region sort (opt-in) orders by covered tile; the rest by tile count
+    let tile_count = if by_coverage && region.is_none() {
+        info.earth_nav_tile_count
+    } else {
+        0
+    };
+
+    (
+        info.category.priority(),
+        info.sub_priority,
+        // Orthophotos must cover regular mesh of the same area
+        !info.is_orthophoto(),
+        (region.is_none(), region),
+        tile_count,
+        name.to_lowercase(),
+    )
+}
+
+/// Assign sort_order from each package's position in `sorted_names`
+/// Returns true if any sort_order changed
+fn assign_sort_order(index: &mut SceneryIndex, sorted_names: &[String]) -> bool {
+    let mut has_changes = false;
+    for (new_order, folder_name) in sorted_names.iter().enumerate() {
+        if let Some(info) = index.packages.get_mut(folder_name) {
+            let new_order_u32 = new_order as u32;
+            if info.sort_order != new_order_u32 {
+                has_changes = true;
+                info.sort_order = new_order_u32;
             }
         }
-        other => other,
+    }
+    has_changes
+}
+
+/// index_metadata key holding the user's SceneryOrderingConfig (JSON)
+const ORDERING_CONFIG_KEY: &str = "ordering_config";
+
+/// index_metadata key holding the enabled states captured when entering safe mode
+const SAFE_MODE_SNAPSHOT_KEY: &str = "safe_mode_snapshot";
+
+/// index_metadata key holding the disabled packages seen with missing libraries (JSON list)
+const MISSING_LIBRARY_DISABLED_KEY: &str = "missing_library_disabled";
+const INDEX_PARALLELISM_KEY: &str = "index_parallelism";
+
+/// Whether a category stays enabled in safe mode (airports, libraries and fixed high priority)
+fn is_safe_mode_essential(category: &SceneryCategory) -> bool {
+    matches!(
+        category,
+        SceneryCategory::FixedHighPriority
+            | SceneryCategory::Airport
+            | SceneryCategory::DefaultAirport
+            | SceneryCategory::Library
+    )
+}
+
+/// Serializes rebuild_index / update_index
+/// Managers are created per command, so the lock has to be process-wide. A second scan
+/// waits for the running one and then starts from the index it saved, instead of both
+/// scanning from the same snapshot and the later save overwriting the earlier one
+static INDEX_SCAN_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// Dedicated classification thread pool and the thread count it was built with
+/// Built on first use and kept until the configured parallelism changes, so scans
+/// don't spawn and tear down a set of threads every time
+static INDEX_THREAD_POOL: Lazy<Mutex<Option<(usize, Arc<rayon::ThreadPool>)>>> =
+    Lazy::new(|| Mutex::new(None));
+
+/// Get the classification thread pool for `threads`, building it if needed
+fn index_thread_pool(
+    threads: usize,
+) -> Result<Arc<rayon::ThreadPool>, rayon::ThreadPoolBuildError> {
+    let mut cached = INDEX_THREAD_POOL.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((size, pool)) = cached.as_ref() {
+        if *size == threads {
+            return Ok(Arc::clone(pool));
+        }
+    }
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()?;
+    let pool = Arc::new(pool);
+    *cached = Some((threads, Arc::clone(&pool)));
+    Ok(pool)
+}
+
+/// Drop the cached pool when the parallelism changes; the next scan builds a new one
+fn reset_index_thread_pool(threads: usize) {
+    let mut cached = INDEX_THREAD_POOL.lock().unwrap_or_else(|e| e.into_inner());
+    if cached.as_ref().is_some_and(|(size, _)| *size != threads) {
+        *cached = None;
+    }
+}
+
+/// Acquire INDEX_SCAN_LOCK, logging when another scan has to finish first
+fn lock_index_scan() -> MutexGuard<'static, ()> {
+    match INDEX_SCAN_LOCK.try_lock() {
+        Ok(guard) => guard,
+        Err(TryLockError::Poisoned(e)) => e.into_inner(),
+        Err(TryLockError::WouldBlock) => {
+            logger::log_info(
+                "Another scenery index scan is running, waiting for it to finish",
+                Some("scenery_index"),
+            );
+            INDEX_SCAN_LOCK.lock().unwrap_or_else(|e| e.into_inner())
+        }
     }
 }
 
@@ -297,6 +461,8 @@ pub struct SceneryIndexManager {
     xplane_path: PathBuf,
     /// Lazy-initialized database connection
     db_initialized: Mutex<bool>,
+    /// Progress / cancellation for index rebuilds and updates
+    walk_monitor: Option<Arc<WalkMonitor>>,
 }
 
 impl SceneryIndexManager {
@@ -305,6 +471,38 @@ impl SceneryIndexManager {
         Self {
             xplane_path: xplane_path.to_path_buf(),
             db_initialized: Mutex::new(false),
+            walk_monitor: None,
+        }
+    }
+
+    /// Report classified packages to `monitor` during rebuild_index / update_index
+    /// and abort them when its scan is cancelled
+    pub fn with_walk_monitor(mut self, monitor: Arc<WalkMonitor>) -> Self {
+        self.walk_monitor = Some(monitor);
+        self
+    }
+
+    /// Report a package about to be classified
+    /// Returns false once the scan has been cancelled
+    fn walk_step(&self, path: &Path) -> bool {
+        match &self.walk_monitor {
+            Some(monitor) => monitor.step(path).is_ok(),
+            None => true,
+        }
+    }
+
+    /// Tell the attached walk monitor how many packages are about to be classified
+    fn walk_expect(&self, packages: usize) {
+        if let Some(monitor) = &self.walk_monitor {
+            monitor.expect_steps(packages as u64);
+        }
+    }
+
+    /// Fail if the attached walk monitor's scan has been cancelled
+    fn check_walk_cancelled(&self) -> Result<()> {
+        match &self.walk_monitor {
+            Some(monitor) => monitor.check_cancelled(),
+            None => Ok(()),
         }
     }
 
@@ -349,6 +547,60 @@ impl SceneryIndexManager {
         SceneryQueries::save_all(&mut conn, index).map_err(|e| anyhow!("{}", e))
     }
 
+    /// Write the full index (categories, sort order, enabled state) to a pretty JSON file
+    /// Returns the number of exported packages
+    pub fn export_json(&self, path: &Path) -> Result<usize> {
+        let index = self.load_index()?;
+        let json = serde_json::to_string_pretty(&index)?;
+        fs::write(path, json).map_err(|e| anyhow!("Failed to write {:?}: {}", path, e))?;
+
+        logger::log_info(
+            &format!(
+                "Exported {} scenery package(s) to {:?}",
+                index.packages.len(),
+                path
+            ),
+            Some("scenery_index"),
+        );
+        Ok(index.packages.len())
+    }
+
+    /// Merge a JSON export into the index by folder name, skipping packages whose folders
+    /// no longer exist. Exported packages replace their local entries; packages that are
+    /// only installed locally are kept
+    pub fn import_json(&self, path: &Path) -> Result<SceneryIndexImportResult> {
+        let json =
+            fs::read_to_string(path).map_err(|e| anyhow!("Failed to read {:?}: {}", path, e))?;
+        let mut imported = parse_index_export(&json)?;
+
+        let custom_scenery_path = self.xplane_path.join("Custom Scenery");
+        let skipped = drop_missing_packages(&mut imported, &custom_scenery_path);
+        let imported_count = imported.packages.len();
+
+        let mut index = self.load_index()?;
+        let kept = merge_index_export(&mut index, imported);
+        index.version = CURRENT_SCHEMA_VERSION as u32;
+        index.last_updated = SystemTime::now();
+        self.save_index(&index)?;
+        scenery_audit::clear(&self.xplane_path, "index import");
+
+        logger::log_info(
+            &format!(
+                "Imported {} scenery package(s) from {:?} ({} skipped, {} local kept)",
+                imported_count,
+                path,
+                skipped.len(),
+                kept
+            ),
+            Some("scenery_index"),
+        );
+        Ok(SceneryIndexImportResult {
+            imported: imported_count,
+            skipped,
+            kept,
+        })
+    }
+
     /// Update or add a single package in the index
     pub fn update_package(&self, package_info: SceneryPackageInfo) -> Result<()> {
         self.ensure_initialized()?;
@@ -356,8 +608,111 @@ impl SceneryIndexManager {
         SceneryQueries::update_package(&mut conn, &package_info).map_err(|e| anyhow!("{}", e))
     }
 
+    /// Load the scenery ordering config (defaults if none has been saved)
+    pub fn get_ordering_config(&self) -> Result<SceneryOrderingConfig> {
+        self.ensure_initialized()?;
+        let conn = open_connection().map_err(|e| anyhow!("{}", e))?;
+        let value = SceneryQueries::get_metadata(&conn, ORDERING_CONFIG_KEY)
+            .map_err(|e| anyhow!("{}", e))?;
+
+        match value {
+            Some(json) => Ok(serde_json::from_str(&json)?),
+            None => Ok(SceneryOrderingConfig::default()),
+        }
+    }
+
+    /// Save the scenery ordering config
+    pub fn set_ordering_config(&self, config: &SceneryOrderingConfig) -> Result<()> {
+        self.ensure_initialized()?;
+        let conn = open_connection().map_err(|e| anyhow!("{}", e))?;
+        let json = serde_json::to_string(config)?;
+        SceneryQueries::set_metadata(&conn, ORDERING_CONFIG_KEY, &json)
+            .map_err(|e| anyhow!("{}", e))
+    }
+
+    /// Get the number of threads used to classify packages (0 = rayon default)
+    pub fn get_index_parallelism(&self) -> Result<usize> {
+        self.ensure_initialized()?;
+        let conn = open_connection().map_err(|e| anyhow!("{}", e))?;
+        let value = SceneryQueries::get_metadata(&conn, INDEX_PARALLELISM_KEY)
+            .map_err(|e| anyhow!("{}", e))?;
+
+        Ok(value.and_then(|v| v.parse().ok()).unwrap_or(0))
+    }
+
+    /// Set the number of threads used to classify packages.
+    /// Lower values reduce I/O thrashing on spinning disks; 0 restores the rayon default
+    pub fn set_index_parallelism(&self, threads: usize) -> Result<()> {
+        self.ensure_initialized()?;
+        let conn = open_connection().map_err(|e| anyhow!("{}", e))?;
+        if threads == 0 {
+            SceneryQueries::delete_metadata(&conn, INDEX_PARALLELISM_KEY)
+                .map_err(|e| anyhow!("{}", e))?;
+        } else {
+            SceneryQueries::set_metadata(&conn, INDEX_PARALLELISM_KEY, &threads.to_string())
+                .map_err(|e| anyhow!("{}", e))?;
+        }
+        reset_index_thread_pool(threads);
+        Ok(())
+    }
+
+    /// Run a parallel classification job, using a dedicated thread pool when the
+    /// index parallelism has been configured and the global rayon pool otherwise
+    fn with_index_parallelism<R, F>(&self, op: F) -> R
+    where
+        R: Send,
+        F: FnOnce() -> R + Send,
+    {
+        let threads = self.get_index_parallelism().unwrap_or(0);
+        if threads == 0 {
+            return op();
+        }
+
+        match index_thread_pool(threads) {
+            Ok(pool) => {
+                crate::log_debug!(
+                    &format!("Classifying scenery with {} threads", threads),
+                    "scenery_index"
+                );
+                pool.install(op)
+            }
+            Err(e) => {
+                logger::log_error(
+                    &format!("Failed to build classification thread pool: {}", e),
+                    Some("scenery_index"),
+                );
+                op()
+            }
+        }
+    }
+
+    /// Collect the south-west tile of each Mesh package for region sorting
+    /// Returns an empty map unless region sorting is enabled in the ordering config
+    fn collect_mesh_regions<'a>(
+        &self,
+        packages: impl Iterator<Item = &'a SceneryPackageInfo>,
+    ) -> HashMap<String, (i32, i32)> {
+        let config = self.get_ordering_config().unwrap_or_default();
+        if !config.sort_mesh_by_region {
+            return HashMap::new();
+        }
+
+        let custom_scenery_path = self.xplane_path.join("Custom Scenery");
+        packages
+            .filter(|info| info.category == SceneryCategory::Mesh)
+            .filter(|info| !is_dynamic_package(&info.folder_name))
+            .filter_map(|info| {
+                let coords = get_mesh_dsf_coordinates(&custom_scenery_path.join(&info.folder_name))?;
+                let south_west = coords.into_iter().min()?;
+                Some((info.folder_name.clone(), south_west))
+            })
+            .collect()
+    }
+
     /// Rebuild entire index by scanning all scenery packages
+    /// Waits for a rebuild or update that is already running
     pub fn rebuild_index(&self) -> Result<SceneryIndex> {
+        let _scan_guard = lock_index_scan();
         let custom_scenery_path = self.xplane_path.join("Custom Scenery");
         if !custom_scenery_path.exists() {
             return Err(anyhow!("Custom Scenery folder not found"));
@@ -440,13 +795,51 @@ impl SceneryIndexManager {
             Some("scenery_index"),
         );
 
+        // Dynamically generated packages (AutoOrtho XPME_* etc.) keep their indexed entry
+        // instead of being classified again, like update_index does
+        let (reused_packages, scenery_folders): (Vec<_>, Vec<_>) = scenery_folders
+            .into_iter()
+            .map(|folder| {
+                let entry_name = match shortcut_target_map.get(&folder) {
+                    Some((shortcut_name, _)) => Some(shortcut_name.clone()),
+                    None => folder
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .map(|n| n.to_string()),
+                };
+                let reused = entry_name
+                    .filter(|name| is_dynamic_package(name))
+                    .and_then(|name| existing_index.packages.get(&name).cloned());
+                (reused, folder)
+            })
+            .partition(|(reused, _)| reused.is_some());
+        let reused_packages: Vec<SceneryPackageInfo> = reused_packages
+            .into_iter()
+            .filter_map(|(reused, _)| reused)
+            .collect();
+        let scenery_folders: Vec<PathBuf> = scenery_folders
+            .into_iter()
+            .map(|(_, folder)| folder)
+            .collect();
+        if !reused_packages.is_empty() {
+            logger::log_info(
+                &format!(
+                    "Keeping {} dynamically generated packages without re-classifying",
+                    reused_packages.len()
+                ),
+                Some("scenery_index"),
+            );
+        }
+
         // Classify all packages
         // Track which path each package came from to correctly handle shortcuts
+        self.walk_expect(scenery_folders.len());
         // Use sequential processing in debug log mode for ordered logs, parallel otherwise
         let packages_with_paths: Vec<(PathBuf, SceneryPackageInfo)> = if logger::is_debug_enabled() {
             // Sequential processing for ordered debug logs
             scenery_folders
                 .iter()
+                .filter(|folder| self.walk_step(folder))
                 .filter_map(|folder| match classify_scenery(folder, &self.xplane_path) {
                     Ok(info) => Some((folder.clone(), info)),
                     Err(e) => {
@@ -460,20 +853,24 @@ impl SceneryIndexManager {
                 .collect()
         } else {
             // Parallel processing for better performance when not in debug mode
-            scenery_folders
-                .par_iter()
-                .filter_map(|folder| match classify_scenery(folder, &self.xplane_path) {
-                    Ok(info) => Some((folder.clone(), info)),
-                    Err(e) => {
-                        logger::log_info(
-                            &format!("Failed to classify {:?}: {}", folder, e),
-                            Some("scenery_index"),
-                        );
-                        None
-                    }
-                })
-                .collect()
+            self.with_index_parallelism(|| {
+                scenery_folders
+                    .par_iter()
+                    .filter(|folder| self.walk_step(folder))
+                    .filter_map(|folder| match classify_scenery(folder, &self.xplane_path) {
+                        Ok(info) => Some((folder.clone(), info)),
+                        Err(e) => {
+                            logger::log_info(
+                                &format!("Failed to classify {:?}: {}", folder, e),
+                                Some("scenery_index"),
+                            );
+                            None
+                        }
+                    })
+                    .collect()
+            })
         };
+        self.check_walk_cancelled()?;
 
         // Post-process: Set folder_name and actual_path for shortcut entries
         // For shortcuts, use the shortcut name (not target folder name) to avoid conflicts
@@ -493,13 +890,20 @@ impl SceneryIndexManager {
             }
             packages_vec.push(info);
         }
+        packages_vec.extend(reused_packages);
 
         // Post-process: Detect airport-associated mesh packages
         self.detect_airport_mesh_packages(&mut packages_vec);
 
+        // Carry locked categories forward, like enabled states
+        for info in packages_vec.iter_mut() {
+            carry_category_lock(info, existing_index.packages.get(&info.folder_name));
+        }
+
         // Sort packages using the common sorting function
+        let mesh_regions = self.collect_mesh_regions(packages_vec.iter());
         packages_vec.sort_by(|a, b| {
-            compare_packages_for_sorting(&a.folder_name, a, &b.folder_name, b)
+            compare_packages_for_sorting(&a.folder_name, a, &b.folder_name, b, &mesh_regions)
         });
 
         // Assign sort_order and apply enabled states from index
@@ -524,6 +928,7 @@ impl SceneryIndexManager {
         };
 
         self.save_index(&index)?;
+        scenery_audit::clear(&self.xplane_path, "index rebuild");
         logger::log_info(
             &format!(
                 "Scenery index rebuilt with {} packages",
@@ -533,23 +938,48 @@ impl SceneryIndexManager {
         );
 
         // Update missing libraries for all packages using the complete index
-        let index = self.update_missing_libraries(index)?;
+        let index = self.update_missing_libraries(index, None)?;
 
         Ok(index)
     }
 
-    /// Update missing libraries for all packages using the complete index
-    fn update_missing_libraries(&self, mut index: SceneryIndex) -> Result<SceneryIndex> {
-        logger::log_info(
-            "Updating missing libraries for all packages...",
-            Some("scenery_index"),
-        );
+    /// Update missing libraries using the complete index
+    ///
+    /// When `only` is given, just those packages are re-checked and all others keep
+    /// their previously computed missing libraries.
+    fn update_missing_libraries(
+        &self,
+        mut index: SceneryIndex,
+        only: Option<&HashSet<String>>,
+    ) -> Result<SceneryIndex> {
+        match only {
+            Some(folders) => logger::log_info(
+                &format!(
+                    "Updating missing libraries for {} packages...",
+                    folders.len()
+                ),
+                Some("scenery_index"),
+            ),
+            None => logger::log_info(
+                "Updating missing libraries for all packages...",
+                Some("scenery_index"),
+            ),
+        }
+
+        // Remember disabled packages that lack a library, before and after the re-check,
+        // so enable_packages_with_satisfied_deps can find them once the library is installed
+        let mut missing_library_disabled = self.load_missing_library_disabled()?;
+        missing_library_disabled.extend(disabled_with_missing_libraries(&index));
 
         // Build library index from the complete scenery index
         let library_index = build_library_index_from_scenery_index(&index);
 
         // Update each package's missing_libraries
         for (folder_name, package_info) in index.packages.iter_mut() {
+            if only.is_some_and(|folders| !folders.contains(folder_name)) {
+                continue;
+            }
+
             let mut missing = Vec::new();
 
             for lib_name in &package_info.required_libraries {
@@ -576,12 +1006,76 @@ impl SceneryIndexManager {
 
         // Save the updated index
         self.save_index(&index)?;
+
+        missing_library_disabled.extend(disabled_with_missing_libraries(&index));
+        missing_library_disabled
+            .retain(|name| index.packages.get(name).is_some_and(|info| !info.enabled));
+        self.save_missing_library_disabled(&missing_library_disabled)?;
+        logger::log_info("Missing libraries updated", Some("scenery_index"));
+
+        Ok(index)
+    }
+
+    /// Load the disabled packages recorded with missing libraries by update_missing_libraries
+    fn load_missing_library_disabled(&self) -> Result<HashSet<String>> {
+        self.ensure_initialized()?;
+        let conn = open_connection().map_err(|e| anyhow!("{}", e))?;
+        let value = SceneryQueries::get_metadata(&conn, MISSING_LIBRARY_DISABLED_KEY)
+            .map_err(|e| anyhow!("{}", e))?;
+        Ok(value
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default())
+    }
+
+    fn save_missing_library_disabled(&self, folders: &HashSet<String>) -> Result<()> {
+        let mut folders: Vec<&String> = folders.iter().collect();
+        folders.sort();
+        let json = serde_json::to_string(&folders)?;
+        let conn = open_connection().map_err(|e| anyhow!("{}", e))?;
+        SceneryQueries::set_metadata(&conn, MISSING_LIBRARY_DISABLED_KEY, &json)
+            .map_err(|e| anyhow!("{}", e))
+    }
+
+    /// Re-check missing libraries for all packages, then re-enable disabled packages
+    /// that were seen with missing libraries and have none now (e.g. after installing
+    /// the library). The change goes through `batch_update_entries`, so it is
+    /// recorded in the audit log and can be reverted with `undo_last`
+    /// Returns the folder names of the re-enabled packages
+    pub fn enable_packages_with_satisfied_deps(&self) -> Result<Vec<String>> {
+        let index = self.update_missing_libraries(self.load_index()?, None)?;
+        let mut tracked = self.load_missing_library_disabled()?;
+
+        let enabled = newly_satisfied_packages(&tracked, &index);
+        if enabled.is_empty() {
+            return Ok(enabled);
+        }
+
+        let updates: Vec<crate::models::SceneryEntryUpdate> = enabled
+            .iter()
+            .filter_map(|name| index.packages.get(name))
+            .map(|info| crate::models::SceneryEntryUpdate {
+                folder_name: info.folder_name.clone(),
+                enabled: true,
+                sort_order: info.sort_order,
+            })
+            .collect();
+        self.batch_update_entries(&updates)?;
+
+        // Undoing the re-enable leaves them disabled on purpose, so stop tracking them
+        for name in &enabled {
+            tracked.remove(name);
+        }
+        self.save_missing_library_disabled(&tracked)?;
+
         logger::log_info(
-            "Missing libraries updated for all packages",
+            &format!(
+                "Re-enabled {} packages whose libraries are now installed",
+                enabled.len()
+            ),
             Some("scenery_index"),
         );
 
-        Ok(index)
+        Ok(enabled)
     }
 
     /// Recalculate sort_order for all packages using the same sorting logic as rebuild_index
@@ -591,12 +1085,15 @@ impl SceneryIndexManager {
             return;
         }
 
-        // Promote SAM libraries to FixedHighPriority before sorting
+        // Promote SAM libraries (and user-defined high-priority libraries) to FixedHighPriority
+        let config = self.get_ordering_config().unwrap_or_default();
+        let high_priority_patterns = compile_high_priority_patterns(&config);
         for (name, info) in index.packages.iter_mut() {
-            if is_sam_folder_name(name)
+            if is_high_priority_folder_name(name, &high_priority_patterns)
                 && info.has_library_txt
                 && !info.has_dsf
                 && !info.has_apt_dat
+                && !info.category_locked
             {
                 if info.category != SceneryCategory::FixedHighPriority {
                     info.category = SceneryCategory::FixedHighPriority;
@@ -632,8 +1129,9 @@ impl SceneryIndexManager {
             .filter(|(_, info)| info.category != SceneryCategory::FixedHighPriority)
             .collect();
 
+        let mesh_regions = self.collect_mesh_regions(other_packages.iter().map(|(_, info)| *info));
         other_packages.sort_by(|(name_a, info_a), (name_b, info_b)| {
-            compare_packages_for_sorting(name_a, info_a, name_b, info_b)
+            compare_packages_for_sorting(name_a, info_a, name_b, info_b, &mesh_regions)
         });
 
         // Collect sorted names and update sort_order
@@ -658,8 +1156,89 @@ impl SceneryIndexManager {
         );
     }
 
+    /// Lock or unlock a package's category
+    /// A locked category was chosen by the user and survives re-classification
+    pub fn set_category_locked(&self, folder_name: &str, locked: bool) -> Result<()> {
+        self.ensure_initialized()?;
+        let conn = open_connection().map_err(|e| anyhow!("{}", e))?;
+        SceneryQueries::set_category_locked(&conn, folder_name, locked)
+            .map_err(|e| anyhow!("{}", e))?;
+        Ok(())
+    }
+
+    /// Move a package to another category and re-sort the index so it lands in that
+    /// category's block. The category is locked, so later update_index and
+    /// rebuild_index runs keep it instead of re-classifying the package.
+    /// `None` clears the lock and puts the package back in its classified category.
+    /// The change and the re-sort are recorded as one audit entry, so undo_last reverts both
+    pub fn set_category(&self, folder_name: &str, category: Option<SceneryCategory>) -> Result<()> {
+        let before = self.load_index()?;
+        let Some(info) = before.packages.get(folder_name) else {
+            return Err(anyhow!("Package not found in index: {}", folder_name));
+        };
+
+        let (category, sub_priority, locked) = match category {
+            Some(category) => (category, info.sub_priority, true),
+            None => {
+                let classified = self.reclassify_unlocked(info)?;
+                (classified.category, classified.sub_priority, false)
+            }
+        };
+
+        let mut index = self.load_index()?;
+        if let Some(info) = index.packages.get_mut(folder_name) {
+            info.category = category.clone();
+            info.sub_priority = sub_priority;
+            info.category_locked = locked;
+        }
+        self.recalculate_sort_order(&mut index);
+        index.last_updated = SystemTime::now();
+        self.save_index(&index)?;
+        scenery_audit::record(
+            &self.xplane_path,
+            scenery_audit::OP_SET_CATEGORY,
+            index_changes(&before, &index),
+        );
+
+        logger::log_info(
+            &format!("Set category of {} to {:?}", folder_name, category),
+            Some("scenery_index"),
+        );
+
+        Ok(())
+    }
+
+    /// Classify a package again, ignoring its locked category
+    /// Airport mesh detection needs the other packages, so it runs over the whole index
+    fn reclassify_unlocked(&self, info: &SceneryPackageInfo) -> Result<SceneryPackageInfo> {
+        let custom_scenery_path = self.xplane_path.join("Custom Scenery");
+        let folder_path = custom_scenery_path.join(&info.folder_name);
+        let scenery_path = match &info.actual_path {
+            Some(actual_path) if !folder_path.is_dir() => PathBuf::from(actual_path),
+            _ => folder_path,
+        };
+        let mut classified = classify_scenery(&scenery_path, &self.xplane_path)?;
+        classified.folder_name = info.folder_name.clone();
+
+        let mut packages: Vec<SceneryPackageInfo> = self
+            .load_index()?
+            .packages
+            .into_values()
+            .filter(|p| p.folder_name != info.folder_name)
+            .collect();
+        packages.push(classified);
+        self.detect_airport_mesh_packages(&mut packages);
+
+        packages
+            .into_iter()
+            .find(|p| p.folder_name == info.folder_name)
+            .ok_or_else(|| anyhow!("Package not found in index: {}", info.folder_name))
+    }
+
     /// Update index incrementally - only re-classify modified packages
+    /// Waits for a rebuild or update that is already running
     pub fn update_index(&self) -> Result<SceneryIndex> {
+        let _scan_guard = lock_index_scan();
         let custom_scenery_path = self.xplane_path.join("Custom Scenery");
         if !custom_scenery_path.exists() {
             return Err(anyhow!("Custom Scenery folder not found"));
@@ -667,6 +1246,11 @@ impl SceneryIndexManager {
 
         let mut index = self.load_index()?;
 
+        // Snapshot exported libraries so only packages affected by changes need
+        // their missing libraries recomputed
+        let old_library_index = build_library_index_from_scenery_index(&index);
+        let mut changed_packages: HashSet<String> = HashSet::new();
+
         // Track shortcuts by their target path
         // Key: target path, Value: (shortcut_name without .lnk, normalized_target_path for ini)
         let mut shortcut_target_map: HashMap<PathBuf, (String, String)> = HashMap::new();
@@ -734,7 +1318,7 @@ impl SceneryIndexManager {
                 // Skip dynamic content packages (e.g., AutoOrtho XPME_* packages)
                 // These packages generate content on-the-fly and their modification time
                 // changes frequently, which would cause unnecessary re-indexing
-                if name.starts_with("XPME_") {
+                if is_dynamic_package(name) {
                     // Only update if not in index (new package)
                     return !index.packages.contains_key(*name);
                 }
@@ -763,11 +1347,13 @@ impl SceneryIndexManager {
 
             // Classify updated packages
             // Track which path each package came from to correctly handle shortcuts
+            self.walk_expect(packages_to_update.len());
             // Use sequential processing in debug log mode for ordered logs, parallel otherwise
             let packages_with_paths: Vec<(PathBuf, SceneryPackageInfo)> = if logger::is_debug_enabled() {
                 // Sequential processing for ordered debug logs
                 packages_to_update
                     .iter()
+                    .filter(|folder| self.walk_step(folder))
                     .filter_map(|folder| {
                         classify_scenery(folder, &self.xplane_path)
                             .ok()
@@ -776,15 +1362,19 @@ impl SceneryIndexManager {
                     .collect()
             } else {
                 // Parallel processing for better performance when not in debug mode
-                packages_to_update
-                    .par_iter()
-                    .filter_map(|folder| {
-                        classify_scenery(folder, &self.xplane_path)
-                            .ok()
-                            .map(|info| (folder.clone(), info))
-                    })
-                    .collect()
+                self.with_index_parallelism(|| {
+                    packages_to_update
+                        .par_iter()
+                        .filter(|folder| self.walk_step(folder))
+                        .filter_map(|folder| {
+                            classify_scenery(folder, &self.xplane_path)
+                                .ok()
+                                .map(|info| (folder.clone(), info))
+                        })
+                        .collect()
+                })
             };
+            self.check_walk_cancelled()?;
 
             for (path, mut info) in packages_with_paths {
                 // Check if this entry came from a shortcut
@@ -793,12 +1383,16 @@ impl SceneryIndexManager {
                     info.folder_name = shortcut_name.clone();
                     info.actual_path = Some(actual_path.clone());
                 }
+                // Only the file flags are refreshed for locked packages
+                carry_category_lock(&mut info, index.packages.get(&info.folder_name));
+                changed_packages.insert(info.folder_name.clone());
                 index.packages.insert(info.folder_name.clone(), info);
             }
 
             // After adding new packages, recalculate sort_order using the same logic as rebuild_index
             // This ensures incremental updates produce the same ordering as full rebuilds
             self.recalculate_sort_order(&mut index);
+            scenery_audit::clear(&self.xplane_path, "index update");
         }
 
         // Also update actual_path for existing entries that are shortcuts
@@ -816,7 +1410,21 @@ impl SceneryIndexManager {
         }
 
         index.last_updated = SystemTime::now();
-        let index = self.update_missing_libraries(index)?;
+
+        // Recompute missing libraries for changed packages and for packages requiring
+        // a library whose provider was added, removed or changed
+        let new_library_index = build_library_index_from_scenery_index(&index);
+        let changed_libraries = diff_library_indexes(&old_library_index, &new_library_index);
+        for (folder_name, info) in &index.packages {
+            if info
+                .required_libraries
+                .iter()
+                .any(|lib| changed_libraries.contains(lib))
+            {
+                changed_packages.insert(folder_name.clone());
+            }
+        }
+        let index = self.update_missing_libraries(index, Some(&changed_packages))?;
 
         Ok(index)
     }
@@ -842,6 +1450,32 @@ impl SceneryIndexManager {
         Ok(index.packages.get(folder_name).cloned())
     }
 
+    /// Read the DSF header classification would use for a package (diagnostics)
+    /// Shortcut entries are resolved through the actual_path stored in the index
+    pub fn get_dsf_info(&self, folder_name: &str) -> Result<Option<crate::models::DsfInfo>> {
+        let folder_path = self.xplane_path.join("Custom Scenery").join(folder_name);
+        let scenery_path = if folder_path.is_dir() {
+            folder_path
+        } else {
+            self.get_package(folder_name)?
+                .and_then(|info| info.actual_path)
+                .map(PathBuf::from)
+                .ok_or_else(|| anyhow!("Scenery folder not found: {}", folder_name))?
+        };
+
+        crate::scenery_classifier::read_dsf_info(&scenery_path)
+    }
+
+    /// Get the packages of one category sorted by sort_order, without loading the whole index
+    pub fn get_packages_by_category(
+        &self,
+        category: &SceneryCategory,
+    ) -> Result<Vec<SceneryPackageInfo>> {
+        self.ensure_initialized()?;
+        let conn = open_connection().map_err(|e| anyhow!("{}", e))?;
+        SceneryQueries::load_by_category(&conn, category).map_err(|e| anyhow!("{}", e))
+    }
+
     /// Get or classify a package (uses cache if available and not stale)
     pub fn get_or_classify(&self, folder_path: &Path) -> Result<SceneryPackageInfo> {
         let folder_name = folder_path
@@ -920,7 +1554,182 @@ impl SceneryIndexManager {
         })
     }
 
-    /// Get index statistics
+    /// Re-resolve every shortcut (.lnk) entry and report those whose target no longer
+    /// matches the actual_path stored in the index (e.g. after moving the target drive)
+    /// The index is not modified; an index update refreshes the stored paths
+    pub fn detect_moved_targets(&self) -> Result<Vec<MovedShortcutTarget>> {
+        // Shortcuts can only be resolved on Windows
+        if !cfg!(windows) {
+            return Ok(Vec::new());
+        }
+
+        let index = self.load_index()?;
+        let custom_scenery_path = self.xplane_path.join("Custom Scenery");
+
+        let mut moved: Vec<MovedShortcutTarget> = index
+            .packages
+            .values()
+            .filter_map(|info| {
+                let stored_path = info.actual_path.as_ref()?;
+                let lnk_path = custom_scenery_path.join(format!("{}.lnk", info.folder_name));
+                let resolved_path = resolve_shortcut(&lnk_path)
+                    .map(|target| target.to_string_lossy().replace('\\', "/"));
+
+                let unchanged = resolved_path
+                    .as_deref()
+                    .is_some_and(|resolved| shortcut_targets_match(stored_path, resolved));
+                (!unchanged).then(|| MovedShortcutTarget {
+                    folder_name: info.folder_name.clone(),
+                    stored_path: stored_path.clone(),
+                    resolved_path,
+                })
+            })
+            .collect();
+        moved.sort_by(|a, b| {
+            a.folder_name
+                .to_lowercase()
+                .cmp(&b.folder_name.to_lowercase())
+        });
+
+        if !moved.is_empty() {
+            logger::log_info(
+                &format!("Found {} shortcut entries with moved targets", moved.len()),
+                Some("scenery_index"),
+            );
+        }
+
+        Ok(moved)
+    }
+
+    /// Re-resolve shortcut (.lnk) entries, store their current targets as actual_path
+    /// and rewrite scenery_packs.ini, without reclassifying any package
+    /// Symlinked folders need no repair since the ini references them by folder name
+    /// Returns the number of entries whose path was updated
+    pub fn refresh_shortcut_targets(&self) -> Result<usize> {
+        let moved = self.detect_moved_targets()?;
+        if moved.is_empty() {
+            return Ok(0);
+        }
+
+        let mut index = self.load_index()?;
+        let mut refreshed = 0;
+        for target in moved {
+            let Some(resolved_path) = target.resolved_path else {
+                logger::log_info(
+                    &format!(
+                        "Shortcut {} can't be resolved, keeping {}",
+                        target.folder_name, target.stored_path
+                    ),
+                    Some("scenery_index"),
+                );
+                continue;
+            };
+            if let Some(info) = index.packages.get_mut(&target.folder_name) {
+                logger::log_info(
+                    &format!(
+                        "Shortcut {}: {} -> {}",
+                        target.folder_name, target.stored_path, resolved_path
+                    ),
+                    Some("scenery_index"),
+                );
+                info.actual_path = Some(resolved_path);
+                refreshed += 1;
+            }
+        }
+
+        if refreshed > 0 {
+            index.last_updated = SystemTime::now();
+            self.save_index(&index)?;
+            SceneryPacksManager::new(&self.xplane_path).apply_from_index()?;
+        }
+
+        Ok(refreshed)
+    }
+
+    /// Tiles covered by each airport (apt.dat airports) and mesh/ortho (DSF tiles) package
+    /// Coverage is cached in the database and only recomputed for packages that are new
+    /// or whose last_modified changed since (i.e. after a reindex picked up a change).
+    /// Aborts through the attached walk monitor; work done so far is still cached
+    pub fn get_coverage(&self) -> Result<Vec<TileCoverage>> {
+        let index = self.load_index()?;
+        let mut conn = open_connection().map_err(|e| anyhow!("{}", e))?;
+        let cached = SceneryQueries::load_tile_coverage(&conn).map_err(|e| anyhow!("{}", e))?;
+
+        let mut packages: Vec<&SceneryPackageInfo> = index
+            .packages
+            .values()
+            .filter(|info| has_tile_coverage(&info.category))
+            .collect();
+        packages.sort_by_key(|info| info.sort_order);
+
+        let mut coverage = Vec::new();
+        let mut updated = Vec::new();
+        let mut cancelled = false;
+        for info in packages {
+            let tiles = match cached.get(&info.folder_name) {
+                Some((last_modified, tiles)) if *last_modified == info.last_modified => {
+                    tiles.clone()
+                }
+                _ => {
+                    let scenery_path = match &info.actual_path {
+                        Some(actual_path) => PathBuf::from(actual_path),
+                        None => self
+                            .xplane_path
+                            .join("Custom Scenery")
+                            .join(&info.folder_name),
+                    };
+                    if !self.walk_step(&scenery_path) {
+                        cancelled = true;
+                        break;
+                    }
+                    let tiles = compute_tile_coverage(&scenery_path, &info.category);
+                    updated.push((info.folder_name.clone(), info.last_modified, tiles.clone()));
+                    tiles
+                }
+            };
+
+            if !tiles.is_empty() {
+                coverage.push(TileCoverage {
+                    folder_name: info.folder_name.clone(),
+                    category: info.category.clone(),
+                    tiles,
+                });
+            }
+        }
+
+        // Drop cached coverage of packages that were removed or reclassified
+        let removed: Vec<String> = cached
+            .keys()
+            .filter(|name| {
+                !index
+                    .packages
+                    .get(*name)
+                    .is_some_and(|info| has_tile_coverage(&info.category))
+            })
+            .cloned()
+            .collect();
+
+        if !updated.is_empty() || !removed.is_empty() {
+            SceneryQueries::save_tile_coverage(&mut conn, &updated, &removed)
+                .map_err(|e| anyhow!("{}", e))?;
+            logger::log_info(
+                &format!(
+                    "Tile coverage: {} packages computed, {} removed",
+                    updated.len(),
+                    removed.len()
+                ),
+                Some("scenery_index"),
+            );
+        }
+
+        if cancelled {
+            return Err(anyhow!("Scan cancelled"));
+        }
+
+        Ok(coverage)
+    }
+
+    /// Get index statistics
     pub fn get_stats(&self) -> Result<SceneryIndexStats> {
         let index = self.load_index()?;
 
@@ -939,11 +1748,26 @@ impl SceneryIndexManager {
 
     /// Batch update multiple entries' enabled state and sort_order from UI
     pub fn batch_update_entries(&self, entries: &[crate::models::SceneryEntryUpdate]) -> Result<()> {
+        let changes = self.batch_update_entries_unrecorded(entries)?;
+        scenery_audit::record(
+            &self.xplane_path,
+            scenery_audit::OP_BATCH_UPDATE_ENTRIES,
+            changes,
+        );
+        Ok(())
+    }
+
+    /// Batch update entries without writing an audit entry
+    /// Returns the changes so the caller can record them once the whole operation succeeded
+    pub fn batch_update_entries_unrecorded(
+        &self,
+        entries: &[crate::models::SceneryEntryUpdate],
+    ) -> Result<Vec<SceneryAuditChange>> {
         if entries.is_empty() {
-            return Ok(());
+            return Ok(Vec::new());
         }
 
-        self.ensure_initialized()?;
+        let before = self.load_index()?;
         let mut conn = open_connection().map_err(|e| anyhow!("{}", e))?;
         SceneryQueries::batch_update_entries(&mut conn, entries).map_err(|e| anyhow!("{}", e))?;
 
@@ -952,7 +1776,72 @@ impl SceneryIndexManager {
             Some("scenery_index"),
         );
 
-        Ok(())
+        Ok(entries
+            .iter()
+            .filter_map(|update| {
+                let before = audit_state(before.packages.get(&update.folder_name)?);
+                Some(SceneryAuditChange {
+                    folder_name: update.folder_name.clone(),
+                    after: SceneryAuditState {
+                        enabled: update.enabled,
+                        sort_order: update.sort_order,
+                        ..before.clone()
+                    },
+                    before,
+                })
+            })
+            .collect())
+    }
+
+    /// Enable or disable many packages at once, leaving sort_order untouched
+    /// Returns the number of packages whose enabled state actually changed
+    pub fn set_enabled_bulk(&self, folder_names: &[String], enabled: bool) -> Result<usize> {
+        if folder_names.is_empty() {
+            return Ok(0);
+        }
+
+        // Only rows whose state actually flips are updated, so only they are audited
+        let before = self.load_index()?;
+        let mut seen = HashSet::new();
+        let changes: Vec<SceneryAuditChange> = folder_names
+            .iter()
+            .filter(|folder_name| seen.insert(folder_name.as_str()))
+            .filter_map(|folder_name| before.packages.get(folder_name))
+            .filter(|info| info.enabled != enabled)
+            .map(|info| {
+                let before = audit_state(info);
+                SceneryAuditChange {
+                    folder_name: info.folder_name.clone(),
+                    after: SceneryAuditState {
+                        enabled,
+                        ..before.clone()
+                    },
+                    before,
+                }
+            })
+            .collect();
+
+        let mut conn = open_connection().map_err(|e| anyhow!("{}", e))?;
+        let changed = SceneryQueries::set_enabled_bulk(&mut conn, folder_names, enabled)
+            .map_err(|e| anyhow!("{}", e))?;
+
+        scenery_audit::record(
+            &self.xplane_path,
+            scenery_audit::OP_SET_ENABLED_BULK,
+            changes,
+        );
+
+        logger::log_info(
+            &format!(
+                "{} {} of {} scenery packages",
+                if enabled { "Enabled" } else { "Disabled" },
+                changed,
+                folder_names.len()
+            ),
+            Some("scenery_index"),
+        );
+
+        Ok(changed)
     }
 
     /// Update a single entry's enabled state, sort_order, and/or category
@@ -963,13 +1852,88 @@ impl SceneryIndexManager {
         sort_order: Option<u32>,
         category: Option<SceneryCategory>,
     ) -> Result<()> {
-        self.ensure_initialized()?;
+        let before = self
+            .get_package(folder_name)?
+            .map(|info| audit_state(&info));
         let conn = open_connection().map_err(|e| anyhow!("{}", e))?;
         SceneryQueries::update_entry(&conn, folder_name, enabled, sort_order, category.as_ref())
             .map_err(|e| anyhow!("{}", e))?;
+
+        if let Some(before) = before {
+            let after = SceneryAuditState {
+                enabled: enabled.unwrap_or(before.enabled),
+                sort_order: sort_order.unwrap_or(before.sort_order),
+                category: category.unwrap_or_else(|| before.category.clone()),
+                category_locked: before.category_locked,
+            };
+            scenery_audit::record(
+                &self.xplane_path,
+                scenery_audit::OP_UPDATE_ENTRY,
+                vec![SceneryAuditChange {
+                    folder_name: folder_name.to_string(),
+                    before,
+                    after,
+                }],
+            );
+        }
+
         Ok(())
     }
 
+    /// Undo the most recent audited scenery manager operation by restoring the
+    /// recorded "before" state of every package it changed, then rewrite scenery_packs.ini
+    /// The undo itself is not recorded. Returns the undone entry, or None if there is none
+    pub fn undo_last(&self) -> Result<Option<SceneryAuditEntry>> {
+        let Some(entry) = scenery_audit::last_entry(&self.xplane_path)? else {
+            return Ok(None);
+        };
+
+        if entry.op == scenery_audit::OP_MOVE_ENTRY {
+            // Moving back shifts the entries in between back as well
+            for change in entry.changes.iter().rev() {
+                self.move_entry_unrecorded(&change.folder_name, change.before.sort_order)?;
+            }
+        } else {
+            self.ensure_initialized()?;
+            let mut conn = open_connection().map_err(|e| anyhow!("{}", e))?;
+            // All or nothing, so a failed undo leaves the index as it was
+            let tx = conn.transaction()?;
+            for change in entry.changes.iter().rev() {
+                SceneryQueries::update_entry(
+                    &tx,
+                    &change.folder_name,
+                    Some(change.before.enabled),
+                    Some(change.before.sort_order),
+                    Some(&change.before.category),
+                )
+                .map_err(|e| anyhow!("{}", e))?;
+                SceneryQueries::set_category_locked(
+                    &tx,
+                    &change.folder_name,
+                    change.before.category_locked,
+                )
+                .map_err(|e| anyhow!("{}", e))?;
+            }
+            tx.commit()?;
+        }
+
+        SceneryPacksManager::new(&self.xplane_path).apply_from_index()?;
+        // Only drop the entry once it has been undone, so a failed undo can be retried
+        scenery_audit::remove_last(&entry)?;
+
+        logger::log_info(
+            &format!(
+                "Undid {} ({} packages) from {}",
+                entry.op,
+                entry.changes.len(),
+                entry.timestamp
+            ),
+            Some("scenery_index"),
+        );
+
+        Ok(Some(entry))
+    }
+
     /// Remove an entry from the index
     pub fn remove_entry(&self, folder_name: &str) -> Result<()> {
         self.ensure_initialized()?;
@@ -987,22 +1951,164 @@ impl SceneryIndexManager {
         Ok(())
     }
 
+    /// Delete a scenery package: removes its index row and scenery_packs.ini entry and,
+    /// when `remove_files` is set, its folder in Custom Scenery.
+    /// For shortcuts (.lnk or symlink) only the link is deleted; the target folder is
+    /// removed as well only if `delete_shortcut_target` is set.
+    /// scenery_packs.ini is backed up before it is rewritten. Files are only deleted
+    /// after the ini and index no longer reference the package
+    pub fn delete_scenery(
+        &self,
+        folder_name: &str,
+        remove_files: bool,
+        delete_shortcut_target: bool,
+    ) -> Result<()> {
+        // Security: Validate folder_name doesn't contain path traversal sequences
+        if folder_name.is_empty()
+            || folder_name.contains("..")
+            || folder_name.contains('/')
+            || folder_name.contains('\\')
+        {
+            return Err(anyhow!("Invalid folder name: {}", folder_name));
+        }
+
+        if is_protected_scenery_folder(folder_name) {
+            return Err(anyhow!(
+                "Refusing to delete protected scenery: {}",
+                folder_name
+            ));
+        }
+
+        let package = self.get_package(folder_name)?;
+
+        // Resolve and validate everything to delete while the index still knows the package
+        let removal = if remove_files {
+            Some(self.resolve_scenery_files(folder_name, delete_shortcut_target)?)
+        } else {
+            None
+        };
+
+        // Shortcut entries are listed in the ini by their target path
+        let ini_entry = package
+            .as_ref()
+            .and_then(|p| p.actual_path.clone())
+            .unwrap_or_else(|| format!("Custom Scenery/{}/", folder_name));
+        SceneryPacksManager::new(&self.xplane_path).remove_entry(&ini_entry)?;
+
+        self.remove_entry(folder_name)?;
+
+        if let Some(removal) = removal {
+            removal.remove().map_err(|e| {
+                anyhow!(
+                    "Removed {} from the index, but its files could not be deleted: {}",
+                    folder_name,
+                    e
+                )
+            })?;
+        }
+
+        logger::log_info(
+            &format!(
+                "Deleted scenery {} (files removed: {})",
+                folder_name, remove_files
+            ),
+            Some("scenery_index"),
+        );
+
+        Ok(())
+    }
+
+    /// Find the on-disk folder (or shortcut) of a scenery package and check it may be deleted
+    fn resolve_scenery_files(
+        &self,
+        folder_name: &str,
+        delete_shortcut_target: bool,
+    ) -> Result<SceneryFileRemoval> {
+        let custom_scenery_path = self.xplane_path.join("Custom Scenery");
+        let lnk_path = custom_scenery_path.join(format!("{}.lnk", folder_name));
+        let folder_path = custom_scenery_path.join(folder_name);
+
+        if lnk_path.is_file() {
+            let target = if delete_shortcut_target {
+                let target = resolve_shortcut(&lnk_path)
+                    .ok_or_else(|| anyhow!("Failed to resolve shortcut: {:?}", lnk_path))?;
+                Some(self.deletable_shortcut_target(&target)?)
+            } else {
+                None
+            };
+            return Ok(SceneryFileRemoval::Link {
+                link: lnk_path,
+                target,
+            });
+        }
+
+        let metadata = fs::symlink_metadata(&folder_path)
+            .map_err(|e| anyhow!("Scenery folder not found: {} ({})", folder_name, e))?;
+
+        if metadata.file_type().is_symlink() {
+            let target = if delete_shortcut_target {
+                Some(self.deletable_shortcut_target(&folder_path)?)
+            } else {
+                None
+            };
+            return Ok(SceneryFileRemoval::Link {
+                link: folder_path,
+                target,
+            });
+        }
+
+        // Security: Use canonicalize for strict path validation to prevent path traversal attacks
+        let canonical_path = folder_path.canonicalize()?;
+        let canonical_base = custom_scenery_path.canonicalize()?;
+        if !canonical_path.starts_with(&canonical_base) || canonical_path == canonical_base {
+            return Err(anyhow!("Path traversal attempt detected: {}", folder_name));
+        }
+
+        Ok(SceneryFileRemoval::Folder(canonical_path))
+    }
+
+    /// Resolve the folder a scenery shortcut points to, refusing anything that
+    /// contains the X-Plane installation or lies inside it (other than an indexed package)
+    fn deletable_shortcut_target(&self, target: &Path) -> Result<PathBuf> {
+        let target = target.canonicalize()?;
+        let xplane_root = self.xplane_path.canonicalize()?;
+        let is_indexed = |name: &str| self.get_package(name).ok().flatten().is_some();
+        if !is_deletable_shortcut_target(&target, &xplane_root, is_indexed) {
+            return Err(anyhow!("Refusing to delete shortcut target: {:?}", target));
+        }
+        Ok(target)
+    }
+
     /// Move an entry from one position to another, auto-adjusting other entries
     pub fn move_entry(&self, folder_name: &str, new_sort_order: u32) -> Result<()> {
+        if let Some(change) = self.move_entry_unrecorded(folder_name, new_sort_order)? {
+            scenery_audit::record(&self.xplane_path, scenery_audit::OP_MOVE_ENTRY, vec![change]);
+        }
+        Ok(())
+    }
+
+    /// Move an entry without writing an audit entry
+    /// Returns the change of the moved entry, or None if it was already in place
+    fn move_entry_unrecorded(
+        &self,
+        folder_name: &str,
+        new_sort_order: u32,
+    ) -> Result<Option<SceneryAuditChange>> {
         let mut index = self.load_index()?;
 
-        // Get current sort_order
-        let current_sort_order = match index.packages.get(folder_name) {
-            Some(info) => info.sort_order,
+        // Get current state
+        let before = match index.packages.get(folder_name) {
+            Some(info) => audit_state(info),
             None => return Err(anyhow!("Package not found: {}", folder_name)),
         };
+        let current_sort_order = before.sort_order;
 
         // Validate and clamp new_sort_order to valid range [0, packages.len() - 1]
         let max_valid_order = index.packages.len().saturating_sub(1) as u32;
         let new_sort_order = new_sort_order.min(max_valid_order);
 
         if current_sort_order == new_sort_order {
-            return Ok(()); // No change needed
+            return Ok(None); // No change needed
         }
 
         // Adjust sort_orders of other entries
@@ -1030,9 +2136,91 @@ impl SceneryIndexManager {
         index.last_updated = SystemTime::now();
         self.save_index(&index)?;
 
+        Ok(Some(SceneryAuditChange {
+            folder_name: folder_name.to_string(),
+            after: SceneryAuditState {
+                sort_order: new_sort_order,
+                ..before.clone()
+            },
+            before,
+        }))
+    }
+
+    /// Replace the whole ordering at once: sort_order is assigned from each folder's
+    /// position in the list. The list must contain exactly the folders in the index;
+    /// any duplicates, unknown or missing folders are reported and nothing is changed.
+    pub fn set_full_order(&self, folder_names: &[String]) -> Result<()> {
+        let index = self.load_index()?;
+
+        let mut seen: HashSet<&str> = HashSet::new();
+        let mut duplicates: Vec<&str> = Vec::new();
+        let mut extras: Vec<&str> = Vec::new();
+        for name in folder_names {
+            if !seen.insert(name.as_str()) {
+                duplicates.push(name.as_str());
+            } else if !index.packages.contains_key(name) {
+                extras.push(name.as_str());
+            }
+        }
+
+        let mut missing: Vec<&str> = index
+            .packages
+            .keys()
+            .map(|k| k.as_str())
+            .filter(|k| !seen.contains(k))
+            .collect();
+        missing.sort();
+
+        if !duplicates.is_empty() || !extras.is_empty() || !missing.is_empty() {
+            return Err(anyhow!(
+                "Order does not match scenery index: missing {:?}, unknown {:?}, duplicated {:?}",
+                missing,
+                extras,
+                duplicates
+            ));
+        }
+
+        self.ensure_initialized()?;
+        let mut conn = open_connection().map_err(|e| anyhow!("{}", e))?;
+        SceneryQueries::set_sort_orders(&mut conn, folder_names).map_err(|e| anyhow!("{}", e))?;
+        scenery_audit::clear(&self.xplane_path, "full reorder");
+
+        logger::log_info(
+            &format!("Applied full ordering of {} scenery entries", folder_names.len()),
+            Some("scenery_index"),
+        );
+
         Ok(())
     }
 
+    /// Mirror the load order of a scenery_packs.ini exported from another machine.
+    /// Folders are matched to the local index by name (case-insensitively); local packages
+    /// not listed in the imported ini are appended at the end in category order.
+    /// Only sort_order is updated; apply_from_index writes the ini.
+    /// Returns the folders listed in the imported ini that don't exist locally
+    pub fn import_order_from_ini(&self, ini_contents: &str) -> Result<Vec<String>> {
+        let index = self.load_index()?;
+        let ini_paths: Vec<String> = parse_ini(ini_contents)
+            .into_iter()
+            .filter(|e| !e.is_global_airports)
+            .map(|e| e.path)
+            .collect();
+
+        let (order, unknown) = build_imported_order(&ini_paths, &index);
+        self.set_full_order(&order)?;
+
+        logger::log_info(
+            &format!(
+                "Imported scenery order from ini: {} entries, {} not found locally",
+                ini_paths.len(),
+                unknown.len()
+            ),
+            Some("scenery_index"),
+        );
+
+        Ok(unknown)
+    }
+
     /// Reset sort_order for all packages based on category priority
     /// This recalculates the sort order using the classification algorithm
     /// without writing to the ini file
@@ -1044,13 +2232,67 @@ impl SceneryIndexManager {
             return Ok(false);
         }
 
-        // Promote SAM libraries to FixedHighPriority before sorting
+        let (sorted_names, category_changed) = self.compute_sorted_order(&mut index);
+        self.save_sort_order(index, &sorted_names, category_changed)
+    }
+
+    /// The index as reset_sort_order would leave it, without saving anything
+    /// Used to preview a sort before it is applied
+    pub fn preview_sorted_index(&self) -> Result<SceneryIndex> {
+        let mut index = self.load_index()?;
+        if !index.packages.is_empty() {
+            let (sorted_names, _) = self.compute_sorted_order(&mut index);
+            assign_sort_order(&mut index, &sorted_names);
+        }
+        Ok(index)
+    }
+
+    /// Like `reset_sort_order`, but packages in `excluded` categories keep their
+    /// current positions and only the remaining packages are sorted around them
+    /// Returns true if the sort order was changed, false if it was already correct
+    pub fn reset_sort_order_except(&self, excluded: &[SceneryCategory]) -> Result<bool> {
+        let mut index = self.load_index()?;
+
+        if index.packages.is_empty() {
+            return Ok(false);
+        }
+
+        let mut current_order: Vec<(&String, u32)> = index
+            .packages
+            .iter()
+            .map(|(name, info)| (name, info.sort_order))
+            .collect();
+        current_order.sort_by_key(|(_, order)| *order);
+        let current_order: Vec<String> = current_order
+            .into_iter()
+            .map(|(name, _)| name.clone())
+            .collect();
+
+        let (sorted_names, category_changed) = self.compute_sorted_order(&mut index);
+        let merged = merge_sorted_with_excluded(&current_order, &sorted_names, |name| {
+            index
+                .packages
+                .get(name)
+                .is_some_and(|info| excluded.contains(&info.category))
+        });
+
+        self.save_sort_order(index, &merged, category_changed)
+    }
+
+    /// Sort packages by category priority, promoting SAM (and user-defined
+    /// high-priority) libraries to FixedHighPriority first
+    /// Returns the folder names in sorted order and whether any category changed
+    fn compute_sorted_order(&self, index: &mut SceneryIndex) -> (Vec<String>, bool) {
+        // Promote SAM libraries (and user-defined high-priority libraries) to FixedHighPriority
+        let config = self.get_ordering_config().unwrap_or_default();
+        let high_priority_patterns = compile_high_priority_patterns(&config);
         let mut category_changed = false;
         for (name, info) in index.packages.iter_mut() {
-            if is_sam_folder_name(name)
+            if is_high_priority_folder_name(name, &high_priority_patterns)
                 && info.has_library_txt
                 && !info.has_dsf
                 && !info.has_apt_dat
+                && !info.category_locked
             {
                 if info.category != SceneryCategory::FixedHighPriority {
                     info.category = SceneryCategory::FixedHighPriority;
@@ -1087,31 +2329,33 @@ impl SceneryIndexManager {
             .filter(|(_, info)| info.category != SceneryCategory::FixedHighPriority)
             .collect();
 
+        let mesh_regions = self.collect_mesh_regions(other_packages.iter().map(|(_, info)| *info));
         other_packages.sort_by(|(name_a, info_a), (name_b, info_b)| {
-            compare_packages_for_sorting(name_a, info_a, name_b, info_b)
+            compare_packages_for_sorting(name_a, info_a, name_b, info_b, &mesh_regions)
         });
 
-        // Update sort_order based on sorted position and check for changes
         let sorted_names: Vec<String> = fixed_packages
             .iter()
             .map(|(name, _)| (*name).clone())
             .chain(other_packages.iter().map(|(name, _)| (*name).clone()))
             .collect();
-        let mut has_changes = category_changed;
 
-        for (new_order, folder_name) in sorted_names.iter().enumerate() {
-            if let Some(info) = index.packages.get_mut(folder_name) {
-                let new_order_u32 = new_order as u32;
-                if info.sort_order != new_order_u32 {
-                    has_changes = true;
-                    info.sort_order = new_order_u32;
-                }
-            }
-        }
+        (sorted_names, category_changed)
+    }
+
+    /// Assign sort_order from the position in `sorted_names` and save the index if anything changed
+    fn save_sort_order(
+        &self,
+        mut index: SceneryIndex,
+        sorted_names: &[String],
+        category_changed: bool,
+    ) -> Result<bool> {
+        let has_changes = assign_sort_order(&mut index, sorted_names) || category_changed;
 
         if has_changes {
             index.last_updated = SystemTime::now();
             self.save_index(&index)?;
+            scenery_audit::clear(&self.xplane_path, "sort order reset");
 
             logger::log_info(
                 &format!("Reset sort order for {} packages", sorted_names.len()),
@@ -1127,15 +2371,162 @@ impl SceneryIndexManager {
         Ok(has_changes)
     }
 
+    /// Check whether safe mode is currently active (a snapshot is stored)
+    pub fn is_safe_mode_active(&self) -> Result<bool> {
+        self.ensure_initialized()?;
+        let conn = open_connection().map_err(|e| anyhow!("{}", e))?;
+        let snapshot = SceneryQueries::get_metadata(&conn, SAFE_MODE_SNAPSHOT_KEY)
+            .map_err(|e| anyhow!("{}", e))?;
+        Ok(snapshot.is_some())
+    }
+
+    /// Enter safe mode: disable all non-essential packages (Mesh, AirportMesh, Overlay, Other)
+    /// while keeping airports, libraries and fixed high priority packages enabled.
+    /// The prior enabled states are stored in index_metadata so exit_safe_mode can restore them,
+    /// and scenery_packs.ini is backed up and rewritten from the index.
+    /// Returns the number of packages that were disabled
+    pub fn enter_safe_mode(&self) -> Result<usize> {
+        if self.is_safe_mode_active()? {
+            return Err(anyhow!("Safe mode is already active"));
+        }
+
+        let index = self.load_index()?;
+        if index.packages.is_empty() {
+            return Err(anyhow!("Scenery index is empty"));
+        }
+
+        // Record the prior enabled state of every package
+        let snapshot: HashMap<String, bool> = index
+            .packages
+            .values()
+            .map(|info| (info.folder_name.clone(), info.enabled))
+            .collect();
+        let snapshot_json = serde_json::to_string(&snapshot)?;
+
+        let updates: Vec<crate::models::SceneryEntryUpdate> = index
+            .packages
+            .values()
+            .filter(|info| info.enabled && !is_safe_mode_essential(&info.category))
+            .map(|info| crate::models::SceneryEntryUpdate {
+                folder_name: info.folder_name.clone(),
+                enabled: false,
+                sort_order: info.sort_order,
+            })
+            .collect();
+
+        // Store the snapshot before touching entries so a failure can always be undone
+        {
+            let conn = open_connection().map_err(|e| anyhow!("{}", e))?;
+            SceneryQueries::set_metadata(&conn, SAFE_MODE_SNAPSHOT_KEY, &snapshot_json)
+                .map_err(|e| anyhow!("{}", e))?;
+        }
+
+        self.batch_update_entries(&updates)?;
+
+        let packs_manager = crate::scenery_packs_manager::SceneryPacksManager::new(&self.xplane_path);
+        packs_manager.apply_from_index()?;
+
+        logger::log_info(
+            &format!("Entered safe mode: disabled {} scenery packages", updates.len()),
+            Some("scenery_index"),
+        );
+
+        Ok(updates.len())
+    }
+
+    /// Exit safe mode: restore the enabled states recorded by enter_safe_mode and rewrite
+    /// scenery_packs.ini. Packages added since entering safe mode keep their current state,
+    /// and packages removed since then are ignored.
+    /// Returns the number of packages whose enabled state was restored
+    pub fn exit_safe_mode(&self) -> Result<usize> {
+        self.ensure_initialized()?;
+        let snapshot_json = {
+            let conn = open_connection().map_err(|e| anyhow!("{}", e))?;
+            SceneryQueries::get_metadata(&conn, SAFE_MODE_SNAPSHOT_KEY)
+                .map_err(|e| anyhow!("{}", e))?
+                .ok_or_else(|| anyhow!("Safe mode is not active"))?
+        };
+        let snapshot: HashMap<String, bool> = serde_json::from_str(&snapshot_json)?;
+
+        let index = self.load_index()?;
+        let updates: Vec<crate::models::SceneryEntryUpdate> = index
+            .packages
+            .values()
+            .filter_map(|info| {
+                let enabled = *snapshot.get(&info.folder_name)?;
+                if enabled == info.enabled {
+                    return None;
+                }
+                Some(crate::models::SceneryEntryUpdate {
+                    folder_name: info.folder_name.clone(),
+                    enabled,
+                    sort_order: info.sort_order,
+                })
+            })
+            .collect();
+
+        self.batch_update_entries(&updates)?;
+
+        {
+            let conn = open_connection().map_err(|e| anyhow!("{}", e))?;
+            SceneryQueries::delete_metadata(&conn, SAFE_MODE_SNAPSHOT_KEY)
+                .map_err(|e| anyhow!("{}", e))?;
+        }
+
+        let packs_manager = crate::scenery_packs_manager::SceneryPacksManager::new(&self.xplane_path);
+        packs_manager.apply_from_index()?;
+
+        logger::log_info(
+            &format!("Exited safe mode: restored {} scenery packages", updates.len()),
+            Some("scenery_index"),
+        );
+
+        Ok(updates.len())
+    }
+
+    /// Get folder names of packages exporting a library name (case-insensitive)
+    pub fn packages_exporting(&self, library_name: &str) -> Result<Vec<String>> {
+        self.ensure_initialized()?;
+        let conn = open_connection().map_err(|e| anyhow!("{}", e))?;
+        SceneryQueries::packages_exporting(&conn, library_name).map_err(|e| anyhow!("{}", e))
+    }
+
+    /// Resolve a library to installed-but-disabled packages that export it.
+    /// Uses the same rule as detect_disabled_dependencies: only libraries no enabled
+    /// package exports have candidates. Returns folder names in sort order
+    pub fn resolve_missing_library(&self, library_name: &str) -> Result<Vec<String>> {
+        let index = self.load_index()?;
+        Ok(disabled_providers_of(&index, library_name))
+    }
+
+    /// Find enabled packages that require libraries provided only by disabled packages.
+    /// Returns (enabled package, disabled library folders) pairs in sort order
+    pub fn detect_disabled_dependencies(&self) -> Result<Vec<(String, Vec<String>)>> {
+        let index = self.load_index()?;
+        Ok(find_disabled_dependencies(&index))
+    }
+
+    /// Find library names exported by more than one package
+    /// Returns library name -> exporting folder names (sorted)
+    pub fn detect_duplicate_library_exports(&self) -> Result<HashMap<String, Vec<String>>> {
+        let index = self.load_index()?;
+        Ok(find_duplicate_library_exports(&index))
+    }
+
     /// Get scenery manager data for UI
     pub fn get_manager_data(&self) -> Result<SceneryManagerData> {
         let index = self.load_index()?;
 
         // Check if ini is synced with index
         let packs_manager = crate::scenery_packs_manager::SceneryPacksManager::new(&self.xplane_path);
-        let needs_sync = !packs_manager.is_synced_with_index().unwrap_or(true);
+        let sync_divergence = packs_manager
+            .check_sync_with_index()
+            .ok()
+            .filter(|divergence| !divergence.is_synced());
+        let needs_sync = sync_divergence.is_some();
 
         // Convert to manager entries and sort by sort_order
+        // The install marker comes from the index (read at scan time), not from disk
         let mut entries: Vec<SceneryManagerEntry> = index
             .packages
             .values()
@@ -1147,6 +2538,15 @@ impl SceneryIndexManager {
                 sort_order: info.sort_order,
                 missing_libraries: info.missing_libraries.clone(),
                 required_libraries: info.required_libraries.clone(),
+                is_dynamic: is_dynamic_package(&info.folder_name),
+                is_incomplete: info.is_incomplete(),
+                is_orthophoto: info.is_orthophoto(),
+                install_marker: info.install_marker.clone(),
+                icao_code: info.icao_code.clone(),
+                airport_name: info.airport_name.clone(),
+                airport_elevation_ft: info.airport_elevation_ft,
+                category_locked: info.category_locked,
+                icao_mismatch: info.icao_mismatch,
             })
             .collect();
 
@@ -1167,6 +2567,7 @@ impl SceneryIndexManager {
             enabled_count,
             missing_deps_count,
             needs_sync,
+            sync_divergence,
         })
     }
 
@@ -1190,16 +2591,30 @@ impl SceneryIndexManager {
         
         for pkg in packages.iter() {
             if pkg.category == SceneryCategory::Airport && pkg.has_apt_dat {
-                // Parse apt.dat to get coordinates and ICAO code
+                // Parse apt.dat to get coordinates and ICAO codes of every airport in it,
+                // so regional packs bundling several fields register each of them
                 let scenery_path = self.xplane_path.join("Custom Scenery").join(&pkg.folder_name);
-                if let Some((lat, lon, icao)) = parse_airport_coords(&scenery_path) {
-                    let coord_key = (lat, lon);
-                    airport_coords
-                        .entry(coord_key)
-                        .or_default()
-                        .push((pkg.folder_name.clone(), icao));
-                    
-                    // Extract common prefix (e.g., "ACS_Singapore" from "ACS_Singapore_0_Airport")
+                let airports = parse_all_airport_coords(&scenery_path);
+                for coords in &airports {
+                    if coords.has_icao_mismatch() {
+                        logger::log_info(
+                            &format!(
+                                "  Warning: {} has header ICAO {:?} but icao_code {:?}, using header",
+                                pkg.folder_name, coords.header_icao, coords.metadata_icao
+                            ),
+                            Some("scenery_index"),
+                        );
+                    }
+                    let coord_key = (coords.lat, coords.lon);
+                    let entry = (pkg.folder_name.clone(), coords.icao().map(String::from));
+                    let tile_airports = airport_coords.entry(coord_key).or_default();
+                    if !tile_airports.contains(&entry) {
+                        tile_airports.push(entry);
+                    }
+                }
+
+                // Extract common prefix (e.g., "ACS_Singapore" from "ACS_Singapore_0_Airport")
+                if !airports.is_empty() {
                     if let Some(prefix) = extract_scenery_prefix(&pkg.folder_name) {
                         airport_prefixes.insert(prefix);
                     }
@@ -1221,8 +2636,15 @@ impl SceneryIndexManager {
                 continue;
             }
 
-            // Skip Ortho4XP packages - they are regional orthophotos, not airport-specific
-            if pkg.folder_name.starts_with("zOrtho4XP") {
+            // Skip Ortho4XP and other photo packs - they are regional orthophotos, not airport-specific
+            if pkg.folder_name.starts_with("zOrtho4XP")
+                || is_orthophoto_package(&pkg.folder_name, pkg.texture_count)
+            {
+                continue;
+            }
+
+            // Skip dynamically generated packages - scanning them is slow and meaningless
+            if is_dynamic_package(&pkg.folder_name) {
                 continue;
             }
 
@@ -1347,40 +2769,47 @@ impl SceneryIndexManager {
 /// Parse library.txt file and extract all exported library names
 /// Returns a set of library name prefixes that this library exports
 pub fn parse_library_exports(library_txt_path: &Path) -> HashSet<String> {
-    let mut library_names = HashSet::new();
-
     // Check if library.txt exists
     if !library_txt_path.exists() {
-        return library_names;
+        return HashSet::new();
     }
 
-    // Read and parse library.txt
-    if let Ok(file) = fs::File::open(library_txt_path) {
-        let reader = BufReader::new(file);
+    // Read as bytes so a stray non-UTF-8 line (e.g. in a region comment)
+    // doesn't hide exports that follow it
+    match fs::read(library_txt_path) {
+        Ok(bytes) => parse_library_exports_from(&String::from_utf8_lossy(&bytes)),
+        Err(_) => HashSet::new(),
+    }
+}
 
-        for line in reader.lines().flatten() {
-            let trimmed = line.trim();
+fn parse_library_exports_from(content: &str) -> HashSet<String> {
+    let mut library_names = HashSet::new();
 
-            // Look for EXPORT lines (may have space or tab after EXPORT)
-            if trimmed.starts_with("EXPORT") && trimmed.len() > 6 {
-                // Skip "EXPORT" and any whitespace
-                let after_export = &trimmed[6..].trim_start();
+    for line in content.lines() {
+        // Drop a leading BOM and trailing comments
+        let line = line.trim_start_matches('\u{feff}');
+        let trimmed = line.split('#').next().unwrap_or("").trim();
 
-                // Format: <virtual_path> <actual_path>
-                // We want the first component of <virtual_path>
-                let parts: Vec<&str> = after_export.split_whitespace().collect();
-                if !parts.is_empty() {
-                    let virtual_path = parts[0];
-                    // Extract first path component (library name)
-                    // Support both forward slash and backslash
-                    let first_component = virtual_path.split(&['/', '\\'][..]).next();
+        let mut parts = trimmed.split_whitespace();
+        let directive = match parts.next() {
+            Some(d) => d,
+            None => continue,
+        };
 
-                    if let Some(component) = first_component {
-                        if !component.is_empty() {
-                            library_names.insert(component.to_string());
-                        }
-                    }
-                }
+        // REGION_DEFINE / REGION_RECT / REGION / REGION_DREF only scope the
+        // exports that follow them; the exports themselves still name the library
+        let virtual_path = match directive {
+            "EXPORT" | "EXPORT_EXTEND" | "EXPORT_EXCLUDE" | "EXPORT_BACKUP" => parts.next(),
+            // Format: EXPORT_RATIO <ratio> <virtual_path> <actual_path>
+            "EXPORT_RATIO" => parts.nth(1),
+            _ => None,
+        };
+
+        // Extract first path component (library name)
+        // Support both forward slash and backslash
+        if let Some(component) = virtual_path.and_then(|v| v.split(&['/', '\\'][..]).next()) {
+            if !component.is_empty() {
+                library_names.insert(component.to_string());
             }
         }
     }
@@ -1410,19 +2839,522 @@ pub fn build_library_index_from_scenery_index(index: &SceneryIndex) -> HashMap<S
         Some("library_index"),
     );
 
-    library_index
+    library_index
+}
+
+/// Map library names exported by more than one package to the exporting folders.
+/// build_library_index_from_scenery_index lets the last package win for these names,
+/// so they explain sceneries picking up objects from the wrong library copy
+fn find_duplicate_library_exports(index: &SceneryIndex) -> HashMap<String, Vec<String>> {
+    let mut exporters: HashMap<String, Vec<String>> = HashMap::new();
+    for (folder_name, info) in &index.packages {
+        for lib_name in &info.exported_library_names {
+            let folders = exporters.entry(lib_name.clone()).or_default();
+            if !folders.contains(folder_name) {
+                folders.push(folder_name.clone());
+            }
+        }
+    }
+
+    exporters.retain(|_, folders| folders.len() > 1);
+    for folders in exporters.values_mut() {
+        folders.sort_by_key(|name| name.to_lowercase());
+    }
+    exporters
+}
+
+/// Map library names that no enabled package exports to the disabled packages exporting them
+fn disabled_library_providers(index: &SceneryIndex) -> HashMap<&str, Vec<&str>> {
+    // Library name -> (disabled providers, provided by an enabled package)
+    let mut providers: HashMap<&str, (Vec<&str>, bool)> = HashMap::new();
+    for (folder_name, info) in &index.packages {
+        for lib_name in &info.exported_library_names {
+            let entry = providers.entry(lib_name.as_str()).or_default();
+            if info.enabled {
+                entry.1 = true;
+            } else {
+                entry.0.push(folder_name.as_str());
+            }
+        }
+    }
+
+    providers
+        .into_iter()
+        .filter(|(_, (_, has_enabled))| !has_enabled)
+        .map(|(lib_name, (folders, _))| (lib_name, folders))
+        .collect()
+}
+
+/// Disabled packages exporting `library_name` (case-insensitive), in sort order.
+/// Empty when an enabled package already exports the library
+fn disabled_providers_of(index: &SceneryIndex, library_name: &str) -> Vec<String> {
+    let mut folders: Vec<&SceneryPackageInfo> = disabled_library_providers(index)
+        .into_iter()
+        .filter(|(lib_name, _)| lib_name.eq_ignore_ascii_case(library_name))
+        .flat_map(|(_, folders)| folders)
+        .filter_map(|folder| index.packages.get(folder))
+        .collect();
+    folders.sort_by(|a, b| {
+        a.sort_order
+            .cmp(&b.sort_order)
+            .then_with(|| a.folder_name.cmp(&b.folder_name))
+    });
+    folders.dedup_by(|a, b| a.folder_name == b.folder_name);
+    folders.into_iter().map(|info| info.folder_name.clone()).collect()
+}
+
+/// Map each enabled package to the disabled packages exporting libraries it requires.
+/// A library is only reported when no enabled package also exports it.
+fn find_disabled_dependencies(index: &SceneryIndex) -> Vec<(String, Vec<String>)> {
+    let providers = disabled_library_providers(index);
+
+    let mut consumers: Vec<&SceneryPackageInfo> = index
+        .packages
+        .values()
+        .filter(|info| info.enabled)
+        .collect();
+    consumers.sort_by_key(|info| info.sort_order);
+
+    consumers
+        .into_iter()
+        .filter_map(|info| {
+            let mut disabled: Vec<String> = info
+                .required_libraries
+                .iter()
+                .filter(|lib| !lib.eq_ignore_ascii_case(&info.folder_name))
+                .filter_map(|lib| providers.get(lib.as_str()))
+                .flat_map(|folders| folders.iter().map(|f| f.to_string()))
+                .collect();
+            if disabled.is_empty() {
+                return None;
+            }
+            disabled.sort();
+            disabled.dedup();
+            Some((info.folder_name.clone(), disabled))
+        })
+        .collect()
+}
+
+/// Library names whose providing package differs between two library indexes
+fn diff_library_indexes(
+    old: &HashMap<String, String>,
+    new: &HashMap<String, String>,
+) -> HashSet<String> {
+    let mut changed: HashSet<String> = old
+        .iter()
+        .filter(|(lib, folder)| new.get(*lib) != Some(*folder))
+        .map(|(lib, _)| lib.clone())
+        .collect();
+    changed.extend(new.keys().filter(|lib| !old.contains_key(*lib)).cloned());
+    changed
+}
+
+/// Build a full package order following the ini paths of an imported scenery_packs.ini
+/// Returns (order of all local packages, imported folder names with no local package)
+fn build_imported_order(ini_paths: &[String], index: &SceneryIndex) -> (Vec<String>, Vec<String>) {
+    // Shortcut entries are listed in the ini by their target path,
+    // so their target folder name is matched as well (real folder names take precedence)
+    let mut by_name: HashMap<String, &String> = HashMap::new();
+    for (name, info) in &index.packages {
+        if let Some(target) = info.actual_path.as_deref().and_then(extract_folder_name) {
+            by_name.entry(target.to_lowercase()).or_insert(name);
+        }
+    }
+    for name in index.packages.keys() {
+        by_name.insert(name.to_lowercase(), name);
+    }
+
+    let mut order: Vec<String> = Vec::with_capacity(index.packages.len());
+    let mut placed: HashSet<&String> = HashSet::new();
+    let mut unknown: Vec<String> = Vec::new();
+    for path in ini_paths {
+        let Some(folder) = extract_folder_name(path) else {
+            continue;
+        };
+        match by_name.get(&folder.to_lowercase()) {
+            Some(&name) => {
+                if placed.insert(name) {
+                    order.push(name.clone());
+                }
+            }
+            None => unknown.push(folder),
+        }
+    }
+
+    // Locally-extra packages go last, in the usual category order
+    let mut remaining: Vec<(&String, &SceneryPackageInfo)> = index
+        .packages
+        .iter()
+        .filter(|(name, _)| !placed.contains(name))
+        .collect();
+    let no_regions = HashMap::new();
+    remaining.sort_by(|(name_a, info_a), (name_b, info_b)| {
+        compare_packages_for_sorting(name_a, info_a, name_b, info_b, &no_regions)
+    });
+    order.extend(remaining.into_iter().map(|(name, _)| name.clone()));
+
+    (order, unknown)
+}
+
+/// Remove a scenery entry from the index (public helper function)
+pub fn remove_scenery_entry(xplane_path: &str, folder_name: &str) -> Result<()> {
+    let manager = SceneryIndexManager::new(Path::new(xplane_path));
+    manager.remove_entry(folder_name)
+}
+
+/// Check whether installing a scenery task would replace an existing folder of the same name
+/// Returns details about the existing package (category from the index, version from its
+/// version files) so the UI can offer overwrite / rename / cancel.
+/// Non-scenery tasks and tasks without an existing target return None
+pub fn check_install_conflicts(
+    xplane_path: &Path,
+    task: &crate::models::InstallTask,
+) -> Result<Option<crate::models::SceneryConflictInfo>> {
+    use crate::models::AddonType;
+
+    if !matches!(task.addon_type, AddonType::Scenery | AddonType::SceneryLibrary) {
+        return Ok(None);
+    }
+
+    let target = Path::new(&task.target_path);
+    if !target.exists() {
+        return Ok(None);
+    }
+
+    let folder_name = target
+        .file_name()
+        .and_then(|s| s.to_str())
+        .ok_or_else(|| anyhow!("Invalid target path: {}", task.target_path))?
+        .to_string();
+
+    // Dynamically generated packages are not real installs to conflict with
+    if is_dynamic_package(&folder_name) {
+        return Ok(None);
+    }
+
+    let manager = SceneryIndexManager::new(xplane_path);
+    let existing_category = if manager.has_index()? {
+        manager.get_package(&folder_name)?.map(|info| info.category)
+    } else {
+        None
+    };
+
+    let (existing_version, _, _) = crate::management_index::read_version_info_with_url(target);
+
+    Ok(Some(crate::models::SceneryConflictInfo {
+        folder_name,
+        existing_category,
+        existing_version,
+    }))
+}
+
+/// Disabled packages that currently have missing libraries
+fn disabled_with_missing_libraries(index: &SceneryIndex) -> impl Iterator<Item = String> + '_ {
+    index
+        .packages
+        .iter()
+        .filter(|(_, info)| !info.enabled && !info.missing_libraries.is_empty())
+        .map(|(name, _)| name.clone())
+}
+
+/// Tracked packages that are still disabled and no longer miss any library
+fn newly_satisfied_packages(tracked: &HashSet<String>, index: &SceneryIndex) -> Vec<String> {
+    let mut names: Vec<String> = index
+        .packages
+        .iter()
+        .filter(|(name, info)| {
+            !info.enabled && info.missing_libraries.is_empty() && tracked.contains(*name)
+        })
+        .map(|(name, _)| name.clone())
+        .collect();
+    names.sort_by_key(|name| name.to_lowercase());
+    names
+}
+
+/// Parse a scenery index JSON export, rejecting versions this build doesn't know
+fn parse_index_export(json: &str) -> Result<SceneryIndex> {
+    let index: SceneryIndex =
+        serde_json::from_str(json).map_err(|e| anyhow!("Invalid index export: {}", e))?;
+    if index.version == 0 || index.version > CURRENT_SCHEMA_VERSION as u32 {
+        return Err(anyhow!(
+            "Unsupported index export version {} (expected 1-{})",
+            index.version,
+            CURRENT_SCHEMA_VERSION
+        ));
+    }
+    Ok(index)
+}
+
+/// Merge `imported` into `index` by folder name and renumber sort_order
+/// Exported packages take their exported positions; local-only packages keep their own
+/// and go after an exported package with the same number. Returns the local-only count
+fn merge_index_export(index: &mut SceneryIndex, imported: SceneryIndex) -> usize {
+    let local_only: Vec<SceneryPackageInfo> = index
+        .packages
+        .drain()
+        .filter(|(name, _)| !imported.packages.contains_key(name))
+        .map(|(_, info)| info)
+        .collect();
+    let kept = local_only.len();
+
+    let mut merged: Vec<(bool, SceneryPackageInfo)> = imported
+        .packages
+        .into_values()
+        .map(|info| (false, info))
+        .chain(local_only.into_iter().map(|info| (true, info)))
+        .collect();
+    merged.sort_by(|(a_local, a), (b_local, b)| {
+        a.sort_order
+            .cmp(&b.sort_order)
+            .then(a_local.cmp(b_local))
+            .then_with(|| a.folder_name.cmp(&b.folder_name))
+    });
+
+    index.packages = merged
+        .into_iter()
+        .enumerate()
+        .map(|(position, (_, mut info))| {
+            info.sort_order = position as u32;
+            (info.folder_name.clone(), info)
+        })
+        .collect();
+    kept
+}
+
+/// Remove packages whose folder (or .lnk shortcut) is gone from Custom Scenery
+/// Returns the removed folder names, sorted
+fn drop_missing_packages(index: &mut SceneryIndex, custom_scenery_path: &Path) -> Vec<String> {
+    let mut skipped: Vec<String> = index
+        .packages
+        .keys()
+        .filter(|name| {
+            let folder = custom_scenery_path.join(name.as_str());
+            let shortcut = custom_scenery_path.join(format!("{}.lnk", name));
+            !folder.is_dir() && resolve_shortcut(&shortcut).is_none()
+        })
+        .cloned()
+        .collect();
+    skipped.sort_by_key(|name| name.to_lowercase());
+
+    for name in &skipped {
+        index.packages.remove(name);
+    }
+    skipped
+}
+
+/// Compare two scenery index JSON exports without touching the database
+/// Reports packages only in one export, category changes, and packages whose
+/// position among the packages common to both exports differs
+pub fn diff_index_exports(a_json: &str, b_json: &str) -> Result<IndexExportDiff> {
+    let a: SceneryIndex =
+        serde_json::from_str(a_json).map_err(|e| anyhow!("Invalid export A: {}", e))?;
+    let b: SceneryIndex =
+        serde_json::from_str(b_json).map_err(|e| anyhow!("Invalid export B: {}", e))?;
+
+    let sorted_names = |names: Vec<&String>| {
+        let mut names: Vec<String> = names.into_iter().cloned().collect();
+        names.sort_by_key(|name| name.to_lowercase());
+        names
+    };
+    let only_in_a = sorted_names(
+        a.packages
+            .keys()
+            .filter(|name| !b.packages.contains_key(*name))
+            .collect(),
+    );
+    let only_in_b = sorted_names(
+        b.packages
+            .keys()
+            .filter(|name| !a.packages.contains_key(*name))
+            .collect(),
+    );
+
+    let mut category_changes: Vec<IndexCategoryDiff> = a
+        .packages
+        .iter()
+        .filter_map(|(name, info_a)| {
+            let info_b = b.packages.get(name)?;
+            (info_a.category != info_b.category).then(|| IndexCategoryDiff {
+                folder_name: name.clone(),
+                category_a: info_a.category.clone(),
+                category_b: info_b.category.clone(),
+            })
+        })
+        .collect();
+    category_changes.sort_by_key(|diff| diff.folder_name.to_lowercase());
+
+    // Rank the common packages by sort order in each export
+    let common_order = |index: &SceneryIndex, other: &SceneryIndex| {
+        let mut packages: Vec<&SceneryPackageInfo> = index
+            .packages
+            .values()
+            .filter(|info| other.packages.contains_key(&info.folder_name))
+            .collect();
+        packages.sort_by(|x, y| {
+            x.sort_order
+                .cmp(&y.sort_order)
+                .then_with(|| x.folder_name.cmp(&y.folder_name))
+        });
+        packages
+            .into_iter()
+            .enumerate()
+            .map(|(position, info)| (info.folder_name.clone(), position))
+            .collect::<HashMap<String, usize>>()
+    };
+    let positions_a = common_order(&a, &b);
+    let positions_b = common_order(&b, &a);
+
+    let mut order_changes: Vec<IndexOrderDiff> = positions_a
+        .iter()
+        .filter_map(|(name, &position_a)| {
+            let position_b = *positions_b.get(name)?;
+            (position_a != position_b).then(|| IndexOrderDiff {
+                folder_name: name.clone(),
+                position_a,
+                position_b,
+            })
+        })
+        .collect();
+    order_changes.sort_by_key(|diff| diff.position_a);
+
+    Ok(IndexExportDiff {
+        only_in_a,
+        only_in_b,
+        category_changes,
+        order_changes,
+    })
+}
+
+/// Audited state of a package
+fn audit_state(info: &SceneryPackageInfo) -> SceneryAuditState {
+    SceneryAuditState {
+        enabled: info.enabled,
+        sort_order: info.sort_order,
+        category: info.category.clone(),
+        category_locked: info.category_locked,
+    }
+}
+
+/// Audit changes for every package whose editable state differs between two indexes
+fn index_changes(before: &SceneryIndex, after: &SceneryIndex) -> Vec<SceneryAuditChange> {
+    let mut changes: Vec<SceneryAuditChange> = after
+        .packages
+        .iter()
+        .filter_map(|(folder_name, info)| {
+            let before = audit_state(before.packages.get(folder_name)?);
+            let after = audit_state(info);
+            (before != after).then(|| SceneryAuditChange {
+                folder_name: folder_name.clone(),
+                before,
+                after,
+            })
+        })
+        .collect();
+    changes.sort_by_key(|change| change.after.sort_order);
+    changes
+}
+
+/// Compare a stored shortcut target with a freshly resolved one
+/// Ignores slash direction, trailing slashes and case (shortcuts are Windows-only)
+fn shortcut_targets_match(stored: &str, resolved: &str) -> bool {
+    let normalize = |path: &str| path.replace('\\', "/").trim_end_matches('/').to_lowercase();
+    normalize(stored) == normalize(resolved)
+}
+
+/// Files of a scenery package to delete, resolved and validated by delete_scenery
+/// before the package is removed from the ini and index
+enum SceneryFileRemoval {
+    /// .lnk shortcut or directory symlink, plus the folder it points to if requested
+    Link { link: PathBuf, target: Option<PathBuf> },
+    /// Regular package folder in Custom Scenery
+    Folder(PathBuf),
+}
+
+impl SceneryFileRemoval {
+    fn remove(self) -> Result<()> {
+        match self {
+            SceneryFileRemoval::Link { link, target } => {
+                if let Some(target) = target {
+                    fs::remove_dir_all(&target)?;
+                    logger::log_info(
+                        &format!("Deleted shortcut target: {:?}", target),
+                        Some("scenery_index"),
+                    );
+                }
+                // Directory symlinks are removed with remove_dir on Windows
+                fs::remove_file(&link).or_else(|_| fs::remove_dir(&link))?;
+            }
+            SceneryFileRemoval::Folder(folder) => fs::remove_dir_all(&folder)?,
+        }
+        Ok(())
+    }
+}
+
+/// Merge a sorted order with entries that must stay in place
+/// Entries for which `is_excluded` returns true keep their slot from `current_order`;
+/// the remaining slots are filled with the other entries in `sorted_order`
+fn merge_sorted_with_excluded(
+    current_order: &[String],
+    sorted_order: &[String],
+    is_excluded: impl Fn(&str) -> bool,
+) -> Vec<String> {
+    let mut sorted_rest = sorted_order.iter().filter(|name| !is_excluded(name));
+
+    current_order
+        .iter()
+        .filter_map(|name| {
+            if is_excluded(name) {
+                Some(name.clone())
+            } else {
+                sorted_rest.next().cloned()
+            }
+        })
+        .collect()
+}
+
+/// Airport tile, ICAO codes and header details read from an apt.dat
+#[derive(Debug, Clone, PartialEq)]
+pub struct AirportCoords {
+    /// Latitude of the airport, floored to its DSF tile
+    pub lat: i32,
+    /// Longitude of the airport, floored to its DSF tile
+    pub lon: i32,
+    /// ICAO from the airport header row (row code 1/16/17)
+    pub header_icao: Option<String>,
+    /// ICAO from the `1302 icao_code` metadata row
+    pub metadata_icao: Option<String>,
+    /// Airport name from the header row
+    pub name: Option<String>,
+    /// Elevation in feet from the header row
+    pub elevation_ft: Option<i32>,
+}
+
+impl AirportCoords {
+    /// ICAO used for airport-mesh matching
+    /// The header ICAO wins because package names usually follow it
+    pub fn icao(&self) -> Option<&str> {
+        self.header_icao
+            .as_deref()
+            .or(self.metadata_icao.as_deref())
+    }
+
+    /// Whether the header and metadata ICAO codes disagree (author error)
+    pub fn has_icao_mismatch(&self) -> bool {
+        match (&self.header_icao, &self.metadata_icao) {
+            (Some(header), Some(metadata)) => header != metadata,
+            _ => false,
+        }
+    }
 }
 
-/// Remove a scenery entry from the index (public helper function)
-pub fn remove_scenery_entry(xplane_path: &str, folder_name: &str) -> Result<()> {
-    let manager = SceneryIndexManager::new(Path::new(xplane_path));
-    manager.remove_entry(folder_name)
+/// ICAO code, name and elevation of the (first) airport in a package's apt.dat
+pub fn read_airport_details(scenery_path: &Path) -> Option<AirportCoords> {
+    parse_airport_coords(scenery_path)
 }
 
-/// Parse airport apt.dat to extract coordinates and ICAO code
-/// Returns (latitude_floor, longitude_floor, Option<icao_code>)
+/// Parse airport apt.dat to extract coordinates and ICAO codes
 /// Tries datum_lat/datum_lon first, falls back to runway coordinates
-fn parse_airport_coords(scenery_path: &Path) -> Option<(i32, i32, Option<String>)> {
+fn parse_airport_coords(scenery_path: &Path) -> Option<AirportCoords> {
     // Find apt.dat file
     let apt_dat_path = scenery_path.join("Earth nav data").join("apt.dat");
     if !apt_dat_path.exists() {
@@ -1430,11 +3362,16 @@ fn parse_airport_coords(scenery_path: &Path) -> Option<(i32, i32, Option<String>
     }
 
     let file = fs::File::open(&apt_dat_path).ok()?;
-    let reader = BufReader::new(file);
+    parse_airport_coords_from(BufReader::new(file))
+}
 
+fn parse_airport_coords_from(reader: impl BufRead) -> Option<AirportCoords> {
     let mut datum_lat: Option<f64> = None;
     let mut datum_lon: Option<f64> = None;
     let mut icao_code: Option<String> = None;
+    let mut header_icao: Option<String> = None;
+    let mut name: Option<String> = None;
+    let mut elevation_ft: Option<i32> = None;
     let mut runway_lat: Option<f64> = None;
     let mut runway_lon: Option<f64> = None;
 
@@ -1463,12 +3400,28 @@ fn parse_airport_coords(scenery_path: &Path) -> Option<(i32, i32, Option<String>
                         }
                     }
                     "icao_code" => {
-                        icao_code = Some(parts[2].to_string());
+                        icao_code = Some(parts[2].to_uppercase());
                     }
                     _ => {}
                 }
             }
         }
+        // Airport header: 1 (land), 16 (seaplane base) or 17 (heliport)
+        // Format: <code> elevation deprecated deprecated icao name...
+        else if header_icao.is_none()
+            && (trimmed.starts_with("1 ")
+                || trimmed.starts_with("16 ")
+                || trimmed.starts_with("17 "))
+        {
+            let parts: Vec<&str> = trimmed.split_whitespace().collect();
+            if parts.len() >= 5 {
+                header_icao = Some(parts[4].to_uppercase());
+                elevation_ft = parts[1].parse::<f64>().ok().map(|e| e.round() as i32);
+                if parts.len() > 5 {
+                    name = Some(parts[5..].join(" "));
+                }
+            }
+        }
         // Fallback: parse runway line (row code 100) for coordinates
         // Format: 100 width surface shoulder smoothness centerline edge autosign runway_number lat lon ...
         // See X-Plane apt.dat specification for full format
@@ -1487,8 +3440,12 @@ fn parse_airport_coords(scenery_path: &Path) -> Option<(i32, i32, Option<String>
             }
         }
 
-        // If we have datum coords and icao, we can stop early
-        if datum_lat.is_some() && datum_lon.is_some() && icao_code.is_some() {
+        // If we have datum coords and both ICAO codes, we can stop early
+        if datum_lat.is_some()
+            && datum_lon.is_some()
+            && icao_code.is_some()
+            && header_icao.is_some()
+        {
             break;
         }
     }
@@ -1503,9 +3460,72 @@ fn parse_airport_coords(scenery_path: &Path) -> Option<(i32, i32, Option<String>
     };
 
     // Floor the coordinates to get the tile
-    let lat_floor = lat.floor() as i32;
-    let lon_floor = lon.floor() as i32;
-    Some((lat_floor, lon_floor, icao_code))
+    Some(AirportCoords {
+        lat: lat.floor() as i32,
+        lon: lon.floor() as i32,
+        header_icao,
+        metadata_icao: icao_code,
+        name,
+        elevation_ft,
+    })
+}
+
+/// Parse every airport in a package's apt.dat
+/// Airports without usable coordinates are skipped
+fn parse_all_airport_coords(scenery_path: &Path) -> Vec<AirportCoords> {
+    let apt_dat_path = scenery_path.join("Earth nav data").join("apt.dat");
+    match fs::File::open(&apt_dat_path) {
+        Ok(file) => parse_all_airport_coords_from(BufReader::new(file)),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn parse_all_airport_coords_from(reader: impl BufRead) -> Vec<AirportCoords> {
+    let mut airports = Vec::new();
+    let mut block = String::new();
+    let mut block_has_header = false;
+
+    for line in reader.lines().flatten() {
+        let trimmed = line.trim();
+        let is_header =
+            trimmed.starts_with("1 ") || trimmed.starts_with("16 ") || trimmed.starts_with("17 ");
+
+        // Each airport header starts a new block; parse the previous one on its own
+        if is_header && block_has_header {
+            airports.extend(parse_airport_coords_from(block.as_bytes()));
+            block.clear();
+        }
+        block_has_header |= is_header;
+
+        block.push_str(trimmed);
+        block.push('\n');
+    }
+    airports.extend(parse_airport_coords_from(block.as_bytes()));
+
+    airports
+}
+
+/// Whether packages of this category show up on the coverage map
+fn has_tile_coverage(category: &SceneryCategory) -> bool {
+    matches!(
+        category,
+        SceneryCategory::Airport | SceneryCategory::AirportMesh | SceneryCategory::Mesh
+    )
+}
+
+/// Sorted, deduplicated tiles covered by a package
+fn compute_tile_coverage(scenery_path: &Path, category: &SceneryCategory) -> Vec<(i32, i32)> {
+    let mut tiles: Vec<(i32, i32)> = if *category == SceneryCategory::Airport {
+        parse_all_airport_coords(scenery_path)
+            .into_iter()
+            .map(|coords| (coords.lat, coords.lon))
+            .collect()
+    } else {
+        get_mesh_dsf_coordinates(scenery_path).unwrap_or_default()
+    };
+    tiles.sort_unstable();
+    tiles.dedup();
+    tiles
 }
 
 /// Get DSF file coordinates from a mesh scenery package
@@ -1628,9 +3648,46 @@ fn extract_scenery_prefix(folder_name: &str) -> Option<String> {
     None
 }
 
+/// Whether a shortcut target may be deleted. Folders containing the X-Plane root are
+/// never deleted; folders inside it only when the target is a single package folder
+/// directly in Custom Scenery that the index owns. Both paths must be canonical
+fn is_deletable_shortcut_target(
+    target: &Path,
+    xplane_root: &Path,
+    is_indexed: impl Fn(&str) -> bool,
+) -> bool {
+    if xplane_root.starts_with(target) {
+        return false;
+    }
+    if !target.starts_with(xplane_root) {
+        return true;
+    }
+    target.parent() == Some(xplane_root.join("Custom Scenery").as_path())
+        && target
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(is_indexed)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::test_package;
+
+    #[test]
+    fn test_index_thread_pool_is_reused() {
+        let pool = index_thread_pool(2).unwrap();
+        assert_eq!(pool.current_num_threads(), 2);
+        assert!(Arc::ptr_eq(&pool, &index_thread_pool(2).unwrap()));
+
+        reset_index_thread_pool(2);
+        assert!(Arc::ptr_eq(&pool, &index_thread_pool(2).unwrap()));
+
+        reset_index_thread_pool(3);
+        let rebuilt = index_thread_pool(3).unwrap();
+        assert_eq!(rebuilt.current_num_threads(), 3);
+        assert!(!Arc::ptr_eq(&pool, &rebuilt));
+    }
 
     #[test]
     fn test_empty_index_creation() {
@@ -1642,6 +3699,928 @@ mod tests {
         assert!(index.packages.is_empty());
     }
 
+    #[test]
+    fn test_safe_mode_round_trip() {
+        let temp = tempfile::tempdir().unwrap();
+        crate::app_dirs::set_test_app_data_dir(&temp.path().join("app_data"));
+        let xplane_path = temp.path().join("X-Plane");
+        let ini_path = xplane_path.join("Custom Scenery").join("scenery_packs.ini");
+        fs::create_dir_all(ini_path.parent().unwrap()).unwrap();
+
+        let manager = SceneryIndexManager::new(&xplane_path);
+        let mut index = manager.create_empty_index();
+        for (sort_order, (name, category, enabled)) in [
+            ("SAM", SceneryCategory::FixedHighPriority, true),
+            ("KSEA", SceneryCategory::Airport, true),
+            ("OpenSceneryX", SceneryCategory::Library, true),
+            ("Other", SceneryCategory::Other, true),
+            ("Overlay", SceneryCategory::Overlay, false),
+            ("Ortho", SceneryCategory::Mesh, true),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let info = test_package(name, category, sort_order as u32, enabled);
+            index.packages.insert(name.to_string(), info);
+        }
+        manager.save_index(&index).unwrap();
+
+        let enabled_packages = || -> Vec<String> {
+            let mut names: Vec<String> = manager
+                .load_index()
+                .unwrap()
+                .packages
+                .into_values()
+                .filter(|info| info.enabled)
+                .map(|info| info.folder_name)
+                .collect();
+            names.sort();
+            names
+        };
+        let before = enabled_packages();
+
+        // Only the enabled non-essential packages are disabled, and the ini follows
+        assert_eq!(manager.enter_safe_mode().unwrap(), 2);
+        assert!(manager.is_safe_mode_active().unwrap());
+        assert!(manager.enter_safe_mode().is_err());
+        assert_eq!(enabled_packages(), vec!["KSEA", "OpenSceneryX", "SAM"]);
+        let ini = fs::read_to_string(&ini_path).unwrap();
+        assert!(ini.contains("SCENERY_PACK Custom Scenery/KSEA/\n"));
+        assert!(ini.contains("SCENERY_PACK_DISABLED Custom Scenery/Other/\n"));
+        assert!(ini.contains("SCENERY_PACK_DISABLED Custom Scenery/Ortho/\n"));
+
+        // Exiting restores exactly the previous enabled set and rewrites the ini again
+        assert_eq!(manager.exit_safe_mode().unwrap(), 2);
+        assert!(!manager.is_safe_mode_active().unwrap());
+        assert_eq!(enabled_packages(), before);
+        let ini = fs::read_to_string(&ini_path).unwrap();
+        assert!(ini.contains("SCENERY_PACK Custom Scenery/Other/\n"));
+        assert!(ini.contains("SCENERY_PACK Custom Scenery/Ortho/\n"));
+        assert!(ini.contains("SCENERY_PACK_DISABLED Custom Scenery/Overlay/\n"));
+    }
+
+    #[test]
+    fn test_undo_last_keeps_entry_until_restored() {
+        let temp = tempfile::tempdir().unwrap();
+        crate::app_dirs::set_test_app_data_dir(&temp.path().join("app_data"));
+        let xplane_path = temp.path().join("X-Plane");
+        let ini_path = xplane_path.join("Custom Scenery").join("scenery_packs.ini");
+
+        let manager = SceneryIndexManager::new(&xplane_path);
+        let mut index = manager.create_empty_index();
+        for (sort_order, name) in ["A", "B", "C"].into_iter().enumerate() {
+            let info = test_package(name, SceneryCategory::Airport, sort_order as u32, true);
+            index.packages.insert(name.to_string(), info);
+        }
+        manager.save_index(&index).unwrap();
+        manager.move_entry("C", 0).unwrap();
+
+        // Another installation has nothing to undo
+        let other = SceneryIndexManager::new(&temp.path().join("Other X-Plane"));
+        assert!(other.undo_last().unwrap().is_none());
+
+        // The ini can't be written: the undo fails and stays available
+        fs::create_dir_all(&ini_path).unwrap();
+        assert!(manager.undo_last().is_err());
+        assert_eq!(scenery_audit::read_entries(&xplane_path, 10).unwrap().len(), 1);
+
+        fs::remove_dir(&ini_path).unwrap();
+        let undone = manager.undo_last().unwrap().unwrap();
+        assert_eq!(undone.op, scenery_audit::OP_MOVE_ENTRY);
+        assert_eq!(manager.load_index().unwrap().packages["C"].sort_order, 2);
+        assert!(scenery_audit::read_entries(&xplane_path, 10).unwrap().is_empty());
+        assert!(manager.undo_last().unwrap().is_none());
+    }
+
+    fn mesh_package(name: &str, tile_count: u32) -> SceneryPackageInfo {
+        SceneryPackageInfo {
+            has_dsf: true,
+            has_textures: true,
+            texture_count: 5,
+            earth_nav_tile_count: tile_count,
+            ..test_package(name, SceneryCategory::Mesh, 0, true)
+        }
+    }
+
+    /// Minimal overlay DSF referencing one object
+    fn overlay_dsf(object: &str) -> Vec<u8> {
+        fn atom(id: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+            let mut data = id.to_vec();
+            data.extend_from_slice(&(payload.len() as u32 + 8).to_le_bytes());
+            data.extend_from_slice(payload);
+            data
+        }
+
+        let mut data = b"XPLNEDSF".to_vec();
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend(atom(b"DAEH", &atom(b"PORP", b"sim/overlay\01\0")));
+        data.extend(atom(b"NFED", &atom(b"TJBO", format!("{}\0", object).as_bytes())));
+        data
+    }
+
+    #[test]
+    fn test_enable_packages_with_satisfied_deps() {
+        let temp = tempfile::tempdir().unwrap();
+        crate::app_dirs::set_test_app_data_dir(&temp.path().join("app_data"));
+        let xplane_path = temp.path().join("X-Plane");
+        let custom_scenery = xplane_path.join("Custom Scenery");
+        for name in ["Overlay", "Disabled_By_User"] {
+            let tile_dir = custom_scenery.join(name).join("Earth nav data").join("+40-080");
+            fs::create_dir_all(&tile_dir).unwrap();
+            fs::write(
+                tile_dir.join("+40-074.dsf"),
+                overlay_dsf("opensceneryx/objects/tree.obj"),
+            )
+            .unwrap();
+        }
+
+        let manager = SceneryIndexManager::new(&xplane_path);
+        let index = manager.update_index().unwrap();
+        assert_eq!(
+            index.packages["Overlay"].missing_libraries,
+            vec!["opensceneryx".to_string()]
+        );
+        manager.update_entry("Overlay", Some(false), None, None).unwrap();
+
+        // Installing the library recomputes missing_libraries during update_index
+        let library = custom_scenery.join("OpenSceneryX");
+        fs::create_dir_all(&library).unwrap();
+        fs::write(
+            library.join("library.txt"),
+            "EXPORT opensceneryx/objects/tree.obj tree.obj\n",
+        )
+        .unwrap();
+        let index = manager.update_index().unwrap();
+        assert!(index.packages["Overlay"].missing_libraries.is_empty());
+
+        // Disabled only after its library was found, so it stays disabled
+        manager
+            .update_entry("Disabled_By_User", Some(false), None, None)
+            .unwrap();
+
+        assert_eq!(
+            manager.enable_packages_with_satisfied_deps().unwrap(),
+            vec!["Overlay".to_string()]
+        );
+        let index = manager.load_index().unwrap();
+        assert!(index.packages["Overlay"].enabled);
+        assert!(!index.packages["Disabled_By_User"].enabled);
+        assert!(manager
+            .enable_packages_with_satisfied_deps()
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_rebuild_index_keeps_dynamic_packages() {
+        let temp = tempfile::tempdir().unwrap();
+        crate::app_dirs::set_test_app_data_dir(&temp.path().join("app_data"));
+        let xplane_path = temp.path().join("X-Plane");
+        let custom_scenery = xplane_path.join("Custom Scenery");
+        for name in ["XPME_Europe", "Overlay"] {
+            let tile_dir = custom_scenery.join(name).join("Earth nav data").join("+40-080");
+            fs::create_dir_all(&tile_dir).unwrap();
+            fs::write(tile_dir.join("+40-074.dsf"), overlay_dsf("a/b.obj")).unwrap();
+        }
+
+        let manager = SceneryIndexManager::new(&xplane_path);
+        manager.update_index().unwrap();
+        for name in ["XPME_Europe", "Overlay"] {
+            manager
+                .update_entry(name, None, None, Some(SceneryCategory::Mesh))
+                .unwrap();
+        }
+
+        // Only the regular package is classified again
+        let index = manager.rebuild_index().unwrap();
+        assert_eq!(index.packages["XPME_Europe"].category, SceneryCategory::Mesh);
+        assert_eq!(index.packages["Overlay"].category, SceneryCategory::Overlay);
+    }
+
+    #[test]
+    fn test_set_category_locks_and_unlocks() {
+        let temp = tempfile::tempdir().unwrap();
+        crate::app_dirs::set_test_app_data_dir(&temp.path().join("app_data"));
+        let xplane_path = temp.path().join("X-Plane");
+        let tile_dir = xplane_path
+            .join("Custom Scenery")
+            .join("Overlay")
+            .join("Earth nav data")
+            .join("+40-080");
+        fs::create_dir_all(&tile_dir).unwrap();
+        fs::write(tile_dir.join("+40-074.dsf"), overlay_dsf("a/b.obj")).unwrap();
+
+        let manager = SceneryIndexManager::new(&xplane_path);
+        manager.update_index().unwrap();
+
+        manager
+            .set_category("Overlay", Some(SceneryCategory::Mesh))
+            .unwrap();
+        let info = manager.get_package("Overlay").unwrap().unwrap();
+        assert_eq!(info.category, SceneryCategory::Mesh);
+        assert!(info.category_locked);
+
+        // The change is recorded and can be undone, lock included
+        let entry = manager.undo_last().unwrap().unwrap();
+        assert_eq!(entry.op, scenery_audit::OP_SET_CATEGORY);
+        let info = manager.get_package("Overlay").unwrap().unwrap();
+        assert_eq!(info.category, SceneryCategory::Overlay);
+        assert!(!info.category_locked);
+
+        manager
+            .set_category("Overlay", Some(SceneryCategory::Mesh))
+            .unwrap();
+
+        // Clearing the lock restores the classified category
+        manager.set_category("Overlay", None).unwrap();
+        let info = manager.get_package("Overlay").unwrap().unwrap();
+        assert_eq!(info.category, SceneryCategory::Overlay);
+        assert!(!info.category_locked);
+
+        assert!(manager.set_category("Missing", None).is_err());
+    }
+
+    #[test]
+    fn test_set_enabled_bulk_audits_changed_rows() {
+        let temp = tempfile::tempdir().unwrap();
+        crate::app_dirs::set_test_app_data_dir(&temp.path().join("app_data"));
+        let xplane_path = temp.path().join("X-Plane");
+        let manager = SceneryIndexManager::new(&xplane_path);
+        let index = SceneryIndex {
+            version: CURRENT_SCHEMA_VERSION as u32,
+            packages: [
+                test_package("Ortho_A", SceneryCategory::Mesh, 0, true),
+                test_package("Ortho_B", SceneryCategory::Mesh, 1, false),
+            ]
+            .into_iter()
+            .map(|info| (info.folder_name.clone(), info))
+            .collect(),
+            last_updated: SystemTime::now(),
+        };
+        manager.save_index(&index).unwrap();
+
+        let names: Vec<String> = ["Ortho_A", "Ortho_B", "Ortho_A", "Missing"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(manager.set_enabled_bulk(&names, false).unwrap(), 1);
+
+        let entry = scenery_audit::last_entry(&xplane_path).unwrap().unwrap();
+        assert_eq!(entry.op, scenery_audit::OP_SET_ENABLED_BULK);
+        let audited: Vec<&str> = entry
+            .changes
+            .iter()
+            .map(|c| c.folder_name.as_str())
+            .collect();
+        assert_eq!(audited, vec!["Ortho_A"]);
+    }
+
+    #[test]
+    fn test_place_entry_moves_once() {
+        let temp = tempfile::tempdir().unwrap();
+        crate::app_dirs::set_test_app_data_dir(&temp.path().join("app_data"));
+        let xplane_path = temp.path().join("X-Plane");
+        let custom_scenery = xplane_path.join("Custom Scenery");
+        for icao in ["KAAA", "KSEA"] {
+            let nav = custom_scenery.join(icao).join("Earth nav data");
+            fs::create_dir_all(&nav).unwrap();
+            fs::write(
+                nav.join("apt.dat"),
+                format!("I\n1100 Version\n\n1 100 0 0 {} Test\n\n99\n", icao),
+            )
+            .unwrap();
+        }
+        let library = custom_scenery.join("OpenSceneryX");
+        fs::create_dir_all(&library).unwrap();
+        fs::write(library.join("library.txt"), "EXPORT a/b.obj b.obj\n").unwrap();
+
+        let manager = SceneryIndexManager::new(&xplane_path);
+        manager.update_index().unwrap();
+        let order = || -> Vec<String> {
+            let mut packages: Vec<SceneryPackageInfo> =
+                manager.load_index().unwrap().packages.into_values().collect();
+            packages.sort_by_key(|info| info.sort_order);
+            packages.into_iter().map(|info| info.folder_name).collect()
+        };
+
+        SceneryPacksManager::new(&xplane_path)
+            .place_entry("KAAA", 2)
+            .unwrap();
+        assert_eq!(order(), vec!["KSEA", "OpenSceneryX", "KAAA"]);
+        let ini = fs::read_to_string(custom_scenery.join("scenery_packs.ini")).unwrap();
+        let kaaa = ini.find("Custom Scenery/KAAA/").unwrap();
+        assert!(ini.find("Custom Scenery/OpenSceneryX/").unwrap() < kaaa);
+
+        // Nothing is pinned: a later re-sort puts it back in its category
+        manager.reset_sort_order().unwrap();
+        assert_eq!(order(), vec!["KAAA", "KSEA", "OpenSceneryX"]);
+    }
+
+    #[test]
+    fn test_diff_index_exports() {
+        let export = |packages: &[(&str, SceneryCategory, u32)]| {
+            let index = SceneryIndex {
+                version: CURRENT_SCHEMA_VERSION as u32,
+                packages: packages
+                    .iter()
+                    .map(|(name, category, sort_order)| {
+                        let mut info = mesh_package(name, 1);
+                        info.category = category.clone();
+                        info.sort_order = *sort_order;
+                        (name.to_string(), info)
+                    })
+                    .collect(),
+                last_updated: SystemTime::now(),
+            };
+            serde_json::to_string(&index).unwrap()
+        };
+
+        let a = export(&[
+            ("Airport", SceneryCategory::Airport, 0),
+            ("Library", SceneryCategory::Library, 1),
+            ("Old", SceneryCategory::Overlay, 2),
+            ("Mesh", SceneryCategory::Mesh, 3),
+        ]);
+        let b = export(&[
+            ("Library", SceneryCategory::Library, 0),
+            ("Airport", SceneryCategory::AirportMesh, 1),
+            ("Mesh", SceneryCategory::Mesh, 2),
+            ("New", SceneryCategory::Overlay, 3),
+        ]);
+
+        let diff = diff_index_exports(&a, &b).unwrap();
+        assert_eq!(diff.only_in_a, vec!["Old".to_string()]);
+        assert_eq!(diff.only_in_b, vec!["New".to_string()]);
+        assert_eq!(
+            diff.category_changes,
+            vec![IndexCategoryDiff {
+                folder_name: "Airport".to_string(),
+                category_a: SceneryCategory::Airport,
+                category_b: SceneryCategory::AirportMesh,
+            }]
+        );
+        // Removing "Old" alone doesn't count as a move for "Mesh"
+        let moved: Vec<(&str, usize, usize)> = diff
+            .order_changes
+            .iter()
+            .map(|d| (d.folder_name.as_str(), d.position_a, d.position_b))
+            .collect();
+        assert_eq!(moved, vec![("Airport", 0, 1), ("Library", 1, 0)]);
+
+        assert!(diff_index_exports("not json", &b).is_err());
+    }
+
+    #[test]
+    fn test_import_validation() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::create_dir(temp.path().join("Present")).unwrap();
+
+        let mut index = SceneryIndex {
+            version: CURRENT_SCHEMA_VERSION as u32,
+            packages: ["Present", "Gone"]
+                .iter()
+                .map(|name| (name.to_string(), mesh_package(name, 1)))
+                .collect(),
+            last_updated: SystemTime::now(),
+        };
+        let json = serde_json::to_string_pretty(&index).unwrap();
+        assert_eq!(parse_index_export(&json).unwrap().packages.len(), 2);
+
+        let skipped = drop_missing_packages(&mut index, temp.path());
+        assert_eq!(skipped, vec!["Gone".to_string()]);
+        assert!(index.packages.contains_key("Present"));
+
+        // Exports from an unknown (newer) schema are rejected
+        index.version = CURRENT_SCHEMA_VERSION as u32 + 1;
+        let json = serde_json::to_string(&index).unwrap();
+        assert!(parse_index_export(&json).is_err());
+    }
+
+    #[test]
+    fn test_merge_index_export() {
+        let package = |name: &str, sort_order: u32, enabled: bool| {
+            let mut info = mesh_package(name, 1);
+            info.sort_order = sort_order;
+            info.enabled = enabled;
+            (name.to_string(), info)
+        };
+        let mut index = SceneryIndex {
+            version: CURRENT_SCHEMA_VERSION as u32,
+            packages: [
+                package("Shared_A", 0, true),
+                package("Local_Only", 1, true),
+                package("Shared_B", 2, true),
+            ]
+            .into_iter()
+            .collect(),
+            last_updated: SystemTime::now(),
+        };
+        let imported = SceneryIndex {
+            version: CURRENT_SCHEMA_VERSION as u32,
+            packages: [package("Shared_B", 0, false), package("Shared_A", 1, true)]
+                .into_iter()
+                .collect(),
+            last_updated: SystemTime::now(),
+        };
+
+        assert_eq!(merge_index_export(&mut index, imported), 1);
+        let mut order: Vec<(&str, u32)> = index
+            .packages
+            .values()
+            .map(|info| (info.folder_name.as_str(), info.sort_order))
+            .collect();
+        order.sort_by_key(|(_, sort_order)| *sort_order);
+        assert_eq!(
+            order,
+            vec![("Shared_B", 0), ("Shared_A", 1), ("Local_Only", 2)]
+        );
+        assert!(!index.packages["Shared_B"].enabled);
+    }
+
+    #[test]
+    fn test_mesh_region_sorting() {
+        let a = mesh_package("A_Ortho_Europe", 1);
+        let b = mesh_package("B_Ortho_Asia", 2);
+
+        // Default: tile count, then name
+        let no_regions = HashMap::new();
+        assert_eq!(
+            compare_packages_for_sorting(&a.folder_name, &a, &b.folder_name, &b, &no_regions),
+            std::cmp::Ordering::Less
+        );
+
+        // Region sort: south-west tile decides
+        let mut regions = HashMap::new();
+        regions.insert(a.folder_name.clone(), (45, 5));
+        regions.insert(b.folder_name.clone(), (30, 135));
+        assert_eq!(
+            compare_packages_for_sorting(&a.folder_name, &a, &b.folder_name, &b, &regions),
+            std::cmp::Ordering::Greater
+        );
+    }
+
+    #[test]
+    fn test_mesh_sort_mixed_regions_is_transitive() {
+        let a = mesh_package("A", 5);
+        let b = mesh_package("B", 1);
+        let c = mesh_package("C", 3);
+
+        // Only A and C have a region: region packages come first, the rest by tile count
+        let mut regions = HashMap::new();
+        regions.insert(a.folder_name.clone(), (45, 5));
+        regions.insert(c.folder_name.clone(), (30, 135));
+
+        let mut packages = vec![&a, &b, &c];
+        packages.sort_by(|x, y| {
+            compare_packages_for_sorting(&x.folder_name, x, &y.folder_name, y, &regions)
+        });
+        let names: Vec<&str> = packages.iter().map(|p| p.folder_name.as_str()).collect();
+        assert_eq!(names, vec!["C", "A", "B"]);
+    }
+
+    #[test]
+    fn test_orthophotos_sort_above_regular_mesh() {
+        let ortho = mesh_package("zOrtho4XP_+47+008", 1);
+        let mesh = mesh_package("HD_Mesh_v4", 4);
+        // An ortho-named pack without textures stays regular mesh
+        let mut bare = mesh_package("zOrtho4XP_+48+008", 1);
+        bare.texture_count = 0;
+
+        assert!(ortho.is_orthophoto());
+        assert!(!mesh.is_orthophoto());
+        assert!(!bare.is_orthophoto());
+
+        let no_regions = HashMap::new();
+        let mut packages = vec![&mesh, &bare, &ortho];
+        packages.sort_by(|x, y| {
+            compare_packages_for_sorting(&x.folder_name, x, &y.folder_name, y, &no_regions)
+        });
+        let names: Vec<&str> = packages.iter().map(|p| p.folder_name.as_str()).collect();
+        assert_eq!(names, vec!["zOrtho4XP_+47+008", "zOrtho4XP_+48+008", "HD_Mesh_v4"]);
+    }
+
+    #[test]
+    fn test_carry_category_lock() {
+        let mut previous = mesh_package("Misdetected", 1);
+        previous.category = SceneryCategory::Overlay;
+        previous.sub_priority = 3;
+
+        // Unlocked packages take the new classification
+        let mut info = mesh_package("Misdetected", 4);
+        carry_category_lock(&mut info, Some(&previous));
+        assert_eq!(info.category, SceneryCategory::Mesh);
+        assert!(!info.category_locked);
+
+        // Locked packages keep category and sub-priority, but refresh file flags
+        previous.category_locked = true;
+        carry_category_lock(&mut info, Some(&previous));
+        assert_eq!(info.category, SceneryCategory::Overlay);
+        assert_eq!(info.sub_priority, 3);
+        assert!(info.category_locked);
+        assert_eq!(info.earth_nav_tile_count, 4);
+
+        let mut new_package = mesh_package("New", 1);
+        carry_category_lock(&mut new_package, None);
+        assert_eq!(new_package.category, SceneryCategory::Mesh);
+    }
+
+    #[test]
+    fn test_high_priority_folder_patterns() {
+        let config = SceneryOrderingConfig {
+            high_priority_patterns: vec!["*jetway*".to_string(), "GSX_*".to_string()],
+            ..Default::default()
+        };
+        let patterns = compile_high_priority_patterns(&config);
+
+        assert!(is_high_priority_folder_name("SAM_Library", &patterns));
+        assert!(is_high_priority_folder_name("My_Jetways_Lib", &patterns));
+        assert!(is_high_priority_folder_name("gsx_ground_services", &patterns));
+        assert!(!is_high_priority_folder_name("OpenSceneryX", &patterns));
+        assert!(!is_high_priority_folder_name("OpenSceneryX", &[]));
+    }
+
+    #[test]
+    fn test_find_disabled_dependencies() {
+        let mut airport = mesh_package("KSEA_Airport", 1);
+        airport.required_libraries = vec!["opensceneryx".to_string(), "sam".to_string()];
+        airport.sort_order = 1;
+        let mut osx = mesh_package("OpenSceneryX", 0);
+        osx.exported_library_names = vec!["opensceneryx".to_string()];
+        osx.enabled = false;
+        let mut sam = mesh_package("SAM_Library", 0);
+        sam.exported_library_names = vec!["sam".to_string()];
+        let mut disabled_user = mesh_package("Disabled_Airport", 1);
+        disabled_user.required_libraries = vec!["opensceneryx".to_string()];
+        disabled_user.enabled = false;
+
+        let index = SceneryIndex {
+            version: CURRENT_SCHEMA_VERSION as u32,
+            packages: [airport, osx, sam, disabled_user]
+                .into_iter()
+                .map(|info| (info.folder_name.clone(), info))
+                .collect(),
+            last_updated: SystemTime::now(),
+        };
+
+        assert_eq!(
+            find_disabled_dependencies(&index),
+            vec![("KSEA_Airport".to_string(), vec!["OpenSceneryX".to_string()])]
+        );
+    }
+
+    #[test]
+    fn test_disabled_providers_of() {
+        let mut osx = mesh_package("OpenSceneryX", 0);
+        osx.exported_library_names = vec!["opensceneryx".to_string()];
+        osx.enabled = false;
+        osx.sort_order = 2;
+        let mut osx_backup = mesh_package("OSX_Backup", 0);
+        osx_backup.exported_library_names = vec!["OpenSceneryX".to_string()];
+        osx_backup.enabled = false;
+        osx_backup.sort_order = 1;
+        let mut sam = mesh_package("SAM_Library", 0);
+        sam.exported_library_names = vec!["sam".to_string()];
+        let mut sam_old = mesh_package("SAM_Old", 0);
+        sam_old.exported_library_names = vec!["sam".to_string()];
+        sam_old.enabled = false;
+
+        let index = SceneryIndex {
+            version: CURRENT_SCHEMA_VERSION as u32,
+            packages: [osx, osx_backup, sam, sam_old]
+                .into_iter()
+                .map(|info| (info.folder_name.clone(), info))
+                .collect(),
+            last_updated: SystemTime::now(),
+        };
+
+        assert_eq!(
+            disabled_providers_of(&index, "OPENSCENERYX"),
+            vec!["OSX_Backup".to_string(), "OpenSceneryX".to_string()]
+        );
+        // An enabled package already provides sam, so nothing needs enabling
+        assert!(disabled_providers_of(&index, "sam").is_empty());
+        assert!(disabled_providers_of(&index, "missing").is_empty());
+    }
+
+    #[test]
+    fn test_find_duplicate_library_exports() {
+        let mut osx = mesh_package("OpenSceneryX", 0);
+        osx.exported_library_names = vec!["opensceneryx".to_string(), "osx/objects".to_string()];
+        let mut osx_copy = mesh_package("OpenSceneryX_old", 0);
+        osx_copy.exported_library_names = vec!["opensceneryx".to_string()];
+        let mut sam = mesh_package("SAM_Library", 0);
+        sam.exported_library_names = vec!["sam".to_string(), "sam".to_string()];
+
+        let index = SceneryIndex {
+            version: CURRENT_SCHEMA_VERSION as u32,
+            packages: [osx, osx_copy, sam]
+                .into_iter()
+                .map(|info| (info.folder_name.clone(), info))
+                .collect(),
+            last_updated: SystemTime::now(),
+        };
+
+        let duplicates = find_duplicate_library_exports(&index);
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(
+            duplicates["opensceneryx"],
+            vec!["OpenSceneryX".to_string(), "OpenSceneryX_old".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_diff_library_indexes() {
+        let old: HashMap<String, String> = [
+            ("opensceneryx", "OpenSceneryX"),
+            ("ruscenery", "RuScenery"),
+            ("sam", "SAM_Library"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        let mut new = old.clone();
+        new.remove("ruscenery");
+        new.insert("sam".to_string(), "SAM_Library_v2".to_string());
+        new.insert("misterx".to_string(), "MisterX_Library".to_string());
+
+        let changed = diff_library_indexes(&old, &new);
+        assert_eq!(changed.len(), 3);
+        assert!(changed.contains("ruscenery"));
+        assert!(changed.contains("sam"));
+        assert!(changed.contains("misterx"));
+        assert!(diff_library_indexes(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn test_build_imported_order() {
+        let mut airport = mesh_package("KSEA_Airport", 0);
+        airport.category = SceneryCategory::Airport;
+        let mut library = mesh_package("OpenSceneryX", 0);
+        library.category = SceneryCategory::Library;
+        let mut shortcut = mesh_package("Ortho_Link", 1);
+        shortcut.actual_path = Some("D:/Ortho/zOrtho4XP_+47+008/".to_string());
+        let local_only = mesh_package("Local_Mesh", 3);
+
+        let index = SceneryIndex {
+            version: CURRENT_SCHEMA_VERSION as u32,
+            packages: [airport, library, shortcut, local_only]
+                .into_iter()
+                .map(|info| (info.folder_name.clone(), info))
+                .collect(),
+            last_updated: SystemTime::now(),
+        };
+
+        let ini_paths: Vec<String> = [
+            "Custom Scenery/opensceneryx/",
+            "Custom Scenery/Remote_Airport/",
+            "D:/Other/zOrtho4XP_+47+008/",
+            "Custom Scenery/KSEA_Airport/",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+        let (order, unknown) = build_imported_order(&ini_paths, &index);
+        assert_eq!(
+            order,
+            vec!["OpenSceneryX", "Ortho_Link", "KSEA_Airport", "Local_Mesh"]
+        );
+        assert_eq!(unknown, vec!["Remote_Airport".to_string()]);
+    }
+
+    #[test]
+    fn test_is_dynamic_package() {
+        assert!(is_dynamic_package("XPME_Europe"));
+        assert!(is_dynamic_package("z_autoortho"));
+        assert!(is_dynamic_package("z_ao_na"));
+        assert!(!is_dynamic_package("zOrtho4XP_+47+008"));
+        assert!(!is_dynamic_package("KSEA_Airport"));
+    }
+
+    #[test]
+    fn test_is_protected_scenery_folder() {
+        assert!(is_protected_scenery_folder("*GLOBAL_AIRPORTS*"));
+        assert!(is_protected_scenery_folder("Global Airports"));
+        assert!(is_protected_scenery_folder("X-Plane Landmarks - Chicago"));
+        assert!(!is_protected_scenery_folder("KSEA_Airport"));
+    }
+
+    #[test]
+    fn test_shortcut_targets_match() {
+        assert!(shortcut_targets_match(
+            "D:/Ortho/zOrtho4XP_+47+008/",
+            "d:\\Ortho\\zOrtho4XP_+47+008"
+        ));
+        assert!(!shortcut_targets_match(
+            "D:/Ortho/zOrtho4XP_+47+008/",
+            "E:/Ortho/zOrtho4XP_+47+008"
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_delete_scenery_updates_ini_and_index_before_files() {
+        let temp = tempfile::tempdir().unwrap();
+        crate::app_dirs::set_test_app_data_dir(&temp.path().join("app_data"));
+        let xplane_path = temp.path().join("X-Plane");
+        let custom_scenery = xplane_path.join("Custom Scenery");
+        fs::create_dir_all(custom_scenery.join("Pack")).unwrap();
+        fs::create_dir_all(xplane_path.join("Resources")).unwrap();
+        std::os::unix::fs::symlink(xplane_path.join("Resources"), custom_scenery.join("Link"))
+            .unwrap();
+
+        let manager = SceneryIndexManager::new(&xplane_path);
+        let mut index = manager.create_empty_index();
+        for (sort_order, name) in ["Pack", "Link"].into_iter().enumerate() {
+            let info = test_package(name, SceneryCategory::Other, sort_order as u32, true);
+            index.packages.insert(name.to_string(), info);
+        }
+        manager.save_index(&index).unwrap();
+        SceneryPacksManager::new(&xplane_path).apply_from_index().unwrap();
+        let ini_path = custom_scenery.join("scenery_packs.ini");
+
+        // The target can't be deleted: nothing changes, not even the ini or index
+        assert!(manager.delete_scenery("Link", true, true).is_err());
+        assert!(manager.get_package("Link").unwrap().is_some());
+        assert!(fs::read_to_string(&ini_path).unwrap().contains("Custom Scenery/Link/"));
+        assert!(custom_scenery.join("Link").exists());
+
+        manager.delete_scenery("Pack", true, false).unwrap();
+        assert!(manager.get_package("Pack").unwrap().is_none());
+        assert!(!fs::read_to_string(&ini_path).unwrap().contains("Custom Scenery/Pack/"));
+        assert!(!custom_scenery.join("Pack").exists());
+    }
+
+    #[test]
+    fn test_is_deletable_shortcut_target() {
+        let root = Path::new("/xp");
+        let indexed = |name: &str| name == "KSEA Airport";
+
+        // Outside the installation
+        assert!(is_deletable_shortcut_target(
+            Path::new("/data/KSEA Airport"),
+            root,
+            indexed
+        ));
+        // Containing the installation
+        assert!(!is_deletable_shortcut_target(Path::new("/"), root, indexed));
+        assert!(!is_deletable_shortcut_target(root, root, indexed));
+        // Inside the installation: only an indexed package folder
+        assert!(is_deletable_shortcut_target(
+            Path::new("/xp/Custom Scenery/KSEA Airport"),
+            root,
+            indexed
+        ));
+        assert!(!is_deletable_shortcut_target(
+            Path::new("/xp/Custom Scenery/Unknown"),
+            root,
+            indexed
+        ));
+        assert!(!is_deletable_shortcut_target(
+            Path::new("/xp/Custom Scenery"),
+            root,
+            indexed
+        ));
+        assert!(!is_deletable_shortcut_target(
+            Path::new("/xp/Resources/default scenery"),
+            root,
+            indexed
+        ));
+    }
+
+    #[test]
+    fn test_merge_sorted_with_excluded() {
+        let names = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let current = names(&["mesh_b", "airport_b", "ortho", "airport_a", "mesh_a"]);
+        let sorted = names(&["airport_a", "airport_b", "mesh_a", "mesh_b", "ortho"]);
+
+        // Meshes stay where they are, the rest is sorted into the remaining slots
+        let merged = merge_sorted_with_excluded(&current, &sorted, |name| name.starts_with("mesh"));
+        assert_eq!(
+            merged,
+            names(&["mesh_b", "airport_a", "airport_b", "ortho", "mesh_a"])
+        );
+
+        // Nothing excluded: same as a full sort
+        assert_eq!(
+            merge_sorted_with_excluded(&current, &sorted, |_| false),
+            sorted
+        );
+    }
+
+    #[test]
+    fn test_parse_airport_coords_icao_mismatch() {
+        let apt_dat = "I\n1100 Version\n\n1 100 0 0 EGLL London Heathrow\n\
+                       1302 icao_code EGKK\n1302 datum_lat 51.4775\n1302 datum_lon -0.4614\n";
+        let coords = parse_airport_coords_from(apt_dat.as_bytes()).unwrap();
+        assert_eq!((coords.lat, coords.lon), (51, -1));
+        assert_eq!(coords.header_icao.as_deref(), Some("EGLL"));
+        assert_eq!(coords.metadata_icao.as_deref(), Some("EGKK"));
+        assert!(coords.has_icao_mismatch());
+        assert_eq!(coords.icao(), Some("EGLL"));
+        assert_eq!(coords.name.as_deref(), Some("London Heathrow"));
+        assert_eq!(coords.elevation_ft, Some(100));
+
+        // Metadata only (no header row) falls back to icao_code
+        let apt_dat = "1302 icao_code kjfk\n1302 datum_lat 40.6\n1302 datum_lon -73.8\n";
+        let coords = parse_airport_coords_from(apt_dat.as_bytes()).unwrap();
+        assert!(!coords.has_icao_mismatch());
+        assert_eq!(coords.icao(), Some("KJFK"));
+        assert_eq!(coords.name, None);
+    }
+
+    #[test]
+    fn test_parse_library_exports_with_regions() {
+        let temp = tempfile::tempdir().unwrap();
+        let library_txt = temp.path().join("library.txt");
+        fs::write(
+            &library_txt,
+            "A\n800\nLIBRARY\n\n\
+             REGION_DEFINE europe\n\
+             REGION_RECT -10 35 30 70\n\
+             \n\
+             REGION europe\n\
+             EXPORT_EXTEND eu_lib/objects/house.obj objects/house_eu.obj\n\
+             EXPORT_RATIO 0.5 ratio_lib/trees/oak.for forests/oak.for\n\
+             \n\
+             REGION all\n\
+             EXPORT\tmy_lib/objects/tower.obj objects/tower.obj # comment\n\
+             EXPORT_EXCLUDE excl_lib\\facades\\wall.fac facades/wall.fac\n\
+             EXPORT\n",
+        )
+        .unwrap();
+
+        let exports = parse_library_exports(&library_txt);
+        let expected: HashSet<String> = ["eu_lib", "ratio_lib", "my_lib", "excl_lib"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(exports, expected);
+
+        // Region-only library.txt exports nothing
+        fs::write(
+            &library_txt,
+            "REGION_DEFINE r\nREGION_RECT 0 0 1 1\nREGION r\n",
+        )
+        .unwrap();
+        assert!(parse_library_exports(&library_txt).is_empty());
+    }
+
+    #[test]
+    fn test_parse_all_airport_coords() {
+        let apt_dat = "I\n1100 Version\n\n\
+                       1 100 0 0 EGLL London Heathrow\n\
+                       1302 datum_lat 51.4775\n1302 datum_lon -0.4614\n\n\
+                       17 50 0 0 XHEL Heliport\n\
+                       100 20 1 0 0 0 0 0 09 48.5 8.2 0 0 0 0 0 0 27 48.5 8.3\n\n\
+                       16 0 0 0 XSEA Seaplane Base\n\n99\n";
+        let airports = parse_all_airport_coords_from(apt_dat.as_bytes());
+        let tiles: Vec<(i32, i32)> = airports.iter().map(|a| (a.lat, a.lon)).collect();
+        // The seaplane base has no coordinates and is skipped
+        assert_eq!(tiles, vec![(51, -1), (48, 8)]);
+        assert_eq!(airports[1].icao(), Some("XHEL"));
+    }
+
+    #[test]
+    fn test_detect_airport_mesh_multiple_airports() {
+        let temp = tempfile::tempdir().unwrap();
+        let custom_scenery = temp.path().join("Custom Scenery");
+
+        // One regional pack covering two airports on different tiles
+        let pack_nav = custom_scenery.join("Regional_Pack").join("Earth nav data");
+        fs::create_dir_all(&pack_nav).unwrap();
+        fs::write(
+            pack_nav.join("apt.dat"),
+            "I\n1100 Version\n\n\
+             1 100 0 0 KSEA Seattle\n1302 datum_lat 47.45\n1302 datum_lon -122.31\n\n\
+             1 100 0 0 KPDX Portland\n1302 datum_lat 45.59\n1302 datum_lon -122.60\n\n99\n",
+        )
+        .unwrap();
+
+        // Small meshes sitting on the second airport's tile and on an unrelated tile
+        for (name, dsf) in [("KPDX_Mesh", "+45-123"), ("Other_Mesh", "+10+010")] {
+            let dsf_dir = custom_scenery
+                .join(name)
+                .join("Earth nav data")
+                .join("+40-130");
+            fs::create_dir_all(&dsf_dir).unwrap();
+            fs::write(dsf_dir.join(format!("{}.dsf", dsf)), b"").unwrap();
+        }
+
+        let mut airport = mesh_package("Regional_Pack", 0);
+        airport.category = SceneryCategory::Airport;
+        airport.has_apt_dat = true;
+        let mut packages = vec![
+            airport,
+            mesh_package("KPDX_Mesh", 1),
+            mesh_package("Other_Mesh", 1),
+        ];
+
+        SceneryIndexManager::new(temp.path()).detect_airport_mesh_packages(&mut packages);
+        assert_eq!(packages[1].category, SceneryCategory::AirportMesh);
+        assert_eq!(packages[2].category, SceneryCategory::Mesh);
+    }
+
     #[test]
     fn test_extract_scenery_prefix() {
         // Test "_<number>_" pattern extraction
@@ -4,16 +4,29 @@
 //! based on scenery classifications.
 
 use crate::logger;
-use crate::models::{SceneryCategory, SceneryPackEntry};
-use crate::scenery_index::SceneryIndexManager;
+use crate::models::{
+    IniCaseMismatch, IniLintFinding, IniLintKind, IniReconcileResult, IniSyncDivergence,
+    SceneryCategory, SceneryEntryUpdate, SceneryOrderingConfig, SceneryPackEntry,
+    SceneryPackageInfo, SceneryPacksBackup, SortPreviewChange,
+};
+use crate::scenery_index::{resolve_shortcut, SceneryIndexManager};
 use anyhow::{anyhow, Result};
-use chrono::Local;
+use chrono::{Local, NaiveDateTime, TimeZone};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
 const INI_HEADER: &str = "I\n1000 Version\nSCENERY\n\n";
 
+/// Prefix of scenery_packs.ini backups, followed by a BACKUP_TIMESTAMP_FORMAT timestamp
+/// Milliseconds keep back-to-back backups apart; older names without them still parse
+const BACKUP_PREFIX: &str = "scenery_packs.ini.backup.";
+const BACKUP_TIMESTAMP_FORMAT: &str = "%Y%m%d_%H%M%S%.3f";
+
+/// Number of scenery_packs.ini backups kept; older ones are deleted first
+const MAX_BACKUPS: usize = 10;
+
 /// Normalize a scenery path for scenery_packs.ini
 /// Converts backslashes to forward slashes and ensures trailing slash
 fn normalize_scenery_path(path: &str) -> String {
@@ -82,19 +95,31 @@ impl SceneryPacksManager {
         Ok(())
     }
 
-    /// Create a backup of scenery_packs.ini
+    /// Create a backup of scenery_packs.ini, then delete the oldest backups
+    /// beyond the retention limit
     pub fn backup_ini(&self) -> Result<PathBuf> {
         if !self.ini_path.exists() {
             return Err(anyhow!("scenery_packs.ini does not exist"));
         }
 
-        let timestamp = Local::now().format("%Y%m%d_%H%M%S");
-        let backup_name = format!("scenery_packs.ini.backup.{}", timestamp);
-        let backup_path = self
+        let backup_dir = self
             .ini_path
             .parent()
-            .ok_or_else(|| anyhow!("Invalid ini path: no parent directory"))?
-            .join(backup_name);
+            .ok_or_else(|| anyhow!("Invalid ini path: no parent directory"))?;
+
+        // Never rename onto an existing backup (e.g. the one being restored)
+        let mut timestamp = Local::now().naive_local();
+        let backup_path = loop {
+            let path = backup_dir.join(format!(
+                "{}{}",
+                BACKUP_PREFIX,
+                timestamp.format(BACKUP_TIMESTAMP_FORMAT)
+            ));
+            if !path.exists() {
+                break path;
+            }
+            timestamp += chrono::Duration::milliseconds(1);
+        };
 
         fs::rename(&self.ini_path, &backup_path)?;
         logger::log_info(
@@ -102,9 +127,178 @@ impl SceneryPacksManager {
             Some("scenery_packs"),
         );
 
+        match prune_backups(backup_dir, MAX_BACKUPS) {
+            Ok(0) => {}
+            Ok(removed) => logger::log_info(
+                &format!("Removed {} old scenery_packs.ini backup(s)", removed),
+                Some("scenery_packs"),
+            ),
+            Err(e) => logger::log_error(
+                &format!("Failed to remove old backups: {}", e),
+                Some("scenery_packs"),
+            ),
+        }
+
         Ok(backup_path)
     }
 
+    /// List the available scenery_packs.ini backups, newest first
+    pub fn list_backups(&self) -> Result<Vec<SceneryPacksBackup>> {
+        let Some(dir) = self.ini_path.parent().filter(|d| d.exists()) else {
+            return Ok(Vec::new());
+        };
+
+        Ok(list_backup_files(dir)?
+            .into_iter()
+            .map(|(created, path)| SceneryPacksBackup {
+                file_name: path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                path: path.to_string_lossy().to_string(),
+                created_at: Local
+                    .from_local_datetime(&created)
+                    .earliest()
+                    .map(|t| t.to_rfc3339())
+                    .unwrap_or_else(|| created.to_string()),
+            })
+            .collect())
+    }
+
+    /// Replace scenery_packs.ini with the backup at `backup_path`, which must be a
+    /// backup file next to the ini. The current ini is kept as a new backup first
+    pub fn restore_backup(&self, backup_path: &Path) -> Result<()> {
+        let ini_dir = self
+            .ini_path
+            .parent()
+            .ok_or_else(|| anyhow!("Invalid ini path: no parent directory"))?;
+
+        let is_backup = backup_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(parse_backup_name)
+            .is_some();
+        let in_custom_scenery = match (backup_path.parent(), ini_dir.canonicalize()) {
+            (Some(parent), Ok(ini_dir)) => parent.canonicalize().is_ok_and(|p| p == ini_dir),
+            _ => false,
+        };
+        if !is_backup || !in_custom_scenery || !backup_path.is_file() {
+            return Err(anyhow!(
+                "Not a scenery_packs.ini backup in Custom Scenery: {:?}",
+                backup_path
+            ));
+        }
+
+        // Stage the backup first: rotating in the safety backup below may prune it
+        let temp_path = self.ini_path.with_extension("ini.tmp");
+        fs::copy(backup_path, &temp_path)?;
+
+        if self.ini_path.exists() {
+            if let Err(e) = self.backup_ini() {
+                let _ = fs::remove_file(&temp_path);
+                return Err(e);
+            }
+        }
+        fs::rename(&temp_path, &self.ini_path)?;
+
+        logger::log_info(
+            &format!("Restored scenery_packs.ini from {:?}", backup_path),
+            Some("scenery_packs"),
+        );
+
+        Ok(())
+    }
+
+    /// Check scenery_packs.ini for formatting problems X-Plane may trip over
+    /// (missing final newline, tab separators, mixed CRLF/LF line endings)
+    pub fn lint_ini(&self) -> Result<Vec<IniLintFinding>> {
+        if !self.ini_path.exists() {
+            return Err(anyhow!("scenery_packs.ini does not exist"));
+        }
+
+        let content = fs::read_to_string(&self.ini_path)?;
+        Ok(lint_ini_content(&content))
+    }
+
+    /// Rewrite scenery_packs.ini cleanly (standard header, one space separator,
+    /// forward slashes, LF line endings and a final newline), keeping entry order
+    /// and enabled states. A backup is created first.
+    /// Returns the number of entries written
+    pub fn normalize_ini(&self) -> Result<usize> {
+        if !self.ini_path.exists() {
+            return Err(anyhow!("scenery_packs.ini does not exist"));
+        }
+
+        let content = fs::read_to_string(&self.ini_path)?;
+        let entries = parse_ini(&content);
+
+        self.backup_ini()?;
+        self.write_ini(&entries)?;
+
+        logger::log_info(
+            &format!("Normalized scenery_packs.ini ({} entries)", entries.len()),
+            Some("scenery_packs"),
+        );
+
+        Ok(entries.len())
+    }
+
+    /// Find ini entries whose Custom Scenery folder name differs in case from the
+    /// folder on disk. X-Plane can't load these on case-sensitive filesystems, so
+    /// this only reports on Linux; elsewhere the list is always empty
+    pub fn detect_case_mismatches(&self) -> Result<Vec<IniCaseMismatch>> {
+        if !cfg!(target_os = "linux") || !self.ini_path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&self.ini_path)?;
+        Ok(find_case_mismatches(
+            &parse_ini(&content),
+            &self.list_scenery_folders()?,
+        ))
+    }
+
+    /// Rewrite ini entries reported by `detect_case_mismatches` to use the real
+    /// folder name. A backup is created first.
+    /// Returns the number of entries fixed
+    pub fn fix_case_mismatches(&self) -> Result<usize> {
+        let mismatches = self.detect_case_mismatches()?;
+        if mismatches.is_empty() {
+            return Ok(0);
+        }
+
+        let content = fs::read_to_string(&self.ini_path)?;
+        let mut entries = parse_ini(&content);
+        for entry in entries.iter_mut() {
+            if let Some(mismatch) = mismatches.iter().find(|m| m.ini_path == entry.path) {
+                entry.path = format!("Custom Scenery/{}/", mismatch.actual_folder);
+            }
+        }
+
+        self.backup_ini()?;
+        self.write_ini(&entries)?;
+
+        logger::log_info(
+            &format!(
+                "Fixed folder name casing of {} scenery_packs.ini entries",
+                mismatches.len()
+            ),
+            Some("scenery_packs"),
+        );
+
+        Ok(mismatches.len())
+    }
+
+    /// Names of the folders (and folder symlinks) in Custom Scenery
+    fn list_scenery_folders(&self) -> Result<Vec<String>> {
+        let custom_scenery_path = self.xplane_path.join("Custom Scenery");
+        Ok(fs::read_dir(&custom_scenery_path)?
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().is_dir())
+            .filter_map(|e| e.file_name().into_string().ok())
+            .collect())
+    }
+
     /// Add a new entry to scenery_packs.ini (used after installation)
     pub fn add_entry(&self, folder_name: &str, category: &SceneryCategory) -> Result<()> {
         let index_manager = SceneryIndexManager::new(&self.xplane_path);
@@ -133,6 +327,111 @@ impl SceneryPacksManager {
         self.auto_sort_from_index()
     }
 
+    /// Move a package to `sort_order` once and rewrite scenery_packs.ini
+    /// Used after installation to place a new package instead of leaving it at its
+    /// category position; later re-sorts treat it like any other package.
+    /// The package is indexed first if needed; does nothing if the index hasn't been
+    /// created yet
+    pub fn place_entry(&self, folder_name: &str, sort_order: u32) -> Result<()> {
+        let index_manager = SceneryIndexManager::new(&self.xplane_path);
+        if !index_manager.has_index()? {
+            return Ok(());
+        }
+
+        let folder_path = self.xplane_path.join("Custom Scenery").join(folder_name);
+        index_manager.get_or_classify(&folder_path)?;
+        index_manager.move_entry(folder_name, sort_order)?;
+        self.auto_sort_from_index()
+    }
+
+    /// Remove the entry with the given path (e.g. "Custom Scenery/MyScenery/") from
+    /// scenery_packs.ini. A backup is created before the file is rewritten.
+    /// Returns true if an entry was removed
+    pub fn remove_entry(&self, path: &str) -> Result<bool> {
+        if !self.ini_path.exists() {
+            return Ok(false);
+        }
+
+        let content = fs::read_to_string(&self.ini_path)?;
+        let (entries, removed) = remove_ini_entry(parse_ini(&content), path);
+        if !removed {
+            return Ok(false);
+        }
+
+        self.backup_ini()?;
+        self.write_ini(&entries)?;
+
+        logger::log_info(
+            &format!("Removed {} from scenery_packs.ini", path),
+            Some("scenery_packs"),
+        );
+
+        Ok(true)
+    }
+
+    /// Find ini entries whose scenery folder no longer exists, e.g. after a folder
+    /// was deleted by hand. Relative paths are resolved under the X-Plane root and
+    /// may also point at a Custom Scenery .lnk shortcut; *GLOBAL_AIRPORTS* is skipped
+    pub fn find_orphaned_entries(&self) -> Result<Vec<SceneryPackEntry>> {
+        if !self.ini_path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&self.ini_path)?;
+        Ok(parse_ini(&content)
+            .into_iter()
+            .filter(|e| !e.is_global_airports && !self.entry_target_exists(&e.path))
+            .collect())
+    }
+
+    /// Remove the entries reported by `find_orphaned_entries` from scenery_packs.ini.
+    /// A backup is created first.
+    /// Returns the number of entries removed
+    pub fn remove_orphaned_entries(&self) -> Result<usize> {
+        let orphaned = self.find_orphaned_entries()?;
+        if orphaned.is_empty() {
+            return Ok(0);
+        }
+
+        let content = fs::read_to_string(&self.ini_path)?;
+        let entries: Vec<SceneryPackEntry> = parse_ini(&content)
+            .into_iter()
+            .filter(|e| !orphaned.iter().any(|o| o.path == e.path))
+            .collect();
+
+        self.backup_ini()?;
+        self.write_ini(&entries)?;
+
+        logger::log_info(
+            &format!(
+                "Removed {} orphaned scenery_packs.ini entries",
+                orphaned.len()
+            ),
+            Some("scenery_packs"),
+        );
+
+        Ok(orphaned.len())
+    }
+
+    /// Whether the folder an ini entry points at exists, directly or via a .lnk shortcut
+    fn entry_target_exists(&self, path: &str) -> bool {
+        let trimmed = path.trim_end_matches(['/', '\\']);
+        let target = if Path::new(trimmed).is_absolute() {
+            PathBuf::from(trimmed)
+        } else {
+            self.xplane_path.join(trimmed)
+        };
+        if target.exists() {
+            return true;
+        }
+
+        let Some(name) = target.file_name().and_then(|n| n.to_str()) else {
+            return false;
+        };
+        let lnk_path = target.with_file_name(format!("{}.lnk", name));
+        lnk_path.exists() && resolve_shortcut(&lnk_path).is_some_and(|t| t.exists())
+    }
+
     /// Ensure all installed scenery is in scenery_packs.ini
     /// Only performs incremental indexing if the index has been created
     pub fn sync_with_folder(&self) -> Result<usize> {
@@ -170,13 +469,90 @@ impl SceneryPacksManager {
         Ok(added_count)
     }
 
+    /// Build the scenery_packs.ini entries from the index (sorted by sort_order),
+    /// inserting *GLOBAL_AIRPORTS* at the position chosen in the ordering config
+    fn build_entries_from_index(&self) -> Result<Vec<SceneryPackEntry>> {
+        let index_manager = SceneryIndexManager::new(&self.xplane_path);
+        let index = index_manager.load_index()?;
+        let config = index_manager.get_ordering_config().unwrap_or_default();
+
+        Ok(entries_from_packages(
+            index.packages.values().collect(),
+            &config,
+        ))
+    }
+
+    /// Bring scenery_packs.ini in line with the index without rescanning folders:
+    /// index packages missing from the ini are added, entries without an index package
+    /// are removed, and everything is written in index sort_order. Enabled states
+    /// already in the ini are kept. A backup is created first
+    pub fn reconcile_with_index(&self) -> Result<IniReconcileResult> {
+        let index_manager = SceneryIndexManager::new(&self.xplane_path);
+        if !index_manager.has_index()? {
+            return Err(anyhow!("Scenery index has not been built yet"));
+        }
+
+        let index = index_manager.load_index()?;
+        let config = index_manager.get_ordering_config().unwrap_or_default();
+        let current = if self.ini_path.exists() {
+            parse_ini(&fs::read_to_string(&self.ini_path)?)
+        } else {
+            Vec::new()
+        };
+
+        let (entries, result) =
+            reconcile_entries(&current, index.packages.values().collect(), &config);
+        self.replace_ini(&entries)?;
+
+        logger::log_info(
+            &format!(
+                "Reconciled scenery_packs.ini with index: {} added, {} removed",
+                result.added, result.removed
+            ),
+            Some("scenery_packs"),
+        );
+
+        Ok(result)
+    }
+
+    /// Preview the scenery_packs.ini entries a sort (reset_sort_order followed by
+    /// auto_sort_from_index) would write. The sort runs on an unsaved copy of the
+    /// index, so neither the index nor the ini is touched
+    pub fn preview_auto_sort(&self) -> Result<Vec<SceneryPackEntry>> {
+        let index_manager = SceneryIndexManager::new(&self.xplane_path);
+        let index = index_manager.preview_sorted_index()?;
+        let config = index_manager.get_ordering_config().unwrap_or_default();
+
+        Ok(entries_from_packages(
+            index.packages.values().collect(),
+            &config,
+        ))
+    }
+
+    /// Compare the current scenery_packs.ini with the entries preview_auto_sort
+    /// would write. Neither the ini nor a backup is written
+    pub fn preview_sort(&self) -> Result<Vec<SortPreviewChange>> {
+        let index_manager = SceneryIndexManager::new(&self.xplane_path);
+        if !index_manager.has_index()? {
+            return Ok(Vec::new());
+        }
+
+        let current = if self.ini_path.exists() {
+            parse_ini(&fs::read_to_string(&self.ini_path)?)
+        } else {
+            Vec::new()
+        };
+        let sorted = self.preview_auto_sort()?;
+
+        Ok(diff_sort_entries(&current, &sorted))
+    }
+
     /// Sort scenery_packs.ini based entirely on index sort_order
     /// This is used by the scenery manager after manual reordering
     pub fn auto_sort_from_index(&self) -> Result<()> {
         let index_manager = SceneryIndexManager::new(&self.xplane_path);
-        let index = index_manager.load_index()?;
 
-        if index.packages.is_empty() {
+        if !index_manager.has_index()? {
             logger::log_info(
                 "No scenery packages in index, nothing to sort",
                 Some("scenery_packs"),
@@ -184,6 +560,8 @@ impl SceneryPacksManager {
             return Ok(());
         }
 
+        let entries = self.build_entries_from_index()?;
+
         // Create backup if ini exists
         if self.ini_path.exists() {
             if let Err(e) = self.backup_ini() {
@@ -194,51 +572,6 @@ impl SceneryPacksManager {
             }
         }
 
-        // Build entries from index, sorted by sort_order
-        let mut packages: Vec<_> = index.packages.values().collect();
-        packages.sort_by_key(|p| p.sort_order);
-
-        let mut entries: Vec<SceneryPackEntry> = Vec::new();
-        let mut global_airports_inserted = false;
-
-        for info in packages {
-            // Insert *GLOBAL_AIRPORTS* before the first DefaultAirport entry
-            // (DefaultAirport has priority 2)
-            if !global_airports_inserted
-                && info.category.priority() >= SceneryCategory::DefaultAirport.priority()
-            {
-                entries.push(SceneryPackEntry {
-                    enabled: true,
-                    path: "*GLOBAL_AIRPORTS*".to_string(),
-                    is_global_airports: true,
-                });
-                global_airports_inserted = true;
-            }
-
-            // Use actual_path if set (for shortcuts pointing outside Custom Scenery),
-            // otherwise use the standard Custom Scenery/{folder_name}/ format
-            let path = if let Some(actual_path) = &info.actual_path {
-                actual_path.clone()
-            } else {
-                format!("Custom Scenery/{}/", info.folder_name)
-            };
-
-            entries.push(SceneryPackEntry {
-                enabled: info.enabled,
-                path,
-                is_global_airports: false,
-            });
-        }
-
-        // If *GLOBAL_AIRPORTS* wasn't inserted yet, add it at the end
-        if !global_airports_inserted {
-            entries.push(SceneryPackEntry {
-                enabled: true,
-                path: "*GLOBAL_AIRPORTS*".to_string(),
-                is_global_airports: true,
-            });
-        }
-
         // Write sorted entries
         self.write_ini(&entries)?;
 
@@ -250,6 +583,15 @@ impl SceneryPacksManager {
         Ok(())
     }
 
+    /// Re-sort the index by category priority, leaving packages in `excluded`
+    /// categories at their current positions, then write scenery_packs.ini
+    /// Used for users who order meshes/orthos manually
+    pub fn auto_sort_except(&self, excluded: &[SceneryCategory]) -> Result<()> {
+        let index_manager = SceneryIndexManager::new(&self.xplane_path);
+        index_manager.reset_sort_order_except(excluded)?;
+        self.auto_sort_from_index()
+    }
+
     /// Apply index state (enabled/sort_order) to scenery_packs.ini
     /// This preserves the order from the index and applies enabled states
     pub fn apply_from_index(&self) -> Result<()> {
@@ -258,76 +600,536 @@ impl SceneryPacksManager {
         self.auto_sort_from_index()
     }
 
+    /// Apply scenery manager edits to the index and write scenery_packs.ini from the
+    /// resulting index in one step, creating a single backup.
+    /// If the ini can't be written, the index edits are reverted and the backup restored
+    /// so the index and ini stay consistent
+    pub fn commit_manager_changes(&self, updates: &[SceneryEntryUpdate]) -> Result<()> {
+        let index_manager = SceneryIndexManager::new(&self.xplane_path);
+        let before = index_manager.load_index()?;
+        let previous: Vec<SceneryEntryUpdate> = updates
+            .iter()
+            .filter_map(|u| before.packages.get(&u.folder_name))
+            .map(|info| SceneryEntryUpdate {
+                folder_name: info.folder_name.clone(),
+                enabled: info.enabled,
+                sort_order: info.sort_order,
+            })
+            .collect();
+
+        // The audit entry is only written once the ini was replaced, and the revert
+        // is not recorded, so a failed commit leaves nothing to undo
+        let changes = index_manager.batch_update_entries_unrecorded(updates)?;
+
+        let result = self
+            .build_entries_from_index()
+            .and_then(|entries| self.replace_ini(&entries));
+        if let Err(e) = result {
+            if let Err(revert_err) = index_manager.batch_update_entries_unrecorded(&previous) {
+                logger::log_error(
+                    &format!("Failed to revert scenery index changes: {}", revert_err),
+                    Some("scenery_packs"),
+                );
+            }
+            return Err(e);
+        }
+        crate::scenery_audit::record(
+            &self.xplane_path,
+            crate::scenery_audit::OP_BATCH_UPDATE_ENTRIES,
+            changes,
+        );
+
+        logger::log_info(
+            &format!("Committed {} scenery manager changes", updates.len()),
+            Some("scenery_packs"),
+        );
+
+        Ok(())
+    }
+
+    /// Back up scenery_packs.ini (if present) and write `entries` in its place,
+    /// restoring the backup if the write fails
+    fn replace_ini(&self, entries: &[SceneryPackEntry]) -> Result<()> {
+        let backup_path = if self.ini_path.exists() {
+            Some(self.backup_ini()?)
+        } else {
+            None
+        };
+
+        if let Err(e) = self.write_ini(entries) {
+            if let Some(backup_path) = backup_path {
+                if let Err(restore_err) = fs::rename(&backup_path, &self.ini_path) {
+                    logger::log_error(
+                        &format!("Failed to restore {:?}: {}", backup_path, restore_err),
+                        Some("scenery_packs"),
+                    );
+                }
+            }
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
     /// Check if ini file is in sync with the index
     /// Returns true if ini order/enabled states match index for entries that exist in the index
     /// Note: Extra entries in the ini (manually added) are ignored
     pub fn is_synced_with_index(&self) -> Result<bool> {
-        // If ini doesn't exist, it's not synced
-        if !self.ini_path.exists() {
-            return Ok(false);
-        }
+        Ok(self.check_sync_with_index()?.is_synced())
+    }
 
+    /// Describe how scenery_packs.ini differs from the index: packages missing from
+    /// or duplicated in the ini, enabled state mismatches and packages out of order
+    /// Extra entries in the ini (manually added) are ignored
+    pub fn check_sync_with_index(&self) -> Result<IniSyncDivergence> {
         let index_manager = SceneryIndexManager::new(&self.xplane_path);
         let index = index_manager.load_index()?;
 
         if index.packages.is_empty() {
-            return Ok(true);
+            return Ok(IniSyncDivergence::default());
         }
 
-        // Read current ini file
-        let content = fs::read_to_string(&self.ini_path)?;
+        // If ini doesn't exist, it's not synced
+        if !self.ini_path.exists() {
+            return Ok(IniSyncDivergence {
+                ini_missing: true,
+                ..Default::default()
+            });
+        }
 
-        // Parse ini entries (order matters)
-        let mut ini_entries: Vec<(String, bool)> = Vec::new(); // (folder_name, enabled)
-        for line in content.lines() {
-            let line = line.trim();
-            if line.starts_with("SCENERY_PACK_DISABLED ") {
-                let path = line.trim_start_matches("SCENERY_PACK_DISABLED ");
-                if let Some(folder) = extract_folder_name(path) {
-                    ini_entries.push((folder, false));
-                }
-            } else if line.starts_with("SCENERY_PACK ") {
-                let path = line.trim_start_matches("SCENERY_PACK ");
-                // Skip *GLOBAL_AIRPORTS*
-                if !path.contains("*GLOBAL_AIRPORTS*") {
-                    if let Some(folder) = extract_folder_name(path) {
-                        ini_entries.push((folder, true));
-                    }
-                }
+        let ini_entries = parse_ini(&fs::read_to_string(&self.ini_path)?);
+        let config = index_manager.get_ordering_config().unwrap_or_default();
+        Ok(find_sync_divergence(
+            &ini_entries,
+            index.packages.values().collect(),
+            &config,
+        ))
+    }
+}
+
+/// Compare ini entries (in file order) with index packages in the order
+/// apply_from_index would write them (sort_order, disabled entries last if configured)
+/// Entries are matched to packages by folder name; entries without a package are ignored
+fn find_sync_divergence(
+    ini_entries: &[SceneryPackEntry],
+    packages: Vec<&SceneryPackageInfo>,
+    config: &SceneryOrderingConfig,
+) -> IniSyncDivergence {
+    let by_path: HashMap<String, &SceneryPackageInfo> =
+        packages.iter().map(|p| (package_ini_path(p), *p)).collect();
+    let packages: Vec<&SceneryPackageInfo> = entries_from_packages(packages, config)
+        .iter()
+        .filter(|e| !e.is_global_airports)
+        .filter_map(|e| by_path.get(&e.path).copied())
+        .collect();
+    let index_positions: HashMap<&str, usize> = packages
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (p.folder_name.as_str(), i))
+        .collect();
+
+    let mut divergence = IniSyncDivergence::default();
+    // Index positions of the packages in ini order (first occurrence only)
+    let mut ini_order: Vec<usize> = Vec::new();
+    let mut seen: HashSet<usize> = HashSet::new();
+    for entry in ini_entries.iter().filter(|e| !e.is_global_airports) {
+        let Some(folder) = extract_folder_name(&entry.path) else {
+            continue;
+        };
+        let Some(&position) = index_positions.get(folder.as_str()) else {
+            continue;
+        };
+        if !seen.insert(position) {
+            if !divergence.duplicated_in_ini.contains(&folder) {
+                divergence.duplicated_in_ini.push(folder);
             }
+            continue;
         }
+        if entry.enabled != packages[position].enabled {
+            divergence.enabled_mismatches.push(folder);
+        }
+        ini_order.push(position);
+    }
 
-        // Build expected order from index
-        let mut packages: Vec<_> = index.packages.values().collect();
-        packages.sort_by_key(|p| p.sort_order);
+    divergence.missing_from_ini = packages
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !seen.contains(i))
+        .map(|(_, p)| p.folder_name.clone())
+        .collect();
 
-        // Filter ini entries to only include entries that exist in the index
-        // This allows manually added entries in the ini to be ignored
-        let ini_entries_filtered: Vec<_> = ini_entries
-            .iter()
-            .filter(|(folder, _)| index.packages.contains_key(folder))
-            .collect();
+    // Packages outside the longest run already in index order are the ones to move
+    let in_order: HashSet<usize> = longest_increasing_subsequence(&ini_order)
+        .into_iter()
+        .collect();
+    divergence.out_of_order = ini_order
+        .iter()
+        .filter(|position| !in_order.contains(position))
+        .map(|&position| packages[position].folder_name.clone())
+        .collect();
 
-        // Compare count of index entries
-        if ini_entries_filtered.len() != packages.len() {
-            return Ok(false);
+    divergence
+}
+
+/// Longest strictly increasing subsequence of `values` (patience sorting)
+fn longest_increasing_subsequence(values: &[usize]) -> Vec<usize> {
+    // tails[k]: index into `values` of the smallest tail of an increasing run of length k + 1
+    let mut tails: Vec<usize> = Vec::new();
+    let mut previous: Vec<Option<usize>> = vec![None; values.len()];
+
+    for (i, &value) in values.iter().enumerate() {
+        let k = tails.partition_point(|&t| values[t] < value);
+        previous[i] = k.checked_sub(1).map(|p| tails[p]);
+        if k == tails.len() {
+            tails.push(i);
+        } else {
+            tails[k] = i;
         }
+    }
 
-        // Compare order and enabled state for entries that exist in the index
-        for (i, pkg) in packages.iter().enumerate() {
-            let (ini_folder, ini_enabled) = ini_entries_filtered[i];
-            if ini_folder != &pkg.folder_name || *ini_enabled != pkg.enabled {
-                return Ok(false);
+    let mut result = Vec::with_capacity(tails.len());
+    let mut current = tails.last().copied();
+    while let Some(i) = current {
+        result.push(values[i]);
+        current = previous[i];
+    }
+    result.reverse();
+    result
+}
+
+/// Delete the oldest scenery_packs.ini backups in `dir` so at most `keep` remain.
+/// Only files named BACKUP_PREFIX + a valid timestamp are considered, and their age
+/// comes from that timestamp. Returns the number of backups deleted
+fn prune_backups(dir: &Path, keep: usize) -> Result<usize> {
+    let backups = list_backup_files(dir)?;
+    if backups.len() <= keep {
+        return Ok(0);
+    }
+
+    // Newest first; everything past `keep` gets deleted
+    let mut removed = 0;
+    for (_, path) in backups.into_iter().skip(keep) {
+        fs::remove_file(&path)?;
+        removed += 1;
+    }
+
+    Ok(removed)
+}
+
+/// Parse the creation time out of a backup file name, None if it isn't a backup
+fn parse_backup_name(file_name: &str) -> Option<NaiveDateTime> {
+    let timestamp = file_name.strip_prefix(BACKUP_PREFIX)?;
+    NaiveDateTime::parse_from_str(timestamp, BACKUP_TIMESTAMP_FORMAT).ok()
+}
+
+/// List the scenery_packs.ini backups in `dir`, newest first
+fn list_backup_files(dir: &Path) -> Result<Vec<(NaiveDateTime, PathBuf)>> {
+    let mut backups: Vec<(NaiveDateTime, PathBuf)> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_file()))
+        .filter_map(|entry| {
+            let created = parse_backup_name(entry.file_name().to_str()?)?;
+            Some((created, entry.path()))
+        })
+        .collect();
+    backups.sort_by(|a, b| b.0.cmp(&a.0));
+    Ok(backups)
+}
+
+/// Strip a leading UTF-8 BOM and convert CRLF/CR line endings to LF,
+/// as left behind by editors such as Notepad
+fn normalize_ini_content(content: &str) -> String {
+    content
+        .trim_start_matches('\u{feff}')
+        .replace("\r\n", "\n")
+        .replace('\r', "\n")
+}
+
+/// Parse scenery_packs.ini content into entries, in file order
+/// Accepts any whitespace (including tabs) between the keyword and the path,
+/// a leading BOM and CRLF line endings
+pub fn parse_ini(content: &str) -> Vec<SceneryPackEntry> {
+    let mut entries = Vec::new();
+
+    for line in normalize_ini_content(content).lines() {
+        let line = line.trim();
+        let (enabled, rest) = if let Some(rest) = line.strip_prefix("SCENERY_PACK_DISABLED") {
+            (false, rest)
+        } else if let Some(rest) = line.strip_prefix("SCENERY_PACK") {
+            (true, rest)
+        } else {
+            continue;
+        };
+
+        // Require a separator so keywords followed by garbage aren't accepted
+        if !rest.starts_with(|c: char| c.is_whitespace()) {
+            continue;
+        }
+
+        let path = rest.trim();
+        if path.is_empty() {
+            continue;
+        }
+
+        entries.push(SceneryPackEntry {
+            enabled,
+            path: path.to_string(),
+            is_global_airports: path == "*GLOBAL_AIRPORTS*",
+        });
+    }
+
+    entries
+}
+
+/// Drop every entry whose normalized path matches `path`
+/// Returns the remaining entries and whether anything was removed
+fn remove_ini_entry(entries: Vec<SceneryPackEntry>, path: &str) -> (Vec<SceneryPackEntry>, bool) {
+    let target = normalize_scenery_path(path);
+    let before = entries.len();
+    let remaining: Vec<SceneryPackEntry> = entries
+        .into_iter()
+        .filter(|e| {
+            e.is_global_airports || !normalize_scenery_path(&e.path).eq_ignore_ascii_case(&target)
+        })
+        .collect();
+    let removed = remaining.len() != before;
+    (remaining, removed)
+}
+
+/// Describe how each entry moves between `current` and `sorted`, in sorted order,
+/// followed by entries that only exist in `current` (the sort would drop them).
+/// Entries are matched by normalized path, ignoring case
+fn diff_sort_entries(
+    current: &[SceneryPackEntry],
+    sorted: &[SceneryPackEntry],
+) -> Vec<SortPreviewChange> {
+    let key = |e: &SceneryPackEntry| normalize_scenery_path(&e.path).to_lowercase();
+
+    let mut old_positions: HashMap<String, (usize, bool)> = HashMap::new();
+    for (position, entry) in current.iter().enumerate() {
+        old_positions
+            .entry(key(entry))
+            .or_insert((position, entry.enabled));
+    }
+
+    let mut changes = Vec::new();
+    for (position, entry) in sorted.iter().enumerate() {
+        let old = old_positions.remove(&key(entry));
+        changes.push(SortPreviewChange {
+            path: entry.path.clone(),
+            is_global_airports: entry.is_global_airports,
+            old_position: old.map(|(p, _)| p),
+            new_position: Some(position),
+            old_enabled: old.map(|(_, enabled)| enabled),
+            new_enabled: Some(entry.enabled),
+        });
+    }
+
+    for (position, entry) in current.iter().enumerate() {
+        if old_positions
+            .get(&key(entry))
+            .is_some_and(|(p, _)| *p == position)
+        {
+            changes.push(SortPreviewChange {
+                path: entry.path.clone(),
+                is_global_airports: entry.is_global_airports,
+                old_position: Some(position),
+                new_position: None,
+                old_enabled: Some(entry.enabled),
+                new_enabled: None,
+            });
+        }
+    }
+
+    changes
+}
+
+/// Lint raw scenery_packs.ini content
+fn lint_ini_content(content: &str) -> Vec<IniLintFinding> {
+    let mut findings = Vec::new();
+
+    if !content.is_empty() && !content.ends_with('\n') {
+        findings.push(IniLintFinding {
+            kind: IniLintKind::MissingTrailingNewline,
+            line: Some(content.lines().count()),
+            message: "File does not end with a newline".to_string(),
+        });
+    }
+
+    let crlf_count = content.matches("\r\n").count();
+    let lf_count = content.matches('\n').count();
+    if crlf_count > 0 && crlf_count < lf_count {
+        findings.push(IniLintFinding {
+            kind: IniLintKind::MixedLineEndings,
+            line: None,
+            message: format!(
+                "Mixed line endings: {} CRLF and {} LF",
+                crlf_count,
+                lf_count - crlf_count
+            ),
+        });
+    }
+
+    for (i, line) in content.lines().enumerate() {
+        if line.contains('\t') {
+            findings.push(IniLintFinding {
+                kind: IniLintKind::TabCharacter,
+                line: Some(i + 1),
+                message: "Line contains tab characters".to_string(),
+            });
+        }
+    }
+
+    findings
+}
+
+/// Match Custom Scenery entries against the folders on disk, reporting entries that
+/// only match a folder when case is ignored
+fn find_case_mismatches(entries: &[SceneryPackEntry], folders: &[String]) -> Vec<IniCaseMismatch> {
+    entries
+        .iter()
+        .filter(|entry| !entry.is_global_airports)
+        .filter_map(|entry| {
+            let relative = entry.path.replace('\\', "/");
+            let folder_name = relative
+                .strip_prefix("Custom Scenery/")?
+                .trim_end_matches('/');
+            if folder_name.is_empty() || folder_name.contains('/') {
+                return None;
             }
+            if folders.iter().any(|f| f == folder_name) {
+                return None;
+            }
+
+            let actual = folders
+                .iter()
+                .find(|f| f.eq_ignore_ascii_case(folder_name))?;
+            Some(IniCaseMismatch {
+                ini_path: entry.path.clone(),
+                actual_folder: actual.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Rebuild scenery_packs.ini entries from index packages, keeping the enabled state of
+/// packages (and *GLOBAL_AIRPORTS*) already listed in `current`
+/// Entries are matched by normalized path, ignoring case; each ini entry matches at
+/// most one package, so duplicate lines count as removed
+fn reconcile_entries(
+    current: &[SceneryPackEntry],
+    packages: Vec<&SceneryPackageInfo>,
+    config: &SceneryOrderingConfig,
+) -> (Vec<SceneryPackEntry>, IniReconcileResult) {
+    let mut matched = vec![false; current.len()];
+    let mut find_unmatched = |path: &str, is_global_airports: bool| {
+        let target = normalize_scenery_path(path);
+        let position = current.iter().enumerate().position(|(i, e)| {
+            !matched[i]
+                && e.is_global_airports == is_global_airports
+                && (is_global_airports
+                    || normalize_scenery_path(&e.path).eq_ignore_ascii_case(&target))
+        })?;
+        matched[position] = true;
+        Some(current[position].enabled)
+    };
+
+    let mut added = 0;
+    let packages: Vec<SceneryPackageInfo> = packages
+        .into_iter()
+        .map(|info| {
+            let mut info = info.clone();
+            match find_unmatched(&package_ini_path(&info), false) {
+                Some(enabled) => info.enabled = enabled,
+                None => added += 1,
+            }
+            info
+        })
+        .collect();
+    let global_airports_enabled = find_unmatched("*GLOBAL_AIRPORTS*", true);
+
+    let mut entries = entries_from_packages(packages.iter().collect(), config);
+    if let Some(enabled) = global_airports_enabled {
+        for entry in entries.iter_mut().filter(|e| e.is_global_airports) {
+            entry.enabled = enabled;
         }
+    }
 
-        Ok(true)
+    let removed = current
+        .iter()
+        .zip(&matched)
+        .filter(|(e, m)| !**m && !e.is_global_airports)
+        .count();
+
+    (entries, IniReconcileResult { added, removed })
+}
+
+/// Path of an index package in scenery_packs.ini
+/// Uses actual_path if set (for shortcuts pointing outside Custom Scenery),
+/// otherwise the standard Custom Scenery/{folder_name}/ format
+fn package_ini_path(info: &SceneryPackageInfo) -> String {
+    if let Some(actual_path) = &info.actual_path {
+        actual_path.clone()
+    } else {
+        format!("Custom Scenery/{}/", info.folder_name)
+    }
+}
+
+/// Build scenery_packs.ini entries from index packages (sorted by sort_order),
+/// inserting *GLOBAL_AIRPORTS* at the position chosen in the ordering config
+fn entries_from_packages(
+    mut packages: Vec<&SceneryPackageInfo>,
+    config: &SceneryOrderingConfig,
+) -> Vec<SceneryPackEntry> {
+    let global_airports_priority = config.global_airports_before.priority();
+
+    packages.sort_by_key(|p| p.sort_order);
+    if config.disabled_at_bottom {
+        // Stable sort: both groups keep their sort order
+        packages.sort_by_key(|p| !p.enabled);
+    }
+
+    let mut entries: Vec<SceneryPackEntry> = Vec::new();
+    let mut global_airports_inserted = false;
+
+    for info in packages {
+        // Insert *GLOBAL_AIRPORTS* before the first entry of the configured category band
+        // (DefaultAirport by default, which has priority 2), and at the latest before
+        // the disabled entries when they are grouped at the bottom
+        let reached_disabled = config.disabled_at_bottom && !info.enabled;
+        if !global_airports_inserted
+            && (info.category.priority() >= global_airports_priority || reached_disabled)
+        {
+            entries.push(SceneryPackEntry {
+                enabled: true,
+                path: "*GLOBAL_AIRPORTS*".to_string(),
+                is_global_airports: true,
+            });
+            global_airports_inserted = true;
+        }
+
+        entries.push(SceneryPackEntry {
+            enabled: info.enabled,
+            path: package_ini_path(info),
+            is_global_airports: false,
+        });
     }
+
+    // If *GLOBAL_AIRPORTS* wasn't inserted yet, add it at the end
+    if !global_airports_inserted {
+        entries.push(SceneryPackEntry {
+            enabled: true,
+            path: "*GLOBAL_AIRPORTS*".to_string(),
+            is_global_airports: true,
+        });
+    }
+
+    entries
 }
 
 /// Extract folder name from ini path
 /// e.g., "Custom Scenery/MyScenery/" -> "MyScenery"
-fn extract_folder_name(path: &str) -> Option<String> {
+pub fn extract_folder_name(path: &str) -> Option<String> {
     let path = path.trim().trim_end_matches('/').trim_end_matches('\\');
     if let Some(idx) = path.rfind('/').or_else(|| path.rfind('\\')) {
         Some(path[idx + 1..].to_string())
@@ -339,6 +1141,7 @@ fn extract_folder_name(path: &str) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::test_package;
 
     #[test]
     fn test_category_priority_order() {
@@ -352,4 +1155,417 @@ mod tests {
         assert!(SceneryCategory::Other.priority() < SceneryCategory::Overlay.priority());
         assert!(SceneryCategory::Overlay.priority() < SceneryCategory::Mesh.priority());
     }
+
+    #[test]
+    fn test_entries_from_packages_disabled_at_bottom() {
+        let packages = vec![
+            test_package("AirportA", SceneryCategory::Airport, 0, true),
+            test_package("AirportB", SceneryCategory::Airport, 1, false),
+            test_package("Library", SceneryCategory::Library, 2, true),
+            test_package("Mesh", SceneryCategory::Mesh, 3, false),
+        ];
+        let paths = |config: &SceneryOrderingConfig| -> Vec<String> {
+            entries_from_packages(packages.iter().collect(), config)
+                .into_iter()
+                .map(|e| e.path)
+                .collect()
+        };
+
+        let mut config = SceneryOrderingConfig::default();
+        assert_eq!(
+            paths(&config),
+            vec![
+                "Custom Scenery/AirportA/",
+                "Custom Scenery/AirportB/",
+                "*GLOBAL_AIRPORTS*",
+                "Custom Scenery/Library/",
+                "Custom Scenery/Mesh/",
+            ]
+        );
+
+        config.disabled_at_bottom = true;
+        assert_eq!(
+            paths(&config),
+            vec![
+                "Custom Scenery/AirportA/",
+                "*GLOBAL_AIRPORTS*",
+                "Custom Scenery/Library/",
+                "Custom Scenery/AirportB/",
+                "Custom Scenery/Mesh/",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_entries_from_packages_global_airports_position() {
+        let packages = vec![
+            test_package("SAM", SceneryCategory::FixedHighPriority, 0, true),
+            test_package("KSEA", SceneryCategory::Airport, 1, true),
+            test_package("Default_KBFI", SceneryCategory::DefaultAirport, 2, true),
+            test_package("Library", SceneryCategory::Library, 3, true),
+        ];
+        let paths = |config: &SceneryOrderingConfig| -> Vec<String> {
+            entries_from_packages(packages.iter().collect(), config)
+                .into_iter()
+                .map(|e| e.path)
+                .collect()
+        };
+
+        // Default: after custom airports, before the default airport band
+        let mut config = SceneryOrderingConfig::default();
+        assert_eq!(
+            paths(&config),
+            vec![
+                "Custom Scenery/SAM/",
+                "Custom Scenery/KSEA/",
+                "*GLOBAL_AIRPORTS*",
+                "Custom Scenery/Default_KBFI/",
+                "Custom Scenery/Library/",
+            ]
+        );
+
+        // Before custom airports
+        config.global_airports_before = SceneryCategory::Airport;
+        assert_eq!(
+            paths(&config),
+            vec![
+                "Custom Scenery/SAM/",
+                "*GLOBAL_AIRPORTS*",
+                "Custom Scenery/KSEA/",
+                "Custom Scenery/Default_KBFI/",
+                "Custom Scenery/Library/",
+            ]
+        );
+
+        // After every band that is present
+        config.global_airports_before = SceneryCategory::Mesh;
+        assert_eq!(paths(&config).last().unwrap(), "*GLOBAL_AIRPORTS*");
+    }
+
+    #[test]
+    fn test_reconcile_entries() {
+        let packages = vec![
+            test_package("AirportA", SceneryCategory::Airport, 0, true),
+            test_package("Library", SceneryCategory::Library, 1, true),
+        ];
+        let current = parse_ini(
+            "SCENERY_PACK Custom Scenery/Removed/\n\
+             SCENERY_PACK_DISABLED *GLOBAL_AIRPORTS*\n\
+             SCENERY_PACK_DISABLED Custom Scenery\\airporta\n\
+             SCENERY_PACK Custom Scenery/AirportA/\n",
+        );
+
+        let (entries, result) = reconcile_entries(
+            &current,
+            packages.iter().collect(),
+            &SceneryOrderingConfig::default(),
+        );
+        let summary: Vec<(&str, bool)> = entries
+            .iter()
+            .map(|e| (e.path.as_str(), e.enabled))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Custom Scenery/AirportA/", false),
+                ("*GLOBAL_AIRPORTS*", false),
+                ("Custom Scenery/Library/", true),
+            ]
+        );
+        // Library was added; the unknown folder and the duplicate AirportA line were removed
+        assert_eq!(result.added, 1);
+        assert_eq!(result.removed, 2);
+    }
+
+    #[test]
+    fn test_find_sync_divergence() {
+        let package = |name: &str, enabled: bool, sort_order: u32| {
+            test_package(name, SceneryCategory::Airport, sort_order, enabled)
+        };
+        let packages = vec![
+            package("A", true, 0),
+            package("B", true, 1),
+            package("C", false, 2),
+            package("D", true, 3),
+            package("E", true, 4),
+        ];
+
+        let synced = parse_ini(
+            "SCENERY_PACK Custom Scenery/A/\n\
+             SCENERY_PACK Custom Scenery/Manual/\n\
+             SCENERY_PACK Custom Scenery/B/\n\
+             SCENERY_PACK *GLOBAL_AIRPORTS*\n\
+             SCENERY_PACK_DISABLED Custom Scenery/C/\n\
+             SCENERY_PACK Custom Scenery/D/\n\
+             SCENERY_PACK Custom Scenery/E/\n",
+        );
+        let config = SceneryOrderingConfig::default();
+        assert!(find_sync_divergence(&synced, packages.iter().collect(), &config).is_synced());
+
+        // D moved to the top, C enabled, B listed twice and E missing
+        let drifted = parse_ini(
+            "SCENERY_PACK Custom Scenery/D/\n\
+             SCENERY_PACK Custom Scenery/A/\n\
+             SCENERY_PACK Custom Scenery/B/\n\
+             SCENERY_PACK Custom Scenery/C/\n\
+             SCENERY_PACK Custom Scenery/B/\n",
+        );
+        let divergence = find_sync_divergence(&drifted, packages.iter().collect(), &config);
+        assert_eq!(divergence.out_of_order, vec!["D"]);
+        assert_eq!(divergence.enabled_mismatches, vec!["C"]);
+        assert_eq!(divergence.duplicated_in_ini, vec!["B"]);
+        assert_eq!(divergence.missing_from_ini, vec!["E"]);
+
+        // With disabled packages grouped at the bottom, C belongs after E
+        let config = SceneryOrderingConfig {
+            disabled_at_bottom: true,
+            ..Default::default()
+        };
+        let divergence = find_sync_divergence(&synced, packages.iter().collect(), &config);
+        assert_eq!(divergence.out_of_order, vec!["C"]);
+        let bottom = parse_ini(
+            "SCENERY_PACK Custom Scenery/A/\n\
+             SCENERY_PACK Custom Scenery/B/\n\
+             SCENERY_PACK *GLOBAL_AIRPORTS*\n\
+             SCENERY_PACK Custom Scenery/D/\n\
+             SCENERY_PACK Custom Scenery/E/\n\
+             SCENERY_PACK_DISABLED Custom Scenery/C/\n",
+        );
+        assert!(find_sync_divergence(&bottom, packages.iter().collect(), &config).is_synced());
+    }
+
+    #[test]
+    fn test_lint_ini_content() {
+        let clean = "I\n1000 Version\nSCENERY\n\nSCENERY_PACK Custom Scenery/A/\n";
+        assert!(lint_ini_content(clean).is_empty());
+
+        let messy = "I\r\n1000 Version\nSCENERY\n\nSCENERY_PACK\tCustom Scenery/A/";
+        let kinds: Vec<IniLintKind> = lint_ini_content(messy).into_iter().map(|f| f.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                IniLintKind::MissingTrailingNewline,
+                IniLintKind::MixedLineEndings,
+                IniLintKind::TabCharacter,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_ini() {
+        let content = "I\r\n1000 Version\r\nSCENERY\r\n\r\nSCENERY_PACK\tCustom Scenery/My Airport/\r\nSCENERY_PACK_DISABLED Custom Scenery/Mesh/\nSCENERY_PACK *GLOBAL_AIRPORTS*";
+        let entries = parse_ini(content);
+
+        assert_eq!(entries.len(), 3);
+        assert!(entries[0].enabled);
+        assert_eq!(entries[0].path, "Custom Scenery/My Airport/");
+        assert!(!entries[1].enabled);
+        assert!(entries[2].is_global_airports);
+    }
+
+    #[test]
+    fn test_parse_ini_with_bom_and_crlf() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let custom_scenery = temp_dir.path().join("Custom Scenery");
+        fs::create_dir_all(&custom_scenery).unwrap();
+        let ini_path = custom_scenery.join("scenery_packs.ini");
+
+        // Header-less file: the BOM sits right before the first entry
+        let content =
+            "\u{feff}SCENERY_PACK Custom Scenery/A/\r\nSCENERY_PACK_DISABLED Custom Scenery/B/\r\n";
+        let entries = parse_ini(content);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].path, "Custom Scenery/A/");
+        assert!(!entries[1].enabled);
+
+        let content =
+            "\u{feff}I\r\n1000 Version\r\nSCENERY\r\n\r\nSCENERY_PACK Custom Scenery/A/\r\n";
+        assert_eq!(
+            normalize_ini_content(content),
+            format!("{}SCENERY_PACK Custom Scenery/A/\n", INI_HEADER)
+        );
+        fs::write(&ini_path, content).unwrap();
+
+        // Rewriting produces plain LF output without a BOM
+        let manager = SceneryPacksManager::new(temp_dir.path());
+        manager.normalize_ini().unwrap();
+        let written = fs::read_to_string(&ini_path).unwrap();
+        assert!(written.starts_with(INI_HEADER));
+        assert!(!written.contains('\r'));
+        assert_eq!(parse_ini(&written).len(), 1);
+    }
+
+    #[test]
+    fn test_find_case_mismatches() {
+        let entries = parse_ini(
+            "I\n1000 Version\nSCENERY\n\n\
+             SCENERY_PACK Custom Scenery/MyAirport/\n\
+             SCENERY_PACK Custom Scenery/Exact/\n\
+             SCENERY_PACK Custom Scenery/Missing/\n\
+             SCENERY_PACK *GLOBAL_AIRPORTS*\n\
+             SCENERY_PACK D:/Ortho/myairport/\n",
+        );
+        let folders = vec!["myairport".to_string(), "Exact".to_string()];
+
+        assert_eq!(
+            find_case_mismatches(&entries, &folders),
+            vec![IniCaseMismatch {
+                ini_path: "Custom Scenery/MyAirport/".to_string(),
+                actual_folder: "myairport".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_diff_sort_entries() {
+        let current = parse_ini(
+            "SCENERY_PACK Custom Scenery/B/\nSCENERY_PACK Custom Scenery/A/\nSCENERY_PACK Custom Scenery/Manual/\n",
+        );
+        let sorted = parse_ini(
+            "SCENERY_PACK_DISABLED Custom Scenery/a/\nSCENERY_PACK *GLOBAL_AIRPORTS*\nSCENERY_PACK Custom Scenery/B/\n",
+        );
+
+        let changes = diff_sort_entries(&current, &sorted);
+        assert_eq!(changes.len(), 4);
+
+        assert_eq!(changes[0].path, "Custom Scenery/a/");
+        assert_eq!(changes[0].old_position, Some(1));
+        assert_eq!(changes[0].new_position, Some(0));
+        assert_eq!(changes[0].old_enabled, Some(true));
+        assert_eq!(changes[0].new_enabled, Some(false));
+
+        assert!(changes[1].is_global_airports);
+        assert_eq!(changes[1].old_position, None);
+
+        assert_eq!(changes[2].old_position, Some(0));
+        assert_eq!(changes[2].new_position, Some(2));
+
+        // Entries missing from the index are dropped by the sort
+        assert_eq!(changes[3].path, "Custom Scenery/Manual/");
+        assert_eq!(changes[3].new_position, None);
+        assert_eq!(changes[3].new_enabled, None);
+    }
+
+    #[test]
+    fn test_orphaned_entries() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let xplane_path = temp_dir.path();
+        let custom_scenery = xplane_path.join("Custom Scenery");
+        fs::create_dir_all(custom_scenery.join("Present")).unwrap();
+        fs::write(
+            custom_scenery.join("scenery_packs.ini"),
+            format!(
+                "{}SCENERY_PACK Custom Scenery/Present/\nSCENERY_PACK *GLOBAL_AIRPORTS*\nSCENERY_PACK_DISABLED Custom Scenery/Deleted/\n",
+                INI_HEADER
+            ),
+        )
+        .unwrap();
+
+        let manager = SceneryPacksManager::new(xplane_path);
+        let orphaned = manager.find_orphaned_entries().unwrap();
+        assert_eq!(orphaned.len(), 1);
+        assert_eq!(orphaned[0].path, "Custom Scenery/Deleted/");
+
+        assert_eq!(manager.remove_orphaned_entries().unwrap(), 1);
+        let content = fs::read_to_string(custom_scenery.join("scenery_packs.ini")).unwrap();
+        let remaining = parse_ini(&content);
+        assert_eq!(remaining.len(), 2);
+        assert!(remaining.iter().all(|e| !e.path.contains("Deleted")));
+        assert!(manager.find_orphaned_entries().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_restore_backup() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let xplane_path = temp_dir.path();
+        let custom_scenery = xplane_path.join("Custom Scenery");
+        fs::create_dir_all(&custom_scenery).unwrap();
+        let ini_path = custom_scenery.join("scenery_packs.ini");
+        let backup_path = custom_scenery.join(format!("{}20240101_120000", BACKUP_PREFIX));
+        fs::write(&ini_path, "current").unwrap();
+        fs::write(&backup_path, "old").unwrap();
+
+        let manager = SceneryPacksManager::new(xplane_path);
+        let backups = manager.list_backups().unwrap();
+        assert_eq!(backups.len(), 1);
+        assert_eq!(
+            backups[0].file_name,
+            format!("{}20240101_120000", BACKUP_PREFIX)
+        );
+
+        // Only real backups inside Custom Scenery can be restored
+        let outside = xplane_path.join(format!("{}20240101_120000", BACKUP_PREFIX));
+        fs::write(&outside, "bad").unwrap();
+        assert!(manager.restore_backup(&outside).is_err());
+        assert!(manager.restore_backup(&ini_path).is_err());
+
+        manager.restore_backup(&backup_path).unwrap();
+        assert_eq!(fs::read_to_string(&ini_path).unwrap(), "old");
+
+        // The replaced ini was kept as a new backup
+        let backups = manager.list_backups().unwrap();
+        assert_eq!(backups.len(), 2);
+        assert_eq!(fs::read_to_string(&backups[0].path).unwrap(), "current");
+    }
+
+    #[test]
+    fn test_backups_never_collide() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let xplane_path = temp_dir.path();
+        let custom_scenery = xplane_path.join("Custom Scenery");
+        fs::create_dir_all(&custom_scenery).unwrap();
+        let ini_path = custom_scenery.join("scenery_packs.ini");
+        let manager = SceneryPacksManager::new(xplane_path);
+
+        fs::write(&ini_path, "first").unwrap();
+        let first = manager.backup_ini().unwrap();
+        fs::write(&ini_path, "second").unwrap();
+        let second = manager.backup_ini().unwrap();
+        assert_ne!(first, second);
+        assert_eq!(fs::read_to_string(&first).unwrap(), "first");
+
+        // Restoring right after a backup keeps the restored backup intact
+        fs::write(&ini_path, "third").unwrap();
+        manager.restore_backup(&second).unwrap();
+        assert_eq!(fs::read_to_string(&ini_path).unwrap(), "second");
+        assert_eq!(fs::read_to_string(&second).unwrap(), "second");
+        assert_eq!(manager.list_backups().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_prune_backups() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        let backup = |day: u32| dir.join(format!("{}202401{:02}_120000", BACKUP_PREFIX, day));
+        for day in 1..=5 {
+            fs::write(backup(day), "").unwrap();
+        }
+        // Not backups: must never be deleted
+        fs::write(dir.join("scenery_packs.ini"), "").unwrap();
+        fs::write(dir.join(format!("{}notes", BACKUP_PREFIX)), "").unwrap();
+
+        assert_eq!(prune_backups(dir, 2).unwrap(), 3);
+        assert!(backup(5).exists());
+        assert!(backup(4).exists());
+        assert!(!backup(3).exists());
+        assert!(dir.join("scenery_packs.ini").exists());
+        assert!(dir.join(format!("{}notes", BACKUP_PREFIX)).exists());
+
+        assert_eq!(prune_backups(dir, 2).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_remove_ini_entry() {
+        let content = "SCENERY_PACK Custom Scenery/A/\nSCENERY_PACK_DISABLED Custom Scenery\\B\nSCENERY_PACK *GLOBAL_AIRPORTS*\n";
+
+        let (entries, removed) = remove_ini_entry(parse_ini(content), "Custom Scenery/b/");
+        assert!(removed);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].path, "Custom Scenery/A/");
+        assert!(entries[1].is_global_airports);
+
+        let (entries, removed) = remove_ini_entry(entries, "Custom Scenery/C/");
+        assert!(!removed);
+        assert_eq!(entries.len(), 2);
+    }
 }
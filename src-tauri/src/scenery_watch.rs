@@ -0,0 +1,215 @@
+//! Filesystem watch on Custom Scenery
+//!
+//! Watches the top level of Custom Scenery and emits `SCENERY_CHANGED_EVENT` with the
+//! names of added/removed/renamed entries once changes have settled for
+//! `WATCH_DEBOUNCE`, so the frontend can run update_index instead of polling.
+//! Files written by the app itself (scenery_packs.ini and its backups, install
+//! staging and backup folders) are ignored.
+
+use anyhow::{anyhow, Context, Result};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::atomic_installer::TEMP_DIR_PREFIX;
+use crate::logger;
+
+/// Event name emitted when Custom Scenery folders were added, removed or renamed
+pub const SCENERY_CHANGED_EVENT: &str = "scenery-changed";
+
+/// Quiet period after the last change before the event is emitted
+const WATCH_DEBOUNCE: Duration = Duration::from_secs(2);
+
+/// Custom Scenery watcher kept in Tauri state; at most one watch is active
+pub struct SceneryWatcher {
+    active: Mutex<Option<ActiveWatch>>,
+}
+
+struct ActiveWatch {
+    path: PathBuf,
+    // Dropping the watcher closes the event channel, which ends the debounce thread
+    _watcher: RecommendedWatcher,
+}
+
+impl SceneryWatcher {
+    pub fn new() -> Self {
+        Self {
+            active: Mutex::new(None),
+        }
+    }
+
+    /// Start watching `xplane_path`/Custom Scenery, replacing any previous watch
+    pub fn start(&self, app_handle: AppHandle, xplane_path: &Path) -> Result<()> {
+        let custom_scenery = xplane_path.join("Custom Scenery");
+        if !custom_scenery.is_dir() {
+            return Err(anyhow!(
+                "Custom Scenery folder not found: {:?}",
+                custom_scenery
+            ));
+        }
+
+        let mut active = self
+            .active
+            .lock()
+            .map_err(|_| anyhow!("Scenery watcher lock poisoned"))?;
+        if active.as_ref().is_some_and(|a| a.path == custom_scenery) {
+            return Ok(());
+        }
+        // Stop the previous watch before starting a new one
+        *active = None;
+
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |res| {
+            let _ = tx.send(res);
+        })
+        .context("Failed to create filesystem watcher")?;
+        // Packages are top-level folders, so their contents don't need watching
+        watcher
+            .watch(&custom_scenery, RecursiveMode::NonRecursive)
+            .context(format!("Failed to watch {:?}", custom_scenery))?;
+
+        std::thread::spawn(move || debounce_events(rx, app_handle));
+
+        logger::log_info(
+            &format!("Watching for scenery changes: {:?}", custom_scenery),
+            Some("scenery_watch"),
+        );
+        *active = Some(ActiveWatch {
+            path: custom_scenery,
+            _watcher: watcher,
+        });
+        Ok(())
+    }
+
+    /// Stop the active watch. Returns false if no watch was running
+    pub fn stop(&self) -> bool {
+        let Ok(mut active) = self.active.lock() else {
+            return false;
+        };
+        match active.take() {
+            Some(watch) => {
+                logger::log_info(
+                    &format!("Stopped watching for scenery changes: {:?}", watch.path),
+                    Some("scenery_watch"),
+                );
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Default for SceneryWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Collect relevant changes until the channel has been quiet for WATCH_DEBOUNCE,
+/// then emit one event with the changed entry names. Returns when the watcher is dropped
+fn debounce_events(rx: mpsc::Receiver<notify::Result<notify::Event>>, app_handle: AppHandle) {
+    let mut changed: BTreeSet<String> = BTreeSet::new();
+
+    loop {
+        let received = if changed.is_empty() {
+            rx.recv().map_err(|_| mpsc::RecvTimeoutError::Disconnected)
+        } else {
+            rx.recv_timeout(WATCH_DEBOUNCE)
+        };
+
+        match received {
+            Ok(Ok(event)) => changed.extend(relevant_names(&event)),
+            Ok(Err(e)) => {
+                crate::log_debug!(&format!("Scenery watch error: {}", e), "scenery_watch");
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                let names: Vec<String> = std::mem::take(&mut changed).into_iter().collect();
+                crate::log_debug!(
+                    &format!("Custom Scenery changed: {:?}", names),
+                    "scenery_watch"
+                );
+                let _ = app_handle.emit(SCENERY_CHANGED_EVENT, names);
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+    }
+}
+
+/// Names of the Custom Scenery entries an event adds, removes or renames
+/// Content modifications and the app's own files are ignored
+fn relevant_names(event: &notify::Event) -> Vec<String> {
+    if !matches!(
+        event.kind,
+        EventKind::Create(_)
+            | EventKind::Remove(_)
+            | EventKind::Modify(notify::event::ModifyKind::Name(_))
+    ) {
+        return Vec::new();
+    }
+
+    event
+        .paths
+        .iter()
+        .filter_map(|path| path.file_name()?.to_str())
+        .filter(|name| !is_own_file(name))
+        .map(|name| name.to_string())
+        .collect()
+}
+
+/// Whether a Custom Scenery entry was created by the app itself
+/// (scenery_packs.ini writes and backups, install staging and backup folders)
+/// or is a hidden file such as .DS_Store
+fn is_own_file(name: &str) -> bool {
+    name.starts_with('.')
+        || name.starts_with(TEMP_DIR_PREFIX)
+        || name.to_lowercase().starts_with("scenery_packs.ini")
+        || name.contains(".backup_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{CreateKind, DataChange, ModifyKind, RemoveKind};
+
+    #[test]
+    fn test_is_own_file() {
+        assert!(is_own_file("scenery_packs.ini"));
+        assert!(is_own_file("scenery_packs.ini.tmp"));
+        assert!(is_own_file("scenery_packs.ini.backup.20240101_120000"));
+        assert!(is_own_file(
+            "KSEA Airport.backup_0b7f5a9e-1c1d-4e52-9a57-1f3f4b8f0c2d"
+        ));
+        assert!(is_own_file(".xfastmanager_temp_1234"));
+        assert!(is_own_file(".DS_Store"));
+
+        assert!(!is_own_file("KSEA Airport"));
+        assert!(!is_own_file("zOrtho4XP_+47-123"));
+        assert!(!is_own_file("Backup Library"));
+    }
+
+    #[test]
+    fn test_relevant_names() {
+        let root = Path::new("/xp/Custom Scenery");
+        let event = |kind| {
+            notify::Event::new(kind)
+                .add_path(root.join("KSEA Airport"))
+                .add_path(root.join("scenery_packs.ini.tmp"))
+        };
+
+        assert_eq!(
+            relevant_names(&event(EventKind::Create(CreateKind::Folder))),
+            vec!["KSEA Airport"]
+        );
+        assert_eq!(
+            relevant_names(&event(EventKind::Remove(RemoveKind::Any))),
+            vec!["KSEA Airport"]
+        );
+        assert!(
+            relevant_names(&event(EventKind::Modify(ModifyKind::Data(DataChange::Any)))).is_empty()
+        );
+    }
+}
@@ -1,6 +1,16 @@
-use std::path::PathBuf;
-use std::sync::atomic::{AtomicBool, Ordering};
+use anyhow::{anyhow, Result};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::models::WalkProgress;
+
+/// Event name used for walk progress updates
+pub const WALK_PROGRESS_EVENT: &str = "walk-progress";
+
+/// Minimum interval between two walk progress events
+const WALK_PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
 
 /// Task control state for managing installation cancellation and skipping
 #[derive(Clone)]
@@ -37,6 +47,11 @@ impl TaskControl {
         self.cancel_all.load(Ordering::SeqCst)
     }
 
+    /// Shared cancel flag, for workers that poll it without holding the TaskControl
+    pub fn cancel_token(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.cancel_all)
+    }
+
     /// Check if skip was requested
     pub fn is_skip_requested(&self) -> bool {
         self.skip_current.load(Ordering::SeqCst)
@@ -87,6 +102,148 @@ impl Default for TaskControl {
     }
 }
 
+/// Cancel control for long library scans (separate from installation control
+/// so cancelling a scan never aborts a running installation)
+/// Every scan gets its own token from `begin_scan`, so a scan starting while another
+/// is being cancelled can't clear that cancellation
+#[derive(Clone, Default)]
+pub struct ScanControl {
+    active: Arc<Mutex<Vec<Arc<AtomicBool>>>>,
+}
+
+impl ScanControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a new scan and return its cancel token
+    pub fn begin_scan(&self) -> ScanToken {
+        let cancel = Arc::new(AtomicBool::new(false));
+        if let Ok(mut active) = self.active.lock() {
+            active.push(cancel.clone());
+        }
+        ScanToken {
+            cancel,
+            active: self.active.clone(),
+        }
+    }
+
+    /// Request cancellation of every running scan
+    pub fn request_cancel(&self) {
+        if let Ok(active) = self.active.lock() {
+            for cancel in active.iter() {
+                cancel.store(true, Ordering::SeqCst);
+            }
+        }
+    }
+}
+
+/// Cancel flag of a single scan; unregisters itself from its ScanControl when dropped
+pub struct ScanToken {
+    cancel: Arc<AtomicBool>,
+    active: Arc<Mutex<Vec<Arc<AtomicBool>>>>,
+}
+
+impl ScanToken {
+    /// Check if cancellation was requested for this scan
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for ScanToken {
+    fn drop(&mut self) {
+        if let Ok(mut active) = self.active.lock() {
+            active.retain(|cancel| !Arc::ptr_eq(cancel, &self.cancel));
+        }
+    }
+}
+
+type WalkProgressSink = Box<dyn Fn(&WalkProgress) + Send + Sync>;
+
+/// Progress counter for a single library walk
+/// Every visited folder is passed to `step`, which reports throttled progress to
+/// the sink and fails once the scan has been cancelled. Safe to share across
+/// rayon workers.
+pub struct WalkMonitor {
+    token: ScanToken,
+    scanned: AtomicU64,
+    /// Expected number of steps, 0 while unknown
+    total: AtomicU64,
+    last_report: Mutex<Option<Instant>>,
+    sink: WalkProgressSink,
+}
+
+impl WalkMonitor {
+    pub fn new(token: ScanToken, sink: impl Fn(&WalkProgress) + Send + Sync + 'static) -> Self {
+        Self {
+            token,
+            scanned: AtomicU64::new(0),
+            total: AtomicU64::new(0),
+            last_report: Mutex::new(None),
+            sink: Box::new(sink),
+        }
+    }
+
+    /// Monitor that reports nothing and can't be cancelled
+    pub fn silent() -> Self {
+        Self::new(ScanControl::new().begin_scan(), |_| {})
+    }
+
+    /// Record a visited path
+    /// Returns an error if the scan has been cancelled
+    pub fn step(&self, path: &Path) -> Result<()> {
+        self.check_cancelled()?;
+
+        let scanned = self.scanned.fetch_add(1, Ordering::SeqCst) + 1;
+        let should_report = match self.last_report.lock() {
+            Ok(mut last) => {
+                let due = match *last {
+                    Some(t) => t.elapsed() >= WALK_PROGRESS_INTERVAL,
+                    None => true,
+                };
+                if due {
+                    *last = Some(Instant::now());
+                }
+                due
+            }
+            Err(_) => false,
+        };
+
+        if should_report {
+            let total = self.total.load(Ordering::SeqCst);
+            (self.sink)(&WalkProgress {
+                scanned,
+                current_path: path.to_string_lossy().to_string(),
+                total: (total > 0).then_some(total),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Announce how many more steps the walk will take, so progress can be shown
+    /// as scanned / total. Steps already taken count towards the total
+    pub fn expect_steps(&self, steps: u64) {
+        let scanned = self.scanned.load(Ordering::SeqCst);
+        self.total.store(scanned + steps, Ordering::SeqCst);
+    }
+
+    /// Fail if the scan has been cancelled
+    pub fn check_cancelled(&self) -> Result<()> {
+        if self.token.is_cancelled() {
+            Err(anyhow!("Scan cancelled"))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Number of paths visited so far
+    pub fn scanned(&self) -> u64 {
+        self.scanned.load(Ordering::SeqCst)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -218,4 +375,48 @@ mod tests {
         assert!(control.is_cancelled());
         assert!(!control.is_skip_requested());
     }
+
+    #[test]
+    fn test_walk_monitor_reports_and_cancels() {
+        let control = ScanControl::new();
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let sink_reports = reports.clone();
+        let monitor = WalkMonitor::new(control.begin_scan(), move |p: &WalkProgress| {
+            sink_reports.lock().unwrap().push(p.scanned);
+        });
+
+        monitor.step(Path::new("a")).unwrap();
+        monitor.step(Path::new("b")).unwrap();
+        assert_eq!(monitor.scanned(), 2);
+        // The second step falls inside the throttle interval
+        assert_eq!(*reports.lock().unwrap(), vec![1]);
+
+        control.request_cancel();
+        assert!(monitor.step(Path::new("c")).is_err());
+        assert_eq!(monitor.scanned(), 2);
+
+        // A scan started after the cancel is unaffected and leaves the cancel in place
+        let next = control.begin_scan();
+        assert!(!next.is_cancelled());
+        assert!(monitor.check_cancelled().is_err());
+
+        // Finished scans are no longer cancelled by later requests
+        drop(monitor);
+        assert_eq!(control.active.lock().unwrap().len(), 1);
+        control.request_cancel();
+        assert!(next.is_cancelled());
+    }
+
+    #[test]
+    fn test_walk_monitor_reports_total() {
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let sink_reports = reports.clone();
+        let monitor = WalkMonitor::new(ScanControl::new().begin_scan(), move |p: &WalkProgress| {
+            sink_reports.lock().unwrap().push((p.scanned, p.total));
+        });
+
+        monitor.expect_steps(3);
+        monitor.step(Path::new("a")).unwrap();
+        assert_eq!(*reports.lock().unwrap(), vec![(1, Some(3))]);
+    }
 }
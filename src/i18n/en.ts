@@ -27,7 +27,7 @@ export default {
     setPathFirst: 'Please set X-Plane path first',
     goToSettings: 'Go to Settings',
     dropFilesHere: 'Drop files here',
-    supportedFormats: 'Supports .zip, .7z, .rar files, or folders',
+    supportedFormats: 'Supports .zip, .7z, .rar, .tar.gz files, or folders',
     autoDetect: 'Auto detect',
     fastInstall: 'Fast install',
     batchProcess: 'Batch process',
@@ -367,6 +367,8 @@ export default {
     missingLibraries: 'Missing libraries',
     missingLibrariesTitle: 'Missing Libraries',
     clickToViewMissingLibs: 'Click to view missing libraries',
+    installedByApp: 'Installed by XFast Manager on {date}',
+    incompleteScenery: 'Earth nav data contains no DSF or apt.dat - this may be a broken install',
     missingLibsCopied: 'Missing library names copied',
     copyAllLibNames: 'Copy All Library Names',
     searchOnBing: 'Search on Bing',
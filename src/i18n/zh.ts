@@ -27,7 +27,7 @@ export default {
     setPathFirst: '请先设置X-Plane路径',
     goToSettings: '前往设置配置安装路径',
     dropFilesHere: '拖拽文件到此处',
-    supportedFormats: '支持 .zip、.7z、.rar 文件或文件夹',
+    supportedFormats: '支持 .zip、.7z、.rar、.tar.gz 文件或文件夹',
     autoDetect: '自动检测',
     fastInstall: '快速安装',
     batchProcess: '批量处理',
@@ -380,6 +380,8 @@ export default {
     missingLibraries: '缺少库',
     missingLibrariesTitle: '缺少的库',
     clickToViewMissingLibs: '点击查看缺少的库',
+    installedByApp: '由 XFast Manager 于 {date} 安装',
+    incompleteScenery: 'Earth nav data 中没有 DSF 或 apt.dat，可能是安装不完整',
     missingLibsCopied: '已复制缺少的库名称',
     copyAllLibNames: '复制所有库名',
     searchOnBing: '在必应搜索',
@@ -8,7 +8,8 @@ import type {
   NavdataManagerInfo,
   ManagementData,
   ManagementTab,
-  ManagementItemType
+  ManagementItemType,
+  ManagementDeleteSummary
 } from '@/types'
 import { useAppStore } from './app'
 import { useToastStore } from './toast'
@@ -243,6 +244,8 @@ export const useManagementStore = defineStore('management', () => {
     checkParamName: string
     logName: string
     itemType: 'aircraft' | 'plugin'
+    // Bypass the backend remote version cache as well
+    forceRefresh?: boolean
   }
 
   interface UpdateCheckResult {
@@ -268,7 +271,9 @@ export const useManagementStore = defineStore('management', () => {
     try {
       // Send only items needing check to backend
       const updated = await invoke<T[]>(config.checkCommand, {
-        [config.checkParamName]: itemsToCheck
+        xplanePath: appStore.xplanePath,
+        [config.checkParamName]: itemsToCheck,
+        forceRefresh: config.forceRefresh ?? false
       })
 
       // Update cache with results (only store latestVersion, not hasUpdate)
@@ -339,7 +344,8 @@ export const useManagementStore = defineStore('management', () => {
       checkCommand: 'check_aircraft_updates',
       checkParamName: 'aircraft',
       logName: 'aircraft',
-      itemType: 'aircraft'
+      itemType: 'aircraft',
+      forceRefresh
     })
     // Show toast when check was actually performed and no updates found
     if (result.checked && result.updateCount === 0) {
@@ -379,7 +385,8 @@ export const useManagementStore = defineStore('management', () => {
       checkCommand: 'check_plugins_updates',
       checkParamName: 'plugins',
       logName: 'plugins',
-      itemType: 'plugin'
+      itemType: 'plugin',
+      forceRefresh
     })
     // Show toast when check was actually performed and no updates found
     if (result.checked && result.updateCount === 0) {
@@ -416,7 +423,12 @@ export const useManagementStore = defineStore('management', () => {
   }
 
   // Toggle enabled state
-  async function toggleEnabled(itemType: ManagementItemType, folderName: string) {
+  // For plugins, platform ('win' | 'mac' | 'lin') limits the toggle to that platform's binaries
+  async function toggleEnabled(
+    itemType: ManagementItemType,
+    folderName: string,
+    platform?: 'win' | 'mac' | 'lin'
+  ) {
     if (!appStore.xplanePath) {
       error.value = 'X-Plane path not set'
       return
@@ -426,7 +438,8 @@ export const useManagementStore = defineStore('management', () => {
       const newEnabled = await invoke<boolean>('toggle_management_item', {
         xplanePath: appStore.xplanePath,
         itemType,
-        folderName
+        folderName,
+        platform
       })
 
       // Update local state
@@ -466,6 +479,27 @@ export const useManagementStore = defineStore('management', () => {
     }
   }
 
+  // Remove a deleted item from local state
+  function removeLocalItem(itemType: ManagementItemType, folderName: string) {
+    switch (itemType) {
+      case 'aircraft':
+        aircraft.value = aircraft.value.filter(a => a.folderName !== folderName)
+        aircraftTotalCount.value = aircraft.value.length
+        aircraftEnabledCount.value = aircraft.value.filter(a => a.enabled).length
+        break
+      case 'plugin':
+        plugins.value = plugins.value.filter(p => p.folderName !== folderName)
+        pluginsTotalCount.value = plugins.value.length
+        pluginsEnabledCount.value = plugins.value.filter(p => p.enabled).length
+        break
+      case 'navdata':
+        navdata.value = navdata.value.filter(n => n.folderName !== folderName)
+        navdataTotalCount.value = navdata.value.length
+        navdataEnabledCount.value = navdata.value.filter(n => n.enabled).length
+        break
+    }
+  }
+
   // Delete item
   async function deleteItem(itemType: ManagementItemType, folderName: string) {
     if (!appStore.xplanePath) {
@@ -480,24 +514,7 @@ export const useManagementStore = defineStore('management', () => {
         folderName
       })
 
-      // Remove from local state
-      switch (itemType) {
-        case 'aircraft':
-          aircraft.value = aircraft.value.filter(a => a.folderName !== folderName)
-          aircraftTotalCount.value = aircraft.value.length
-          aircraftEnabledCount.value = aircraft.value.filter(a => a.enabled).length
-          break
-        case 'plugin':
-          plugins.value = plugins.value.filter(p => p.folderName !== folderName)
-          pluginsTotalCount.value = plugins.value.length
-          pluginsEnabledCount.value = plugins.value.filter(p => p.enabled).length
-          break
-        case 'navdata':
-          navdata.value = navdata.value.filter(n => n.folderName !== folderName)
-          navdataTotalCount.value = navdata.value.length
-          navdataEnabledCount.value = navdata.value.filter(n => n.enabled).length
-          break
-      }
+      removeLocalItem(itemType, folderName)
     } catch (e) {
       error.value = String(e)
       logError(`Failed to delete item: ${e}`, 'management')
@@ -505,6 +522,35 @@ export const useManagementStore = defineStore('management', () => {
     }
   }
 
+  // Delete several items in one call; failed items stay in the list
+  async function deleteItems(
+    items: { itemType: ManagementItemType; folderName: string }[]
+  ): Promise<ManagementDeleteSummary> {
+    if (!appStore.xplanePath) {
+      error.value = 'X-Plane path not set'
+      throw new Error(error.value)
+    }
+
+    try {
+      const summary = await invoke<ManagementDeleteSummary>('delete_management_items', {
+        xplanePath: appStore.xplanePath,
+        items: items.map(item => [item.itemType, item.folderName])
+      })
+
+      for (const result of summary.results) {
+        if (result.success) {
+          removeLocalItem(result.itemType as ManagementItemType, result.folderName)
+        }
+      }
+
+      return summary
+    } catch (e) {
+      error.value = String(e)
+      logError(`Failed to delete items: ${e}`, 'management')
+      throw e
+    }
+  }
+
   // Open folder
   async function openFolder(itemType: ManagementItemType, folderName: string) {
     if (!appStore.xplanePath) {
@@ -573,6 +619,7 @@ export const useManagementStore = defineStore('management', () => {
     loadCurrentTabData,
     toggleEnabled,
     deleteItem,
+    deleteItems,
     openFolder,
     setActiveTab,
     clear
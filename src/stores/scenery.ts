@@ -161,6 +161,35 @@ export const useSceneryStore = defineStore('scenery', () => {
     data.value.enabledCount = data.value.entries.filter(e => e.enabled).length
   }
 
+  // Enable or disable many entries at once (e.g. all orthos), written to the index immediately
+  // The ini is only updated on Apply, so the change is marked as needing sync
+  async function setEnabledBulk(folderNames: string[], enabled: boolean): Promise<number> {
+    if (!appStore.xplanePath || !data.value) return 0
+
+    try {
+      const changed = await invoke<number>('set_scenery_enabled_bulk', {
+        xplanePath: appStore.xplanePath,
+        folderNames,
+        enabled
+      })
+
+      const names = new Set(folderNames)
+      for (const entries of [data.value.entries, originalEntries.value]) {
+        for (const entry of entries) {
+          if (names.has(entry.folderName)) entry.enabled = enabled
+        }
+      }
+      data.value.enabledCount = data.value.entries.filter(e => e.enabled).length
+      if (changed > 0) data.value.needsSync = true
+
+      return changed
+    } catch (e) {
+      error.value = String(e)
+      logError(`Failed to update enabled state: ${e}`, 'scenery')
+      throw e
+    }
+  }
+
   // Update category for an entry
   async function updateCategory(folderName: string, newCategory: SceneryCategory) {
     if (!data.value) return
@@ -169,10 +198,13 @@ export const useSceneryStore = defineStore('scenery', () => {
     if (!entry) return
 
     const oldCategory = entry.category
+    const oldLocked = entry.categoryLocked
 
     try {
       // Update locally first for immediate UI feedback
       entry.category = newCategory
+      // The backend locks manually chosen categories against re-classification
+      entry.categoryLocked = true
 
       // Update in backend
       await invoke('update_scenery_entry', {
@@ -185,12 +217,30 @@ export const useSceneryStore = defineStore('scenery', () => {
     } catch (e) {
       // Revert on error
       entry.category = oldCategory
+      entry.categoryLocked = oldLocked
       error.value = String(e)
       logError(`Failed to update category: ${e}`, 'scenery')
       throw e
     }
   }
 
+  // Clear a manually chosen category so the package goes back to its classified one
+  async function unlockCategory(folderName: string) {
+    try {
+      await invoke('set_scenery_category', {
+        xplanePath: appStore.xplanePath,
+        folderName,
+        category: null
+      })
+      // Category and sort order both come from the backend again
+      await loadData()
+    } catch (e) {
+      error.value = String(e)
+      logError(`Failed to unlock category: ${e}`, 'scenery')
+      throw e
+    }
+  }
+
   // Apply a local sort order without persisting immediately
   function applyLocalOrder(newOrder: SceneryManagerEntry[]) {
     if (!data.value) return
@@ -251,7 +301,7 @@ export const useSceneryStore = defineStore('scenery', () => {
         sortOrder: entry.sortOrder
       }))
 
-      await invoke('apply_scenery_changes', {
+      await invoke('commit_manager_changes', {
         xplanePath: appStore.xplanePath,
         entries: updates
       })
@@ -364,7 +414,9 @@ export const useSceneryStore = defineStore('scenery', () => {
     loadData,
     loadIndexStatus,
     toggleEnabled,
+    setEnabledBulk,
     updateCategory,
+    unlockCategory,
     moveEntry,
     reorderEntries,
     applyChanges,
@@ -149,6 +149,14 @@ export interface InstallTask {
   backupConfigFiles?: boolean;
   /** Glob patterns for config files to backup (Aircraft only) */
   configFilePatterns?: string[];
+  /** Whether config file patterns are also matched in subfolders (Aircraft only) */
+  configFileRecursive?: boolean;
+  /** Glob patterns (relative to the package root) kept across clean installs (Scenery only) */
+  sceneryPreservePatterns?: string[];
+  /** Sort position to move the package to after install (Scenery only, with auto-sort) */
+  desiredSortOrder?: number;
+  /** Link the source folder into Custom Scenery instead of copying it (Scenery only) */
+  installAsSymlink?: boolean;
   /** For Livery: the aircraft type this livery belongs to (e.g., "FF777") */
   liveryAircraftType?: string;
   /** For Livery: whether the target aircraft is installed */
@@ -157,11 +165,20 @@ export interface InstallTask {
 
 export interface AnalysisResult {
   tasks: InstallTask[];
+  /** Errors not tied to a single input path; per-path failures are in pathErrors */
   errors: string[];
   /** List of archive paths that require a password */
   passwordRequired: string[];
   /** Map of nested archive paths to their parent archive */
   nestedPasswordRequired?: Record<string, string>;
+  /** Input paths that could not be analyzed (the other inputs are still analyzed) */
+  pathErrors?: AnalysisPathError[];
+}
+
+// An input path that failed during analysis
+export interface AnalysisPathError {
+  path: string;
+  message: string;
 }
 
 export interface ConflictInfo {
@@ -185,6 +202,14 @@ export interface InstallProgress {
   verificationProgress?: number;
 }
 
+/** Progress of a library-wide scan, emitted as the `walk-progress` event */
+export interface WalkProgress {
+  scanned: number;
+  currentPath: string;
+  /** Number of folders the scan will visit, when known (scenery indexing) */
+  total?: number;
+}
+
 export interface TaskResult {
   taskId: string;
   taskName: string;
@@ -232,11 +257,27 @@ export interface SceneryPackageInfo {
   hasTextures: boolean;
   hasObjects: boolean;
   textureCount: number;
+  earthNavTileCount: number;
   indexedAt: number;
   requiredLibraries: string[];
   missingLibraries: string[];
+  exportedLibraryNames: string[];
   enabled: boolean;
   sortOrder: number;
+  /** Resolved target path when the entry is a shortcut */
+  actualPath?: string;
+  /** Airport ICAO code read from apt.dat */
+  icaoCode?: string;
+  /** Airport name from the apt.dat header row */
+  airportName?: string;
+  /** Airport elevation in feet */
+  airportElevationFt?: number;
+  /** Category was chosen by the user and is kept across re-indexing */
+  categoryLocked?: boolean;
+  /** The apt.dat header ICAO and its icao_code row disagree */
+  icaoMismatch?: boolean;
+  /** Provenance marker read when the package was indexed */
+  installMarker?: InstallMarker;
 }
 
 export interface SceneryIndexStats {
@@ -245,11 +286,33 @@ export interface SceneryIndexStats {
   lastUpdated: number;
 }
 
+// Result of compacting the scenery database
+export interface DatabaseVacuumResult {
+  /** Database size in bytes before VACUUM (including the WAL file) */
+  sizeBefore: number;
+  /** Database size in bytes after VACUUM */
+  sizeAfter: number;
+}
+
 export interface SceneryIndexStatus {
   indexExists: boolean;
   totalPackages: number;
 }
 
+// DSF header summary for diagnosing classification issues
+export interface DsfInfo {
+  /** DSF file that was read, relative to the scenery package */
+  dsfPath: string;
+  isOverlay: boolean;
+  creationAgent?: string;
+  objectReferenceCount: number;
+  terrainReferenceCount: number;
+  /** First few object references, in DSF order */
+  sampleObjectReferences: string[];
+  /** First few terrain references, in DSF order */
+  sampleTerrainReferences: string[];
+}
+
 export interface SceneryIndexScanResult {
   indexExists: boolean;
   added: number;
@@ -265,6 +328,34 @@ export interface SceneryManagerEntry {
   sortOrder: number;
   missingLibraries: string[];
   requiredLibraries: string[];
+  /** Content is generated on the fly (e.g., AutoOrtho), so it is skipped by scans */
+  isDynamic: boolean;
+  /** Has an Earth nav data folder but no DSF or apt.dat (likely a broken download) */
+  isIncomplete: boolean;
+  /** Mesh package detected as an orthophoto pack by folder name and texture count */
+  isOrthophoto: boolean;
+  /** Provenance marker if the package was installed by this app */
+  installMarker?: InstallMarker;
+  /** Airport ICAO code (packages with apt.dat only) */
+  icaoCode?: string;
+  /** Airport name (packages with apt.dat only) */
+  airportName?: string;
+  /** Airport elevation in feet (packages with apt.dat only) */
+  airportElevationFt?: number;
+  /** Category was chosen by the user and is kept across re-indexing */
+  categoryLocked: boolean;
+  /** The apt.dat header ICAO and its icao_code row disagree */
+  icaoMismatch: boolean;
+}
+
+/** Provenance marker written into installed packages (.xfastinstall.json) */
+export interface InstallMarker {
+  source: string;
+  /** Installation time (RFC 3339) */
+  installedAt: string;
+  /** "fresh", "clean" or "overwrite" */
+  strategy: string;
+  version?: string;
 }
 
 export interface SceneryManagerData {
@@ -273,6 +364,153 @@ export interface SceneryManagerData {
   enabledCount: number;
   missingDepsCount: number;
   needsSync: boolean;
+  /** What differs, when needsSync is set */
+  syncDivergence?: IniSyncDivergence;
+}
+
+// How scenery_packs.ini differs from the scenery index
+export interface IniSyncDivergence {
+  /** scenery_packs.ini doesn't exist */
+  iniMissing: boolean;
+  /** Index packages not listed in the ini */
+  missingFromIni: string[];
+  /** Index packages listed more than once in the ini */
+  duplicatedInIni: string[];
+  /** Packages whose ini enabled state differs from the index */
+  enabledMismatches: string[];
+  /** Packages that have to move for the ini to follow the index sort order */
+  outOfOrder: string[];
+}
+
+// Editable state of a scenery package, as recorded in the audit log
+export interface SceneryAuditState {
+  enabled: boolean;
+  sortOrder: number;
+  category: SceneryCategory;
+  categoryLocked: boolean;
+}
+
+export interface SceneryAuditChange {
+  folderName: string;
+  before: SceneryAuditState;
+  after: SceneryAuditState;
+}
+
+// One audited scenery manager operation
+export interface SceneryAuditEntry {
+  /** Time of the operation (RFC 3339) */
+  timestamp: string;
+  /** X-Plane installation the operation was made on */
+  xplanePath: string;
+  /** "move_entry", "update_entry" or "batch_update_entries" */
+  op: string;
+  changes: SceneryAuditChange[];
+}
+
+// Shortcut entry whose target no longer matches the path stored in the index
+export interface MovedShortcutTarget {
+  folderName: string;
+  storedPath: string;
+  /** Where the shortcut points now; null if it can't be resolved anymore */
+  resolvedPath?: string | null;
+}
+
+// Differences between two scenery index JSON exports (A and B)
+export interface IndexExportDiff {
+  onlyInA: string[];
+  onlyInB: string[];
+  categoryChanges: { folderName: string; categoryA: SceneryCategory; categoryB: SceneryCategory }[];
+  /** Positions are 0-based among the packages present in both exports */
+  orderChanges: { folderName: string; positionA: number; positionB: number }[];
+}
+
+// Result of importing a scenery index JSON export
+export interface SceneryIndexImportResult {
+  /** Number of packages written to the index */
+  imported: number;
+  /** Packages in the export whose folders no longer exist */
+  skipped: string[];
+  /** Local packages missing from the export, kept as they were */
+  kept: number;
+}
+
+// DSF tiles covered by a scenery package, for the coverage map
+export interface TileCoverage {
+  folderName: string;
+  category: SceneryCategory;
+  /** [latitude, longitude] of each covered tile's south-west corner */
+  tiles: [number, number][];
+}
+
+// Existing scenery folder that an install would replace
+export interface SceneryConflictInfo {
+  folderName: string;
+  existingCategory?: SceneryCategory | null;
+  existingVersion?: string | null;
+}
+
+export type IniLintKind = 'missingTrailingNewline' | 'mixedLineEndings' | 'tabCharacter';
+
+export interface IniLintFinding {
+  kind: IniLintKind;
+  line?: number | null;
+  message: string;
+}
+
+// scenery_packs.ini entry whose folder name differs in case from the folder on disk
+export interface IniCaseMismatch {
+  iniPath: string;
+  actualFolder: string;
+}
+
+// A scenery category with its sorting priority (lower = higher in scenery_packs.ini)
+// Header labels are localized from the category key
+export interface CategoryBand {
+  category: SceneryCategory;
+  priority: number;
+  /** *GLOBAL_AIRPORTS* is written right before this band */
+  globalAirportsBefore: boolean;
+}
+
+export interface SceneryOrderingConfig {
+  sortMeshByRegion: boolean;
+  highPriorityPatterns: string[];
+  globalAirportsBefore: SceneryCategory;
+  disabledAtBottom: boolean;
+}
+
+export interface SceneryPackEntry {
+  enabled: boolean;
+  path: string;
+  isGlobalAirports: boolean;
+}
+
+// A scenery_packs.ini backup in Custom Scenery
+export interface SceneryPacksBackup {
+  path: string;
+  fileName: string;
+  /** Backup time parsed from the file name (RFC 3339) */
+  createdAt: string;
+}
+
+// How one scenery_packs.ini entry would change if the ini were re-sorted
+export interface SortPreviewChange {
+  path: string;
+  isGlobalAirports: boolean;
+  /** 0-based position in the current ini (null if not in it) */
+  oldPosition: number | null;
+  /** 0-based position after sorting (null if the sort would drop it) */
+  newPosition: number | null;
+  oldEnabled: boolean | null;
+  newEnabled: boolean | null;
+}
+
+// Result of reconciling scenery_packs.ini with the scenery index
+export interface IniReconcileResult {
+  /** Index packages added to the ini */
+  added: number;
+  /** Ini entries without an index package that were removed */
+  removed: number;
 }
 
 // ========== Management Types ==========
@@ -289,6 +527,12 @@ export interface AircraftInfo {
   latestVersion?: string;
   hasUpdate: boolean;
   cfgDisabled?: boolean;
+  /** Liveries folder has been moved aside (liveries.disabled) */
+  liveriesDisabled?: boolean;
+  /** Names of the .acf files in the aircraft folder */
+  acfFiles?: string[];
+  /** Names of the .xfma (disabled .acf) files; with acfFiles also set, the enabled state is ambiguous */
+  xfmaFiles?: string[];
 }
 
 export interface PluginInfo {
@@ -312,12 +556,49 @@ export interface NavdataManagerInfo {
   enabled: boolean;
 }
 
+/** What was found at a candidate X-Plane installation path */
+export interface XPlaneInfo {
+  /** Whether the path contains the X-Plane executable */
+  isValid: boolean;
+  hasCustomScenery: boolean;
+  hasAircraft: boolean;
+  hasPlugins: boolean;
+  hasCustomData: boolean;
+  /** X-Plane version (e.g., "12.1.1-r1") */
+  version?: string;
+}
+
 export interface ManagementData<T> {
   entries: T[];
   totalCount: number;
   enabledCount: number;
 }
 
+// Outcome of deleting one management item in a batch
+export interface ManagementDeleteOutcome {
+  itemType: string;
+  folderName: string;
+  success: boolean;
+  errorMessage?: string;
+}
+
+// Summary of a batch delete of management items
+export interface ManagementDeleteSummary {
+  deleted: number;
+  failed: number;
+  /** Per-item outcomes, in request order */
+  results: ManagementDeleteOutcome[];
+}
+
+// Add-on totals across all managers for the home screen
+export interface Overview {
+  scenery: SceneryIndexStats;
+  aircraftCount: number;
+  pluginCount: number;
+  navdataCount: number;
+  updatesAvailable: number;
+}
+
 export type ManagementTab = 'aircraft' | 'plugin' | 'navdata' | 'scenery';
 
 export type ManagementItemType = 'aircraft' | 'plugin' | 'navdata';